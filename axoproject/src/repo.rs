@@ -49,6 +49,107 @@ impl fmt::Display for GithubRepo {
     }
 }
 
+/// Represents a GitLab project, which may be on a self-hosted instance and
+/// may live in nested groups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitlabRepo {
+    /// The domain the project is hosted at (e.g. `"https://gitlab.com"`)
+    pub domain: String,
+    /// The (possibly nested) group or user that owns the project (e.g. `"group/subgroup"`)
+    pub namespace: String,
+    /// The project name.
+    pub name: String,
+}
+
+impl GitlabRepo {
+    /// Returns the domain the project is hosted at.
+    pub fn domain(&self) -> String {
+        self.domain.clone()
+    }
+
+    /// Path component. Used with `domain` to construct `web_url`.
+    pub fn web_path(&self) -> String {
+        format!("/{}", self.project_path())
+    }
+
+    /// Returns a URL suitable for web access to the project.
+    pub fn web_url(&self) -> String {
+        format!("{}{}", self.domain(), self.web_path())
+    }
+
+    /// The full path of the project, as used by the GitLab API (e.g. `"group/subgroup/project"`)
+    pub fn project_path(&self) -> String {
+        format!("{}/{}", self.namespace, self.name)
+    }
+
+    /// Constructs a new GitLab project from a repository URL. Notably, this does not check
+    /// whether the project actually exists.
+    pub fn from_url(repo_url: &str) -> Result<Self> {
+        match GithubRepoInput::new(repo_url.to_string())? {
+            GithubRepoInput::Url(s) => Self::parse_url(s),
+            GithubRepoInput::Ssh(s) => Self::parse_ssh(s),
+        }
+    }
+
+    fn parse_url(repo_string: String) -> Result<Self> {
+        let trimmed = repo_string.strip_prefix("git+").unwrap_or(&repo_string);
+        let parsed = Url::parse(trimmed)?;
+        let Some(host) = parsed.host_str() else {
+            return Err(AxoprojectError::RepoParseError { repo: repo_string });
+        };
+        let domain = match parsed.port() {
+            Some(port) => format!("{}://{host}:{port}", parsed.scheme()),
+            None => format!("{}://{host}", parsed.scheme()),
+        };
+        let segments = parsed
+            .path_segments()
+            .map(|c| c.filter(|s| !s.trim().is_empty()).collect::<Vec<_>>())
+            .unwrap_or_default();
+        Self::from_segments(domain, &segments)
+            .ok_or(AxoprojectError::RepoParseError { repo: repo_string })
+    }
+
+    fn parse_ssh(repo_string: String) -> Result<Self> {
+        let Some((host, path)) = repo_string
+            .strip_prefix("git@")
+            .and_then(|rest| rest.split_once(':'))
+        else {
+            return Err(AxoprojectError::RepoParseError { repo: repo_string });
+        };
+        let domain = format!("https://{host}");
+        let segments = path
+            .split('/')
+            .filter(|s| !s.trim().is_empty())
+            .collect::<Vec<_>>();
+        Self::from_segments(domain, &segments)
+            .ok_or(AxoprojectError::RepoParseError { repo: repo_string })
+    }
+
+    fn from_segments(domain: String, segments: &[&str]) -> Option<Self> {
+        // GitLab web URLs put everything after a `/-/` segment (e.g. `/-/tree/main`)
+        let end = segments
+            .iter()
+            .position(|s| *s == "-")
+            .unwrap_or(segments.len());
+        let segments = &segments[..end];
+        let (name, namespace) = segments.split_last()?;
+        if namespace.is_empty() {
+            return None;
+        }
+        Some(Self {
+            domain,
+            namespace: namespace.join("/"),
+            name: GithubRepoInput::remove_git_suffix(name.to_string()),
+        })
+    }
+}
+
+impl fmt::Display for GitlabRepo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({})", self.project_path())
+    }
+}
+
 impl GithubRepoInput {
     pub fn new(repo_string: String) -> Result<Self> {
         // Handle git+https just the same as https
@@ -153,4 +254,23 @@ mod tests {
         assert_eq!(parsed.owner, actual_owner);
         assert_eq!(parsed.name, actual_name);
     }
+
+    #[test]
+    fn it_parses_a_nested_gitlab_repo_string() {
+        let input = "https://gitlab.example.com/group/subgroup/project.git";
+        let parsed = GitlabRepo::from_url(input).unwrap();
+        assert_eq!(parsed.domain, "https://gitlab.example.com");
+        assert_eq!(parsed.namespace, "group/subgroup");
+        assert_eq!(parsed.name, "project");
+        assert_eq!(parsed.project_path(), "group/subgroup/project");
+    }
+
+    #[test]
+    fn it_parses_a_gitlab_ssh_repo_string() {
+        let input = "git@gitlab.com:axodotdev/oranda.git";
+        let parsed = GitlabRepo::from_url(input).unwrap();
+        assert_eq!(parsed.domain, "https://gitlab.com");
+        assert_eq!(parsed.namespace, "axodotdev");
+        assert_eq!(parsed.name, "oranda");
+    }
 }
@@ -290,6 +290,19 @@ pub struct AssetInfo {
     pub target_triples: Vec<TripleName>,
     /// the linkage of this Asset
     pub linkage: Option<Linkage>,
+    /// the signing providers that signed this Asset
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub signed_by: Vec<SigningInfo>,
+}
+
+/// A record of a signing provider signing a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SigningInfo {
+    /// The name the signing config gives the provider (e.g. "windows-release")
+    pub signer: String,
+    /// The kind of provider that did the signing (e.g. "osslsigncode")
+    pub provider: String,
 }
 
 /// CI backend info
@@ -488,6 +501,10 @@ pub struct GithubLocalJobConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub install_omnibor: Option<GhaRunStep>,
 
+    /// Expression to execute to install appimagetool
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub install_appimagetool: Option<GhaRunStep>,
+
     /// Command to run to install dependencies
     #[serde(skip_serializing_if = "Option::is_none")]
     pub packages_install: Option<PackageInstallScript>,
@@ -677,6 +694,14 @@ pub struct Artifact {
     #[serde(default)]
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     pub checksums: BTreeMap<ChecksumExtension, ChecksumValue>,
+    /// the signing providers that signed this artifact
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub signed_by: Vec<SigningInfo>,
+    /// ids of Artifacts that are detached signatures of this Artifact
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub signatures: Vec<ArtifactId>,
 }
 
 /// An asset contained in an artifact (executable, license, etc.)
@@ -750,9 +775,15 @@ pub enum ArtifactKind {
     /// The checksums of many artifacts
     #[serde(rename = "unified-checksum")]
     UnifiedChecksum,
+    /// A detached signature of another artifact
+    #[serde(rename = "signature")]
+    Signature,
     /// A tarball containing the source code
     #[serde(rename = "source-tarball")]
     SourceTarball,
+    /// A bundle of the installers and everything they download, for offline installs
+    #[serde(rename = "offline-bundle")]
+    OfflineBundle,
     /// Some form of extra artifact produced by a sidecar build
     #[serde(rename = "extra-artifact")]
     ExtraArtifact,
@@ -765,6 +796,9 @@ pub enum ArtifactKind {
     /// An OmniBOR Artifact ID
     #[serde(rename = "omnibor-artifact-id")]
     OmniborArtifactId,
+    /// An in-toto SLSA provenance statement for a release
+    #[serde(rename = "provenance")]
+    Provenance,
     /// Unknown to this version of cargo-dist-schema
     ///
     /// This is a fallback for forward/backward-compat
@@ -1011,6 +1045,14 @@ pub struct Hosting {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub axodotdev: Option<gazenot::ArtifactSet>,
+    /// Hosted on a GitLab generic package registry (and linked from GitLab Releases)
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gitlab: Option<GitlabHosting>,
+    /// Hosted in an S3-compatible bucket
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub s3: Option<S3Hosting>,
 }
 
 /// Github Hosting
@@ -1029,26 +1071,80 @@ pub struct GithubHosting {
     pub repo: String,
 }
 
+/// GitLab Hosting
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GitlabHosting {
+    /// The base URL of the GitLab API, usually `"https://gitlab.com/api/v4"`
+    /// (This can vary for self-hosted GitLab instances)
+    pub api_url: String,
+    /// The path of the generic package holding the artifacts, without the base URL
+    ///
+    /// e.g. `/projects/mygroup%2Fmyproject/packages/generic/myapp/1.0.0`
+    pub artifact_download_path: String,
+    /// The full path of the project
+    ///
+    /// e.g. `mygroup/mysubgroup/myproject`
+    pub project: String,
+}
+
+/// S3 Hosting
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct S3Hosting {
+    /// The public URL the bucket's contents can be downloaded from,
+    /// e.g. `"https://mybucket.s3.us-east-1.amazonaws.com"`
+    pub artifact_base_url: String,
+    /// The path of the release's files in the bucket, without the base URL
+    ///
+    /// e.g. `/releases/myapp/1.0.0`
+    pub artifact_download_path: String,
+    /// The S3 API endpoint to upload to, e.g. `"https://s3.us-east-1.amazonaws.com"`
+    pub endpoint: String,
+    /// The region of the bucket
+    pub region: String,
+    /// The name of the bucket
+    pub bucket: String,
+}
+
 impl Hosting {
     /// Get the base URL that artifacts should be downloaded from (append the artifact name to the URL)
     pub fn artifact_download_url(&self) -> Option<String> {
-        let Hosting { axodotdev, github } = &self;
-        // Prefer axodotdev is present, otherwise github
+        let Hosting {
+            axodotdev,
+            github,
+            gitlab,
+            s3,
+        } = &self;
+        // Prefer axodotdev is present, otherwise s3 (which is usually a mirror that's
+        // reachable from places the others aren't), otherwise github, otherwise gitlab
         if let Some(host) = &axodotdev {
             return host.set_download_url.clone();
         }
+        if let Some(host) = &s3 {
+            return Some(format!(
+                "{}{}",
+                host.artifact_base_url, host.artifact_download_path
+            ));
+        }
         if let Some(host) = &github {
             return Some(format!(
                 "{}{}",
                 host.artifact_base_url, host.artifact_download_path
             ));
         }
+        if let Some(host) = &gitlab {
+            return Some(format!("{}{}", host.api_url, host.artifact_download_path));
+        }
         None
     }
     /// Gets whether there's no hosting
     pub fn is_empty(&self) -> bool {
-        let Hosting { axodotdev, github } = &self;
-        axodotdev.is_none() && github.is_none()
+        let Hosting {
+            axodotdev,
+            github,
+            gitlab,
+            s3,
+        } = &self;
+        axodotdev.is_none() && github.is_none() && gitlab.is_none() && s3.is_none()
     }
 }
 
@@ -99,7 +99,7 @@ use crate::{
     config::LibraryStyle,
     errors::{DistError, DistResult},
     platform::triple_to_display_name,
-    DistGraphBuilder, SortedMap,
+    DistGraphBuilder, SortedMap, SortedSet,
 };
 
 /// details on what we're announcing
@@ -840,7 +840,7 @@ pub fn announcement_axodotdev(manifest: &DistManifest) -> String {
     format!("# {title}\n\n{body}")
 }
 
-/// If we're publishing to Github, generate the announcement body
+/// If we're publishing to Github or GitLab, generate the announcement body
 ///
 /// Currently mutates the manifest, in the future it should output it
 pub fn announcement_github(manifest: &mut DistManifest) {
@@ -858,8 +858,8 @@ pub fn announcement_github(manifest: &mut DistManifest) {
     // Add the contents of each Release to the body
     let mut announcing_github = false;
     for release in &manifest.releases {
-        // Only bother if there's actually github hosting
-        if release.hosting.github.is_none() {
+        // Only bother if there's actually github (or gitlab, which reuses this body) hosting
+        if release.hosting.github.is_none() && release.hosting.gitlab.is_none() {
             continue;
         }
         // Skip "hidden" apps
@@ -957,6 +957,54 @@ pub fn announcement_github(manifest: &mut DistManifest) {
             writeln!(&mut gh_body).unwrap();
         }
 
+        let signed_artifacts: Vec<_> = manifest
+            .artifacts_for_release(release)
+            .filter(|(_name, artifact)| !artifact.signatures.is_empty())
+            .collect();
+        if !signed_artifacts.is_empty() && download_url.is_some() {
+            let download_url = download_url.as_ref().unwrap();
+            writeln!(gh_body, "## Verifying Signatures {heading_suffix}\n").unwrap();
+            gh_body.push_str("|  File  | Signatures |\n");
+            gh_body.push_str("|--------|------------|\n");
+
+            let mut extensions = SortedSet::new();
+            for (name, artifact) in &signed_artifacts {
+                let signatures = artifact
+                    .signatures
+                    .iter()
+                    .map(|signature| {
+                        let ext = signature
+                            .as_str()
+                            .strip_prefix(name.as_str())
+                            .unwrap_or_default()
+                            .trim_start_matches('.');
+                        extensions.insert(ext);
+                        format!("[{ext}]({download_url}/{signature})")
+                    })
+                    .join(" ");
+                writeln!(&mut gh_body, "| {name} | {signatures} |").unwrap();
+            }
+            writeln!(&mut gh_body).unwrap();
+
+            if extensions.contains("asc") {
+                writeln!(&mut gh_body, "```sh\ngpg --verify <file>.asc <file>\n```\n").unwrap();
+            }
+            if extensions.contains("minisig") {
+                writeln!(
+                    &mut gh_body,
+                    "```sh\nminisign -Vm <file> -P <public key>\n```\n"
+                )
+                .unwrap();
+            }
+            if extensions.contains("sigstore.json") {
+                writeln!(
+                    &mut gh_body,
+                    "```sh\ncosign verify-blob --bundle <file>.sigstore.json --certificate-identity <identity> --certificate-oidc-issuer <issuer> <file>\n```\n"
+                )
+                .unwrap();
+            }
+        }
+
         if !other_artifacts.is_empty() && manifest.github_attestations {
             if let Some(GithubHosting { owner, repo, .. }) = &release.hosting.github {
                 writeln!(&mut gh_body, "## Verifying GitHub Artifact Attestations\n",).unwrap();
@@ -1022,9 +1070,78 @@ fn sortable_triple(triple: &TripleNameRef) -> Vec<String> {
 
 #[cfg(test)]
 mod tests {
-    use dist_schema::TripleNameRef;
+    use dist_schema::{
+        Artifact, ArtifactId, ArtifactKind, DistManifest, GithubHosting, TripleName, TripleNameRef,
+    };
+
+    use super::{announcement_github, sortable_triple};
+
+    #[test]
+    fn announce_detached_signatures() {
+        let artifact = |name: &str, kind, signatures: &[&str]| Artifact {
+            name: Some(ArtifactId::new(name.to_owned())),
+            kind,
+            target_triples: vec![TripleName::new("x86_64-unknown-linux-gnu".to_owned())],
+            path: None,
+            assets: vec![],
+            install_hint: None,
+            description: None,
+            checksum: None,
+            checksums: Default::default(),
+            signed_by: Default::default(),
+            signatures: signatures
+                .iter()
+                .map(|id| ArtifactId::new((*id).to_owned()))
+                .collect(),
+        };
+        let artifacts = [
+            artifact(
+                "app-x86_64-unknown-linux-gnu.tar.xz",
+                ArtifactKind::ExecutableZip,
+                &[
+                    "app-x86_64-unknown-linux-gnu.tar.xz.asc",
+                    "app-x86_64-unknown-linux-gnu.tar.xz.minisig",
+                    "app-x86_64-unknown-linux-gnu.tar.xz.sigstore.json",
+                ],
+            ),
+            artifact(
+                "app-x86_64-unknown-linux-gnu.tar.xz.asc",
+                ArtifactKind::Signature,
+                &[],
+            ),
+            artifact(
+                "app-x86_64-unknown-linux-gnu.tar.xz.minisig",
+                ArtifactKind::Signature,
+                &[],
+            ),
+            artifact(
+                "app-x86_64-unknown-linux-gnu.tar.xz.sigstore.json",
+                ArtifactKind::Signature,
+                &[],
+            ),
+        ];
+
+        let mut manifest = DistManifest::new(vec![], Default::default());
+        let release = manifest.ensure_release("app".to_owned(), "1.0.0".to_owned());
+        release.hosting.github = Some(GithubHosting {
+            artifact_base_url: "https://github.com".to_owned(),
+            artifact_download_path: "/owner/app/releases/download/v1.0.0".to_owned(),
+            owner: "owner".to_owned(),
+            repo: "app".to_owned(),
+        });
+        for artifact in artifacts {
+            let id = artifact.name.clone().unwrap();
+            manifest
+                .ensure_release("app".to_owned(), "1.0.0".to_owned())
+                .artifacts
+                .push(id.clone());
+            manifest.artifacts.insert(id, artifact);
+        }
+
+        announcement_github(&mut manifest);
+        insta::assert_snapshot!(manifest.announcement_github_body.unwrap());
+    }
 
-    use super::sortable_triple;
     #[test]
     fn sort_platforms() {
         let mut targets = vec![
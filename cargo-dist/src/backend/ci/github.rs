@@ -9,9 +9,10 @@ use axoprocess::Cmd;
 use camino::{Utf8Path, Utf8PathBuf};
 use dist_schema::{
     target_lexicon::{self, Architecture, OperatingSystem, Triple},
-    AptPackageName, ChocolateyPackageName, ContainerImageRef, GhaRunStep, GithubGlobalJobConfig,
-    GithubLocalJobConfig, GithubMatrix, GithubRunnerConfig, GithubRunnerRef, GithubRunners,
-    HomebrewPackageName, PackageInstallScript, PackageVersion, PipPackageName, TripleNameRef,
+    AptPackageName, ChocolateyPackageName, ContainerConfig, ContainerImageRef, GhaRunStep,
+    GithubGlobalJobConfig, GithubLocalJobConfig, GithubMatrix, GithubRunnerConfig, GithubRunnerRef,
+    GithubRunners, HomebrewPackageName, PackageInstallScript, PackageVersion, PipPackageName,
+    TripleNameRef,
 };
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
@@ -23,7 +24,8 @@ use crate::{
     config::{
         v1::{ci::github::GithubCiConfig, publishers::PublisherConfig},
         DependencyKind, GithubPermission, GithubPermissionMap, GithubReleasePhase, HostingStyle,
-        JinjaGithubRepoPair, JobStyle, ProductionMode, PublishStyle, SystemDependencies,
+        JinjaGithubRepoPair, JobStyle, ProductionMode, PublishStyle, SignatureStyle,
+        SystemDependencies,
     },
     errors::DistResult,
     platform::{github_runners::target_for_github_runner_or_default, targets},
@@ -31,8 +33,9 @@ use crate::{
 };
 
 use super::{
-    CargoAuditableInstallStrategy, CargoCyclonedxInstallStrategy, DistInstallSettings,
-    DistInstallStrategy, InstallStrategy, OmniborInstallStrategy,
+    needs_appimagetool, AppimagetoolInstallStrategy, CargoAuditableInstallStrategy,
+    CargoCyclonedxInstallStrategy, DistInstallSettings, DistInstallStrategy, InstallStrategy,
+    OmniborInstallStrategy,
 };
 
 #[cfg(not(windows))]
@@ -73,6 +76,14 @@ pub struct GithubCiInfo {
     pub global_task: GithubGlobalJobConfig,
     /// homebrew tap
     pub tap: Option<String>,
+    /// scoop bucket
+    pub scoop_bucket: Option<String>,
+    /// winget-pkgs fork
+    pub winget_fork: Option<String>,
+    /// AUR git remote
+    pub aur_remote: Option<String>,
+    /// nix repo
+    pub nix_repo: Option<String>,
     /// plan jobs
     pub plan_jobs: Vec<GithubCiJob>,
     /// local artifacts jobs
@@ -91,6 +102,10 @@ pub struct GithubCiInfo {
     pub ssldotcom_windows_sign: Option<ProductionMode>,
     /// Whether to enable macOS codesigning
     pub macos_sign: bool,
+    /// Whether to sign the unified checksum file with minisign
+    pub minisign_sign: bool,
+    /// Env vars the signing providers and detached signatures read secrets from
+    pub signing_secrets: Vec<String>,
     /// what hosting provider we're using
     pub hosting_providers: Vec<HostingStyle>,
     /// whether to prefix release.yml and the tag pattern
@@ -108,6 +123,8 @@ pub struct GithubCiInfo {
     pub need_cargo_cyclonedx: bool,
     /// Whether to install and run omnibor-cli
     pub need_omnibor: bool,
+    /// Whether any local job has to install appimagetool
+    pub need_appimagetool: bool,
 }
 
 /// Details for github releases
@@ -215,6 +232,15 @@ impl GithubCiInfo {
         let release_branch = ci_config.release_branch.clone();
         let ssldotcom_windows_sign = dist.config.builds.ssldotcom_windows_sign.clone();
         let macos_sign = dist.config.builds.macos_sign;
+        let signatures = &dist.config.artifacts.signatures;
+        // Detached minisign signatures already pass the key to every build job
+        let minisign_sign = dist.config.artifacts.minisign_public_key.is_some()
+            && !signatures.contains(&SignatureStyle::Minisign);
+        let signing_secrets =
+            crate::sign::signing_secret_vars(&dist.config.builds.signing, signatures)
+                .into_iter()
+                .map(|var| var.to_owned())
+                .collect();
         let tag_namespace = ci_config.tag_namespace.clone();
         let pr_run_mode = ci_config.pr_run_mode;
 
@@ -278,6 +304,14 @@ impl GithubCiInfo {
         };
 
         let tap = dist.global_homebrew_tap.clone();
+        let scoop_bucket = dist.global_scoop_bucket.clone();
+        let winget_fork = dist.global_winget_fork.clone();
+        let aur_remote = dist
+            .global_publishers
+            .as_ref()
+            .and_then(|p| p.aur.as_ref())
+            .map(|aur| aur.remote.clone());
+        let nix_repo = dist.global_nix_repo.clone();
 
         let mut job_permissions = ci_config.permissions.clone();
         // user publish jobs default to elevated privileges
@@ -300,15 +334,48 @@ impl GithubCiInfo {
             root_permissions.insert("id-token".to_owned(), GithubPermission::Write);
             root_permissions.insert("attestations".to_owned(), GithubPermission::Write);
         }
+        // Sigstore signing trades the job's OIDC token for a certificate
+        if dist
+            .config
+            .artifacts
+            .signatures
+            .contains(&SignatureStyle::Sigstore)
+        {
+            root_permissions.insert("id-token".to_owned(), GithubPermission::Write);
+        }
 
         let mut publish_jobs = vec![];
-        if let Some(PublisherConfig { homebrew, npm }) = &dist.global_publishers {
+        if let Some(PublisherConfig {
+            homebrew,
+            npm,
+            scoop,
+            winget,
+            aur,
+            nix,
+            pypi,
+        }) = &dist.global_publishers
+        {
             if homebrew.is_some() {
                 publish_jobs.push(PublishStyle::Homebrew.to_string());
             }
             if npm.is_some() {
                 publish_jobs.push(PublishStyle::Npm.to_string());
             }
+            if scoop.is_some() {
+                publish_jobs.push(PublishStyle::Scoop.to_string());
+            }
+            if winget.is_some() {
+                publish_jobs.push(PublishStyle::Winget.to_string());
+            }
+            if aur.is_some() {
+                publish_jobs.push(PublishStyle::Aur.to_string());
+            }
+            if nix.is_some() {
+                publish_jobs.push(PublishStyle::Nix.to_string());
+            }
+            if pypi.is_some() {
+                publish_jobs.push(PublishStyle::Pypi.to_string());
+            }
         }
 
         let plan_jobs = build_jobs(&ci_config.plan_jobs, &job_permissions)?;
@@ -338,7 +405,12 @@ impl GithubCiInfo {
             for target in &targets {
                 write!(dist_args, " --target={target}").unwrap();
             }
-            let packages_install = system_deps_install_script(&runner, &targets, &dependencies)?;
+            let packages_install = system_deps_install_script(
+                &real_triple,
+                runner.container.as_ref(),
+                &targets,
+                &dependencies,
+            )?;
             tasks.push(GithubLocalJobConfig {
                 targets: Some(targets.iter().copied().map(|s| s.to_owned()).collect()),
                 cache_provider: cache_provider_for_runner(&runner),
@@ -348,10 +420,14 @@ impl GithubCiInfo {
                 install_cargo_auditable: need_cargo_auditable
                     .then_some(install_cargo_auditable.to_owned()),
                 install_omnibor: need_omnibor.then_some(install_omnibor.to_owned()),
+                install_appimagetool: needs_appimagetool(dist, &targets)
+                    .then(|| AppimagetoolInstallStrategy.for_triple(&real_triple)),
                 packages_install,
             });
         }
 
+        let need_appimagetool = tasks.iter().any(|t| t.install_appimagetool.is_some());
+
         let github_ci_workflow_dir = dist.repo_dir.join(GITHUB_CI_DIR);
         let github_build_setup = ci_config
             .build_setup
@@ -378,6 +454,10 @@ impl GithubCiInfo {
             dispatch_releases,
             release_branch,
             tap,
+            scoop_bucket,
+            winget_fork,
+            aur_remote,
+            nix_repo,
             plan_jobs,
             local_artifacts_jobs,
             global_artifacts_jobs,
@@ -390,6 +470,8 @@ impl GithubCiInfo {
             global_task,
             ssldotcom_windows_sign,
             macos_sign,
+            minisign_sign,
+            signing_secrets,
             hosting_providers,
             root_permissions,
             github_build_setup,
@@ -397,6 +479,7 @@ impl GithubCiInfo {
             need_cargo_auditable,
             need_cargo_cyclonedx,
             need_omnibor,
+            need_appimagetool,
         })
     }
 
@@ -714,8 +797,13 @@ brew bundle install"#,
     )
 }
 
-fn system_deps_install_script(
-    rc: &GithubRunnerConfig,
+/// Compute the script needed to install the build-time system dependencies of
+/// the given targets on a CI machine.
+///
+/// `container` is the container the job runs in, if any.
+pub(crate) fn system_deps_install_script(
+    host: &Triple,
+    container: Option<&ContainerConfig>,
     targets: &[&TripleNameRef],
     packages: &SystemDependencies,
 ) -> DistResult<Option<PackageInstallScript>> {
@@ -724,7 +812,6 @@ fn system_deps_install_script(
     let mut chocolatey_packages: SortedSet<(ChocolateyPackageName, Option<PackageVersion>)> =
         Default::default();
 
-    let host = rc.real_triple();
     match host.operating_system {
         OperatingSystem::Darwin(_) => {
             for (name, pkg) in &packages.homebrew {
@@ -741,8 +828,8 @@ fn system_deps_install_script(
             // We currently don't support non-apt package managers on Linux
             // is_none() means a native build, probably on GitHub's
             // apt-using runners.
-            if rc.container.is_none()
-                || rc.container.as_ref().and_then(|c| c.package_manager)
+            if container.is_none()
+                || container.and_then(|c| c.package_manager)
                     == Some(dist_schema::PackageManager::Apt)
             {
                 for (name, pkg) in &packages.apt {
@@ -791,7 +878,7 @@ fn system_deps_install_script(
 
     // If we're crossing, we'll most likely be running from a container with
     // no sudo. We should avoid calling sudo in that case.
-    let sudo = if rc.container.is_some() { "" } else { "sudo " };
+    let sudo = if container.is_some() { "" } else { "sudo " };
     if !apt_packages.is_empty() {
         lines.push(format!("{sudo}apt-get update"));
         let args = apt_packages
@@ -819,7 +906,7 @@ fn system_deps_install_script(
     let mut required_wrappers: SortedSet<CargoBuildWrapper> = Default::default();
     for target in targets {
         let target = target.parse().unwrap();
-        if let Some(wrapper) = build_wrapper_for_cross(&host, &target)? {
+        if let Some(wrapper) = build_wrapper_for_cross(host, &target)? {
             required_wrappers.insert(wrapper);
         }
     }
@@ -0,0 +1,559 @@
+//! CI script generation for GitLab
+//!
+//! Unlike GitHub, GitLab can't fan a job out over a matrix computed at runtime
+//! (without child pipelines), so the build jobs are rendered statically from
+//! the same [`DistGraph`][] that `dist plan` uses.
+
+use axoasset::LocalAsset;
+use camino::Utf8PathBuf;
+use dist_schema::{
+    target_lexicon::{Architecture, OperatingSystem, Triple},
+    ContainerConfig, ContainerImage, GhaRunStep, PackageInstallScript, TripleName, TripleNameRef,
+};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::{
+    backend::{diff_files, templates::TEMPLATE_CI_GITLAB},
+    config::{
+        v1::{
+            ci::gitlab::{GitlabCiConfig, GitlabIdTokens, GitlabRunnerConfig},
+            publishers::PublisherConfig,
+        },
+        HostingStyle, JobStyle, PublishStyle, SystemDependencies,
+    },
+    errors::DistResult,
+    platform::targets,
+    DistGraph, SortedMap, SortedSet,
+};
+
+use super::{
+    github::system_deps_install_script, needs_appimagetool, AppimagetoolInstallStrategy,
+    CargoAuditableInstallStrategy, CargoCyclonedxInstallStrategy, DistInstallSettings,
+    InstallStrategy, OmniborInstallStrategy,
+};
+
+const GITLAB_CI_FILE: &str = ".gitlab-ci.yml";
+/// Where user-defined jobs are expected to live
+const GITLAB_USER_JOBS_DIR: &str = ".gitlab/ci/";
+
+/// Image used for linux jobs unless the user says otherwise
+const DEFAULT_LINUX_IMAGE: &str = "rust:latest";
+/// Image used for macOS jobs on GitLab's hosted runners
+const DEFAULT_MACOS_IMAGE: &str = "macos-14-xcode-15";
+
+/// Info about running dist in GitLab CI
+///
+/// THESE FIELDS ARE LOAD-BEARING because they're used in the templates.
+#[derive(Debug, Serialize)]
+pub struct GitlabCiInfo {
+    /// Cached path to the .gitlab-ci.yml file
+    #[serde(skip_serializing)]
+    pub gitlab_ci_path: Utf8PathBuf,
+    /// Version of rust toolchain to install (deprecated)
+    pub rust_version: Option<String>,
+    /// How to install dist when "coordinating" (plan, global build, etc.)
+    pub dist_install_for_coordinator: GhaRunStep,
+    /// Whether to cache builds
+    pub cache_builds: bool,
+    /// Whether to include builtin local artifacts tasks
+    pub build_local_artifacts: bool,
+    /// Whether to make CI get dispatched manually instead of by tag
+    pub dispatch_releases: bool,
+    /// Trigger releases on pushes to this branch instead of ci
+    pub release_branch: Option<String>,
+    /// What kind of job to run on merge requests
+    pub pr_run_mode: dist_schema::PrRunMode,
+    /// Regex (in GitLab's `/.../` syntax) that release tags must match
+    pub tag_regex: String,
+    /// `rules:if` expression that holds when the pipeline is publishing a release
+    pub publishing_if: String,
+    /// `rules:if` expression that holds when the pipeline should build artifacts
+    pub build_if: String,
+    /// The runner used for plan/global/host/publish/announce jobs
+    pub global_runner: GitlabRunnerConfig,
+    /// Expression to execute to install cargo-cyclonedx
+    pub install_cargo_cyclonedx: GhaRunStep,
+    /// Expression to execute to install omnibor-cli on the global runner
+    pub install_omnibor: GhaRunStep,
+    /// Jobs that build local artifacts
+    pub local_jobs: Vec<GitlabLocalJob>,
+    /// homebrew tap
+    pub tap: Option<String>,
+    /// scoop bucket
+    pub scoop_bucket: Option<String>,
+    /// winget-pkgs fork
+    pub winget_fork: Option<String>,
+    /// AUR git remote
+    pub aur_remote: Option<String>,
+    /// nix repo
+    pub nix_repo: Option<String>,
+    /// plan jobs
+    pub plan_jobs: Vec<GitlabCiJob>,
+    /// local artifacts jobs
+    pub local_artifacts_jobs: Vec<GitlabCiJob>,
+    /// global artifacts jobs
+    pub global_artifacts_jobs: Vec<GitlabCiJob>,
+    /// host jobs
+    pub host_jobs: Vec<GitlabCiJob>,
+    /// publish jobs
+    pub publish_jobs: Vec<String>,
+    /// user-specified publish jobs
+    pub user_publish_jobs: Vec<GitlabCiJob>,
+    /// post-announce jobs
+    pub post_announce_jobs: Vec<GitlabCiJob>,
+    /// Every user-specified job file that needs to be included
+    pub user_job_includes: Vec<String>,
+    /// what hosting provider we're using
+    pub hosting_providers: Vec<HostingStyle>,
+    /// Whether to install cargo-auditable
+    pub need_cargo_auditable: bool,
+    /// Whether to run cargo-cyclonedx
+    pub need_cargo_cyclonedx: bool,
+    /// Whether to install and run omnibor-cli
+    pub need_omnibor: bool,
+}
+
+/// A job that builds local artifacts for some targets
+#[derive(Debug, Serialize)]
+pub struct GitlabLocalJob {
+    /// Name of the job
+    pub name: String,
+    /// Where to run this job
+    pub runner: GitlabRunnerConfig,
+    /// Whether the job runs under powershell
+    pub powershell: bool,
+    /// Target triples to build for
+    pub targets: Vec<TripleName>,
+    /// Arguments to pass to dist
+    pub dist_args: String,
+    /// Expression to execute to install dist
+    pub install_dist: GhaRunStep,
+    /// Expression to execute to install cargo-auditable
+    pub install_cargo_auditable: Option<GhaRunStep>,
+    /// Expression to execute to install omnibor-cli
+    pub install_omnibor: Option<GhaRunStep>,
+    /// Expression to execute to install appimagetool
+    pub install_appimagetool: Option<GhaRunStep>,
+    /// Command to run to install dependencies
+    pub packages_install: Option<PackageInstallScript>,
+    /// Name of the manifest this job produces
+    pub build_manifest_name: String,
+    /// Key for the build cache
+    pub cache_key: String,
+}
+
+/// A custom ci job
+#[derive(Debug, Serialize)]
+pub struct GitlabCiJob {
+    /// Name of the job
+    pub name: String,
+    /// ID tokens to give the job
+    pub id_tokens: Option<GitlabIdTokens>,
+}
+
+impl GitlabCiInfo {
+    /// Compute the GitLab CI stuff
+    pub fn new(dist: &DistGraph, ci_config: &GitlabCiConfig) -> DistResult<GitlabCiInfo> {
+        // Legacy deprecated support
+        let rust_version = dist.config.builds.cargo.rust_toolchain_version.clone();
+
+        // If they don't specify a dist version, use this one
+        let self_dist_version = super::SELF_DIST_VERSION.parse().unwrap();
+        let dist_version = dist
+            .config
+            .dist_version
+            .as_ref()
+            .unwrap_or(&self_dist_version);
+        let build_local_artifacts = ci_config.build_local_artifacts;
+        let dispatch_releases = ci_config.dispatch_releases;
+        let release_branch = ci_config.release_branch.clone();
+        let pr_run_mode = ci_config.pr_run_mode;
+
+        let caching_could_be_profitable =
+            release_branch.is_some() || pr_run_mode == dist_schema::PrRunMode::Upload;
+        let cache_builds = ci_config
+            .cache_builds
+            .unwrap_or(caching_could_be_profitable);
+
+        let need_cargo_auditable = dist.config.builds.cargo.cargo_auditable;
+        let need_cargo_cyclonedx = dist.config.builds.cargo.cargo_cyclonedx;
+        let need_omnibor = dist.config.builds.omnibor;
+
+        let tag_regex = tag_regex(ci_config.tag_namespace.as_deref());
+        let publishing_if = if let Some(branch) = &release_branch {
+            format!("$CI_COMMIT_BRANCH == \"{branch}\"")
+        } else if dispatch_releases {
+            "$CI_PIPELINE_SOURCE == \"web\" && $DIST_TAG && $DIST_TAG != \"dry-run\"".to_owned()
+        } else {
+            format!("$CI_COMMIT_TAG =~ {tag_regex}")
+        };
+        let mut build_if = publishing_if.clone();
+        if pr_run_mode == dist_schema::PrRunMode::Upload {
+            build_if.push_str(" || $CI_PIPELINE_SOURCE == \"merge_request_event\"");
+        }
+        if dispatch_releases {
+            build_if.push_str(" || $DIST_TAG == \"dry-run\"");
+        }
+
+        // Figure out what builds we need to do
+        let mut dependencies = SystemDependencies::default();
+        let mut local_targets: SortedSet<&TripleNameRef> = SortedSet::new();
+        for release in &dist.releases {
+            for target in &release.targets {
+                local_targets.insert(target);
+            }
+            dependencies.append(&mut release.config.builds.system_dependencies.clone());
+        }
+
+        let dist_install_strategy = (DistInstallSettings {
+            version: dist_version,
+            url_override: dist.config.dist_url_override.as_deref(),
+        })
+        .install_strategy();
+
+        let hosting_providers = dist
+            .hosting
+            .as_ref()
+            .map(|hosting| hosting.hosts.clone())
+            .unwrap_or_default();
+
+        let global_runner = ci_config
+            .runners
+            .get("global")
+            .cloned()
+            .unwrap_or_else(default_global_runner_config);
+
+        let tap = dist.global_homebrew_tap.clone();
+        let scoop_bucket = dist.global_scoop_bucket.clone();
+        let winget_fork = dist.global_winget_fork.clone();
+        let aur_remote = dist
+            .global_publishers
+            .as_ref()
+            .and_then(|p| p.aur.as_ref())
+            .map(|aur| aur.remote.clone());
+        let nix_repo = dist.global_nix_repo.clone();
+
+        let mut publish_jobs = vec![];
+        if let Some(PublisherConfig {
+            homebrew,
+            npm,
+            scoop,
+            winget,
+            aur,
+            nix,
+            pypi,
+        }) = &dist.global_publishers
+        {
+            if homebrew.is_some() {
+                publish_jobs.push(PublishStyle::Homebrew.to_string());
+            }
+            if npm.is_some() {
+                publish_jobs.push(PublishStyle::Npm.to_string());
+            }
+            if scoop.is_some() {
+                publish_jobs.push(PublishStyle::Scoop.to_string());
+            }
+            if winget.is_some() {
+                publish_jobs.push(PublishStyle::Winget.to_string());
+            }
+            if aur.is_some() {
+                publish_jobs.push(PublishStyle::Aur.to_string());
+            }
+            if nix.is_some() {
+                publish_jobs.push(PublishStyle::Nix.to_string());
+            }
+            if pypi.is_some() {
+                publish_jobs.push(PublishStyle::Pypi.to_string());
+            }
+        }
+
+        let plan_jobs = build_jobs(&ci_config.plan_jobs, &ci_config.id_tokens);
+        let local_artifacts_jobs = build_jobs(&ci_config.build_local_jobs, &ci_config.id_tokens);
+        let global_artifacts_jobs = build_jobs(&ci_config.build_global_jobs, &ci_config.id_tokens);
+        let host_jobs = build_jobs(&ci_config.host_jobs, &ci_config.id_tokens);
+        let user_publish_jobs = build_jobs(&ci_config.publish_jobs, &ci_config.id_tokens);
+        let post_announce_jobs = build_jobs(&ci_config.post_announce_jobs, &ci_config.id_tokens);
+
+        let user_job_includes = [
+            &plan_jobs,
+            &local_artifacts_jobs,
+            &global_artifacts_jobs,
+            &host_jobs,
+            &user_publish_jobs,
+            &post_announce_jobs,
+        ]
+        .into_iter()
+        .flatten()
+        .map(|job| format!("{GITLAB_USER_JOBS_DIR}{}.yml", job.name))
+        .collect::<SortedSet<_>>()
+        .into_iter()
+        .collect();
+
+        // Figure out what Local Artifact tasks we need
+        let local_runs = if ci_config.merge_tasks {
+            distribute_targets_to_runners_merged(local_targets, &ci_config.runners)?
+        } else {
+            distribute_targets_to_runners_split(local_targets, &ci_config.runners)?
+        };
+        let mut local_jobs = vec![];
+        for (runner, targets) in local_runs {
+            use std::fmt::Write;
+            let real_triple: Triple = runner.host.parse()?;
+            let powershell = real_triple.operating_system == OperatingSystem::Windows;
+
+            let mut dist_args = String::from("--artifacts=local");
+            for target in &targets {
+                write!(dist_args, " --target={target}").unwrap();
+            }
+            // Jobs that run in an image are run as root with no sudo
+            let container = runner.image.as_ref().map(|image| ContainerConfig {
+                image: ContainerImage::new(image.clone()),
+                host: runner.host.clone(),
+                package_manager: Some(dist_schema::PackageManager::Apt),
+            });
+            let packages_install = system_deps_install_script(
+                &real_triple,
+                container.as_ref(),
+                &targets,
+                &dependencies,
+            )?;
+            let targets_key = targets.iter().map(|t| t.as_str()).collect::<Vec<_>>();
+            local_jobs.push(GitlabLocalJob {
+                name: format!("build-local-artifacts:{}", targets_key.join("_")),
+                powershell,
+                install_dist: dist_install_strategy.for_triple(&real_triple),
+                install_cargo_auditable: need_cargo_auditable
+                    .then(|| CargoAuditableInstallStrategy.for_triple(&real_triple)),
+                install_omnibor: need_omnibor
+                    .then(|| OmniborInstallStrategy.for_triple(&real_triple)),
+                install_appimagetool: needs_appimagetool(dist, &targets)
+                    .then(|| AppimagetoolInstallStrategy.for_triple(&real_triple)),
+                packages_install,
+                build_manifest_name: format!("{}-dist-manifest.json", targets_key.join("-")),
+                cache_key: targets_key.join("-"),
+                targets: targets.iter().map(|t| (*t).to_owned()).collect(),
+                dist_args,
+                runner,
+            });
+        }
+
+        Ok(GitlabCiInfo {
+            gitlab_ci_path: dist.repo_dir.join(GITLAB_CI_FILE),
+            rust_version,
+            dist_install_for_coordinator: dist_install_strategy.dash(),
+            cache_builds,
+            build_local_artifacts,
+            dispatch_releases,
+            release_branch,
+            pr_run_mode,
+            tag_regex,
+            publishing_if,
+            build_if,
+            global_runner,
+            install_cargo_cyclonedx: CargoCyclonedxInstallStrategy.dash(),
+            install_omnibor: OmniborInstallStrategy.dash(),
+            local_jobs,
+            tap,
+            scoop_bucket,
+            winget_fork,
+            aur_remote,
+            nix_repo,
+            plan_jobs,
+            local_artifacts_jobs,
+            global_artifacts_jobs,
+            host_jobs,
+            publish_jobs,
+            user_publish_jobs,
+            post_announce_jobs,
+            user_job_includes,
+            hosting_providers,
+            need_cargo_auditable,
+            need_cargo_cyclonedx,
+            need_omnibor,
+        })
+    }
+
+    /// Generate the requested configuration and returns it as a string.
+    pub fn generate_gitlab_ci(&self, dist: &DistGraph) -> DistResult<String> {
+        let rendered = dist
+            .templates
+            .render_file_to_clean_string(TEMPLATE_CI_GITLAB, self)?;
+
+        Ok(rendered)
+    }
+
+    /// Write .gitlab-ci.yml to disk
+    pub fn write_to_disk(&self, dist: &DistGraph) -> DistResult<()> {
+        let ci_file = &self.gitlab_ci_path;
+        let rendered = self.generate_gitlab_ci(dist)?;
+
+        LocalAsset::write_new_all(&rendered, ci_file)?;
+        eprintln!("generated GitLab CI to {ci_file}");
+
+        Ok(())
+    }
+
+    /// Check whether the new configuration differs from the config on disk
+    /// without actually writing the result.
+    pub fn check(&self, dist: &DistGraph) -> DistResult<()> {
+        let rendered = self.generate_gitlab_ci(dist)?;
+        diff_files(&self.gitlab_ci_path, &rendered)
+    }
+}
+
+/// Get the GitLab regex literal that matches release tags
+fn tag_regex(tag_namespace: Option<&str>) -> String {
+    let mut prefix = String::new();
+    for c in tag_namespace.unwrap_or_default().chars() {
+        if c.is_ascii_punctuation() && c != '-' && c != '_' {
+            prefix.push('\\');
+        }
+        prefix.push(c);
+    }
+    format!(r"/^{prefix}.*[0-9]+\.[0-9]+\.[0-9]+.*$/")
+}
+
+fn build_jobs(
+    jobs: &[JobStyle],
+    id_tokens: &SortedMap<String, GitlabIdTokens>,
+) -> Vec<GitlabCiJob> {
+    jobs.iter()
+        .map(|JobStyle::User(name)| GitlabCiJob {
+            name: name.clone(),
+            id_tokens: id_tokens.get(name).cloned(),
+        })
+        .collect()
+}
+
+/// Given a set of targets we want to build local artifacts for, map them to GitLab runners
+/// while preferring to merge builds that can happen on the same machine.
+fn distribute_targets_to_runners_merged<'a>(
+    targets: SortedSet<&'a TripleNameRef>,
+    custom_runners: &SortedMap<TripleName, GitlabRunnerConfig>,
+) -> DistResult<Vec<(GitlabRunnerConfig, Vec<&'a TripleNameRef>)>> {
+    let mut groups = SortedMap::<GitlabRunnerConfig, Vec<&TripleNameRef>>::new();
+    for target in targets {
+        let runner = gitlab_runner_for_target_or_fallback(target, custom_runners)?;
+        groups.entry(runner).or_default().push(target);
+    }
+    Ok(groups.into_iter().collect())
+}
+
+/// Given a set of targets we want to build local artifacts for, map them to GitLab runners
+/// while preferring each target gets its own runner for latency and fault-isolation.
+fn distribute_targets_to_runners_split<'a>(
+    targets: SortedSet<&'a TripleNameRef>,
+    custom_runners: &SortedMap<TripleName, GitlabRunnerConfig>,
+) -> DistResult<Vec<(GitlabRunnerConfig, Vec<&'a TripleNameRef>)>> {
+    let mut groups = vec![];
+    for target in targets {
+        let runner = gitlab_runner_for_target_or_fallback(target, custom_runners)?;
+        groups.push((runner, vec![target]));
+    }
+    Ok(groups)
+}
+
+fn gitlab_runner_for_target_or_fallback(
+    target: &TripleNameRef,
+    custom_runners: &SortedMap<TripleName, GitlabRunnerConfig>,
+) -> DistResult<GitlabRunnerConfig> {
+    let runner = gitlab_runner_for_target(target, custom_runners)?;
+    Ok(runner.unwrap_or_else(|| {
+        let fallback = default_global_runner_config();
+        warn!(
+            "not sure which gitlab runner should be used for {target}, assuming {}",
+            fallback.tags.join(",")
+        );
+        fallback
+    }))
+}
+
+fn linux_runner(tag: &str, host: &TripleNameRef) -> GitlabRunnerConfig {
+    GitlabRunnerConfig {
+        tags: vec![tag.to_owned()],
+        image: Some(DEFAULT_LINUX_IMAGE.to_owned()),
+        host: host.to_owned(),
+    }
+}
+
+fn default_global_runner_config() -> GitlabRunnerConfig {
+    linux_runner("saas-linux-small-amd64", targets::TARGET_X64_LINUX_GNU)
+}
+
+/// Get the appropriate GitLab runner for building a target
+///
+/// The defaults are GitLab.com's hosted runners; self-hosted instances will
+/// want to set `runners` in their config.
+fn gitlab_runner_for_target(
+    target: &TripleNameRef,
+    custom_runners: &SortedMap<TripleName, GitlabRunnerConfig>,
+) -> DistResult<Option<GitlabRunnerConfig>> {
+    if let Some(runner) = custom_runners.get(target) {
+        return Ok(Some(runner.clone()));
+    }
+
+    let target_triple: Triple = target.parse()?;
+
+    let result = Some(match target_triple.operating_system {
+        OperatingSystem::Linux => {
+            if matches!(target_triple.architecture, Architecture::Aarch64(_)) {
+                linux_runner("saas-linux-medium-arm64", targets::TARGET_ARM64_LINUX_GNU)
+            } else {
+                linux_runner("saas-linux-medium-amd64", targets::TARGET_X64_LINUX_GNU)
+            }
+        }
+        OperatingSystem::Darwin(_) => GitlabRunnerConfig {
+            tags: vec!["saas-macos-medium-m1".to_owned()],
+            image: Some(DEFAULT_MACOS_IMAGE.to_owned()),
+            host: targets::TARGET_ARM64_MAC.to_owned(),
+        },
+        OperatingSystem::Windows => {
+            // Default to cargo-xwin for Windows cross-compiles
+            if target_triple.architecture != Architecture::X86_64 {
+                GitlabRunnerConfig {
+                    tags: vec!["saas-linux-medium-amd64".to_owned()],
+                    image: Some("messense/cargo-xwin".to_owned()),
+                    host: targets::TARGET_X64_LINUX_MUSL.to_owned(),
+                }
+            } else {
+                GitlabRunnerConfig {
+                    tags: vec!["saas-windows-medium-amd64".to_owned()],
+                    image: None,
+                    host: targets::TARGET_X64_WINDOWS.to_owned(),
+                }
+            }
+        }
+        _ => return Ok(None),
+    });
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_regex_escapes_namespace() {
+        assert_eq!(tag_regex(None), r"/^.*[0-9]+\.[0-9]+\.[0-9]+.*$/");
+        assert_eq!(
+            tag_regex(Some("my-app/v")),
+            r"/^my-app\/v.*[0-9]+\.[0-9]+\.[0-9]+.*$/"
+        );
+    }
+
+    #[test]
+    fn default_runners() {
+        let runners = SortedMap::new();
+        let arm = gitlab_runner_for_target(targets::TARGET_ARM64_LINUX_GNU, &runners)
+            .unwrap()
+            .unwrap();
+        assert_eq!(arm.tags, ["saas-linux-medium-arm64"]);
+        let win = gitlab_runner_for_target(targets::TARGET_X64_WINDOWS, &runners)
+            .unwrap()
+            .unwrap();
+        assert_eq!(win.image, None);
+        assert_eq!(win.host, targets::TARGET_X64_WINDOWS);
+    }
+}
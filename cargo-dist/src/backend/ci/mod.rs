@@ -2,16 +2,20 @@
 
 use dist_schema::{
     target_lexicon::{OperatingSystem, Triple},
-    DashScript, GhaRunStep, PowershellScript,
+    DashScript, GhaRunStep, PowershellScript, TripleNameRef,
 };
 use semver::Version;
 use serde::Serialize;
 
+use crate::backend::installer::appimage::appimage_architecture;
 use crate::config::v0::CargoDistUrlOverrideRef;
+use crate::DistGraph;
 
 use self::github::GithubCiInfo;
+use self::gitlab::GitlabCiInfo;
 
 pub mod github;
+pub mod gitlab;
 
 /// The current version of dist
 const SELF_DIST_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -38,11 +42,20 @@ const BASE_OMNIBOR_FETCH_URL: &str = "https://github.com/omnibor/omnibor-rs/rele
 // SEE ALSO: .github/workflows/ci.yml
 const OMNIBOR_VERSION: &str = "0.7.0";
 
+const BASE_APPIMAGETOOL_FETCH_URL: &str =
+    "https://github.com/AppImage/appimagetool/releases/download";
+
+// NOTE: This is hard-coded to a specific version so that AppImages
+//       don't change out from under users when appimagetool does.
+const APPIMAGETOOL_VERSION: &str = "1.9.0";
+
 /// Info about all the enabled CI backends
 #[derive(Debug, Default)]
 pub struct CiInfo {
     /// Github CI
     pub github: Option<GithubCiInfo>,
+    /// GitLab CI
+    pub gitlab: Option<GitlabCiInfo>,
 }
 
 /// Gives us the full information re: the version of dist we're supposed
@@ -214,3 +227,40 @@ impl InstallStrategy for OmniborInstallStrategy {
         PowershellScript::new(format!(r#"powershell -c "irm {installer_url} | iex""#)).into()
     }
 }
+
+/// Whether a job building these targets will build an AppImage (and so needs appimagetool)
+fn needs_appimagetool(dist: &DistGraph, targets: &[&TripleNameRef]) -> bool {
+    dist.releases.iter().any(|release| {
+        release.config.installers.appimage.is_some()
+            && targets.iter().any(|target| {
+                appimage_architecture(target).is_some()
+                    && release
+                        .targets
+                        .iter()
+                        .any(|t| t.as_str() == target.as_str())
+            })
+    })
+}
+
+struct AppimagetoolInstallStrategy;
+
+impl InstallStrategy for AppimagetoolInstallStrategy {
+    /// Return an sh/dash script to install appimagetool
+    fn dash(&self) -> GhaRunStep {
+        let url = format!(
+            "{BASE_APPIMAGETOOL_FETCH_URL}/{APPIMAGETOOL_VERSION}/appimagetool-$(uname -m).AppImage"
+        );
+        DashScript::new(format!(
+            r#"mkdir -p "${{CARGO_HOME:-$HOME/.cargo}}/bin" && curl --proto '=https' --tlsv1.2 -LsSf "{url}" -o "${{CARGO_HOME:-$HOME/.cargo}}/bin/appimagetool" && chmod +x "${{CARGO_HOME:-$HOME/.cargo}}/bin/appimagetool""#
+        ))
+        .into()
+    }
+
+    /// AppImages are only built on linux, so this shouldn't be used.
+    fn powershell(&self) -> GhaRunStep {
+        PowershellScript::new(
+            r#"Write-Error "appimagetool can only be installed on linux"; exit 1"#.to_owned(),
+        )
+        .into()
+    }
+}
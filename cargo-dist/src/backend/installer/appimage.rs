@@ -0,0 +1,241 @@
+//! Code for generating AppImages
+//!
+//! An AppImage is a squashfs image of an "AppDir" with a small runtime in
+//! front of it that mounts the image and runs its `AppRun`. We lay out the
+//! AppDir ourselves (the executables, any libraries they need that can't be
+//! expected on the user's system, a `.desktop` file and an icon) and hand it
+//! to `appimagetool` to do the packing.
+
+use axoasset::LocalAsset;
+use axoprocess::Cmd;
+use camino::{Utf8Path, Utf8PathBuf};
+use dist_schema::{
+    target_lexicon::{Architecture, ArmArchitecture, Environment},
+    DistManifest, Linkage, TripleNameRef,
+};
+use goblin::elf::Elf;
+use serde::Serialize;
+use tracing::info;
+
+use crate::{
+    backend::templates::TEMPLATE_INSTALLER_APPIMAGE, create_tmp, DistGraph, DistResult, SortedMap,
+};
+
+use super::ExecutableZipFragment;
+
+const APP_RUN: &str = "AppRun";
+const DESKTOP_FILE: &str = "app.desktop";
+const DEFAULT_ICON: &str = "icon.svg";
+
+/// Info about an AppImage
+#[derive(Debug, Clone, Serialize)]
+pub struct AppimageInstallerInfo {
+    /// ExecutableZipFragment for this variant
+    pub artifact: ExecutableZipFragment,
+    /// Final file path of the AppImage
+    pub file_path: Utf8PathBuf,
+    /// Dir stuff goes to
+    pub package_dir: Utf8PathBuf,
+    /// The name of the app
+    pub app_name: String,
+    /// A brief description of the app
+    pub description: Option<String>,
+    /// The executable the AppImage runs
+    pub main_exe: String,
+    /// The AppImage architecture of the image (e.g. "x86_64")
+    pub architecture: String,
+    /// A png or svg icon for the app
+    pub icon: Option<Utf8PathBuf>,
+    /// A .desktop file to use instead of the generated one
+    pub desktop_file: Option<Utf8PathBuf>,
+    /// The categories to list the app under
+    pub categories: Vec<String>,
+    /// Whether the app should be run in a terminal
+    pub terminal: bool,
+}
+
+impl AppimageInstallerInfo {
+    /// Build the AppImage
+    pub fn build(&self, dist: &DistGraph, manifest: &DistManifest) -> DistResult<()> {
+        info!("building an AppImage: {}", self.file_path);
+        let appimagetool = dist.tools.appimagetool()?;
+
+        let (_build_dir, build_dir) = create_tmp()?;
+        let app_dir = build_dir.join(format!("{}.AppDir", self.app_name));
+        let bin_dir = app_dir.join("usr/bin");
+        LocalAsset::create_dir_all(&bin_dir)?;
+        let lib_dir = app_dir.join("usr/lib");
+        LocalAsset::create_dir_all(&lib_dir)?;
+
+        for exe in &self.artifact.executables {
+            LocalAsset::copy_file_to_file(self.package_dir.join(exe), bin_dir.join(exe))?;
+        }
+        let linkage = manifest.linkage_for_artifact(&self.artifact.id);
+        for lib in bundled_libraries(&linkage) {
+            let name = library_file_name(&lib)?;
+            info!("bundling {lib} as {name}");
+            LocalAsset::copy_file_to_file(&lib, lib_dir.join(name))?;
+        }
+
+        let mut files = dist
+            .templates
+            .render_dir_to_clean_strings(TEMPLATE_INSTALLER_APPIMAGE, self)?;
+        self.mangle_files(&mut files)?;
+        for (relpath, rendered) in files {
+            LocalAsset::write_new_all(&rendered, app_dir.join(relpath))?;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let perms = std::fs::Permissions::from_mode(0o755);
+            std::fs::set_permissions(app_dir.join(APP_RUN), perms)?;
+        }
+        if let Some(icon) = &self.icon {
+            let name = format!("{}.{}", self.app_name, icon.extension().unwrap_or("png"));
+            LocalAsset::copy_file_to_file(icon, app_dir.join(name))?;
+            LocalAsset::copy_file_to_file(icon, app_dir.join(".DirIcon"))?;
+        }
+
+        let mut cmd = Cmd::new(&appimagetool.cmd, "build an AppImage");
+        // appimagetool is itself an AppImage, which can't be mounted in most containers
+        cmd.env("APPIMAGE_EXTRACT_AND_RUN", "1");
+        cmd.env("ARCH", &self.architecture);
+        cmd.arg("--no-appstream");
+        cmd.arg(&app_dir).arg(&self.file_path);
+        // Ensures stdout from the build process doesn't taint the dist-manifest
+        cmd.stdout_to_stderr();
+        cmd.run()?;
+
+        Ok(())
+    }
+
+    /// Give the templated files their final names, swapping in the user's own
+    /// desktop file and icon if they have them
+    fn mangle_files(&self, files: &mut SortedMap<Utf8PathBuf, String>) -> DistResult<()> {
+        let desktop = files
+            .remove(Utf8Path::new(DESKTOP_FILE))
+            .expect("appimage template didn't have a desktop file!?");
+        let desktop = if let Some(desktop_file) = &self.desktop_file {
+            LocalAsset::load_string(desktop_file)?
+        } else {
+            desktop
+        };
+        files.insert(
+            Utf8PathBuf::from(format!("{}.desktop", self.app_name)),
+            desktop,
+        );
+
+        let icon = files
+            .remove(Utf8Path::new(DEFAULT_ICON))
+            .expect("appimage template didn't have an icon!?");
+        if self.icon.is_none() {
+            files.insert(Utf8PathBuf::from(".DirIcon"), icon.clone());
+            files.insert(Utf8PathBuf::from(format!("{}.svg", self.app_name)), icon);
+        }
+        Ok(())
+    }
+}
+
+/// Get the libraries an AppImage has to bundle for its executables to run
+///
+/// That's everything linkage checking found that isn't part of the system or
+/// provided by a package manager, as we can't expect users to have those.
+pub fn bundled_libraries(linkage: &Linkage) -> Vec<Utf8PathBuf> {
+    linkage
+        .public_unmanaged
+        .iter()
+        .chain(linkage.other.iter())
+        .filter(|lib| lib.package_manager.is_none())
+        .map(|lib| Utf8PathBuf::from(&lib.path))
+        .collect()
+}
+
+/// Get the name the dynamic loader will look for a library under
+///
+/// Linkage checking records where symlinks like `libfoo.so.1` resolve to, so
+/// we use the library's soname to get the name back.
+fn library_file_name(path: &Utf8Path) -> DistResult<String> {
+    let buf = LocalAsset::load_bytes(path)?;
+    let elf = Elf::parse(&buf)?;
+    let name = match elf.soname {
+        Some(soname) => soname.to_owned(),
+        None => path.file_name().unwrap_or_default().to_owned(),
+    };
+    Ok(name)
+}
+
+/// Get the AppImage architecture for a target, if there is one
+pub fn appimage_architecture(target: &TripleNameRef) -> Option<&'static str> {
+    if !target.is_linux() {
+        return None;
+    }
+    let triple = target.parse().ok()?;
+    let hard_float = matches!(
+        triple.environment,
+        Environment::Gnueabihf | Environment::Musleabihf
+    );
+    let arch = match triple.architecture {
+        Architecture::X86_64 => "x86_64",
+        Architecture::X86_32(_) => "i686",
+        Architecture::Aarch64(_) => "aarch64",
+        Architecture::Arm(ArmArchitecture::Armv7) if hard_float => "armhf",
+        _ => return None,
+    };
+    Some(arch)
+}
+
+#[cfg(test)]
+mod tests {
+    use dist_schema::{Library, PackageManager, TripleName};
+
+    use super::*;
+
+    fn run_arch_comparison(in_str: &str, expected: Option<&str>) {
+        let out = appimage_architecture(&TripleName::new(in_str.to_owned()));
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn arch_common() {
+        run_arch_comparison("x86_64-unknown-linux-gnu", Some("x86_64"));
+        run_arch_comparison("x86_64-unknown-linux-musl", Some("x86_64"));
+        run_arch_comparison("i686-unknown-linux-gnu", Some("i686"));
+        run_arch_comparison("aarch64-unknown-linux-gnu", Some("aarch64"));
+        run_arch_comparison("armv7-unknown-linux-gnueabihf", Some("armhf"));
+        run_arch_comparison("arm-unknown-linux-gnueabi", None);
+        run_arch_comparison("x86_64-pc-windows-msvc", None);
+        run_arch_comparison("aarch64-apple-darwin", None);
+    }
+
+    #[test]
+    fn bundles_unmanaged_libraries() {
+        let library = |path: &str, package_manager| Library {
+            path: path.to_owned(),
+            source: None,
+            package_manager,
+        };
+        let mut linkage = Linkage::default();
+        linkage
+            .system
+            .insert(library("/usr/lib/x86_64-linux-gnu/libc.so.6", None));
+        linkage
+            .public_unmanaged
+            .insert(library("/usr/local/lib/libfoo.so.1.2.3", None));
+        linkage
+            .other
+            .insert(library("/opt/bar/lib/libbar.so.4", None));
+        linkage.other.insert(library(
+            "/opt/baz/lib/libbaz.so.5",
+            Some(PackageManager::Apt),
+        ));
+
+        assert_eq!(
+            bundled_libraries(&linkage),
+            vec![
+                Utf8PathBuf::from("/usr/local/lib/libfoo.so.1.2.3"),
+                Utf8PathBuf::from("/opt/bar/lib/libbar.so.4"),
+            ]
+        );
+    }
+}
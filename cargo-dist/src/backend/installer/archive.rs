@@ -0,0 +1,56 @@
+//! Archive writers shared by the installers that package files up themselves
+
+use flate2::{write::GzEncoder, Compression};
+
+use crate::DistResult;
+
+/// Writes a gzipped tarball with all files owned by root
+pub(crate) struct TarBuilder {
+    builder: tar::Builder<GzEncoder<Vec<u8>>>,
+    mtime: u64,
+}
+
+impl TarBuilder {
+    pub(crate) fn new(mtime: u64) -> Self {
+        Self {
+            builder: tar::Builder::new(GzEncoder::new(vec![], Compression::default())),
+            mtime,
+        }
+    }
+
+    fn header(&self, entry_type: tar::EntryType, mode: u32, size: u64) -> DistResult<tar::Header> {
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(entry_type);
+        header.set_mode(mode);
+        header.set_size(size);
+        header.set_mtime(self.mtime);
+        header.set_uid(0);
+        header.set_gid(0);
+        header.set_username("root")?;
+        header.set_groupname("root")?;
+        Ok(header)
+    }
+
+    pub(crate) fn dir(&mut self, path: &str) -> DistResult<()> {
+        let mut header = self.header(tar::EntryType::Directory, 0o755, 0)?;
+        self.builder
+            .append_data(&mut header, path, std::io::empty())?;
+        Ok(())
+    }
+
+    pub(crate) fn file(&mut self, path: &str, mode: u32, contents: &[u8]) -> DistResult<()> {
+        let mut header = self.header(tar::EntryType::Regular, mode, contents.len() as u64)?;
+        self.builder.append_data(&mut header, path, contents)?;
+        Ok(())
+    }
+
+    pub(crate) fn symlink(&mut self, path: &str, target: &str) -> DistResult<()> {
+        let mut header = self.header(tar::EntryType::Symlink, 0o777, 0)?;
+        self.builder.append_link(&mut header, path, target)?;
+        Ok(())
+    }
+
+    pub(crate) fn finish(self) -> DistResult<Vec<u8>> {
+        Ok(self.builder.into_inner()?.finish()?)
+    }
+}
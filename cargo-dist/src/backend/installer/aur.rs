@@ -0,0 +1,520 @@
+//! Code for generating AUR packages
+//!
+//! An AUR package is a git repo containing a `PKGBUILD` (a bash script that
+//! makepkg sources to learn how to build the package) and a `.SRCINFO` (the
+//! same metadata in a format the AUR can parse without running bash). We make
+//! a `-bin` package that repackages the Linux archives from the release's
+//! hosting, so we fill in the hashes from the checksums we computed for them.
+
+use std::fmt::Write;
+
+use axoasset::LocalAsset;
+use camino::Utf8PathBuf;
+use dist_schema::{ArtifactId, DistManifest};
+use serde::Serialize;
+use spdx::{
+    expression::{ExprNode, Operator},
+    Expression, ParseError,
+};
+use tracing::info;
+
+use crate::{config::ChecksumStyle, DistResult};
+
+/// The checksums makepkg understands, and the PKGBUILD array each goes in
+const AUR_CHECKSUMS: &[(ChecksumStyle, &str)] = &[
+    (ChecksumStyle::Sha256, "sha256sums"),
+    (ChecksumStyle::Sha512, "sha512sums"),
+    (ChecksumStyle::Blake2b, "b2sums"),
+];
+
+/// Info about an AUR package file
+#[derive(Debug, Clone, Serialize)]
+pub struct AurInstallerInfo {
+    /// Which of the package's files this is
+    pub file_type: AurFileType,
+    /// Final file path of the file
+    pub file_path: Utf8PathBuf,
+    /// The name of the package (e.g. "axolotlsay-bin")
+    pub pkgname: String,
+    /// The version of the package, in pkgver format
+    pub pkgver: String,
+    /// The maintainer of the package
+    pub maintainer: Option<String>,
+    /// A brief description of the package
+    pub pkgdesc: String,
+    /// The URL to the package's homepage
+    pub url: Option<String>,
+    /// The licenses of the package
+    pub license: Vec<String>,
+    /// The name of the app, which the package provides
+    pub provides: String,
+    /// The URL the archives can be downloaded from (append the artifact name)
+    pub base_url: String,
+    /// The archives the package repackages
+    pub sources: Vec<AurSource>,
+    /// Aliases to create for executables, as `(alias, executable)` pairs
+    pub aliases: Vec<(String, String)>,
+}
+
+/// The files in an AUR package
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum AurFileType {
+    /// The `PKGBUILD`
+    Pkgbuild,
+    /// The `.SRCINFO`
+    Srcinfo,
+}
+
+impl AurFileType {
+    /// Get the name of this file in our dist dir for the given package
+    pub fn file_name(self, pkgname: &str) -> String {
+        match self {
+            AurFileType::Pkgbuild => format!("{pkgname}.PKGBUILD"),
+            AurFileType::Srcinfo => format!("{pkgname}.SRCINFO"),
+        }
+    }
+}
+
+/// An archive an AUR package repackages
+#[derive(Debug, Clone, Serialize)]
+pub struct AurSource {
+    /// The id of the archive
+    pub id: ArtifactId,
+    /// The architecture of the archive, in makepkg terms
+    pub arch: String,
+    /// The name to save the archive as
+    pub file_name: String,
+    /// The dir in the archive the executables are in, if not the root
+    pub root_dir: Option<String>,
+    /// The executables in the archive
+    pub executables: Vec<String>,
+}
+
+impl AurInstallerInfo {
+    /// Write the file to disk
+    pub fn build(&self, manifest: &DistManifest) -> DistResult<()> {
+        info!("writing an AUR package file: {}", self.file_path);
+        let contents = match self.file_type {
+            AurFileType::Pkgbuild => self.render_pkgbuild(manifest),
+            AurFileType::Srcinfo => self.render_srcinfo(manifest),
+        };
+        LocalAsset::write_new(&contents, &self.file_path)?;
+        Ok(())
+    }
+
+    /// Render the `PKGBUILD`
+    fn render_pkgbuild(&self, manifest: &DistManifest) -> String {
+        let mut out = String::new();
+        if let Some(maintainer) = &self.maintainer {
+            writeln!(out, "# Maintainer: {}", maintainer.replace('\n', " ")).unwrap();
+        }
+        writeln!(out, "pkgname={}", self.pkgname).unwrap();
+        writeln!(out, "pkgver={}", self.pkgver).unwrap();
+        writeln!(out, "pkgrel=1").unwrap();
+        writeln!(out, "pkgdesc={}", sh_quote(&self.pkgdesc)).unwrap();
+        if let Some(url) = &self.url {
+            writeln!(out, "url={}", sh_quote(url)).unwrap();
+        }
+        writeln!(
+            out,
+            "arch=({})",
+            sh_array(self.sources.iter().map(|s| &s.arch))
+        )
+        .unwrap();
+        writeln!(out, "license=({})", sh_array(&self.license)).unwrap();
+        writeln!(out, "provides=({})", sh_quote(&self.provides)).unwrap();
+        writeln!(out, "conflicts=({})", sh_quote(&self.provides)).unwrap();
+        for source in &self.sources {
+            writeln!(out).unwrap();
+            writeln!(
+                out,
+                "source_{}=(\"{}::{}/{}\")",
+                source.arch, source.file_name, self.base_url, source.id
+            )
+            .unwrap();
+            for (array, sum) in self.checksums(manifest, source) {
+                writeln!(out, "{array}_{}=('{sum}')", source.arch).unwrap();
+            }
+        }
+        writeln!(out).unwrap();
+        writeln!(out, "package() {{").unwrap();
+        writeln!(out, "  case \"$CARCH\" in").unwrap();
+        for source in &self.sources {
+            writeln!(out, "    {})", source.arch).unwrap();
+            if let Some(root_dir) = &source.root_dir {
+                writeln!(out, "      cd \"$srcdir/{root_dir}\"").unwrap();
+            } else {
+                writeln!(out, "      cd \"$srcdir\"").unwrap();
+            }
+            for exe in &source.executables {
+                writeln!(out, "      install -Dm755 {exe} \"$pkgdir/usr/bin/{exe}\"").unwrap();
+            }
+            for (alias, exe) in &self.aliases {
+                if source.executables.contains(exe) {
+                    writeln!(out, "      ln -s {exe} \"$pkgdir/usr/bin/{alias}\"").unwrap();
+                }
+            }
+            writeln!(out, "      ;;").unwrap();
+        }
+        writeln!(out, "  esac").unwrap();
+        writeln!(out, "}}").unwrap();
+        out
+    }
+
+    /// Render the `.SRCINFO`
+    fn render_srcinfo(&self, manifest: &DistManifest) -> String {
+        let mut out = String::new();
+        writeln!(out, "pkgbase = {}", self.pkgname).unwrap();
+        writeln!(out, "\tpkgdesc = {}", self.pkgdesc).unwrap();
+        writeln!(out, "\tpkgver = {}", self.pkgver).unwrap();
+        writeln!(out, "\tpkgrel = 1").unwrap();
+        if let Some(url) = &self.url {
+            writeln!(out, "\turl = {url}").unwrap();
+        }
+        for source in &self.sources {
+            writeln!(out, "\tarch = {}", source.arch).unwrap();
+        }
+        for license in &self.license {
+            writeln!(out, "\tlicense = {license}").unwrap();
+        }
+        writeln!(out, "\tprovides = {}", self.provides).unwrap();
+        writeln!(out, "\tconflicts = {}", self.provides).unwrap();
+        for source in &self.sources {
+            writeln!(
+                out,
+                "\tsource_{} = {}::{}/{}",
+                source.arch, source.file_name, self.base_url, source.id
+            )
+            .unwrap();
+            for (array, sum) in self.checksums(manifest, source) {
+                writeln!(out, "\t{array}_{} = {sum}", source.arch).unwrap();
+            }
+        }
+        writeln!(out).unwrap();
+        writeln!(out, "pkgname = {}", self.pkgname).unwrap();
+        out
+    }
+
+    /// Get the `(array, checksum)` pairs to list for a source
+    ///
+    /// We only emit the kinds of checksum we have for *every* source, because
+    /// makepkg requires each array to cover all of them. If we have none
+    /// (e.g. we didn't actually build the archives) we tell makepkg to SKIP.
+    fn checksums(&self, manifest: &DistManifest, source: &AurSource) -> Vec<(&str, String)> {
+        let lookup = |id: &ArtifactId, style: ChecksumStyle| {
+            manifest
+                .artifacts
+                .get(id)
+                .and_then(|a| a.checksums.get(style.ext()))
+        };
+        let sums = AUR_CHECKSUMS
+            .iter()
+            .filter(|(style, _)| self.sources.iter().all(|s| lookup(&s.id, *style).is_some()))
+            .filter_map(|(style, array)| Some((*array, lookup(&source.id, *style)?.to_string())))
+            .collect::<Vec<_>>();
+        if sums.is_empty() {
+            vec![("sha256sums", "SKIP".to_owned())]
+        } else {
+            sums
+        }
+    }
+}
+
+/// Quote a string for bash
+fn sh_quote(input: &str) -> String {
+    format!("'{}'", input.replace('\'', r"'\''"))
+}
+
+/// Quote the items of a bash array
+fn sh_array<S: AsRef<str>>(items: impl IntoIterator<Item = S>) -> String {
+    items
+        .into_iter()
+        .map(|s| sh_quote(s.as_ref()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Get the makepkg architecture for a target triple, if it has one we support
+pub fn aur_architecture(target: &str) -> Option<&'static str> {
+    if target.starts_with("x86_64-") {
+        Some("x86_64")
+    } else if target.starts_with("aarch64-") {
+        Some("aarch64")
+    } else {
+        None
+    }
+}
+
+/// Get the AUR package name for an app
+///
+/// Packages that repackage prebuilt binaries are conventionally suffixed with `-bin`.
+pub fn aur_package_name(app_name: &str) -> String {
+    format!("{}-bin", app_name.to_lowercase())
+}
+
+/// Convert a version into pkgver format, which doesn't allow dashes
+///
+/// Prerelease versions are written the way Arch packages conventionally
+/// write them, so `0.1.0-beta.1` becomes `0.1.0beta1`, which pacman sorts
+/// before `0.1.0`.
+pub fn aur_package_version(version: &str) -> String {
+    let version = version
+        .split_once('+')
+        .map_or(version, |(version, _)| version);
+    match version.split_once('-') {
+        Some((release, pre)) => {
+            let pre = pre
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric())
+                .collect::<String>();
+            format!("{release}{pre}")
+        }
+        None => version.to_owned(),
+    }
+}
+
+/// Flatten a description onto one line, since pkgdesc can't span lines
+pub fn aur_package_description(desc: &str) -> String {
+    desc.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Converts SPDX license string into entries for a PKGBUILD `license` array
+///
+/// Arch uses SPDX expressions in the array, with each entry being
+/// required, so licenses that must all be satisfied get their own entries.
+// Arch licensing reference: https://rfc.archlinux.page/0016-spdx-license-identifiers/
+pub fn to_aur_license_format(app_license: &str) -> Result<Vec<String>, ParseError> {
+    enum Node {
+        License(String),
+        Op(Operator, Vec<Node>),
+    }
+
+    fn render(node: &Node) -> String {
+        match node {
+            Node::License(license) => license.clone(),
+            Node::Op(op, operands) => {
+                let separator = match op {
+                    Operator::And => " AND ",
+                    Operator::Or => " OR ",
+                };
+                operands
+                    .iter()
+                    .map(|operand| match operand {
+                        Node::License(_) => render(operand),
+                        // Operands were squashed together with their parent if they
+                        // had the same operator, so this is always a different one
+                        Node::Op(..) => format!("({})", render(operand)),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(separator)
+            }
+        }
+    }
+
+    let spdx = Expression::parse(app_license)?;
+    let mut buffer: Vec<Node> = vec![];
+    for token in spdx.iter() {
+        match token {
+            ExprNode::Req(req) => buffer.push(Node::License(req.req.to_string())),
+            ExprNode::Op(op) => {
+                // Operations are postfix, so we pop off the previous two elements and combine,
+                // squashing operands with the same operation into one list.
+                let second_operand = buffer.pop().expect("Operator missing second operand.");
+                let first_operand = buffer.pop().expect("Operator missing first operand.");
+                let mut operands = vec![];
+                for operand in [first_operand, second_operand] {
+                    match operand {
+                        Node::Op(inner_op, inner) if inner_op == *op => operands.extend(inner),
+                        other => operands.push(other),
+                    }
+                }
+                buffer.push(Node::Op(*op, operands));
+            }
+        }
+    }
+
+    // After all tokens have been iterated through, if the SPDX expression is well-formed, there
+    // should only be a single element left in the buffer: a single license or outermost clause.
+    let licenses = match buffer.pop().expect("SPDX expression was empty") {
+        Node::Op(Operator::And, operands) => operands.iter().map(render).collect(),
+        node => vec![render(&node)],
+    };
+    Ok(licenses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dist_schema::{Artifact, ArtifactKind, ChecksumExtension, ChecksumValue};
+
+    fn info(file_type: AurFileType) -> AurInstallerInfo {
+        let pkgname = "axolotlsay-bin".to_owned();
+        AurInstallerInfo {
+            file_type,
+            file_path: Utf8PathBuf::from(file_type.file_name(&pkgname)),
+            pkgname,
+            pkgver: "0.2.2".to_owned(),
+            maintainer: Some("axodotdev <hello@axo.dev>".to_owned()),
+            pkgdesc: "💬 a CLI for learning to distribute CLIs in rust".to_owned(),
+            url: Some("https://github.com/axodotdev/axolotlsay".to_owned()),
+            license: vec!["MIT OR Apache-2.0".to_owned()],
+            provides: "axolotlsay".to_owned(),
+            base_url: "https://github.com/axodotdev/axolotlsay/releases/download/v0.2.2".to_owned(),
+            sources: vec![
+                AurSource {
+                    id: ArtifactId::new("axolotlsay-x86_64-unknown-linux-gnu.tar.xz".to_owned()),
+                    arch: "x86_64".to_owned(),
+                    file_name: "axolotlsay-bin-0.2.2-x86_64.tar.xz".to_owned(),
+                    root_dir: Some("axolotlsay-x86_64-unknown-linux-gnu".to_owned()),
+                    executables: vec!["axolotlsay".to_owned()],
+                },
+                AurSource {
+                    id: ArtifactId::new("axolotlsay-aarch64-unknown-linux-gnu.tar.xz".to_owned()),
+                    arch: "aarch64".to_owned(),
+                    file_name: "axolotlsay-bin-0.2.2-aarch64.tar.xz".to_owned(),
+                    root_dir: Some("axolotlsay-aarch64-unknown-linux-gnu".to_owned()),
+                    executables: vec!["axolotlsay".to_owned()],
+                },
+            ],
+            aliases: vec![("axo".to_owned(), "axolotlsay".to_owned())],
+        }
+    }
+
+    fn manifest(hashes: &[(&str, &str, &str)]) -> DistManifest {
+        let mut manifest = DistManifest::new(vec![], Default::default());
+        for (id, style, hash) in hashes {
+            let id = ArtifactId::new((*id).to_owned());
+            let artifact = manifest.artifacts.entry(id.clone()).or_insert(Artifact {
+                name: Some(id),
+                kind: ArtifactKind::ExecutableZip,
+                target_triples: vec![],
+                path: None,
+                assets: vec![],
+                install_hint: None,
+                description: None,
+                checksum: None,
+                checksums: Default::default(),
+                signed_by: Default::default(),
+                signatures: Default::default(),
+            });
+            artifact.checksums.insert(
+                ChecksumExtension::new((*style).to_owned()),
+                ChecksumValue::new((*hash).to_owned()),
+            );
+        }
+        manifest
+    }
+
+    #[test]
+    fn test_pkgbuild() {
+        let manifest = manifest(&[
+            (
+                "axolotlsay-x86_64-unknown-linux-gnu.tar.xz",
+                "sha256",
+                "0f9a2f5f9d5fb6ed5ab83dba2fa72b1ae2b4bbc4e1e2c3c9c6b1d3b1d8f4a9e1",
+            ),
+            (
+                "axolotlsay-aarch64-unknown-linux-gnu.tar.xz",
+                "sha256",
+                "9c3d0a8f6b8f7e3e0d1f4c5b2a6e7d8c9b0a1f2e3d4c5b6a7f8e9d0c1b2a3f4e",
+            ),
+            (
+                "axolotlsay-x86_64-unknown-linux-gnu.tar.xz",
+                "blake2b",
+                "b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2",
+            ),
+        ]);
+        insta::assert_snapshot!(info(AurFileType::Pkgbuild).render_pkgbuild(&manifest));
+    }
+
+    #[test]
+    fn test_srcinfo() {
+        let manifest = manifest(&[
+            (
+                "axolotlsay-x86_64-unknown-linux-gnu.tar.xz",
+                "sha256",
+                "0f9a2f5f9d5fb6ed5ab83dba2fa72b1ae2b4bbc4e1e2c3c9c6b1d3b1d8f4a9e1",
+            ),
+            (
+                "axolotlsay-aarch64-unknown-linux-gnu.tar.xz",
+                "sha256",
+                "9c3d0a8f6b8f7e3e0d1f4c5b2a6e7d8c9b0a1f2e3d4c5b6a7f8e9d0c1b2a3f4e",
+            ),
+        ]);
+        insta::assert_snapshot!(info(AurFileType::Srcinfo).render_srcinfo(&manifest));
+    }
+
+    #[test]
+    fn test_checksums_skipped_without_hashes() {
+        let manifest = manifest(&[]);
+        let info = info(AurFileType::Pkgbuild);
+        assert_eq!(
+            info.checksums(&manifest, &info.sources[0]),
+            vec![("sha256sums", "SKIP".to_owned())]
+        );
+    }
+
+    #[test]
+    fn test_sh_quote() {
+        assert_eq!(sh_quote("axolotlsay"), "'axolotlsay'");
+        assert_eq!(sh_quote("it's $HOME"), r"'it'\''s $HOME'");
+    }
+
+    #[test]
+    fn test_aur_package_name_and_version() {
+        assert_eq!(aur_package_name("axolotlsay"), "axolotlsay-bin");
+        assert_eq!(aur_package_name("My_App"), "my_app-bin");
+        assert_eq!(aur_package_version("0.2.2"), "0.2.2");
+        assert_eq!(
+            aur_package_version("1.0.0-prerelease.1"),
+            "1.0.0prerelease1"
+        );
+        assert_eq!(aur_package_version("0.1.0-beta.1"), "0.1.0beta1");
+        assert_eq!(aur_package_version("0.1.0-rc1+build.5"), "0.1.0rc1");
+    }
+
+    #[test]
+    fn test_aur_package_description() {
+        assert_eq!(
+            aur_package_description("a CLI\n  for learning\tthings\n"),
+            "a CLI for learning things"
+        );
+    }
+
+    fn run_spdx_comparison(spdx_string: &str, licenses: &[&str]) {
+        let result = to_aur_license_format(spdx_string).unwrap();
+        assert_eq!(result, licenses);
+    }
+
+    #[test]
+    fn spdx_single_license() {
+        run_spdx_comparison("MIT", &["MIT"]);
+    }
+
+    #[test]
+    fn spdx_two_licenses_any() {
+        run_spdx_comparison("MIT OR 0BSD", &["MIT OR 0BSD"]);
+    }
+
+    #[test]
+    fn spdx_two_licenses_all() {
+        run_spdx_comparison("MIT AND 0BSD", &["MIT", "0BSD"]);
+    }
+
+    #[test]
+    fn spdx_nested_licenses() {
+        run_spdx_comparison(
+            "MIT AND (Apache-2.0 OR 0BSD)",
+            &["MIT", "Apache-2.0 OR 0BSD"],
+        );
+        run_spdx_comparison(
+            "MIT OR (Apache-2.0 AND 0BSD) OR Zlib",
+            &["MIT OR (Apache-2.0 AND 0BSD) OR Zlib"],
+        );
+    }
+
+    #[test]
+    fn spdx_invalid_license() {
+        assert!(to_aur_license_format("foo and bar").is_err());
+    }
+}
@@ -0,0 +1,213 @@
+//! Code for generating Chocolatey packages
+//!
+//! A Chocolatey package is a `.nupkg` (a zip) containing a `.nuspec` with the
+//! package metadata, and a `tools/chocolateyinstall.ps1` script that Chocolatey
+//! runs to install it. Our install script downloads the Windows archives from
+//! the release's hosting, so we fill in the hashes from the checksums we
+//! computed for the archives.
+
+use axoasset::LocalAsset;
+use camino::{Utf8Path, Utf8PathBuf};
+use dist_schema::{ArtifactId, ChecksumValue, DistManifest};
+use serde::Serialize;
+
+use crate::{
+    backend::templates::TEMPLATE_INSTALLER_CHOCOLATEY, config::ChecksumStyle, errors::DistResult,
+    DistError, DistGraph, SortedMap,
+};
+
+const NUSPEC: &str = "package.nuspec";
+const UNINSTALL_PS1: &str = "tools/chocolateyuninstall.ps1";
+
+/// The checksums Chocolatey understands, and its name for each
+const CHOCOLATEY_CHECKSUMS: &[(ChecksumStyle, &str)] = &[
+    (ChecksumStyle::Sha256, "sha256"),
+    (ChecksumStyle::Sha512, "sha512"),
+];
+
+/// Info about a Chocolatey package
+#[derive(Debug, Clone, Serialize)]
+pub struct ChocolateyInstallerInfo {
+    /// Dir to build the package in
+    pub package_dir: Utf8PathBuf,
+    /// The package id (e.g. "axolotlsay")
+    pub package_id: String,
+    /// The package version
+    pub version: String,
+    /// The human-readable name of the package
+    pub title: String,
+    /// The authors of the package, comma-separated
+    pub authors: String,
+    /// A description of the package
+    pub description: String,
+    /// The URL to the package's homepage
+    pub homepage: Option<String>,
+    /// Keywords to tag the package with
+    pub tags: Vec<String>,
+    /// The URL the archives can be downloaded from (append the artifact name)
+    pub base_url: String,
+    /// The archive for 32-bit windows
+    pub x86: Option<ChocolateyArchive>,
+    /// The archive for 64-bit windows
+    pub x64: Option<ChocolateyArchive>,
+    /// Aliases to create for executables, as `(alias, executable)` pairs
+    pub aliases: Vec<(String, String)>,
+}
+
+/// An archive the Chocolatey package downloads
+#[derive(Debug, Clone, Serialize)]
+pub struct ChocolateyArchive {
+    /// The id of the archive
+    pub id: ArtifactId,
+    /// The checksum of the archive, filled in at build time
+    pub checksum: Option<ChecksumValue>,
+    /// Chocolatey's name for the kind of checksum (e.g. "sha256")
+    pub checksum_type: Option<String>,
+}
+
+impl ChocolateyInstallerInfo {
+    /// Write the package's files to its dir (it gets zipped up afterwards)
+    pub fn build(&self, dist: &DistGraph, manifest: &DistManifest) -> DistResult<()> {
+        let mut info = self.clone();
+        // The metadata ends up in the nuspec, which is XML
+        // (ids and versions from Cargo.toml never need escaping)
+        for field in [&mut info.title, &mut info.authors, &mut info.description]
+            .into_iter()
+            .chain(info.homepage.as_mut())
+            .chain(info.tags.iter_mut())
+        {
+            *field = xml_escape(field);
+        }
+        for archive in [&mut info.x86, &mut info.x64].into_iter().flatten() {
+            let (checksum_type, checksum) =
+                checksum_for(manifest, &archive.id).ok_or_else(|| {
+                    DistError::ChocolateyNoChecksum {
+                        artifact: archive.id.to_string(),
+                    }
+                })?;
+            archive.checksum = Some(checksum);
+            archive.checksum_type = Some(checksum_type.to_owned());
+        }
+
+        let mut files = dist
+            .templates
+            .render_dir_to_clean_strings(TEMPLATE_INSTALLER_CHOCOLATEY, &info)?;
+        mangle_files(&info, &mut files);
+
+        for (relpath, rendered) in files {
+            LocalAsset::write_new_all(&rendered, info.package_dir.join(relpath))?;
+        }
+        Ok(())
+    }
+}
+
+/// Find a checksum of the artifact that Chocolatey can verify
+fn checksum_for(manifest: &DistManifest, id: &ArtifactId) -> Option<(&'static str, ChecksumValue)> {
+    let artifact = manifest.artifacts.get(id)?;
+    CHOCOLATEY_CHECKSUMS.iter().find_map(|(style, name)| {
+        let checksum = artifact.checksums.get(style.ext())?;
+        Some((*name, checksum.clone()))
+    })
+}
+
+fn mangle_files(info: &ChocolateyInstallerInfo, files: &mut SortedMap<Utf8PathBuf, String>) {
+    // The nuspec has to be named after the package
+    let nuspec = files
+        .remove(Utf8Path::new(NUSPEC))
+        .expect("chocolatey template didn't have a nuspec!?");
+    files.insert(
+        Utf8PathBuf::from(format!("{}.nuspec", info.package_id)),
+        nuspec,
+    );
+
+    // Chocolatey cleans up everything but aliases itself
+    if info.aliases.is_empty() {
+        files.remove(Utf8Path::new(UNINSTALL_PS1));
+    }
+}
+
+/// Escape any characters in the input that have a special meaning in XML
+fn xml_escape(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&apos;"),
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+/// Get the Chocolatey package id for an app
+///
+/// Chocolatey ids are conventionally lowercase and dash-separated.
+pub fn chocolatey_package_id(app_name: &str) -> String {
+    app_name.to_lowercase().replace('_', "-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dist_schema::{Artifact, ArtifactKind, ChecksumExtension};
+
+    #[test]
+    fn test_checksum_for() {
+        let id = ArtifactId::new("axolotlsay-x86_64-pc-windows-msvc.zip".to_owned());
+        let mut manifest = DistManifest::new(vec![], Default::default());
+        assert_eq!(checksum_for(&manifest, &id), None);
+
+        let mut artifact = Artifact {
+            name: Some(id.clone()),
+            kind: ArtifactKind::ExecutableZip,
+            target_triples: vec![],
+            path: None,
+            assets: vec![],
+            install_hint: None,
+            description: None,
+            checksum: None,
+            checksums: Default::default(),
+            signed_by: Default::default(),
+            signatures: Default::default(),
+        };
+        // Chocolatey can't check a blake2b
+        artifact.checksums.insert(
+            ChecksumExtension::new("blake2b".to_owned()),
+            ChecksumValue::new("00ff".to_owned()),
+        );
+        manifest.artifacts.insert(id.clone(), artifact.clone());
+        assert_eq!(checksum_for(&manifest, &id), None);
+
+        artifact.checksums.insert(
+            ChecksumExtension::new("sha512".to_owned()),
+            ChecksumValue::new("cf83e1".to_owned()),
+        );
+        manifest.artifacts.insert(id.clone(), artifact);
+        assert_eq!(
+            checksum_for(&manifest, &id),
+            Some(("sha512", ChecksumValue::new("cf83e1".to_owned())))
+        );
+    }
+
+    #[test]
+    fn test_xml_escape() {
+        assert_eq!(xml_escape("axolotlsay"), "axolotlsay");
+        assert_eq!(
+            xml_escape("Axo Dev <hi@axo.dev>"),
+            "Axo Dev &lt;hi@axo.dev&gt;"
+        );
+        assert_eq!(
+            xml_escape("\"this\" & 'that'"),
+            "&quot;this&quot; &amp; &apos;that&apos;"
+        );
+    }
+
+    #[test]
+    fn test_chocolatey_package_id() {
+        assert_eq!(chocolatey_package_id("axolotlsay"), "axolotlsay");
+        assert_eq!(chocolatey_package_id("My_App"), "my-app");
+    }
+}
@@ -0,0 +1,319 @@
+//! Code for generating Debian .deb packages
+//!
+//! A .deb is just an `ar` archive containing a version marker, a tarball of
+//! package metadata, and a tarball of the files to install. All of those are
+//! simple enough to write ourselves, so unlike msi and pkg we don't need any
+//! platform tooling (like `dpkg-deb`) and can build debs anywhere.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use axoasset::LocalAsset;
+use camino::Utf8PathBuf;
+use dist_schema::{
+    target_lexicon::{Architecture, ArmArchitecture, Environment},
+    DistManifest, PackageManager, TripleNameRef,
+};
+use semver::Version;
+use serde::Serialize;
+use tracing::info;
+
+use crate::DistResult;
+
+use super::{archive::TarBuilder, source_date_epoch, ExecutableZipFragment};
+
+/// Info about a Debian package
+#[derive(Debug, Clone, Serialize)]
+pub struct DebInstallerInfo {
+    /// ExecutableZipFragment for this variant
+    pub artifact: ExecutableZipFragment,
+    /// Final file path of the deb
+    pub file_path: Utf8PathBuf,
+    /// Dir stuff goes to
+    pub package_dir: Utf8PathBuf,
+    /// The name of the package
+    pub package_name: String,
+    /// The package version (in Debian's format)
+    pub version: String,
+    /// The Debian architecture of the package (e.g. "amd64")
+    pub architecture: String,
+    /// The maintainer of the package
+    pub maintainer: String,
+    /// A brief description of the app
+    pub description: Option<String>,
+    /// The license of the app
+    pub license: Option<String>,
+    /// The URL to the app's homepage
+    pub homepage: Option<String>,
+    /// Packages the app needs at runtime (in addition to the ones found by linkage checking)
+    pub depends: Vec<String>,
+    /// Executable aliases
+    pub bin_aliases: BTreeMap<String, Vec<String>>,
+}
+
+impl DebInstallerInfo {
+    /// Build the deb
+    pub fn build(&self, manifest: &DistManifest) -> DistResult<()> {
+        info!("building a deb: {}", self.file_path);
+
+        let mtime = source_date_epoch();
+        let mut data = TarBuilder::new(mtime);
+        data.dir("./")?;
+        data.dir("usr/")?;
+        data.dir("usr/bin/")?;
+        let mut installed_size = 0;
+        for exe in &self.artifact.executables {
+            let contents = LocalAsset::load_bytes(self.package_dir.join(exe))?;
+            installed_size += contents.len();
+            data.file(&format!("usr/bin/{exe}"), 0o755, &contents)?;
+        }
+        for (bin, aliases) in &self.bin_aliases {
+            for alias in aliases {
+                data.symlink(&format!("usr/bin/{alias}"), bin)?;
+            }
+        }
+        let libs = self
+            .artifact
+            .cdylibs
+            .iter()
+            .chain(self.artifact.cstaticlibs.iter())
+            .collect::<Vec<_>>();
+        if !libs.is_empty() {
+            data.dir("usr/lib/")?;
+        }
+        for lib in libs {
+            let contents = LocalAsset::load_bytes(self.package_dir.join(lib))?;
+            installed_size += contents.len();
+            data.file(&format!("usr/lib/{lib}"), 0o644, &contents)?;
+        }
+        // Debian keeps licensing info in a copyright file rather than the control file
+        if let Some(license) = &self.license {
+            let doc_dir = format!("usr/share/doc/{}/", self.package_name);
+            data.dir("usr/share/")?;
+            data.dir("usr/share/doc/")?;
+            data.dir(&doc_dir)?;
+            let copyright = self.copyright_file(license);
+            installed_size += copyright.len();
+            data.file(&format!("{doc_dir}copyright"), 0o644, copyright.as_bytes())?;
+        }
+
+        let mut control = TarBuilder::new(mtime);
+        control.dir("./")?;
+        let control_file = self.control_file(manifest, installed_size.div_ceil(1024));
+        control.file("control", 0o644, control_file.as_bytes())?;
+
+        let mut deb = b"!<arch>\n".to_vec();
+        add_ar_member(&mut deb, "debian-binary", mtime, b"2.0\n");
+        add_ar_member(&mut deb, "control.tar.gz", mtime, &control.finish()?);
+        add_ar_member(&mut deb, "data.tar.gz", mtime, &data.finish()?);
+        std::fs::write(&self.file_path, deb)?;
+
+        Ok(())
+    }
+
+    /// Produce the contents of the control file
+    fn control_file(&self, manifest: &DistManifest, installed_size: usize) -> String {
+        let mut fields = vec![
+            ("Package", self.package_name.clone()),
+            ("Version", self.version.clone()),
+            ("Architecture", self.architecture.clone()),
+            ("Maintainer", self.maintainer.clone()),
+            ("Installed-Size", installed_size.to_string()),
+        ];
+        let depends = self.depends(manifest);
+        if !depends.is_empty() {
+            fields.push(("Depends", depends.join(", ")));
+        }
+        fields.push(("Section", "utils".to_owned()));
+        fields.push(("Priority", "optional".to_owned()));
+        if let Some(homepage) = &self.homepage {
+            fields.push(("Homepage", homepage.clone()));
+        }
+
+        // The first line of the description is the synopsis, and every line after
+        // is indented, with blank lines written as " ."
+        let description = self
+            .description
+            .as_deref()
+            .unwrap_or(&self.package_name)
+            .trim();
+        let mut lines = description.lines();
+        let mut description = lines.next().unwrap_or_default().trim().to_owned();
+        for line in lines {
+            let line = line.trim_end();
+            description.push_str("\n ");
+            description.push_str(if line.is_empty() { "." } else { line });
+        }
+        fields.push(("Description", description));
+
+        fields
+            .into_iter()
+            .map(|(name, value)| format!("{name}: {value}\n"))
+            .collect()
+    }
+
+    /// Get the full list of dependencies, merging the configured ones
+    /// with the apt packages linkage checking found libraries in
+    fn depends(&self, manifest: &DistManifest) -> Vec<String> {
+        let mut depends = self.depends.clone();
+        let configured = self
+            .depends
+            .iter()
+            .map(|dep| dep.split_whitespace().next().unwrap_or_default().to_owned())
+            .collect::<BTreeSet<_>>();
+
+        let linkage = manifest.linkage_for_artifact(&self.artifact.id);
+        let found = linkage
+            .system
+            .iter()
+            .chain(linkage.other.iter())
+            .filter(|lib| lib.package_manager == Some(PackageManager::Apt))
+            .filter_map(|lib| lib.source.clone())
+            .filter(|source| source != &self.package_name && !configured.contains(source))
+            .collect::<BTreeSet<_>>();
+        depends.extend(found);
+
+        depends
+    }
+
+    /// Produce the contents of the copyright file
+    fn copyright_file(&self, license: &str) -> String {
+        let mut copyright = String::from(
+            "Format: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/\n",
+        );
+        copyright.push_str(&format!("Upstream-Name: {}\n", self.package_name));
+        if let Some(homepage) = &self.homepage {
+            copyright.push_str(&format!("Source: {homepage}\n"));
+        }
+        copyright.push_str("\nFiles: *\n");
+        copyright.push_str(&format!("Copyright: {}\n", self.maintainer));
+        copyright.push_str(&format!("License: {license}\n"));
+        copyright
+    }
+}
+
+/// Get the Debian architecture name for a target, if Debian has one
+pub fn deb_architecture(target: &TripleNameRef) -> Option<&'static str> {
+    let triple = target.parse().ok()?;
+    let hard_float = matches!(
+        triple.environment,
+        Environment::Gnueabihf | Environment::Musleabihf
+    );
+    let arch = match triple.architecture {
+        Architecture::X86_64 => "amd64",
+        Architecture::X86_32(_) => "i386",
+        Architecture::Aarch64(_) => "arm64",
+        Architecture::Arm(ArmArchitecture::Armv7) if hard_float => "armhf",
+        Architecture::Arm(_) if !hard_float => "armel",
+        Architecture::Powerpc64le => "ppc64el",
+        Architecture::S390x => "s390x",
+        Architecture::Riscv64(_) => "riscv64",
+        Architecture::LoongArch64 => "loong64",
+        _ => return None,
+    };
+    Some(arch)
+}
+
+/// Get the Debian equivalent of a package name
+///
+/// Debian package names can only contain lowercase letters, digits, and `+-.`
+pub fn deb_package_name(name: &str) -> String {
+    name.to_ascii_lowercase().replace('_', "-")
+}
+
+/// Get the Debian equivalent of a version
+///
+/// Prereleases need to use `~` so that they sort before the final release,
+/// and `-` is reserved for the Debian revision.
+pub fn deb_version(version: &Version) -> String {
+    let mut output = format!("{}.{}.{}", version.major, version.minor, version.patch);
+    if !version.pre.is_empty() {
+        output.push('~');
+        output.push_str(&version.pre.replace('-', "."));
+    }
+    if !version.build.is_empty() {
+        output.push('+');
+        output.push_str(&version.build.replace('-', "."));
+    }
+    output
+}
+
+/// Append a file to an `ar` archive
+fn add_ar_member(archive: &mut Vec<u8>, name: &str, mtime: u64, contents: &[u8]) {
+    let header = format!(
+        "{name:<16}{mtime:<12}{:<6}{:<6}{:<8}{:<10}`\n",
+        0,
+        0,
+        "100644",
+        contents.len()
+    );
+    archive.extend_from_slice(header.as_bytes());
+    archive.extend_from_slice(contents);
+    // Members are aligned to 2 bytes
+    if contents.len() % 2 == 1 {
+        archive.push(b'\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dist_schema::TripleName;
+    use semver::Version;
+
+    use super::{deb_architecture, deb_package_name, deb_version};
+
+    fn run_version_comparison(in_str: &str, expected: &str) {
+        let out_str = deb_version(&Version::parse(in_str).unwrap());
+
+        assert_eq!(out_str, expected);
+    }
+
+    fn run_arch_comparison(in_str: &str, expected: Option<&str>) {
+        let out = deb_architecture(&TripleName::new(in_str.to_owned()));
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn version_basic() {
+        run_version_comparison("1.2.3", "1.2.3");
+    }
+
+    #[test]
+    fn version_prerelease_sorts_first() {
+        run_version_comparison("1.2.3-beta.1", "1.2.3~beta.1");
+    }
+
+    #[test]
+    fn version_prerelease_with_dashes() {
+        run_version_comparison("1.2.3-rc-1", "1.2.3~rc.1");
+    }
+
+    #[test]
+    fn version_build_metadata() {
+        run_version_comparison("1.2.3-alpha+build-5", "1.2.3~alpha+build.5");
+    }
+
+    #[test]
+    fn arch_common() {
+        run_arch_comparison("x86_64-unknown-linux-gnu", Some("amd64"));
+        run_arch_comparison("x86_64-unknown-linux-musl", Some("amd64"));
+        run_arch_comparison("aarch64-unknown-linux-gnu", Some("arm64"));
+        run_arch_comparison("i686-unknown-linux-gnu", Some("i386"));
+    }
+
+    #[test]
+    fn arch_arm() {
+        run_arch_comparison("armv7-unknown-linux-gnueabihf", Some("armhf"));
+        run_arch_comparison("arm-unknown-linux-gnueabi", Some("armel"));
+    }
+
+    #[test]
+    fn arch_unknown() {
+        run_arch_comparison("mips-unknown-linux-gnu", None);
+    }
+
+    #[test]
+    fn package_name_underscores() {
+        assert_eq!(deb_package_name("My_App"), "my-app");
+    }
+}
@@ -1,22 +1,30 @@
-//! Code for generating formula.rb
+//! Code for generating formula.rb, and the bottles it can list
 
 use std::collections::BTreeMap;
 
 use axoasset::LocalAsset;
-use dist_schema::{ChecksumValue, DistManifest, HomebrewPackageName};
+use camino::{Utf8Path, Utf8PathBuf};
+use dist_schema::{ArtifactId, ChecksumValue, DistManifest, HomebrewPackageName, TripleNameRef};
 use serde::Serialize;
 use spdx::{
     expression::{ExprNode, Operator},
     Expression, ParseError,
 };
+use tracing::info;
 
-use super::InstallerInfo;
+use super::{archive::TarBuilder, source_date_epoch, InstallerInfo};
 use crate::{
     backend::templates::TEMPLATE_INSTALLER_RB,
-    config::{ChecksumStyle, CompletionTrigger, LibraryStyle, ParamFormat},
+    config::{
+        v1::installers::homebrew::{HomebrewService, HomebrewTest},
+        ChecksumStyle, CompletionTrigger, LibraryStyle, ParamFormat,
+    },
     errors::DistResult,
     installer::ExecutableZipFragment,
-    tasks::DistGraph,
+    platform::targets::{
+        TARGET_ARM64_LINUX_GNU, TARGET_ARM64_MAC, TARGET_X64_LINUX_GNU, TARGET_X64_MAC,
+    },
+    tasks::{DistGraph, StaticAssetKind},
 };
 
 /// Info about a Homebrew formula
@@ -40,6 +48,48 @@ pub struct HomebrewInstallerInfo {
     pub dependencies: Vec<HomebrewPackageName>,
     /// Whether to install packaged C dynamic libraries
     pub install_libraries: Vec<LibraryStyle>,
+    /// A `test do` block, for `brew test`
+    pub test: Option<HomebrewTest>,
+    /// A `service do` block, for `brew services`
+    pub service: Option<HomebrewService>,
+    /// Text to show after installing
+    pub caveats: Option<String>,
+    /// Bottles of the archives to list in the formula
+    pub bottles: Vec<HomebrewBottle>,
+}
+
+/// A bottle a formula can list
+#[derive(Debug, Clone, Serialize)]
+pub struct HomebrewBottle {
+    /// The id of the bottle
+    pub id: ArtifactId,
+    /// Homebrew's tag for the platform the bottle is for (e.g. "arm64_big_sur")
+    pub tag: String,
+}
+
+/// Info about a Homebrew bottle
+///
+/// A bottle is just a tarball of the formula's "keg", the `<formula>/<version>`
+/// dir Homebrew would otherwise install into, so we build it straight from the
+/// archive's contents the same way the formula's `install` would lay them out.
+#[derive(Debug, Clone)]
+pub struct HomebrewBottleInfo {
+    /// ExecutableZipFragment for this variant
+    pub artifact: ExecutableZipFragment,
+    /// Final file path of the bottle
+    pub file_path: Utf8PathBuf,
+    /// Dir stuff goes to
+    pub package_dir: Utf8PathBuf,
+    /// The name of the formula
+    pub formula: String,
+    /// The version of the formula
+    pub version: String,
+    /// Executable aliases
+    pub bin_aliases: BTreeMap<String, Vec<String>>,
+    /// Static assets to include
+    pub static_assets: Vec<(StaticAssetKind, Utf8PathBuf)>,
+    /// Whether to include packaged C libraries
+    pub install_libraries: Vec<LibraryStyle>,
 }
 
 /// All homebrew-specific fragments
@@ -61,7 +111,19 @@ pub(crate) fn write_homebrew_formula(
     fragments: &HomebrewFragments<ExecutableZipFragment>,
     manifest: &DistManifest,
 ) -> DistResult<()> {
-    let info = info.clone();
+    let mut info = info.clone();
+    // The test, service and caveats end up in Ruby strings
+    info.caveats = info.caveats.as_deref().map(ruby_escape);
+    if let Some(test) = &mut info.test {
+        test.command = ruby_escape(&test.command);
+        test.output = ruby_escape(&test.output).replace("{version}", "#{version}");
+    }
+    if let Some(service) = &mut info.service {
+        for arg in &mut service.run {
+            *arg = ruby_escape(arg);
+        }
+        service.working_dir = service.working_dir.as_deref().map(ruby_escape);
+    }
 
     let checksum_key = ChecksumStyle::Sha256.ext();
     let map_fragment = |fragment: ExecutableZipFragment| -> HomebrewFragment {
@@ -124,11 +186,28 @@ pub(crate) fn write_homebrew_formula(
         completions.insert(bin.clone(), completion);
     }
 
+    // Bottles can only be listed with their sha256
+    let bottles = info
+        .bottles
+        .iter()
+        .filter_map(|bottle| {
+            let sha256 = manifest
+                .artifacts
+                .get(&bottle.id)
+                .and_then(|a| a.checksums.get(checksum_key))?;
+            Some(HomebrewBottleFragment {
+                tag: bottle.tag.clone(),
+                sha256: sha256.clone(),
+            })
+        })
+        .collect();
+
     let dest_path = info.inner.dest_path.clone();
     let inputs = HomebrewTemplateInputs {
         info,
         fragments,
         completions,
+        bottle_fragments: bottles,
     };
 
     let script = dist
@@ -147,6 +226,130 @@ struct HomebrewTemplateInputs {
     fragments: HomebrewFragments<HomebrewFragment>,
 
     completions: BTreeMap<String, String>,
+
+    bottle_fragments: Vec<HomebrewBottleFragment>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct HomebrewBottleFragment {
+    tag: String,
+    sha256: ChecksumValue,
+}
+
+impl HomebrewBottleInfo {
+    /// Build the bottle
+    pub fn build(&self) -> DistResult<()> {
+        info!("building a Homebrew bottle: {}", self.file_path);
+
+        let mut bottle = TarBuilder::new(source_date_epoch());
+        let keg = format!("{}/{}/", self.formula, self.version);
+        bottle.dir(&format!("{}/", self.formula))?;
+        bottle.dir(&keg)?;
+
+        bottle.dir(&format!("{keg}bin/"))?;
+        for exe in &self.artifact.executables {
+            let contents = LocalAsset::load_bytes(self.package_dir.join(exe))?;
+            bottle.file(&format!("{keg}bin/{exe}"), 0o755, &contents)?;
+        }
+        for (bin, aliases) in &self.bin_aliases {
+            for alias in aliases {
+                bottle.symlink(&format!("{keg}bin/{alias}"), bin)?;
+            }
+        }
+
+        let mut libs = vec![];
+        if self.install_libraries.contains(&LibraryStyle::CDynamic) {
+            libs.extend(&self.artifact.cdylibs);
+        }
+        if self.install_libraries.contains(&LibraryStyle::CStatic) {
+            libs.extend(&self.artifact.cstaticlibs);
+        }
+        if !libs.is_empty() {
+            bottle.dir(&format!("{keg}lib/"))?;
+        }
+        for lib in libs {
+            let contents = LocalAsset::load_bytes(self.package_dir.join(lib))?;
+            bottle.file(&format!("{keg}lib/{lib}"), 0o644, &contents)?;
+        }
+
+        // Homebrew puts docs at the root of the keg, and the formula puts
+        // anything else in pkgshare
+        let pkgshare = format!("{keg}share/{}/", self.formula);
+        let mut made_pkgshare = false;
+        for (kind, src_path) in &self.static_assets {
+            let Some(name) = src_path.file_name() else {
+                continue;
+            };
+            let dest = match kind {
+                StaticAssetKind::Readme | StaticAssetKind::License | StaticAssetKind::Changelog => {
+                    format!("{keg}{name}")
+                }
+                StaticAssetKind::Other => {
+                    if !made_pkgshare {
+                        bottle.dir(&format!("{keg}share/"))?;
+                        bottle.dir(&pkgshare)?;
+                        made_pkgshare = true;
+                    }
+                    format!("{pkgshare}{name}")
+                }
+            };
+            add_static_asset(&mut bottle, &dest, src_path)?;
+        }
+
+        std::fs::write(&self.file_path, bottle.finish()?)?;
+        Ok(())
+    }
+}
+
+/// Add a static asset to a bottle, which may be a whole dir
+fn add_static_asset(bottle: &mut TarBuilder, dest: &str, src_path: &Utf8Path) -> DistResult<()> {
+    if src_path.is_dir() {
+        bottle.dir(&format!("{dest}/"))?;
+        let mut entries = src_path.read_dir_utf8()?.collect::<Result<Vec<_>, _>>()?;
+        entries.sort_by(|a, b| a.file_name().cmp(b.file_name()));
+        for entry in entries {
+            add_static_asset(
+                bottle,
+                &format!("{dest}/{}", entry.file_name()),
+                entry.path(),
+            )?;
+        }
+    } else {
+        let contents = LocalAsset::load_bytes(src_path)?;
+        bottle.file(dest, 0o644, &contents)?;
+    }
+    Ok(())
+}
+
+/// Get Homebrew's bottle tag for a target, if it's one Homebrew supports
+///
+/// Homebrew will pour a bottle for an older macOS on newer ones, so macOS
+/// bottles are tagged with Big Sur, the first macOS on Apple Silicon.
+pub fn homebrew_bottle_tag(target: &TripleNameRef) -> Option<&'static str> {
+    if target == TARGET_ARM64_MAC {
+        Some("arm64_big_sur")
+    } else if target == TARGET_X64_MAC {
+        Some("big_sur")
+    } else if target == TARGET_ARM64_LINUX_GNU {
+        Some("arm64_linux")
+    } else if target == TARGET_X64_LINUX_GNU {
+        Some("x86_64_linux")
+    } else {
+        None
+    }
+}
+
+/// Get the name Homebrew expects a bottle to have
+pub fn homebrew_bottle_name(formula: &str, version: &str, tag: &str) -> String {
+    format!("{formula}--{version}.{tag}.bottle.tar.gz")
+}
+
+/// Escape any characters in the input that have a special meaning in a Ruby string
+fn ruby_escape(input: &str) -> String {
+    input
+        .replace('\\', r"\\")
+        .replace('"', "\\\"")
+        .replace("#{", "\\#{")
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -288,7 +491,11 @@ pub fn to_homebrew_license_format(app_license: &str) -> Result<String, ParseErro
 mod tests {
     use spdx::ParseError;
 
-    use super::{to_class_case, to_homebrew_license_format};
+    use super::{
+        homebrew_bottle_name, homebrew_bottle_tag, ruby_escape, to_class_case,
+        to_homebrew_license_format,
+    };
+    use crate::platform::targets::{TARGET_ARM64_MAC, TARGET_X64_LINUX_GNU, TARGET_X64_WINDOWS};
 
     fn run_comparison(in_str: &str, expected: &str) {
         let out_str = to_class_case(in_str);
@@ -476,4 +683,25 @@ mod tests {
     fn spdx_invalid_adjacent_operator() {
         run_malformed_spdx("MIT AND OR Apache-2.0");
     }
+
+    #[test]
+    fn bottle_tags() {
+        assert_eq!(homebrew_bottle_tag(TARGET_ARM64_MAC), Some("arm64_big_sur"));
+        assert_eq!(
+            homebrew_bottle_tag(TARGET_X64_LINUX_GNU),
+            Some("x86_64_linux")
+        );
+        assert_eq!(homebrew_bottle_tag(TARGET_X64_WINDOWS), None);
+        assert_eq!(
+            homebrew_bottle_name("axolotlsay", "0.2.2", "arm64_big_sur"),
+            "axolotlsay--0.2.2.arm64_big_sur.bottle.tar.gz"
+        );
+    }
+
+    #[test]
+    fn ruby_escapes() {
+        assert_eq!(ruby_escape("axolotlsay --version"), "axolotlsay --version");
+        assert_eq!(ruby_escape(r#"say "hi" \o/"#), r#"say \"hi\" \\o/"#);
+        assert_eq!(ruby_escape("echo #{ENV}"), r"echo \#{ENV}");
+    }
 }
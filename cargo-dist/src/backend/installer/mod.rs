@@ -4,28 +4,47 @@
 
 use std::collections::BTreeMap;
 
+use appimage::AppimageInstallerInfo;
+use aur::AurInstallerInfo;
 use camino::Utf8PathBuf;
+use chocolatey::ChocolateyInstallerInfo;
+use deb::DebInstallerInfo;
 use dist_schema::{ArtifactId, EnvironmentVariables, Hosting, TripleName};
-use homebrew::HomebrewFragments;
+use homebrew::{HomebrewBottleInfo, HomebrewFragments};
 use macpkg::PkgInstallerInfo;
+use nix::NixInstallerInfo;
+use pypi::PypiInstallerInfo;
+use rpm::RpmInstallerInfo;
+use scoop::ScoopInstallerInfo;
 use serde::Serialize;
+use winget::WingetInstallerInfo;
 
 use crate::{
-    config::{CompletionConfig, JinjaInstallPathStrategy, LibraryStyle, ZipStyle},
+    config::{ChecksumStyle, CompletionConfig, JinjaInstallPathStrategy, LibraryStyle, ZipStyle},
     platform::{PlatformSupport, RuntimeConditions},
     InstallReceipt, ReleaseIdx,
 };
 
 use self::homebrew::HomebrewInstallerInfo;
 use self::msi::MsiInstallerInfo;
-use self::npm::NpmInstallerInfo;
+use self::npm::{NpmInstallerInfo, NpmPlatformInstallerInfo};
 
+pub mod appimage;
+mod archive;
+pub mod aur;
+pub mod chocolatey;
+pub mod deb;
 pub mod homebrew;
 pub mod macpkg;
 pub mod msi;
+pub mod nix;
 pub mod npm;
 pub mod powershell;
+pub mod pypi;
+pub mod rpm;
+pub mod scoop;
 pub mod shell;
+pub mod winget;
 
 /// A kind of an installer
 #[derive(Debug, Clone)]
@@ -37,12 +56,34 @@ pub enum InstallerImpl {
     Powershell(InstallerInfo),
     /// npm installer package
     Npm(NpmInstallerInfo),
+    /// npm package with the binaries for one platform
+    NpmPlatform(NpmPlatformInstallerInfo),
     /// Homebrew formula
     Homebrew(HomebrewImpl),
+    /// Homebrew bottle
+    HomebrewBottle(HomebrewBottleInfo),
     /// Windows msi installer
     Msi(MsiInstallerInfo),
     /// Mac pkg installer
     Pkg(PkgInstallerInfo),
+    /// Debian deb installer
+    Deb(DebInstallerInfo),
+    /// RPM installer
+    Rpm(RpmInstallerInfo),
+    /// Scoop manifest
+    Scoop(ScoopInstallerInfo),
+    /// winget manifest
+    Winget(WingetInstallerInfo),
+    /// Chocolatey package
+    Chocolatey(ChocolateyInstallerInfo),
+    /// AUR package file
+    Aur(AurInstallerInfo),
+    /// Nix derivation
+    Nix(NixInstallerInfo),
+    /// Python wheel
+    Pypi(PypiInstallerInfo),
+    /// Portable AppImage
+    Appimage(AppimageInstallerInfo),
 }
 
 /// Information needed to make a homebrew installer
@@ -79,6 +120,8 @@ pub struct InstallerInfo {
     pub hint: String,
     /// Where to install binaries
     pub install_paths: Vec<JinjaInstallPathStrategy>,
+    /// Where to install binaries with `--system`, if not the installer's default
+    pub system_install_path: Option<String>,
     /// Custom message to display on install success
     pub install_success_msg: String,
     /// Install receipt to write, if any
@@ -95,6 +138,91 @@ pub struct InstallerInfo {
     pub platform_support: Option<PlatformSupport>,
     /// Environment variables for installer customization
     pub env_vars: Option<EnvironmentVariables>,
+    /// How to verify the signed unified checksum file, if it's signed
+    pub checksum_signature: Option<ChecksumSignatureInfo>,
+    /// How to find other releases, for `--version` and `--channel`
+    pub release_selection: Option<ReleaseSelection>,
+}
+
+/// Info for verifying the signed unified checksum file
+#[derive(Debug, Clone, Serialize)]
+pub struct ChecksumSignatureInfo {
+    /// The unified checksum file (e.g. `sha256.sum`)
+    pub checksums: ArtifactId,
+    /// The detached minisign signature of it (e.g. `sha256.sum.sig`)
+    pub signature: ArtifactId,
+    /// The style of the checksums in it (e.g. `sha256`)
+    pub checksum_style: String,
+    /// The minisign public key to verify the signature with (just the base64 line)
+    pub public_key: String,
+    /// Environment variable to install without verifying the signature
+    pub no_verify_env_var: String,
+}
+
+/// How a script installer can find releases other than the one it was made for
+///
+/// Every place we host releases at puts the version (or a tag ending in it) at
+/// the end of the download URL, so we just need to know what comes before it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseSelection {
+    /// The download URL of a release up to its version
+    ///
+    /// For GitHub this is just the path, as the installer picks the base URL at runtime
+    pub download_prefix: String,
+    /// The part of a release's tag before its version (e.g. `v` or `my-app-v`)
+    pub tag_prefix: String,
+    /// The GitHub repo (`owner/repo`) whose releases can be listed to resolve `--channel`
+    pub github_repo: Option<String>,
+    /// The checksum (from its dist-manifest) to check another release's archive against
+    ///
+    /// This is the configured checksum if both installers can compute it, and
+    /// otherwise sha256, which the manifest always has.
+    pub checksum_style: String,
+}
+
+impl ReleaseSelection {
+    /// Work out how to find other releases from how this one is hosted, if we can
+    pub fn from_hosting(hosting: &Hosting, version: &str, checksum: ChecksumStyle) -> Option<Self> {
+        let github_tag = hosting.github.as_ref().and_then(|github| {
+            let (_, tag) = github
+                .artifact_download_path
+                .split_once("/releases/download/")?;
+            Some(tag)
+        });
+        let tag_prefix = github_tag.and_then(|tag| tag.strip_suffix(version));
+
+        // This needs to agree with the URL the installers download from
+        let download_prefix = match (&hosting.github, &hosting.s3) {
+            (Some(github), None) => github
+                .artifact_download_path
+                .strip_suffix(version)?
+                .to_owned(),
+            _ if hosting.axodotdev.is_some() => {
+                // Artifact sets are released under their tag
+                let url = hosting.artifact_download_url()?;
+                let (set_dir, _set_id) = url.rsplit_once('/')?;
+                format!("{set_dir}/{}", tag_prefix?)
+            }
+            _ => hosting
+                .artifact_download_url()?
+                .strip_suffix(version)?
+                .to_owned(),
+        };
+
+        Some(Self {
+            download_prefix,
+            tag_prefix: tag_prefix.unwrap_or_default().to_owned(),
+            github_repo: hosting
+                .github
+                .as_ref()
+                .filter(|_| tag_prefix.is_some())
+                .map(|github| format!("{}/{}", github.owner, github.repo)),
+            checksum_style: match checksum {
+                ChecksumStyle::Sha256 | ChecksumStyle::Sha512 => checksum.ext().to_string(),
+                _ => ChecksumStyle::Sha256.ext().to_string(),
+            },
+        })
+    }
 }
 
 /// A fake fragment of an ExecutableZip artifact for installers
@@ -126,3 +254,134 @@ pub struct UpdaterFragment {
     /// The binary the artifact contains (name, assumed at root)
     pub binary: ArtifactId,
 }
+
+/// The env var reproducible build tools use to agree on a timestamp
+const SOURCE_DATE_EPOCH_VAR: &str = "SOURCE_DATE_EPOCH";
+/// 1980-01-01, the earliest date every archive format we write can represent
+const DEFAULT_SOURCE_DATE_EPOCH: u64 = 315532800;
+
+/// The timestamp to record in the packages we build ourselves
+///
+/// This is [`SOURCE_DATE_EPOCH`](https://reproducible-builds.org/specs/source-date-epoch/)
+/// if it's set, and a fixed date otherwise, so rebuilding a release gives identical packages.
+pub(crate) fn source_date_epoch() -> u64 {
+    std::env::var(SOURCE_DATE_EPOCH_VAR)
+        .ok()
+        .and_then(|epoch| epoch.trim().parse().ok())
+        .unwrap_or(DEFAULT_SOURCE_DATE_EPOCH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dist_schema::{GithubHosting, GitlabHosting, S3Hosting};
+
+    fn github(tag: &str) -> GithubHosting {
+        GithubHosting {
+            artifact_base_url: "https://github.com".to_owned(),
+            artifact_download_path: format!("/axodotdev/axolotlsay/releases/download/{tag}"),
+            owner: "axodotdev".to_owned(),
+            repo: "axolotlsay".to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_release_selection_github() {
+        let hosting = Hosting {
+            github: Some(github("v0.2.2")),
+            ..Default::default()
+        };
+        assert_eq!(
+            ReleaseSelection::from_hosting(&hosting, "0.2.2", ChecksumStyle::Sha256),
+            Some(ReleaseSelection {
+                download_prefix: "/axodotdev/axolotlsay/releases/download/v".to_owned(),
+                tag_prefix: "v".to_owned(),
+                github_repo: Some("axodotdev/axolotlsay".to_owned()),
+                checksum_style: "sha256".to_owned(),
+            })
+        );
+
+        let hosting = Hosting {
+            github: Some(github("axolotlsay/v0.2.2")),
+            ..Default::default()
+        };
+        let selection =
+            ReleaseSelection::from_hosting(&hosting, "0.2.2", ChecksumStyle::Sha256).unwrap();
+        assert_eq!(selection.tag_prefix, "axolotlsay/v");
+
+        // Other releases are checked with the configured checksum if the installers can compute it
+        let selection =
+            ReleaseSelection::from_hosting(&hosting, "0.2.2", ChecksumStyle::Sha512).unwrap();
+        assert_eq!(selection.checksum_style, "sha512");
+        let selection =
+            ReleaseSelection::from_hosting(&hosting, "0.2.2", ChecksumStyle::Blake2b).unwrap();
+        assert_eq!(selection.checksum_style, "sha256");
+
+        // A tag that doesn't end in the version leaves us nothing to replace
+        let hosting = Hosting {
+            github: Some(github("latest")),
+            ..Default::default()
+        };
+        assert_eq!(
+            ReleaseSelection::from_hosting(&hosting, "0.2.2", ChecksumStyle::Sha256),
+            None
+        );
+    }
+
+    #[test]
+    fn test_release_selection_other_hosts() {
+        let s3 = S3Hosting {
+            artifact_base_url: "https://bucket.s3.us-east-1.amazonaws.com".to_owned(),
+            artifact_download_path: "/releases/axolotlsay/0.2.2".to_owned(),
+            endpoint: "https://s3.us-east-1.amazonaws.com".to_owned(),
+            region: "us-east-1".to_owned(),
+            bucket: "bucket".to_owned(),
+        };
+        // S3 takes precedence over GitHub for downloads, but GitHub still lists releases
+        let mut hosting = Hosting {
+            github: Some(github("v0.2.2")),
+            s3: Some(s3),
+            ..Default::default()
+        };
+        assert_eq!(
+            ReleaseSelection::from_hosting(&hosting, "0.2.2", ChecksumStyle::Sha256),
+            Some(ReleaseSelection {
+                download_prefix: "https://bucket.s3.us-east-1.amazonaws.com/releases/axolotlsay/"
+                    .to_owned(),
+                tag_prefix: "v".to_owned(),
+                github_repo: Some("axodotdev/axolotlsay".to_owned()),
+                checksum_style: "sha256".to_owned(),
+            })
+        );
+
+        // ...unless axodotdev is also there, which releases artifact sets under their tag
+        hosting.axodotdev = Some(gazenot::ArtifactSet::mock("axolotlsay".to_owned()));
+        let selection =
+            ReleaseSelection::from_hosting(&hosting, "0.2.2", ChecksumStyle::Sha256).unwrap();
+        assert_eq!(
+            selection.download_prefix,
+            "https://fake.axo.dev/faker/axolotlsay/v"
+        );
+
+        let hosting = Hosting {
+            gitlab: Some(GitlabHosting {
+                api_url: "https://gitlab.com/api/v4".to_owned(),
+                artifact_download_path:
+                    "/projects/axodotdev%2Faxolotlsay/packages/generic/axolotlsay/0.2.2".to_owned(),
+                project: "axodotdev/axolotlsay".to_owned(),
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            ReleaseSelection::from_hosting(&hosting, "0.2.2", ChecksumStyle::Sha256),
+            Some(ReleaseSelection {
+                download_prefix:
+                    "https://gitlab.com/api/v4/projects/axodotdev%2Faxolotlsay/packages/generic/axolotlsay/"
+                        .to_owned(),
+                tag_prefix: String::new(),
+                github_repo: None,
+                checksum_style: "sha256".to_owned(),
+            })
+        );
+    }
+}
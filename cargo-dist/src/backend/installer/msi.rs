@@ -6,7 +6,7 @@ use dist_schema::TripleName;
 use tracing::info;
 use wix::print::{wxs::WxsRenders, RenderOutput};
 
-use crate::{backend::diff_files, config, errors::*, DistGraph};
+use crate::{backend::diff_files, config, errors::*};
 
 const METADATA_WIX: &str = "wix";
 const WIX_GUID_KEYS: &[&str] = &["upgrade-guid", "path-guid"];
@@ -34,7 +34,7 @@ impl MsiInstallerInfo {
     ///
     /// Note that this assumes `write_wsx_to_disk` was run beforehand (via `dist generate`),
     /// which should be enforced by `check_wsx` (via `dist generate --check`).
-    pub fn build(&self, dist: &DistGraph) -> DistResult<()> {
+    pub fn build(&self) -> DistResult<()> {
         info!("building an msi: {}", self.file_path);
 
         let mut b = wix::create::Builder::new();
@@ -62,7 +62,6 @@ impl MsiInstallerInfo {
         })?;
 
         assert!(self.file_path.exists());
-        dist.signer.sign(&self.file_path)?;
         Ok(())
     }
 
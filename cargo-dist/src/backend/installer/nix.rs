@@ -0,0 +1,243 @@
+//! Code for generating Nix derivations
+//!
+//! The derivation is a `callPackage`-able file that fetches the release's
+//! prebuilt archives with `fetchurl` and installs the binaries from them. Nix
+//! wants SRI-formatted hashes, so we convert the checksums we computed for the
+//! archives, and Linux binaries that dynamically link against anything get
+//! patched with `autoPatchelfHook` so they can find their libraries.
+
+use axoasset::LocalAsset;
+use base64::Engine;
+use camino::Utf8PathBuf;
+use dist_schema::{ArtifactId, DistManifest};
+use serde::Serialize;
+use spdx::{Expression, LicenseItem};
+
+use crate::{
+    backend::templates::TEMPLATE_INSTALLER_NIX, config::ChecksumStyle, decode_hex,
+    errors::DistResult, DistError, DistGraph,
+};
+
+/// The checksums Nix understands, and the SRI prefix for each
+const NIX_CHECKSUMS: &[(ChecksumStyle, &str)] = &[
+    (ChecksumStyle::Sha256, "sha256"),
+    (ChecksumStyle::Sha512, "sha512"),
+];
+
+/// Info about a Nix derivation
+#[derive(Debug, Clone, Serialize)]
+pub struct NixInstallerInfo {
+    /// Final file path of the derivation
+    pub file_path: Utf8PathBuf,
+    /// The name of the package
+    pub pname: String,
+    /// The version of the package
+    pub version: String,
+    /// A brief description of the package
+    pub description: Option<String>,
+    /// The URL to the package's homepage
+    pub homepage: Option<String>,
+    /// The SPDX ids of the package's licenses
+    pub licenses: Vec<String>,
+    /// The URL the archives can be downloaded from (append the artifact name)
+    pub base_url: String,
+    /// The archives the derivation can fetch
+    pub sources: Vec<NixSource>,
+    /// The executables to install
+    pub executables: Vec<String>,
+    /// Aliases to create for executables, as `(alias, executable)` pairs
+    pub aliases: Vec<(String, String)>,
+}
+
+/// An archive a Nix derivation can fetch
+#[derive(Debug, Clone, Serialize)]
+pub struct NixSource {
+    /// The id of the archive
+    pub id: ArtifactId,
+    /// The Nix system the archive is for (e.g. "x86_64-linux")
+    pub system: String,
+    /// The dir in the archive the executables are in (`.` for the root)
+    pub root_dir: String,
+    /// Whether the archive is a zip (Nix needs `unzip` to unpack these)
+    pub is_zip: bool,
+    /// The SRI hash of the archive, filled in at build time
+    pub hash: Option<String>,
+    /// Whether the binaries need to be patched to find their libraries,
+    /// filled in at build time
+    pub needs_patchelf: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct NixTemplateInputs {
+    #[serde(flatten)]
+    info: NixInstallerInfo,
+    /// Whether any archive needs `unzip`
+    needs_unzip: bool,
+    /// Whether any archive needs `autoPatchelfHook`
+    needs_patchelf: bool,
+}
+
+impl NixInstallerInfo {
+    /// Write the derivation to disk
+    pub fn build(&self, dist: &DistGraph, manifest: &DistManifest) -> DistResult<()> {
+        let mut info = self.clone();
+        // The metadata ends up in Nix strings
+        for field in [&mut info.description, &mut info.homepage]
+            .into_iter()
+            .flatten()
+        {
+            *field = nix_escape(field);
+        }
+        for source in &mut info.sources {
+            let hash = sri_hash(manifest, &source.id).ok_or_else(|| DistError::NixNoChecksum {
+                artifact: source.id.to_string(),
+            })?;
+            source.hash = Some(hash);
+            let linkage = manifest.linkage_for_artifact(&source.id);
+            source.needs_patchelf = source.system.ends_with("-linux")
+                && !(linkage.system.is_empty()
+                    && linkage.homebrew.is_empty()
+                    && linkage.public_unmanaged.is_empty()
+                    && linkage.other.is_empty());
+        }
+
+        let inputs = NixTemplateInputs {
+            needs_unzip: info.sources.iter().any(|s| s.is_zip),
+            needs_patchelf: info.sources.iter().any(|s| s.needs_patchelf),
+            info,
+        };
+        let derivation = dist
+            .templates
+            .render_file_to_clean_string(TEMPLATE_INSTALLER_NIX, &inputs)?;
+        LocalAsset::write_new(&derivation, &self.file_path)?;
+        Ok(())
+    }
+}
+
+/// Get the SRI hash of an artifact from the checksums we computed for it, if any
+fn sri_hash(manifest: &DistManifest, id: &ArtifactId) -> Option<String> {
+    let artifact = manifest.artifacts.get(id)?;
+    NIX_CHECKSUMS.iter().find_map(|(style, prefix)| {
+        let hex = artifact.checksums.get(style.ext())?;
+        let bytes = decode_hex(hex.as_str())?;
+        let encoded = base64::prelude::BASE64_STANDARD.encode(bytes);
+        Some(format!("{prefix}-{encoded}"))
+    })
+}
+
+/// Escape any characters in the input that have a special meaning in a Nix string
+fn nix_escape(input: &str) -> String {
+    input
+        .replace('\\', r"\\")
+        .replace('"', "\\\"")
+        .replace("${", "\\${")
+}
+
+/// Get the Nix system for a target triple, if it has one we support
+pub fn nix_system(target: &str) -> Option<&'static str> {
+    if target.starts_with("x86_64-") && target.contains("-linux-") {
+        Some("x86_64-linux")
+    } else if target.starts_with("aarch64-") && target.contains("-linux-") {
+        Some("aarch64-linux")
+    } else if target.starts_with("x86_64-apple-darwin") {
+        Some("x86_64-darwin")
+    } else if target.starts_with("aarch64-apple-darwin") {
+        Some("aarch64-darwin")
+    } else {
+        None
+    }
+}
+
+/// Get the SPDX license ids from a Cargo license expression, for `lib.getLicenseFromSpdxId`
+///
+/// nixpkgs lists every license a package is available under, so we flatten the
+/// expression. Anything that isn't a plain SPDX id is left out.
+pub fn nix_licenses(app_license: &str) -> Vec<String> {
+    let Ok(spdx) = Expression::parse(app_license) else {
+        return vec![];
+    };
+    let mut licenses = vec![];
+    for req in spdx.requirements() {
+        if let LicenseItem::Spdx { id, .. } = req.req.license {
+            let id = id.name.to_owned();
+            if !licenses.contains(&id) {
+                licenses.push(id);
+            }
+        }
+    }
+    licenses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dist_schema::{Artifact, ArtifactKind, ChecksumExtension, ChecksumValue};
+
+    #[test]
+    fn test_sri_hash() {
+        let id = ArtifactId::new("axolotlsay-x86_64-unknown-linux-gnu.tar.xz".to_owned());
+        let mut manifest = DistManifest::new(vec![], Default::default());
+        assert_eq!(sri_hash(&manifest, &id), None);
+
+        let mut artifact = Artifact {
+            name: Some(id.clone()),
+            kind: ArtifactKind::ExecutableZip,
+            target_triples: vec![],
+            path: None,
+            assets: vec![],
+            install_hint: None,
+            description: None,
+            checksum: None,
+            checksums: Default::default(),
+            signed_by: Default::default(),
+            signatures: Default::default(),
+        };
+        // sha256 of the empty string
+        artifact.checksums.insert(
+            ChecksumExtension::new("sha256".to_owned()),
+            ChecksumValue::new(
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855".to_owned(),
+            ),
+        );
+        manifest.artifacts.insert(id.clone(), artifact);
+        assert_eq!(
+            sri_hash(&manifest, &id).as_deref(),
+            Some("sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=")
+        );
+    }
+
+    #[test]
+    fn test_decode_hex() {
+        assert_eq!(decode_hex("00ff7f"), Some(vec![0x00, 0xff, 0x7f]));
+        assert_eq!(decode_hex("0"), None);
+        assert_eq!(decode_hex("zz"), None);
+    }
+
+    #[test]
+    fn test_nix_escape() {
+        assert_eq!(nix_escape("axolotlsay"), "axolotlsay");
+        assert_eq!(nix_escape(r#"say "${hi}" \o/"#), r#"say \"\${hi}\" \\o/"#);
+    }
+
+    #[test]
+    fn test_nix_system() {
+        assert_eq!(nix_system("x86_64-unknown-linux-gnu"), Some("x86_64-linux"));
+        assert_eq!(
+            nix_system("aarch64-unknown-linux-musl"),
+            Some("aarch64-linux")
+        );
+        assert_eq!(nix_system("aarch64-apple-darwin"), Some("aarch64-darwin"));
+        assert_eq!(nix_system("x86_64-pc-windows-msvc"), None);
+    }
+
+    #[test]
+    fn test_nix_licenses() {
+        assert_eq!(nix_licenses("MIT"), vec!["MIT"]);
+        assert_eq!(
+            nix_licenses("MIT OR (Apache-2.0 AND MIT)"),
+            vec!["MIT", "Apache-2.0"]
+        );
+        assert_eq!(nix_licenses("Apache-2.0+"), vec!["Apache-2.0"]);
+        assert!(nix_licenses("not a license").is_empty());
+    }
+}
@@ -1,15 +1,24 @@
 //! Code for generating npm-installer.tar.gz
+//!
+//! By default the package downloads the binaries for the user's platform when it's
+//! installed. With `npm-platform-packages` we instead publish a package per platform
+//! with the binaries in it, and the main package lists them all as
+//! `optionalDependencies`, letting npm pick the one that matches the platform
+//! (by their `os`/`cpu`/`libc` fields).
 
 use axoasset::{LocalAsset, SourceFile};
 use camino::{Utf8Path, Utf8PathBuf};
-use dist_schema::{ArtifactId, GlibcVersion, TripleName};
+use dist_schema::{ArtifactId, GlibcVersion, TripleName, TripleNameRef};
 use serde::Serialize;
 
 use super::InstallerInfo;
 use crate::{
-    backend::templates::{Templates, TEMPLATE_INSTALLER_NPM, TEMPLATE_INSTALLER_NPM_RUN_JS},
+    backend::templates::{
+        Templates, TEMPLATE_INSTALLER_NPM, TEMPLATE_INSTALLER_NPM_PLATFORMS,
+        TEMPLATE_INSTALLER_NPM_RUN_JS,
+    },
     errors::DistResult,
-    platform::LibcVersion,
+    platform::{LibcVersion, PlatformSupport},
     DistGraph, SortedMap, SortedSet,
 };
 
@@ -34,10 +43,38 @@ pub struct NpmInstallerInfo {
     pub npm_package_keywords: Option<Vec<String>>,
     /// Dir to build the package in
     pub package_dir: Utf8PathBuf,
+    /// The names of the per-platform packages with the binaries in them, by the platform
+    /// they're for (None if the binaries are downloaded on install instead)
+    pub platform_packages: Option<SortedMap<TripleName, String>>,
     /// Generic installer info
     pub inner: InstallerInfo,
 }
 
+/// Info about one of the per-platform npm packages with the binaries in it
+#[derive(Debug, Clone, Serialize)]
+pub struct NpmPlatformInstallerInfo {
+    /// The name of the npm package
+    pub npm_package_name: String,
+    /// The version of the npm package
+    pub npm_package_version: String,
+    /// Short description of the package
+    pub npm_package_desc: Option<String>,
+    /// URL to repository
+    pub npm_package_repository_url: Option<String>,
+    /// URL to homepage
+    pub npm_package_homepage_url: Option<String>,
+    /// The license of the package
+    pub npm_package_license: Option<String>,
+    /// The npm `os` the package is for
+    pub os: String,
+    /// The npm `cpu` the package is for
+    pub cpu: String,
+    /// The npm `libc` the package is for, if it only works with one
+    pub libc: Option<String>,
+    /// Dir to build the package in (the binaries go in its root)
+    pub package_dir: Utf8PathBuf,
+}
+
 const RUN_JS: &str = "run.js";
 const PACKAGE_JSON: &str = "package.json";
 const PACKAGE_LOCK: &str = "npm-shrinkwrap.json";
@@ -49,6 +86,8 @@ struct PackageJsonPlatform {
     artifact_name: ArtifactId,
     bins: SortedMap<String, String>,
     zip_ext: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    package_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -66,11 +105,20 @@ struct RunInfo {
 pub(crate) fn write_npm_project(dist: &DistGraph, info: &NpmInstallerInfo) -> DistResult<()> {
     // First render the dir
     let templates = &dist.templates;
-    let mut files = templates.render_dir_to_clean_strings(TEMPLATE_INSTALLER_NPM, info)?;
     let platforms = platforms(info);
-    mangle_run_js(templates, &platforms, &mut files)?;
+    let mut files = if info.platform_packages.is_some() {
+        // Nothing to download, so no dependencies to lock
+        let mut files =
+            templates.render_dir_to_clean_strings(TEMPLATE_INSTALLER_NPM_PLATFORMS, info)?;
+        add_run_js(templates, &platforms, &mut files)?;
+        files
+    } else {
+        let mut files = templates.render_dir_to_clean_strings(TEMPLATE_INSTALLER_NPM, info)?;
+        mangle_run_js(templates, &platforms, &mut files)?;
+        mangle_package_lock(info, &platforms, &mut files)?;
+        files
+    };
     mangle_package_json(info, &platforms, &mut files)?;
-    mangle_package_lock(info, &platforms, &mut files)?;
 
     // Finally, write the results
     let zip_dir = &info.package_dir;
@@ -92,6 +140,14 @@ fn mangle_run_js(
         .remove(run_js_path)
         .expect("npm template didn't have a run.js!?");
 
+    add_run_js(templates, platforms, files)
+}
+
+fn add_run_js(
+    templates: &Templates,
+    platforms: &PlatformSummary,
+    files: &mut SortedMap<Utf8PathBuf, String>,
+) -> DistResult<()> {
     for bin in &platforms.bins {
         let filename = platforms.run_js_name_for_bin(bin);
         let info = RunInfo {
@@ -177,12 +233,23 @@ fn mangle_package_json(
     }
     // installer-specific fields
     package_json["bin"] = platforms.bins_json();
-    // These two fields are "non-standard" in the package.json format, but the
-    // installer expects to find them when it reads its own package.json (with `require`).
+    // This field is "non-standard" in the package.json format, but the
+    // installer expects to find it when it reads its own package.json (with `require`).
     // It's fairly normal to add random stuff to a package.json like this,
     // as it's a format that's infamously ill-defined with minimal validation.
-    package_json["artifactDownloadUrl"] = info.inner.base_url.clone().into();
     package_json["supportedPlatforms"] = platforms.platform_support_json();
+    if let Some(platform_packages) = &info.platform_packages {
+        // npm installs whichever of these match the platform, and skips the rest
+        let mut deps = SortedMap::<&str, &str>::new();
+        for package_name in platform_packages.values() {
+            deps.insert(package_name, &info.npm_package_version);
+        }
+        package_json["optionalDependencies"] =
+            serde_json::to_value(&deps).expect("serde_json failed");
+    } else {
+        // As is this one
+        package_json["artifactDownloadUrl"] = info.inner.base_url.clone().into();
+    }
 
     match info.inner.runtime_conditions.min_glibc_version {
         Some(LibcVersion { major, series }) => {
@@ -243,12 +310,137 @@ fn platforms(info: &NpmInstallerInfo) -> PlatformSummary {
             artifact_name: archive.id.clone(),
             bins,
             zip_ext: archive.zip_style.ext().to_owned(),
+            package_name: info
+                .platform_packages
+                .as_ref()
+                .and_then(|packages| packages.get(&target).cloned()),
         };
         output.platforms.insert(target, platform);
     }
     output
 }
 
+/// Write one of the per-platform packages (the binaries are already in its dir)
+pub(crate) fn write_npm_platform_package(info: &NpmPlatformInstallerInfo) -> DistResult<()> {
+    let mut package_json = serde_json::Map::new();
+    package_json.insert("name".to_owned(), info.npm_package_name.clone().into());
+    package_json.insert(
+        "version".to_owned(),
+        info.npm_package_version.clone().into(),
+    );
+    if let Some(val) = info.npm_package_desc.clone() {
+        package_json.insert("description".to_owned(), val.into());
+    }
+    if let Some(val) = info.npm_package_repository_url.clone() {
+        package_json.insert("repository".to_owned(), val.into());
+    }
+    if let Some(val) = info.npm_package_homepage_url.clone() {
+        package_json.insert("homepage".to_owned(), val.into());
+    }
+    if let Some(val) = info.npm_package_license.clone() {
+        package_json.insert("license".to_owned(), val.into());
+    }
+    // Yarn won't run binaries out of a zipped package
+    package_json.insert("preferUnplugged".to_owned(), true.into());
+    package_json.insert("os".to_owned(), vec![info.os.clone()].into());
+    package_json.insert("cpu".to_owned(), vec![info.cpu.clone()].into());
+    if let Some(libc) = &info.libc {
+        package_json.insert("libc".to_owned(), vec![libc.clone()].into());
+    }
+
+    let package_json = serde_json::to_string_pretty(&package_json).expect("serde_json failed");
+    LocalAsset::write_new_all(&package_json, info.package_dir.join(PACKAGE_JSON))?;
+    Ok(())
+}
+
+/// Get the name of the per-platform package with the binaries for a target
+pub fn npm_platform_package_name(npm_package_name: &str, target: &TripleNameRef) -> String {
+    format!("{npm_package_name}-{target}")
+}
+
+/// Get the per-platform packages an npm package should depend on, by the platform
+/// they're for
+///
+/// Several platforms can share a package, e.g. when static musl binaries stand in
+/// for glibc ones. Platforms that would run another cpu's binaries through emulation
+/// are left out, as npm only installs a package on the cpus it lists.
+pub fn npm_platform_packages(
+    npm_package_name: &str,
+    platform_support: &PlatformSupport,
+) -> SortedMap<TripleName, String> {
+    let mut packages = SortedMap::new();
+    for fragment in platform_support.fragments() {
+        let Some(archive) = platform_support
+            .archives
+            .iter()
+            .find(|archive| archive.id == fragment.id)
+        else {
+            continue;
+        };
+        let Some((_, cpu)) = npm_os_cpu(&archive.target_triple) else {
+            continue;
+        };
+        if npm_os_cpu(&fragment.target_triple).map(|(_, cpu)| cpu) != Some(cpu) {
+            continue;
+        }
+        // Packages are named after the target they were built for, which isn't
+        // the archive's desugared triple (e.g. "-musl", not "-musl-static")
+        let Some(build_target) = archive.target_triples.first() else {
+            continue;
+        };
+        packages.insert(
+            fragment.target_triple,
+            npm_platform_package_name(npm_package_name, build_target),
+        );
+    }
+    packages
+}
+
+/// Get the npm `os` and `cpu` for a target triple, if npm has names for them
+pub fn npm_os_cpu(target: &TripleNameRef) -> Option<(&'static str, &'static str)> {
+    let target = target.as_str();
+    let os = if target.contains("-apple-darwin") {
+        "darwin"
+    } else if target.contains("-windows-") {
+        "win32"
+    } else if target.contains("-linux-") {
+        "linux"
+    } else if target.contains("-freebsd") {
+        "freebsd"
+    } else {
+        return None;
+    };
+    let cpu = match target.split('-').next()? {
+        "x86_64" => "x64",
+        "aarch64" => "arm64",
+        "i686" | "i586" => "ia32",
+        "armv7" | "arm" => "arm",
+        "powerpc64le" | "powerpc64" => "ppc64",
+        "s390x" => "s390x",
+        "riscv64gc" => "riscv64",
+        "loongarch64" => "loong64",
+        _ => return None,
+    };
+    Some((os, cpu))
+}
+
+/// Get the npm `libc` for a per-platform package, given the platforms it's used for
+///
+/// A package only gets restricted to a libc if every platform it's used for agrees
+/// on it, so static musl binaries standing in for missing glibc ones get installed
+/// on glibc systems too.
+pub fn npm_libc(platforms: &[TripleName]) -> Option<&'static str> {
+    if platforms.is_empty() {
+        None
+    } else if platforms.iter().all(|p| p.as_str().contains("-linux-gnu")) {
+        Some("glibc")
+    } else if platforms.iter().all(|p| p.as_str().contains("-linux-musl")) {
+        Some("musl")
+    } else {
+        None
+    }
+}
+
 impl PlatformSummary {
     fn bins_json(&self) -> serde_json::Value {
         let mut bins = SortedMap::<String, String>::new();
@@ -269,3 +461,115 @@ impl PlatformSummary {
         format!("run-{bin}.js")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CompressionImpl, ZipStyle};
+    use crate::platform::{FetchableArchive, PlatformEntry, SupportQuality};
+
+    #[test]
+    fn test_npm_os_cpu() {
+        let os_cpu = |target: &str| npm_os_cpu(&TripleName::new(target.to_owned()));
+        assert_eq!(os_cpu("x86_64-unknown-linux-gnu"), Some(("linux", "x64")));
+        assert_eq!(os_cpu("aarch64-apple-darwin"), Some(("darwin", "arm64")));
+        assert_eq!(os_cpu("i686-pc-windows-msvc"), Some(("win32", "ia32")));
+        assert_eq!(os_cpu("x86_64-unknown-illumos"), None);
+    }
+
+    #[test]
+    fn test_npm_libc() {
+        let libc = |platforms: &[&str]| {
+            let platforms = platforms
+                .iter()
+                .map(|p| TripleName::new((*p).to_owned()))
+                .collect::<Vec<_>>();
+            npm_libc(&platforms)
+        };
+        assert_eq!(libc(&["x86_64-unknown-linux-gnu"]), Some("glibc"));
+        assert_eq!(
+            libc(&[
+                "x86_64-unknown-linux-musl-dynamic",
+                "x86_64-unknown-linux-musl-static"
+            ]),
+            Some("musl")
+        );
+        // Static musl binaries standing in for glibc ones
+        assert_eq!(
+            libc(&[
+                "x86_64-unknown-linux-gnu",
+                "x86_64-unknown-linux-musl-static"
+            ]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_npm_platform_packages() {
+        let triple = |target: &str| TripleName::new(target.to_owned());
+        // Like PlatformSupport::new, "musl" gets desugared to "musl-static"
+        let archive = |target: &str| FetchableArchive {
+            id: ArtifactId::new(format!("app-{target}.tar.gz")),
+            native_runtime_conditions: Default::default(),
+            target_triple: triple(&target.replace("-musl", "-musl-static")),
+            target_triples: vec![triple(target)],
+            checksum: None,
+            executables: vec!["app".to_owned()],
+            cdylibs: vec![],
+            cstaticlibs: vec![],
+            zip_style: ZipStyle::Tar(CompressionImpl::Gzip),
+            updater: None,
+        };
+        let entry = |quality, archive_idx| PlatformEntry {
+            quality,
+            runtime_conditions: Default::default(),
+            archive_idx,
+        };
+        let platform_support = PlatformSupport {
+            archives: vec![
+                archive("x86_64-apple-darwin"),
+                archive("x86_64-unknown-linux-musl"),
+            ],
+            updaters: vec![],
+            platforms: SortedMap::from_iter([
+                (
+                    triple("x86_64-apple-darwin"),
+                    vec![entry(SupportQuality::HostNative, 0)],
+                ),
+                (
+                    triple("aarch64-apple-darwin"),
+                    vec![entry(SupportQuality::Emulated, 0)],
+                ),
+                (
+                    triple("x86_64-unknown-linux-musl-static"),
+                    vec![entry(SupportQuality::HostNative, 1)],
+                ),
+                (
+                    triple("x86_64-unknown-linux-gnu"),
+                    vec![entry(SupportQuality::ImperfectNative, 1)],
+                ),
+            ]),
+        };
+
+        let packages = npm_platform_packages("app", &platform_support);
+        // The x64 package would refuse to install on arm64 macs, so they aren't
+        // pointed at it
+        assert_eq!(
+            packages.into_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    triple("x86_64-apple-darwin"),
+                    "app-x86_64-apple-darwin".to_owned()
+                ),
+                (
+                    triple("x86_64-unknown-linux-gnu"),
+                    "app-x86_64-unknown-linux-musl".to_owned()
+                ),
+                (
+                    triple("x86_64-unknown-linux-musl-static"),
+                    "app-x86_64-unknown-linux-musl".to_owned()
+                ),
+            ]
+        );
+    }
+}
@@ -11,6 +11,71 @@ pub(crate) fn write_install_ps_script(dist: &DistGraph, info: &InstallerInfo) ->
         .templates
         .render_file_to_clean_string(TEMPLATE_INSTALLER_PS1, info)?;
     LocalAsset::write_new(&script, &info.dest_path)?;
-    dist.signer.sign(&info.dest_path)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    // A real key and signature made by minisign, over the message "test"
+    const PUBLIC_KEY: &str = "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+    const SIGNATURE: &str =
+        "RWQf6LRCGA9i59SLOFxz6NxvASXDJeRtuZykwQepbDEGt87ig1BNpWaVWuNrm73YiIiJbq71Wi+dP9eKL8OC351vwIasSSbXxwA=";
+
+    /// Check the signature of `message` with the installer's ed25519 verifier,
+    /// if there's a powershell around to run it with
+    fn verify_with_installer(message: &str) -> Option<bool> {
+        let template = include_str!("../../../templates/installer/installer.ps1.j2");
+        let start = template.find("function Test-Ed25519Signature").unwrap();
+        let end = start + template[start..].find("{%- endif %}").unwrap();
+        let functions = &template[start..end];
+        let script = format!(
+            r#"{functions}
+$public_key = [Convert]::FromBase64String("{PUBLIC_KEY}")
+$signature = [Convert]::FromBase64String("{SIGNATURE}")
+$message = [Text.Encoding]::UTF8.GetBytes("{message}")
+if (Test-Ed25519Signature $public_key[10..41] $message $signature[10..73]) {{ "valid" }} else {{ "invalid" }}
+"#
+        );
+        let tmp = tempfile::tempdir().unwrap();
+        let script_path = tmp.path().join("verify.ps1");
+        std::fs::write(&script_path, script).unwrap();
+
+        for powershell in ["pwsh", "powershell"] {
+            let Ok(output) = Command::new(powershell)
+                .arg("-NoProfile")
+                .arg("-File")
+                .arg(&script_path)
+                .output()
+            else {
+                continue;
+            };
+            assert!(
+                output.status.success(),
+                "{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+            return Some(String::from_utf8(output.stdout).unwrap().trim() == "valid");
+        }
+        None
+    }
+
+    #[test]
+    fn test_ed25519_accepts_minisign_signature() {
+        let Some(valid) = verify_with_installer("test") else {
+            eprintln!("skipping, there's no powershell to run the verifier with");
+            return;
+        };
+        assert!(valid);
+    }
+
+    #[test]
+    fn test_ed25519_rejects_other_message() {
+        let Some(valid) = verify_with_installer("tesT") else {
+            eprintln!("skipping, there's no powershell to run the verifier with");
+            return;
+        };
+        assert!(!valid);
+    }
+}
@@ -0,0 +1,403 @@
+//! Code for generating Python wheels
+//!
+//! A wheel is a zip with a `{name}-{version}.dist-info` dir holding the package
+//! metadata and a RECORD of every file's hash. Files under
+//! `{name}-{version}.data/scripts` get installed next to the Python executable,
+//! which is exactly where we want our binaries, so a wheel never needs to
+//! contain any Python. Like debs and rpms, we write all of this ourselves so
+//! wheels can be built anywhere without Python being installed.
+
+use std::io::Write;
+
+use axoasset::LocalAsset;
+use base64::Engine;
+use camino::Utf8PathBuf;
+use dist_schema::{
+    target_lexicon::{Architecture, ArmArchitecture, Environment},
+    PipPackageName, TripleNameRef,
+};
+use semver::Version;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tracing::info;
+use zip::{write::SimpleFileOptions, CompressionMethod, DateTime, ZipWriter};
+
+use crate::{
+    platform::{LibcVersion, RuntimeConditions},
+    DistResult,
+};
+
+use super::ExecutableZipFragment;
+
+/// Info about a Python wheel
+#[derive(Debug, Clone, Serialize)]
+pub struct PypiInstallerInfo {
+    /// ExecutableZipFragment for this variant
+    pub artifact: ExecutableZipFragment,
+    /// Final file path of the wheel
+    pub file_path: Utf8PathBuf,
+    /// Dir stuff goes to
+    pub package_dir: Utf8PathBuf,
+    /// The name of the package on PyPI
+    pub package_name: PipPackageName,
+    /// The package version (in PEP 440's format)
+    pub version: String,
+    /// The wheel's platform tag (e.g. "manylinux_2_35_x86_64")
+    pub platform_tag: String,
+    /// A brief description of the app
+    pub description: Option<String>,
+    /// The authors of the app
+    pub authors: Vec<String>,
+    /// The license of the app
+    pub license: Option<String>,
+    /// The URL to the app's homepage
+    pub homepage: Option<String>,
+    /// The URL to the app's repository
+    pub repository: Option<String>,
+    /// Keywords for the app
+    pub keywords: Vec<String>,
+}
+
+impl PypiInstallerInfo {
+    /// Build the wheel
+    pub fn build(&self) -> DistResult<()> {
+        info!("building a wheel: {}", self.file_path);
+
+        let prefix = format!(
+            "{}-{}",
+            wheel_distribution_name(&self.package_name),
+            self.version
+        );
+        let mut files = vec![];
+        for exe in &self.artifact.executables {
+            files.push((
+                format!("{prefix}.data/scripts/{exe}"),
+                0o755,
+                LocalAsset::load_bytes(self.package_dir.join(exe))?,
+            ));
+        }
+        files.push((
+            format!("{prefix}.dist-info/METADATA"),
+            0o644,
+            self.metadata().into_bytes(),
+        ));
+        files.push((
+            format!("{prefix}.dist-info/WHEEL"),
+            0o644,
+            self.wheel().into_bytes(),
+        ));
+
+        // RECORD lists every file with its hash, except itself
+        let record_path = format!("{prefix}.dist-info/RECORD");
+        let mut record = String::new();
+        for (path, _, contents) in &files {
+            record.push_str(&record_line(path, contents));
+        }
+        record.push_str(&format!("{record_path},,\n"));
+        files.push((record_path, 0o644, record.into_bytes()));
+
+        // Use a fixed timestamp so that the wheel is reproducible
+        let mut zip = ZipWriter::new(std::io::Cursor::new(vec![]));
+        for (path, mode, contents) in files {
+            let options = SimpleFileOptions::default()
+                .compression_method(CompressionMethod::Deflated)
+                .last_modified_time(DateTime::default())
+                .unix_permissions(mode);
+            zip.start_file(path, options)?;
+            zip.write_all(&contents)?;
+        }
+        let wheel = zip.finish()?.into_inner();
+        std::fs::write(&self.file_path, wheel)?;
+
+        Ok(())
+    }
+
+    /// The core metadata of the package (`METADATA`)
+    fn metadata(&self) -> String {
+        let mut metadata = String::new();
+        let mut field = |key: &str, value: &str| {
+            metadata.push_str(&format!("{key}: {value}\n"));
+        };
+        field("Metadata-Version", "2.1");
+        field("Name", self.package_name.as_str());
+        field("Version", &self.version);
+        if let Some(description) = &self.description {
+            // The summary has to be a single line
+            let summary = description.trim().lines().next().unwrap_or_default();
+            field("Summary", summary);
+        }
+        if let Some(homepage) = &self.homepage {
+            field("Home-page", homepage);
+        }
+        // Authors with an email address ("Name <email>") belong in Author-email,
+        // which is how PyPI knows to link them
+        let (with_email, without_email): (Vec<&str>, Vec<&str>) = self
+            .authors
+            .iter()
+            .map(|author| author.trim())
+            .partition(|author| author.ends_with('>') && author.contains('<'));
+        if !without_email.is_empty() {
+            field("Author", &without_email.join(", "));
+        }
+        if !with_email.is_empty() {
+            field("Author-email", &with_email.join(", "));
+        }
+        if let Some(license) = &self.license {
+            field("License", license);
+        }
+        if !self.keywords.is_empty() {
+            field("Keywords", &self.keywords.join(","));
+        }
+        if let Some(repository) = &self.repository {
+            field("Project-URL", &format!("Repository, {repository}"));
+        }
+        metadata
+    }
+
+    /// The wheel format metadata (`WHEEL`)
+    fn wheel(&self) -> String {
+        format!(
+            "Wheel-Version: 1.0\nGenerator: dist ({})\nRoot-Is-Purelib: false\nTag: py3-none-{}\n",
+            env!("CARGO_PKG_VERSION"),
+            self.platform_tag
+        )
+    }
+}
+
+/// A line of a RECORD file for the given file
+fn record_line(path: &str, contents: &[u8]) -> String {
+    let digest = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(Sha256::digest(contents));
+    format!("{path},sha256={digest},{}\n", contents.len())
+}
+
+/// Get the name of a package as it appears in wheel filenames
+///
+/// Runs of `-`, `_` and `.` become a single `_`, and the result is lowercased.
+pub fn wheel_distribution_name(package_name: &PipPackageName) -> String {
+    let mut output = String::new();
+    let mut in_separator = false;
+    for c in package_name.as_str().chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !in_separator {
+                output.push('_');
+            }
+            in_separator = true;
+        } else {
+            output.push(c.to_ascii_lowercase());
+            in_separator = false;
+        }
+    }
+    output
+}
+
+/// Get the filename of a wheel
+pub fn wheel_filename(package_name: &PipPackageName, version: &str, platform_tag: &str) -> String {
+    format!(
+        "{}-{version}-py3-none-{platform_tag}.whl",
+        wheel_distribution_name(package_name)
+    )
+}
+
+/// Get the platform tag of a wheel for a target, if there's one pip understands
+///
+/// Linux wheels use the `manylinux` and `musllinux` tags, which encode the
+/// oldest libc the binaries will run on.
+pub fn wheel_platform_tag(
+    target: &TripleNameRef,
+    runtime_conditions: &RuntimeConditions,
+) -> Option<String> {
+    let triple = target.parse().ok()?;
+    if target.is_linux() {
+        let hard_float = matches!(
+            triple.environment,
+            Environment::Gnueabihf | Environment::Musleabihf
+        );
+        let arch = match triple.architecture {
+            Architecture::X86_64 => "x86_64",
+            Architecture::X86_32(_) => "i686",
+            Architecture::Aarch64(_) => "aarch64",
+            Architecture::Arm(ArmArchitecture::Armv7) if hard_float => "armv7l",
+            Architecture::Powerpc64le => "ppc64le",
+            Architecture::S390x => "s390x",
+            Architecture::Riscv64(_) => "riscv64",
+            _ => return None,
+        };
+        if target.is_musl() {
+            let LibcVersion { major, series } =
+                runtime_conditions.min_musl_version.unwrap_or(LibcVersion {
+                    major: 1,
+                    series: 2,
+                });
+            Some(format!("musllinux_{major}_{series}_{arch}"))
+        } else {
+            let LibcVersion { major, series } = runtime_conditions
+                .min_glibc_version
+                .unwrap_or_else(LibcVersion::default_glibc);
+            Some(format!("manylinux_{major}_{series}_{arch}"))
+        }
+    } else if target.is_darwin() {
+        match triple.architecture {
+            Architecture::X86_64 => Some("macosx_10_12_x86_64".to_owned()),
+            Architecture::Aarch64(_) => Some("macosx_11_0_arm64".to_owned()),
+            _ => None,
+        }
+    } else if target.is_windows() {
+        match triple.architecture {
+            Architecture::X86_64 => Some("win_amd64".to_owned()),
+            Architecture::X86_32(_) => Some("win32".to_owned()),
+            Architecture::Aarch64(_) => Some("win_arm64".to_owned()),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+/// Get the PEP 440 equivalent of a version
+///
+/// The common prerelease labels map onto PEP 440's `a`, `b` and `rc`, and any
+/// other prerelease becomes a `.dev` release. Build metadata is dropped, as
+/// PyPI rejects local versions.
+pub fn pep440_version(version: &Version) -> String {
+    let mut output = format!("{}.{}.{}", version.major, version.minor, version.patch);
+    if version.pre.is_empty() {
+        return output;
+    }
+
+    let mut parts = version.pre.as_str().split(['.', '-']);
+    let first = parts.next().unwrap_or_default();
+    let label = first.trim_end_matches(|c: char| c.is_ascii_digit());
+    let number = match &first[label.len()..] {
+        "" => parts.next().and_then(|part| part.parse::<u64>().ok()),
+        digits => digits.parse().ok(),
+    }
+    .unwrap_or(0);
+    let label = match label.to_ascii_lowercase().as_str() {
+        "alpha" | "a" => "a",
+        "beta" | "b" => "b",
+        "rc" | "c" | "pre" | "preview" => "rc",
+        _ => {
+            output.push_str(&format!(".dev{number}"));
+            return output;
+        }
+    };
+    output.push_str(&format!("{label}{number}"));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use dist_schema::{PipPackageName, TripleName};
+    use semver::Version;
+
+    use super::*;
+
+    fn run_tag_comparison(in_str: &str, expected: Option<&str>) {
+        let out = wheel_platform_tag(
+            &TripleName::new(in_str.to_owned()),
+            &RuntimeConditions::default(),
+        );
+
+        assert_eq!(out.as_deref(), expected);
+    }
+
+    #[test]
+    fn tag_common() {
+        run_tag_comparison("x86_64-unknown-linux-gnu", Some("manylinux_2_35_x86_64"));
+        run_tag_comparison("aarch64-unknown-linux-musl", Some("musllinux_1_2_aarch64"));
+        run_tag_comparison(
+            "armv7-unknown-linux-gnueabihf",
+            Some("manylinux_2_35_armv7l"),
+        );
+        run_tag_comparison("x86_64-apple-darwin", Some("macosx_10_12_x86_64"));
+        run_tag_comparison("aarch64-apple-darwin", Some("macosx_11_0_arm64"));
+        run_tag_comparison("x86_64-pc-windows-msvc", Some("win_amd64"));
+        run_tag_comparison("i686-pc-windows-gnu", Some("win32"));
+        run_tag_comparison("arm-unknown-linux-gnueabi", None);
+        run_tag_comparison("x86_64-unknown-freebsd", None);
+    }
+
+    #[test]
+    fn tag_min_glibc() {
+        let conditions = RuntimeConditions {
+            min_glibc_version: Some(LibcVersion {
+                major: 2,
+                series: 17,
+            }),
+            ..Default::default()
+        };
+        let out = wheel_platform_tag(
+            &TripleName::new("x86_64-unknown-linux-gnu".to_owned()),
+            &conditions,
+        );
+        assert_eq!(out.as_deref(), Some("manylinux_2_17_x86_64"));
+    }
+
+    #[test]
+    fn versions() {
+        let run = |input: &str| pep440_version(&Version::parse(input).unwrap());
+        assert_eq!(run("1.2.3"), "1.2.3");
+        assert_eq!(run("1.2.3+build.5"), "1.2.3");
+        assert_eq!(run("1.0.0-alpha.1"), "1.0.0a1");
+        assert_eq!(run("1.0.0-beta"), "1.0.0b0");
+        assert_eq!(run("1.0.0-rc2"), "1.0.0rc2");
+        assert_eq!(run("1.0.0-prerelease.3"), "1.0.0.dev3");
+    }
+
+    #[test]
+    fn names() {
+        let name = PipPackageName::new("My-App.cli".to_owned());
+        assert_eq!(wheel_distribution_name(&name), "my_app_cli");
+        assert_eq!(
+            wheel_filename(&name, "1.0.0a1", "win_amd64"),
+            "my_app_cli-1.0.0a1-py3-none-win_amd64.whl"
+        );
+    }
+
+    #[test]
+    fn metadata_authors() {
+        let info = PypiInstallerInfo {
+            artifact: ExecutableZipFragment {
+                id: dist_schema::ArtifactId::new("app.tar.gz".to_owned()),
+                target_triple: TripleName::new("x86_64-unknown-linux-gnu".to_owned()),
+                executables: vec![],
+                cdylibs: vec![],
+                cstaticlibs: vec![],
+                zip_style: crate::config::ZipStyle::Zip,
+                updater: None,
+                runtime_conditions: RuntimeConditions::default(),
+            },
+            file_path: "app.whl".into(),
+            package_dir: "app_pypi".into(),
+            package_name: PipPackageName::new("app".to_owned()),
+            version: "1.0.0".to_owned(),
+            platform_tag: "manylinux_2_35_x86_64".to_owned(),
+            description: None,
+            authors: vec![
+                "Axo Developer Co. <hello@axo.dev>".to_owned(),
+                "The Axolotl Team".to_owned(),
+                "Ada <ada@example.com>".to_owned(),
+            ],
+            license: None,
+            homepage: None,
+            repository: None,
+            keywords: vec![],
+        };
+        assert_eq!(
+            info.metadata(),
+            "Metadata-Version: 2.1\n\
+             Name: app\n\
+             Version: 1.0.0\n\
+             Author: The Axolotl Team\n\
+             Author-email: Axo Developer Co. <hello@axo.dev>, Ada <ada@example.com>\n"
+        );
+    }
+
+    #[test]
+    fn record() {
+        assert_eq!(
+            record_line("a/b", b"hello"),
+            "a/b,sha256=LPJNul-wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ,5\n"
+        );
+    }
+}
@@ -0,0 +1,606 @@
+//! Code for generating RPM packages
+//!
+//! An rpm is a fixed-size "lead", a signature header holding digests of the
+//! rest of the file, a header holding the package metadata and file list, and
+//! a compressed cpio archive of the files themselves. Like debs, we write all
+//! of this ourselves so rpms can be built anywhere without `rpmbuild`.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use axoasset::LocalAsset;
+use camino::Utf8PathBuf;
+use dist_schema::{
+    target_lexicon::{Architecture, ArmArchitecture, Environment},
+    TripleNameRef,
+};
+use flate2::{write::GzEncoder, Compression};
+use semver::Version;
+use serde::Serialize;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use tracing::info;
+
+use crate::{encode_hex, DistResult};
+
+use super::{source_date_epoch, ExecutableZipFragment};
+
+/// Info about an RPM package
+#[derive(Debug, Clone, Serialize)]
+pub struct RpmInstallerInfo {
+    /// ExecutableZipFragment for this variant
+    pub artifact: ExecutableZipFragment,
+    /// Final file path of the rpm
+    pub file_path: Utf8PathBuf,
+    /// Dir stuff goes to
+    pub package_dir: Utf8PathBuf,
+    /// The name of the package
+    pub package_name: String,
+    /// The package version (in RPM's format)
+    pub version: String,
+    /// The RPM architecture of the package (e.g. "x86_64")
+    pub architecture: String,
+    /// Where libraries get installed (e.g. "/usr/lib64")
+    pub lib_dir: String,
+    /// The packager of the app
+    pub packager: Option<String>,
+    /// A brief description of the app
+    pub description: Option<String>,
+    /// The license of the app
+    pub license: Option<String>,
+    /// The URL to the app's homepage
+    pub homepage: Option<String>,
+    /// Executable aliases
+    pub bin_aliases: BTreeMap<String, Vec<String>>,
+}
+
+/// The release of the package, which we don't have any notion of
+const RELEASE: &str = "1";
+
+/// A file in the package
+struct RpmFile {
+    dir: String,
+    name: String,
+    mode: u16,
+    contents: Vec<u8>,
+    link_to: Option<String>,
+}
+
+impl RpmInstallerInfo {
+    /// Build the rpm
+    pub fn build(&self) -> DistResult<()> {
+        info!("building an rpm: {}", self.file_path);
+
+        let mut files = vec![];
+        for exe in &self.artifact.executables {
+            files.push(RpmFile {
+                dir: "/usr/bin/".to_owned(),
+                name: exe.clone(),
+                mode: 0o100755,
+                contents: LocalAsset::load_bytes(self.package_dir.join(exe))?,
+                link_to: None,
+            });
+        }
+        for (bin, aliases) in &self.bin_aliases {
+            for alias in aliases {
+                files.push(RpmFile {
+                    dir: "/usr/bin/".to_owned(),
+                    name: alias.clone(),
+                    mode: 0o120777,
+                    contents: bin.clone().into_bytes(),
+                    link_to: Some(bin.clone()),
+                });
+            }
+        }
+        let libs = self
+            .artifact
+            .cdylibs
+            .iter()
+            .map(|lib| (lib, 0o100755))
+            .chain(self.artifact.cstaticlibs.iter().map(|lib| (lib, 0o100644)));
+        for (lib, mode) in libs {
+            files.push(RpmFile {
+                dir: format!("{}/", self.lib_dir),
+                name: lib.clone(),
+                mode,
+                contents: LocalAsset::load_bytes(self.package_dir.join(lib))?,
+                link_to: None,
+            });
+        }
+        // rpm looks files up by binary search, so they need to be sorted
+        files.sort_by(|a, b| (&a.dir, &a.name).cmp(&(&b.dir, &b.name)));
+
+        let mtime = source_date_epoch() as u32;
+        let (payload, payload_size) = cpio_payload(&files, mtime)?;
+        let header = self
+            .header(&files, mtime, &payload)
+            .to_bytes(HEADER_IMMUTABLE);
+
+        let mut signature = RpmHeader::default();
+        signature.add(
+            SIGTAG_SHA1,
+            Value::String(encode_hex(&Sha1::digest(&header))),
+        );
+        signature.add(
+            SIGTAG_SHA256,
+            Value::String(encode_hex(&Sha256::digest(&header))),
+        );
+        signature.add(
+            SIGTAG_SIZE,
+            Value::Int32(vec![(header.len() + payload.len()) as u32]),
+        );
+        signature.add(SIGTAG_PAYLOADSIZE, Value::Int32(vec![payload_size]));
+        let mut signature = signature.to_bytes(HEADER_SIGNATURES);
+        // The signature header is padded to a multiple of 8 bytes
+        signature.resize(signature.len().next_multiple_of(8), 0);
+
+        let mut rpm = self.lead();
+        rpm.extend_from_slice(&signature);
+        rpm.extend_from_slice(&header);
+        rpm.extend_from_slice(&payload);
+        std::fs::write(&self.file_path, rpm)?;
+
+        Ok(())
+    }
+
+    /// The legacy "lead" of the file, which is mostly ignored these days
+    fn lead(&self) -> Vec<u8> {
+        let mut lead = vec![0xed, 0xab, 0xee, 0xdb, 3, 0];
+        // binary package
+        lead.extend_from_slice(&0u16.to_be_bytes());
+        lead.extend_from_slice(&rpm_arch_num(&self.architecture).to_be_bytes());
+        let mut name = format!("{}-{}-{RELEASE}", self.package_name, self.version).into_bytes();
+        name.resize(66, 0);
+        name[65] = 0;
+        lead.extend_from_slice(&name);
+        // linux
+        lead.extend_from_slice(&1u16.to_be_bytes());
+        // signature header follows
+        lead.extend_from_slice(&5u16.to_be_bytes());
+        lead.resize(96, 0);
+        lead
+    }
+
+    /// Produce the main header, with all the metadata and the file list
+    fn header(&self, files: &[RpmFile], mtime: u32, payload: &[u8]) -> RpmHeader {
+        let mut dir_names: Vec<String> = vec![];
+        let mut dir_indexes = vec![];
+        for file in files {
+            if dir_names.last() != Some(&file.dir) {
+                dir_names.push(file.dir.clone());
+            }
+            dir_indexes.push(dir_names.len() as u32 - 1);
+        }
+
+        let description = self
+            .description
+            .as_deref()
+            .unwrap_or(&self.package_name)
+            .trim()
+            .to_owned();
+        let summary = description.lines().next().unwrap_or_default().to_owned();
+        let full_version = format!("{}-{RELEASE}", self.version);
+
+        let mut requires = vec![
+            ("rpmlib(CompressedFileNames)", "3.0.4-1"),
+            ("rpmlib(FileDigests)", "4.6.0-1"),
+            ("rpmlib(PayloadFilesHavePrefix)", "4.0-1"),
+        ];
+        if self.version.contains('~') {
+            requires.push(("rpmlib(TildeInVersions)", "4.10.0-1"));
+        }
+
+        let mut header = RpmHeader::default();
+        header.add(
+            TAG_HEADERI18NTABLE,
+            Value::StringArray(vec!["C".to_owned()]),
+        );
+        header.add(TAG_NAME, Value::String(self.package_name.clone()));
+        header.add(TAG_VERSION, Value::String(self.version.clone()));
+        header.add(TAG_RELEASE, Value::String(RELEASE.to_owned()));
+        header.add(TAG_SUMMARY, Value::I18nString(summary));
+        header.add(TAG_DESCRIPTION, Value::I18nString(description));
+        header.add(TAG_BUILDTIME, Value::Int32(vec![mtime]));
+        header.add(TAG_BUILDHOST, Value::String("localhost".to_owned()));
+        header.add(
+            TAG_SIZE,
+            Value::Int32(vec![files
+                .iter()
+                .map(|file| file.contents.len() as u32)
+                .sum()]),
+        );
+        header.add(
+            TAG_LICENSE,
+            Value::String(self.license.clone().unwrap_or_default()),
+        );
+        if let Some(packager) = &self.packager {
+            header.add(TAG_PACKAGER, Value::String(packager.clone()));
+        }
+        header.add(TAG_GROUP, Value::I18nString("Unspecified".to_owned()));
+        if let Some(homepage) = &self.homepage {
+            header.add(TAG_URL, Value::String(homepage.clone()));
+        }
+        header.add(TAG_OS, Value::String("linux".to_owned()));
+        header.add(TAG_ARCH, Value::String(self.architecture.clone()));
+        header.add(
+            TAG_FILESIZES,
+            Value::Int32(files.iter().map(|f| f.contents.len() as u32).collect()),
+        );
+        header.add(
+            TAG_FILEMODES,
+            Value::Int16(files.iter().map(|f| f.mode).collect()),
+        );
+        header.add(TAG_FILERDEVS, Value::Int16(vec![0; files.len()]));
+        header.add(TAG_FILEMTIMES, Value::Int32(vec![mtime; files.len()]));
+        header.add(
+            TAG_FILEDIGESTS,
+            Value::StringArray(
+                files
+                    .iter()
+                    .map(|f| match f.link_to {
+                        Some(_) => String::new(),
+                        None => encode_hex(&Sha256::digest(&f.contents)),
+                    })
+                    .collect(),
+            ),
+        );
+        header.add(
+            TAG_FILELINKTOS,
+            Value::StringArray(
+                files
+                    .iter()
+                    .map(|f| f.link_to.clone().unwrap_or_default())
+                    .collect(),
+            ),
+        );
+        header.add(TAG_FILEFLAGS, Value::Int32(vec![0; files.len()]));
+        header.add(
+            TAG_SOURCERPM,
+            Value::String(format!("{}-{full_version}.src.rpm", self.package_name)),
+        );
+        header.add(
+            TAG_FILEUSERNAME,
+            Value::StringArray(vec!["root".to_owned(); files.len()]),
+        );
+        header.add(
+            TAG_FILEGROUPNAME,
+            Value::StringArray(vec!["root".to_owned(); files.len()]),
+        );
+        header.add(
+            TAG_PROVIDENAME,
+            Value::StringArray(vec![self.package_name.clone()]),
+        );
+        header.add(
+            TAG_REQUIREFLAGS,
+            Value::Int32(vec![
+                SENSE_LESS | SENSE_EQUAL | SENSE_RPMLIB;
+                requires.len()
+            ]),
+        );
+        header.add(
+            TAG_REQUIRENAME,
+            Value::StringArray(requires.iter().map(|(name, _)| name.to_string()).collect()),
+        );
+        header.add(
+            TAG_REQUIREVERSION,
+            Value::StringArray(requires.iter().map(|(_, ver)| ver.to_string()).collect()),
+        );
+        header.add(TAG_FILEDEVICES, Value::Int32(vec![1; files.len()]));
+        header.add(
+            TAG_FILEINODES,
+            Value::Int32((1..=files.len() as u32).collect()),
+        );
+        header.add(
+            TAG_FILELANGS,
+            Value::StringArray(vec![String::new(); files.len()]),
+        );
+        header.add(TAG_PROVIDEFLAGS, Value::Int32(vec![SENSE_EQUAL]));
+        header.add(TAG_PROVIDEVERSION, Value::StringArray(vec![full_version]));
+        header.add(TAG_DIRINDEXES, Value::Int32(dir_indexes));
+        header.add(
+            TAG_BASENAMES,
+            Value::StringArray(files.iter().map(|f| f.name.clone()).collect()),
+        );
+        header.add(TAG_DIRNAMES, Value::StringArray(dir_names));
+        header.add(TAG_PAYLOADFORMAT, Value::String("cpio".to_owned()));
+        header.add(TAG_PAYLOADCOMPRESSOR, Value::String("gzip".to_owned()));
+        header.add(TAG_PAYLOADFLAGS, Value::String("9".to_owned()));
+        header.add(TAG_FILEDIGESTALGO, Value::Int32(vec![HASH_SHA256]));
+        header.add(
+            TAG_PAYLOADDIGEST,
+            Value::StringArray(vec![encode_hex(&Sha256::digest(payload))]),
+        );
+        header.add(TAG_PAYLOADDIGESTALGO, Value::Int32(vec![HASH_SHA256]));
+        header
+    }
+}
+
+/// Get the RPM architecture name for a target, if RPM has one
+pub fn rpm_architecture(target: &TripleNameRef) -> Option<&'static str> {
+    let triple = target.parse().ok()?;
+    let hard_float = matches!(
+        triple.environment,
+        Environment::Gnueabihf | Environment::Musleabihf
+    );
+    let arch = match triple.architecture {
+        Architecture::X86_64 => "x86_64",
+        Architecture::X86_32(_) => "i686",
+        Architecture::Aarch64(_) => "aarch64",
+        Architecture::Arm(ArmArchitecture::Armv7) if hard_float => "armv7hl",
+        Architecture::Powerpc64le => "ppc64le",
+        Architecture::S390x => "s390x",
+        Architecture::Riscv64(_) => "riscv64",
+        Architecture::LoongArch64 => "loongarch64",
+        _ => return None,
+    };
+    Some(arch)
+}
+
+/// Get the directory libraries are installed to for the given RPM architecture
+pub fn rpm_lib_dir(architecture: &str) -> &'static str {
+    match architecture {
+        "i686" | "armv7hl" => "/usr/lib",
+        _ => "/usr/lib64",
+    }
+}
+
+/// Get the RPM equivalent of a version
+///
+/// RPM uses the same `~` convention as Debian to sort prereleases first.
+pub fn rpm_version(version: &Version) -> String {
+    super::deb::deb_version(version)
+}
+
+/// The number the lead uses for an architecture
+fn rpm_arch_num(architecture: &str) -> u16 {
+    match architecture {
+        "x86_64" | "i686" => 1,
+        "armv7hl" => 12,
+        "s390x" => 15,
+        "ppc64le" => 16,
+        "aarch64" => 19,
+        _ => 0,
+    }
+}
+
+/// Write the files as a gzipped cpio archive, returning it and its uncompressed size
+fn cpio_payload(files: &[RpmFile], mtime: u32) -> DistResult<(Vec<u8>, u32)> {
+    let mut cpio = vec![];
+    let mut add_entry = |name: &str, ino: usize, mode: u16, contents: &[u8]| {
+        let fields = [
+            ino as u32,
+            mode as u32,
+            0,
+            0,
+            1,
+            mtime,
+            contents.len() as u32,
+            0,
+            0,
+            0,
+            0,
+            name.len() as u32 + 1,
+            0,
+        ];
+        cpio.extend_from_slice(b"070701");
+        for field in fields {
+            cpio.extend_from_slice(format!("{field:08x}").as_bytes());
+        }
+        cpio.extend_from_slice(name.as_bytes());
+        cpio.push(0);
+        cpio.resize(cpio.len().next_multiple_of(4), 0);
+        cpio.extend_from_slice(contents);
+        cpio.resize(cpio.len().next_multiple_of(4), 0);
+    };
+    for (idx, file) in files.iter().enumerate() {
+        let name = format!(".{}{}", file.dir, file.name);
+        add_entry(&name, idx + 1, file.mode, &file.contents);
+    }
+    add_entry("TRAILER!!!", 0, 0, &[]);
+
+    let size = cpio.len() as u32;
+    let mut encoder = GzEncoder::new(vec![], Compression::best());
+    encoder.write_all(&cpio)?;
+    Ok((encoder.finish()?, size))
+}
+
+// Tags and constants, see rpmtag.h and rpmds.h in rpm
+const HEADER_SIGNATURES: u32 = 62;
+const HEADER_IMMUTABLE: u32 = 63;
+const TAG_HEADERI18NTABLE: u32 = 100;
+const SIGTAG_SHA1: u32 = 269;
+const SIGTAG_SHA256: u32 = 273;
+const SIGTAG_SIZE: u32 = 1000;
+const SIGTAG_PAYLOADSIZE: u32 = 1007;
+const TAG_NAME: u32 = 1000;
+const TAG_VERSION: u32 = 1001;
+const TAG_RELEASE: u32 = 1002;
+const TAG_SUMMARY: u32 = 1004;
+const TAG_DESCRIPTION: u32 = 1005;
+const TAG_BUILDTIME: u32 = 1006;
+const TAG_BUILDHOST: u32 = 1007;
+const TAG_SIZE: u32 = 1009;
+const TAG_LICENSE: u32 = 1014;
+const TAG_PACKAGER: u32 = 1015;
+const TAG_GROUP: u32 = 1016;
+const TAG_URL: u32 = 1020;
+const TAG_OS: u32 = 1021;
+const TAG_ARCH: u32 = 1022;
+const TAG_FILESIZES: u32 = 1028;
+const TAG_FILEMODES: u32 = 1030;
+const TAG_FILERDEVS: u32 = 1033;
+const TAG_FILEMTIMES: u32 = 1034;
+const TAG_FILEDIGESTS: u32 = 1035;
+const TAG_FILELINKTOS: u32 = 1036;
+const TAG_FILEFLAGS: u32 = 1037;
+const TAG_FILEUSERNAME: u32 = 1039;
+const TAG_FILEGROUPNAME: u32 = 1040;
+const TAG_SOURCERPM: u32 = 1044;
+const TAG_PROVIDENAME: u32 = 1047;
+const TAG_REQUIREFLAGS: u32 = 1048;
+const TAG_REQUIRENAME: u32 = 1049;
+const TAG_REQUIREVERSION: u32 = 1050;
+const TAG_FILEDEVICES: u32 = 1095;
+const TAG_FILEINODES: u32 = 1096;
+const TAG_FILELANGS: u32 = 1097;
+const TAG_PROVIDEFLAGS: u32 = 1112;
+const TAG_PROVIDEVERSION: u32 = 1113;
+const TAG_DIRINDEXES: u32 = 1116;
+const TAG_BASENAMES: u32 = 1117;
+const TAG_DIRNAMES: u32 = 1118;
+const TAG_PAYLOADFORMAT: u32 = 1124;
+const TAG_PAYLOADCOMPRESSOR: u32 = 1125;
+const TAG_PAYLOADFLAGS: u32 = 1126;
+const TAG_FILEDIGESTALGO: u32 = 5011;
+const TAG_PAYLOADDIGEST: u32 = 5092;
+const TAG_PAYLOADDIGESTALGO: u32 = 5093;
+const SENSE_LESS: u32 = 1 << 1;
+const SENSE_EQUAL: u32 = 1 << 3;
+const SENSE_RPMLIB: u32 = 1 << 24;
+const HASH_SHA256: u32 = 8;
+
+/// Append an entry to a header's index
+fn add_index(index: &mut Vec<u8>, tag: u32, kind: u32, offset: u32, count: u32) {
+    for field in [tag, kind, offset, count] {
+        index.extend_from_slice(&field.to_be_bytes());
+    }
+}
+
+/// A value in an rpm header
+enum Value {
+    Int16(Vec<u16>),
+    Int32(Vec<u32>),
+    String(String),
+    StringArray(Vec<String>),
+    I18nString(String),
+}
+
+/// An rpm header (the format is shared by the signature and the main header)
+#[derive(Default)]
+struct RpmHeader {
+    entries: Vec<(u32, Value)>,
+}
+
+impl RpmHeader {
+    fn add(&mut self, tag: u32, value: Value) {
+        self.entries.push((tag, value));
+    }
+
+    /// Serialize the header, wrapping it in the given region tag
+    fn to_bytes(&self, region_tag: u32) -> Vec<u8> {
+        let mut entries = self.entries.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|(tag, _)| *tag);
+
+        let mut index = vec![];
+        let mut data = vec![];
+        for (tag, value) in entries {
+            let (kind, count) = match value {
+                Value::Int16(vals) => {
+                    data.resize(data.len().next_multiple_of(2), 0);
+                    (3, vals.len())
+                }
+                Value::Int32(vals) => {
+                    data.resize(data.len().next_multiple_of(4), 0);
+                    (4, vals.len())
+                }
+                Value::String(_) => (6, 1),
+                Value::StringArray(vals) => (8, vals.len()),
+                Value::I18nString(_) => (9, 1),
+            };
+            add_index(&mut index, *tag, kind, data.len() as u32, count as u32);
+            match value {
+                Value::Int16(vals) => vals
+                    .iter()
+                    .for_each(|val| data.extend_from_slice(&val.to_be_bytes())),
+                Value::Int32(vals) => vals
+                    .iter()
+                    .for_each(|val| data.extend_from_slice(&val.to_be_bytes())),
+                Value::String(val) | Value::I18nString(val) => {
+                    data.extend_from_slice(val.as_bytes());
+                    data.push(0);
+                }
+                Value::StringArray(vals) => vals.iter().for_each(|val| {
+                    data.extend_from_slice(val.as_bytes());
+                    data.push(0);
+                }),
+            }
+        }
+
+        // The region tag comes first, and points at a trailer at the end of the
+        // data, which in turn points back at the start of the index
+        let entry_count = index.len() / 16 + 1;
+        let mut region = vec![];
+        // (7 is the "binary" type)
+        add_index(&mut region, region_tag, 7, data.len() as u32, 16);
+        add_index(
+            &mut data,
+            region_tag,
+            7,
+            (-(entry_count as i32 * 16)) as u32,
+            16,
+        );
+
+        let mut header = vec![0x8e, 0xad, 0xe8, 0x01, 0, 0, 0, 0];
+        header.extend_from_slice(&(entry_count as u32).to_be_bytes());
+        header.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        header.extend_from_slice(&region);
+        header.extend_from_slice(&index);
+        header.extend_from_slice(&data);
+        header
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dist_schema::TripleName;
+
+    use super::{rpm_architecture, rpm_lib_dir, RpmHeader, Value, HEADER_IMMUTABLE};
+
+    fn run_arch_comparison(in_str: &str, expected: Option<&str>) {
+        let out = rpm_architecture(&TripleName::new(in_str.to_owned()));
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn arch_common() {
+        run_arch_comparison("x86_64-unknown-linux-gnu", Some("x86_64"));
+        run_arch_comparison("aarch64-unknown-linux-musl", Some("aarch64"));
+        run_arch_comparison("i686-unknown-linux-gnu", Some("i686"));
+        run_arch_comparison("armv7-unknown-linux-gnueabihf", Some("armv7hl"));
+        run_arch_comparison("arm-unknown-linux-gnueabi", None);
+    }
+
+    #[test]
+    fn lib_dir() {
+        assert_eq!(rpm_lib_dir("x86_64"), "/usr/lib64");
+        assert_eq!(rpm_lib_dir("i686"), "/usr/lib");
+    }
+
+    #[test]
+    fn header_layout() {
+        let mut header = RpmHeader::default();
+        header.add(1000, Value::String("a".to_owned()));
+        header.add(1030, Value::Int16(vec![1]));
+        header.add(1028, Value::Int32(vec![2]));
+        let bytes = header.to_bytes(HEADER_IMMUTABLE);
+
+        let word =
+            |offset: usize| u32::from_be_bytes(bytes[offset..offset + 4].try_into().unwrap());
+        // magic, then 4 entries (including the region) and the data size
+        assert_eq!(bytes[..8], [0x8e, 0xad, 0xe8, 0x01, 0, 0, 0, 0]);
+        assert_eq!(word(8), 4);
+        let data_start = 16 + 4 * 16;
+        let data_len = word(12) as usize;
+        assert_eq!(bytes.len(), data_start + data_len);
+        // entries are sorted, and the int32 is aligned
+        assert_eq!(word(16 + 16), 1000);
+        assert_eq!(word(16 + 32), 1028);
+        assert_eq!(word(16 + 32 + 8), 4);
+        // the region points at a trailer which points back at the index
+        assert_eq!(word(16), HEADER_IMMUTABLE);
+        let trailer = data_start + word(16 + 8) as usize;
+        assert_eq!(word(trailer), HEADER_IMMUTABLE);
+        assert_eq!(word(trailer + 8) as i32, -4 * 16);
+    }
+}
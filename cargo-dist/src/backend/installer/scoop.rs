@@ -0,0 +1,296 @@
+//! Code for generating Scoop manifests
+//!
+//! A Scoop manifest is a JSON file describing where to download an app's
+//! archives for each Windows architecture, and which executables in them to
+//! put on the PATH. Like the Homebrew formula it fetches from the release's
+//! hosting, so we fill in the hashes from the checksums we computed for the
+//! archives.
+
+use std::collections::BTreeMap;
+
+use axoasset::LocalAsset;
+use camino::Utf8PathBuf;
+use dist_schema::{DistManifest, Hosting};
+use serde::Serialize;
+use tracing::info;
+
+use crate::{
+    config::{ChecksumStyle, ZipStyle},
+    host::gitlab::encode_project,
+    DistResult,
+};
+
+use super::ExecutableZipFragment;
+
+/// Info about a Scoop manifest
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoopInstallerInfo {
+    /// Final file path of the manifest
+    pub file_path: Utf8PathBuf,
+    /// The app's version
+    pub version: String,
+    /// A brief description of the app
+    pub description: Option<String>,
+    /// The URL to the app's homepage
+    pub homepage: Option<String>,
+    /// The license of the app
+    pub license: Option<String>,
+    /// The URL the archives can be downloaded from (append the artifact name)
+    pub base_url: String,
+    /// Where the release is hosted
+    pub hosting: Hosting,
+    /// The part of the release's tag that comes before the version (e.g. "v")
+    pub tag_prefix: Option<String>,
+    /// The checksum style of the release's checksum files
+    pub checksum: ChecksumStyle,
+    /// The archive to download for each Scoop architecture (e.g. "64bit")
+    pub architectures: BTreeMap<String, ExecutableZipFragment>,
+    /// Executable aliases
+    pub bin_aliases: BTreeMap<String, Vec<String>>,
+    /// The command to install the manifest with
+    pub hint: String,
+}
+
+/// A Scoop manifest, with keys in the order Scoop's own manifests use
+#[derive(Debug, Clone, Serialize)]
+struct ScoopManifest {
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    homepage: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    license: Option<String>,
+    architecture: BTreeMap<String, ScoopArchitecture>,
+    bin: Vec<ScoopBin>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checkver: Option<ScoopCheckver>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    autoupdate: Option<ScoopAutoupdate>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ScoopArchitecture {
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extract_dir: Option<String>,
+}
+
+/// An entry in `bin`, either a plain executable or an `[executable, alias]` pair
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+enum ScoopBin {
+    Exe(String),
+    Alias(String, String),
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ScoopCheckver {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    github: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    jsonpath: Option<String>,
+    regex: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ScoopAutoupdate {
+    architecture: BTreeMap<String, ScoopAutoupdateArchitecture>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hash: Option<ScoopHashSource>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ScoopAutoupdateArchitecture {
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extract_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ScoopHashSource {
+    url: String,
+}
+
+impl ScoopInstallerInfo {
+    /// Build the manifest
+    pub fn build(&self, manifest: &DistManifest) -> DistResult<()> {
+        info!("writing a scoop manifest: {}", self.file_path);
+
+        let architecture = self
+            .architectures
+            .iter()
+            .map(|(arch, fragment)| {
+                let entry = ScoopArchitecture {
+                    url: format!("{}/{}", self.base_url, fragment.id),
+                    hash: scoop_hash(manifest, fragment),
+                    extract_dir: extract_dir(fragment),
+                };
+                (arch.clone(), entry)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let mut bin = vec![];
+        let mut seen = vec![];
+        for fragment in self.architectures.values() {
+            for exe in &fragment.executables {
+                if seen.contains(exe) {
+                    continue;
+                }
+                seen.push(exe.clone());
+                bin.push(ScoopBin::Exe(exe.clone()));
+                let name = exe.strip_suffix(".exe").unwrap_or(exe);
+                for alias in self.bin_aliases.get(name).into_iter().flatten() {
+                    bin.push(ScoopBin::Alias(exe.clone(), alias.clone()));
+                }
+            }
+        }
+
+        let checkver = self.checkver();
+        // Scoop can only autoupdate if it can find new versions, and if it can
+        // guess the new URLs by swapping out the version in the old ones
+        let autoupdate = if checkver.is_some() && self.base_url.contains(&self.version) {
+            let architecture = architecture
+                .iter()
+                .map(|(arch, entry)| {
+                    let entry = ScoopAutoupdateArchitecture {
+                        url: entry.url.replace(&self.version, "$version"),
+                        extract_dir: entry.extract_dir.clone(),
+                    };
+                    (arch.clone(), entry)
+                })
+                .collect();
+            let hash = (self.checksum == ChecksumStyle::Sha256).then(|| ScoopHashSource {
+                url: "$url.sha256".to_owned(),
+            });
+            Some(ScoopAutoupdate { architecture, hash })
+        } else {
+            None
+        };
+
+        let scoop_manifest = ScoopManifest {
+            version: self.version.clone(),
+            description: self.description.clone(),
+            homepage: self.homepage.clone(),
+            license: self.license.clone(),
+            architecture,
+            bin,
+            checkver,
+            autoupdate,
+        };
+
+        // Scoop's own manifests are indented with 4 spaces
+        let mut contents = vec![];
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+        let mut serializer = serde_json::Serializer::with_formatter(&mut contents, formatter);
+        scoop_manifest
+            .serialize(&mut serializer)
+            .expect("scoop manifests are always valid json");
+        contents.push(b'\n');
+        let contents = String::from_utf8(contents).expect("serde_json wrote invalid utf8!?");
+        LocalAsset::write_new(&contents, &self.file_path)?;
+        Ok(())
+    }
+
+    /// Tell Scoop how to find the latest version, if the hosting has a way to do that
+    fn checkver(&self) -> Option<ScoopCheckver> {
+        let prefix = escape_regex(self.tag_prefix.as_deref()?);
+        if let Some(github) = &self.hosting.github {
+            Some(ScoopCheckver {
+                github: Some(format!(
+                    "{}/{}/{}",
+                    github.artifact_base_url, github.owner, github.repo
+                )),
+                url: None,
+                jsonpath: None,
+                regex: format!("/releases/tag/{prefix}([\\w.+-]+)"),
+            })
+        } else {
+            self.hosting.gitlab.as_ref().map(|gitlab| ScoopCheckver {
+                github: None,
+                url: Some(format!(
+                    "{}/projects/{}/releases/permalink/latest",
+                    gitlab.api_url,
+                    encode_project(&gitlab.project)
+                )),
+                jsonpath: Some("$.tag_name".to_owned()),
+                regex: format!("^{prefix}([\\w.+-]+)$"),
+            })
+        }
+    }
+}
+
+/// Get the hash of an archive in the format Scoop expects
+///
+/// Scoop assumes sha256, and wants other algorithms to be prefixed.
+fn scoop_hash(manifest: &DistManifest, fragment: &ExecutableZipFragment) -> Option<String> {
+    let checksums = &manifest.artifacts.get(&fragment.id)?.checksums;
+    if let Some(hash) = checksums.get(ChecksumStyle::Sha256.ext()) {
+        Some(hash.to_string())
+    } else {
+        checksums
+            .get(ChecksumStyle::Sha512.ext())
+            .map(|hash| format!("sha512:{hash}"))
+    }
+}
+
+/// Tarballs have a root directory that Scoop needs to be told to look in
+fn extract_dir(fragment: &ExecutableZipFragment) -> Option<String> {
+    if fragment.zip_style == ZipStyle::Zip {
+        return None;
+    }
+    fragment
+        .id
+        .as_str()
+        .strip_suffix(fragment.zip_style.ext())
+        .map(|dir| dir.to_owned())
+}
+
+/// Get the Scoop architecture name for a windows target, if Scoop has one
+pub fn scoop_architecture(target: &str) -> Option<&'static str> {
+    if target.starts_with("x86_64-") {
+        Some("64bit")
+    } else if target.starts_with("i686-") {
+        Some("32bit")
+    } else if target.starts_with("aarch64-") {
+        Some("arm64")
+    } else {
+        None
+    }
+}
+
+/// Escape any characters in the input that have a special meaning in a regex
+fn escape_regex(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for c in input.chars() {
+        if "\\.+*?()|[]{}^$#".contains(c) {
+            output.push('\\');
+        }
+        output.push(c);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scoop_architecture() {
+        assert_eq!(scoop_architecture("x86_64-pc-windows-msvc"), Some("64bit"));
+        assert_eq!(scoop_architecture("i686-pc-windows-gnu"), Some("32bit"));
+        assert_eq!(scoop_architecture("aarch64-pc-windows-msvc"), Some("arm64"));
+        assert_eq!(scoop_architecture("thumbv7a-pc-windows-msvc"), None);
+    }
+
+    #[test]
+    fn test_escape_regex() {
+        assert_eq!(escape_regex("v"), "v");
+        assert_eq!(escape_regex("my-app-v"), "my-app-v");
+        assert_eq!(escape_regex("release.1+"), "release\\.1\\+");
+    }
+}
@@ -0,0 +1,395 @@
+//! Code for generating winget manifests
+//!
+//! winget packages are described by a set of three YAML manifests: a
+//! "version" manifest that ties the set together, an "installer" manifest
+//! listing the files to download for each architecture, and a "defaultLocale"
+//! manifest with the human-readable metadata. Like the Homebrew formula they
+//! fetch from the release's hosting, so we fill in the hashes from the
+//! checksums we computed for the artifacts they point at.
+
+use std::collections::BTreeMap;
+
+use axoasset::LocalAsset;
+use camino::Utf8PathBuf;
+use dist_schema::{ArtifactId, DistManifest};
+use serde::Serialize;
+use tracing::info;
+
+use crate::{config::ChecksumStyle, DistError, DistResult};
+
+/// The version of the winget manifest schema we generate
+pub const WINGET_MANIFEST_VERSION: &str = "1.6.0";
+/// The locale we write the package metadata in
+pub const WINGET_DEFAULT_LOCALE: &str = "en-US";
+
+/// Info about a winget manifest
+#[derive(Debug, Clone, Serialize)]
+pub struct WingetInstallerInfo {
+    /// Which of the manifests in the set this is
+    pub manifest_type: WingetManifestType,
+    /// Final file path of the manifest
+    pub file_path: Utf8PathBuf,
+    /// The package identifier (e.g. "Publisher.App")
+    pub identifier: String,
+    /// The package version
+    pub version: String,
+    /// The publisher of the package
+    pub publisher: String,
+    /// The name of the package
+    pub name: String,
+    /// The license of the package
+    pub license: String,
+    /// A brief description of the package
+    pub short_description: String,
+    /// The URL to the package's homepage
+    pub homepage: Option<String>,
+    /// The URL the artifacts can be downloaded from (append the artifact name)
+    pub base_url: String,
+    /// The artifacts winget can install
+    pub installers: Vec<WingetInstallerSource>,
+    /// Executable aliases
+    pub bin_aliases: BTreeMap<String, Vec<String>>,
+}
+
+/// The kinds of manifest in a winget manifest set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WingetManifestType {
+    /// The version manifest (`Publisher.App.yaml`)
+    Version,
+    /// The installer manifest (`Publisher.App.installer.yaml`)
+    Installer,
+    /// The default locale manifest (`Publisher.App.locale.en-US.yaml`)
+    DefaultLocale,
+}
+
+impl WingetManifestType {
+    /// Get the file name of this manifest for the given package identifier
+    pub fn file_name(self, identifier: &str) -> String {
+        match self {
+            WingetManifestType::Version => format!("{identifier}.yaml"),
+            WingetManifestType::Installer => format!("{identifier}.installer.yaml"),
+            WingetManifestType::DefaultLocale => {
+                format!("{identifier}.locale.{WINGET_DEFAULT_LOCALE}.yaml")
+            }
+        }
+    }
+}
+
+/// An artifact winget can install
+#[derive(Debug, Clone, Serialize)]
+pub struct WingetInstallerSource {
+    /// The id of the artifact
+    pub id: ArtifactId,
+    /// The winget architecture (e.g. "x64")
+    pub architecture: String,
+    /// The executables in the archive, for zips (assumed to be in root)
+    ///
+    /// If this is None the artifact is an msi
+    pub executables: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct VersionManifest<'a> {
+    package_identifier: &'a str,
+    package_version: &'a str,
+    default_locale: &'a str,
+    manifest_type: WingetManifestType,
+    manifest_version: &'a str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct InstallerManifest<'a> {
+    package_identifier: &'a str,
+    package_version: &'a str,
+    installers: Vec<Installer>,
+    manifest_type: WingetManifestType,
+    manifest_version: &'a str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct Installer {
+    architecture: String,
+    installer_type: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nested_installer_type: Option<&'static str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    nested_installer_files: Vec<NestedInstallerFile>,
+    installer_url: String,
+    installer_sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct NestedInstallerFile {
+    relative_file_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    portable_command_alias: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct DefaultLocaleManifest<'a> {
+    package_identifier: &'a str,
+    package_version: &'a str,
+    package_locale: &'a str,
+    publisher: &'a str,
+    package_name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    package_url: Option<&'a str>,
+    license: &'a str,
+    short_description: &'a str,
+    manifest_type: WingetManifestType,
+    manifest_version: &'a str,
+}
+
+impl WingetInstallerInfo {
+    /// Build the manifest
+    pub fn build(&self, manifest: &DistManifest) -> DistResult<()> {
+        info!("writing a winget manifest: {}", self.file_path);
+        let contents = self.render(manifest)?;
+        LocalAsset::write_new(&contents, &self.file_path)?;
+        Ok(())
+    }
+
+    /// Render the manifest to a string
+    fn render(&self, manifest: &DistManifest) -> DistResult<String> {
+        let manifest_type = self.manifest_type;
+        let yaml = match manifest_type {
+            WingetManifestType::Version => serde_yaml::to_string(&VersionManifest {
+                package_identifier: &self.identifier,
+                package_version: &self.version,
+                default_locale: WINGET_DEFAULT_LOCALE,
+                manifest_type,
+                manifest_version: WINGET_MANIFEST_VERSION,
+            }),
+            WingetManifestType::Installer => serde_yaml::to_string(&InstallerManifest {
+                package_identifier: &self.identifier,
+                package_version: &self.version,
+                installers: self
+                    .installers
+                    .iter()
+                    .map(|source| self.installer(manifest, source))
+                    .collect::<DistResult<_>>()?,
+                manifest_type,
+                manifest_version: WINGET_MANIFEST_VERSION,
+            }),
+            WingetManifestType::DefaultLocale => serde_yaml::to_string(&DefaultLocaleManifest {
+                package_identifier: &self.identifier,
+                package_version: &self.version,
+                package_locale: WINGET_DEFAULT_LOCALE,
+                publisher: &self.publisher,
+                package_name: &self.name,
+                package_url: self.homepage.as_deref(),
+                license: &self.license,
+                short_description: &self.short_description,
+                manifest_type,
+                manifest_version: WINGET_MANIFEST_VERSION,
+            }),
+        }
+        .expect("winget manifests are always valid yaml");
+
+        let schema = match manifest_type {
+            WingetManifestType::Version => "version",
+            WingetManifestType::Installer => "installer",
+            WingetManifestType::DefaultLocale => "defaultLocale",
+        };
+        Ok(format!("# yaml-language-server: $schema=https://aka.ms/winget-manifest.{schema}.{WINGET_MANIFEST_VERSION}.schema.json\n\n{yaml}"))
+    }
+
+    fn installer(
+        &self,
+        manifest: &DistManifest,
+        source: &WingetInstallerSource,
+    ) -> DistResult<Installer> {
+        // winget only accepts sha256, and conventionally writes it in uppercase
+        let installer_sha256 = manifest
+            .artifacts
+            .get(&source.id)
+            .and_then(|a| a.checksums.get(ChecksumStyle::Sha256.ext()))
+            .map(|hash| hash.to_string().to_ascii_uppercase())
+            .ok_or_else(|| DistError::WingetNoSha256 {
+                artifact: source.id.to_string(),
+            })?;
+        let installer_url = format!("{}/{}", self.base_url, source.id);
+
+        if let Some(executables) = &source.executables {
+            let mut nested_installer_files = vec![];
+            for exe in executables {
+                nested_installer_files.push(NestedInstallerFile {
+                    relative_file_path: exe.clone(),
+                    portable_command_alias: None,
+                });
+                let name = exe.strip_suffix(".exe").unwrap_or(exe);
+                for alias in self.bin_aliases.get(name).into_iter().flatten() {
+                    nested_installer_files.push(NestedInstallerFile {
+                        relative_file_path: exe.clone(),
+                        portable_command_alias: Some(alias.clone()),
+                    });
+                }
+            }
+            Ok(Installer {
+                architecture: source.architecture.clone(),
+                installer_type: "zip",
+                nested_installer_type: Some("portable"),
+                nested_installer_files,
+                installer_url,
+                installer_sha256,
+            })
+        } else {
+            Ok(Installer {
+                architecture: source.architecture.clone(),
+                installer_type: "wix",
+                nested_installer_type: None,
+                nested_installer_files: vec![],
+                installer_url,
+                installer_sha256,
+            })
+        }
+    }
+}
+
+/// Get the winget architecture name for a windows target, if winget has one
+pub fn winget_architecture(target: &str) -> Option<&'static str> {
+    if target.starts_with("x86_64-") {
+        Some("x64")
+    } else if target.starts_with("i686-") {
+        Some("x86")
+    } else if target.starts_with("aarch64-") {
+        Some("arm64")
+    } else {
+        None
+    }
+}
+
+/// Make a default package identifier from the publisher and app name
+///
+/// Each part of an identifier can't contain whitespace or dots
+/// (among other things), so they're stripped out.
+pub fn winget_identifier(publisher: &str, name: &str) -> String {
+    let clean = |part: &str| {
+        part.chars()
+            .filter(|c| !c.is_whitespace() && !".\\/:*?\"<>|".contains(*c))
+            .collect::<String>()
+    };
+    format!("{}.{}", clean(publisher), clean(name))
+}
+
+/// Get the publisher's name from a Cargo.toml author, dropping any email
+pub fn winget_publisher(author: &str) -> String {
+    author
+        .split_once('<')
+        .map(|(name, _)| name)
+        .unwrap_or(author)
+        .trim()
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dist_schema::{Artifact, ArtifactKind, ChecksumExtension, ChecksumValue};
+
+    fn info(manifest_type: WingetManifestType) -> WingetInstallerInfo {
+        let identifier = "axodotdev.axolotlsay".to_owned();
+        WingetInstallerInfo {
+            manifest_type,
+            file_path: Utf8PathBuf::from(manifest_type.file_name(&identifier)),
+            identifier,
+            version: "0.2.2".to_owned(),
+            publisher: "axodotdev".to_owned(),
+            name: "axolotlsay".to_owned(),
+            license: "MIT OR Apache-2.0".to_owned(),
+            short_description: "💬 a CLI for learning to distribute CLIs in rust".to_owned(),
+            homepage: Some("https://github.com/axodotdev/axolotlsay".to_owned()),
+            base_url: "https://github.com/axodotdev/axolotlsay/releases/download/v0.2.2".to_owned(),
+            installers: vec![
+                WingetInstallerSource {
+                    id: ArtifactId::new("axolotlsay-x86_64-pc-windows-msvc.zip".to_owned()),
+                    architecture: "x64".to_owned(),
+                    executables: Some(vec!["axolotlsay.exe".to_owned()]),
+                },
+                WingetInstallerSource {
+                    id: ArtifactId::new("axolotlsay-x86_64-pc-windows-msvc.msi".to_owned()),
+                    architecture: "x64".to_owned(),
+                    executables: None,
+                },
+            ],
+            bin_aliases: BTreeMap::from([("axolotlsay".to_owned(), vec!["axo".to_owned()])]),
+        }
+    }
+
+    fn manifest() -> DistManifest {
+        let mut manifest = DistManifest::new(vec![], Default::default());
+        let hashes = [
+            (
+                "axolotlsay-x86_64-pc-windows-msvc.zip",
+                "0f9a2f5f9d5fb6ed5ab83dba2fa72b1ae2b4bbc4e1e2c3c9c6b1d3b1d8f4a9e1",
+            ),
+            (
+                "axolotlsay-x86_64-pc-windows-msvc.msi",
+                "9c3d0a8f6b8f7e3e0d1f4c5b2a6e7d8c9b0a1f2e3d4c5b6a7f8e9d0c1b2a3f4e",
+            ),
+        ];
+        for (id, hash) in hashes {
+            let mut artifact = Artifact {
+                name: Some(ArtifactId::new(id.to_owned())),
+                kind: ArtifactKind::ExecutableZip,
+                target_triples: vec![],
+                path: None,
+                assets: vec![],
+                install_hint: None,
+                description: None,
+                checksum: None,
+                checksums: Default::default(),
+                signed_by: Default::default(),
+                signatures: Default::default(),
+            };
+            artifact.checksums.insert(
+                ChecksumExtension::new("sha256".to_owned()),
+                ChecksumValue::new(hash.to_owned()),
+            );
+            manifest
+                .artifacts
+                .insert(ArtifactId::new(id.to_owned()), artifact);
+        }
+        manifest
+    }
+
+    #[test]
+    fn test_installer_manifest_needs_sha256() {
+        let info = info(WingetManifestType::Installer);
+        let mut manifest = manifest();
+        let msi = ArtifactId::new("axolotlsay-x86_64-pc-windows-msvc.msi".to_owned());
+        manifest.artifacts.get_mut(&msi).unwrap().checksums.clear();
+        let err = info.render(&manifest).unwrap_err();
+        assert!(matches!(
+            err,
+            DistError::WingetNoSha256 { artifact } if artifact == msi.as_str()
+        ));
+    }
+
+    #[test]
+    fn test_winget_identifier() {
+        assert_eq!(
+            winget_identifier("My Cool Company", "my-app"),
+            "MyCoolCompany.my-app"
+        );
+        assert_eq!(
+            winget_identifier("axo.dev", "axolotlsay"),
+            "axodev.axolotlsay"
+        );
+    }
+
+    #[test]
+    fn test_winget_publisher() {
+        assert_eq!(
+            winget_publisher("My Cool Company <support@mycoolcompany.org>"),
+            "My Cool Company"
+        );
+        assert_eq!(winget_publisher("axodotdev"), "axodotdev");
+    }
+}
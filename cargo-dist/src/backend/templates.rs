@@ -21,8 +21,18 @@ pub const TEMPLATE_INSTALLER_RB: TemplateId = "installer/homebrew.rb";
 pub const TEMPLATE_INSTALLER_NPM: TemplateId = "installer/npm";
 /// Template key for the npm installer dir
 pub const TEMPLATE_INSTALLER_NPM_RUN_JS: TemplateId = "installer/npm/run.js";
+/// Template key for the npm installer dir, when publishing a package per platform
+pub const TEMPLATE_INSTALLER_NPM_PLATFORMS: TemplateId = "installer/npm-platforms";
+/// Template key for the Chocolatey package dir
+pub const TEMPLATE_INSTALLER_CHOCOLATEY: TemplateId = "installer/chocolatey";
+/// Template key for the Nix derivation
+pub const TEMPLATE_INSTALLER_NIX: TemplateId = "installer/default.nix";
+/// Template key for the AppImage's AppDir
+pub const TEMPLATE_INSTALLER_APPIMAGE: TemplateId = "installer/appimage";
 /// Template key for the github ci.yml
 pub const TEMPLATE_CI_GITHUB: TemplateId = "ci/github/release.yml";
+/// Template key for the gitlab .gitlab-ci.yml
+pub const TEMPLATE_CI_GITLAB: TemplateId = "ci/gitlab/release.yml";
 
 /// ID used to look up an environment in [`Templates::envs`][]
 type EnvId = &'static str;
@@ -381,11 +391,22 @@ mod test {
         templates.get_template_file(TEMPLATE_INSTALLER_SH).unwrap();
         templates.get_template_file(TEMPLATE_INSTALLER_RB).unwrap();
         templates.get_template_file(TEMPLATE_INSTALLER_PS1).unwrap();
+        templates.get_template_file(TEMPLATE_INSTALLER_NIX).unwrap();
         templates.get_template_dir(TEMPLATE_INSTALLER_NPM).unwrap();
         templates
             .get_template_file(TEMPLATE_INSTALLER_NPM_RUN_JS)
             .unwrap();
+        templates
+            .get_template_dir(TEMPLATE_INSTALLER_NPM_PLATFORMS)
+            .unwrap();
+        templates
+            .get_template_dir(TEMPLATE_INSTALLER_CHOCOLATEY)
+            .unwrap();
+        templates
+            .get_template_dir(TEMPLATE_INSTALLER_APPIMAGE)
+            .unwrap();
 
         templates.get_template_file(TEMPLATE_CI_GITHUB).unwrap();
+        templates.get_template_file(TEMPLATE_CI_GITLAB).unwrap();
     }
 }
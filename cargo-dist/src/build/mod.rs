@@ -6,8 +6,8 @@ use dist_schema::{AssetInfo, DistManifest, TripleNameRef};
 use tracing::info;
 
 use crate::{
-    copy_file, linkage::determine_linkage, Binary, BinaryIdx, BinaryKind, DistError, DistGraph,
-    DistResult, SortedMap,
+    config::SigningKind, copy_file, linkage::determine_linkage, Binary, BinaryIdx, BinaryKind,
+    DistError, DistGraph, DistResult, SortedMap,
 };
 
 pub mod cargo;
@@ -202,7 +202,9 @@ impl BuildExpectations {
             .as_ref()
             .expect("bin src_path should have been checked by caller");
 
-        dist.signer.sign(src_path)?;
+        let signed_by = dist
+            .signer
+            .sign(SigningKind::Binaries, Some(target), src_path)?;
 
         // If we're faking it, don't run the linkage stuff
         let linkage = if self.fake {
@@ -227,6 +229,7 @@ impl BuildExpectations {
                 system: dist.system_id.clone(),
                 linkage: Some(linkage),
                 target_triples: vec![target.to_owned()],
+                signed_by,
             },
         );
         Ok(())
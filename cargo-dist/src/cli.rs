@@ -387,6 +387,8 @@ pub struct UpdateArgs {
 pub enum CiStyle {
     /// Generate github CI that uploads to github releases
     Github,
+    /// Generate gitlab CI
+    Gitlab,
 }
 
 impl CiStyle {
@@ -394,6 +396,7 @@ impl CiStyle {
     pub fn to_lib(self) -> dist::config::CiStyle {
         match self {
             CiStyle::Github => dist::config::CiStyle::Github,
+            CiStyle::Gitlab => dist::config::CiStyle::Gitlab,
         }
     }
 }
@@ -411,6 +414,24 @@ pub enum InstallerStyle {
     Homebrew,
     /// Generates an msi for each windows platform
     Msi,
+    /// Generates a deb for each linux platform
+    Deb,
+    /// Generates an rpm for each linux platform
+    Rpm,
+    /// Generates a Scoop manifest for windows platforms
+    Scoop,
+    /// Generates winget manifests for windows platforms
+    Winget,
+    /// Generates a Chocolatey package for windows platforms
+    Chocolatey,
+    /// Generates an AUR PKGBUILD for linux platforms
+    Aur,
+    /// Generates a Nix derivation for linux and macOS platforms
+    Nix,
+    /// Generates Python wheels for PyPI that bundle the executables
+    Pypi,
+    /// Generates portable AppImages for linux platforms
+    Appimage,
 }
 
 impl InstallerStyle {
@@ -422,6 +443,15 @@ impl InstallerStyle {
             InstallerStyle::Npm => dist::config::InstallerStyle::Npm,
             InstallerStyle::Homebrew => dist::config::InstallerStyle::Homebrew,
             InstallerStyle::Msi => dist::config::InstallerStyle::Msi,
+            InstallerStyle::Deb => dist::config::InstallerStyle::Deb,
+            InstallerStyle::Rpm => dist::config::InstallerStyle::Rpm,
+            InstallerStyle::Scoop => dist::config::InstallerStyle::Scoop,
+            InstallerStyle::Winget => dist::config::InstallerStyle::Winget,
+            InstallerStyle::Chocolatey => dist::config::InstallerStyle::Chocolatey,
+            InstallerStyle::Aur => dist::config::InstallerStyle::Aur,
+            InstallerStyle::Nix => dist::config::InstallerStyle::Nix,
+            InstallerStyle::Pypi => dist::config::InstallerStyle::Pypi,
+            InstallerStyle::Appimage => dist::config::InstallerStyle::Appimage,
         }
     }
 }
@@ -497,6 +527,8 @@ impl HostingStyle {
         match self {
             HostingStyle::Github => dist::config::HostingStyle::Github,
             HostingStyle::Axodotdev => dist::config::HostingStyle::Axodotdev,
+            HostingStyle::Gitlab => dist::config::HostingStyle::Gitlab,
+            HostingStyle::S3 => dist::config::HostingStyle::S3,
         }
     }
 }
@@ -508,6 +540,10 @@ pub enum HostingStyle {
     Github,
     /// Host on Axo Releases ("Abyss")
     Axodotdev,
+    /// Host on GitLab Releases
+    Gitlab,
+    /// Host in an S3-compatible bucket
+    S3,
 }
 
 impl std::fmt::Display for HostingStyle {
@@ -515,6 +551,8 @@ impl std::fmt::Display for HostingStyle {
         let string = match self {
             HostingStyle::Github => "github",
             HostingStyle::Axodotdev => "axodotdev",
+            HostingStyle::Gitlab => "gitlab",
+            HostingStyle::S3 => "s3",
         };
         string.fmt(f)
     }
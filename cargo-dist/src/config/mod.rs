@@ -163,12 +163,15 @@ impl std::fmt::Display for ArtifactMode {
 pub enum CiStyle {
     /// Generate Github CI
     Github,
+    /// Generate GitLab CI
+    Gitlab,
 }
 impl CiStyle {
     /// If the CI provider provides a native release hosting system, get it
     pub(crate) fn native_hosting(&self) -> Option<HostingStyle> {
         match self {
             CiStyle::Github => Some(HostingStyle::Github),
+            CiStyle::Gitlab => Some(HostingStyle::Gitlab),
         }
     }
 }
@@ -177,6 +180,7 @@ impl std::fmt::Display for CiStyle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let string = match self {
             CiStyle::Github => "github",
+            CiStyle::Gitlab => "gitlab",
         };
         string.fmt(f)
     }
@@ -187,6 +191,7 @@ impl std::str::FromStr for CiStyle {
     fn from_str(val: &str) -> DistResult<Self> {
         let res = match val {
             "github" => CiStyle::Github,
+            "gitlab" => CiStyle::Gitlab,
             s => {
                 return Err(DistError::UnrecognizedCiStyle {
                     style: s.to_string(),
@@ -250,6 +255,24 @@ pub enum InstallerStyle {
     Msi,
     /// Generate an Apple pkg installer that embeds the binary
     Pkg,
+    /// Generate a Debian package that embeds the binary
+    Deb,
+    /// Generate an RPM package that embeds the binary
+    Rpm,
+    /// Generate a Scoop manifest that fetches from [`dist_schema::Release::artifact_download_url`][]
+    Scoop,
+    /// Generate winget manifests that fetch from [`dist_schema::Release::artifact_download_url`][]
+    Winget,
+    /// Generate a Chocolatey package that fetches from [`dist_schema::Release::artifact_download_url`][]
+    Chocolatey,
+    /// Generate an AUR PKGBUILD that fetches from [`dist_schema::Release::artifact_download_url`][]
+    Aur,
+    /// Generate a Nix derivation that fetches from [`dist_schema::Release::artifact_download_url`][]
+    Nix,
+    /// Build a Python wheel that bundles the executables, for PyPI
+    Pypi,
+    /// Build an AppImage that bundles the executables and the libraries they need
+    Appimage,
 }
 
 impl std::fmt::Display for InstallerStyle {
@@ -261,6 +284,15 @@ impl std::fmt::Display for InstallerStyle {
             InstallerStyle::Homebrew => "homebrew",
             InstallerStyle::Msi => "msi",
             InstallerStyle::Pkg => "pkg",
+            InstallerStyle::Deb => "deb",
+            InstallerStyle::Rpm => "rpm",
+            InstallerStyle::Scoop => "scoop",
+            InstallerStyle::Winget => "winget",
+            InstallerStyle::Chocolatey => "chocolatey",
+            InstallerStyle::Aur => "aur",
+            InstallerStyle::Nix => "nix",
+            InstallerStyle::Pypi => "pypi",
+            InstallerStyle::Appimage => "appimage",
         };
         string.fmt(f)
     }
@@ -298,6 +330,10 @@ pub enum HostingStyle {
     Github,
     /// Host on Axo Releases ("Abyss")
     Axodotdev,
+    /// Host on a GitLab generic package registry, linked from GitLab Releases
+    Gitlab,
+    /// Host in an S3-compatible bucket
+    S3,
 }
 
 impl std::fmt::Display for HostingStyle {
@@ -305,6 +341,8 @@ impl std::fmt::Display for HostingStyle {
         let string = match self {
             HostingStyle::Github => "github",
             HostingStyle::Axodotdev => "axodotdev",
+            HostingStyle::Gitlab => "gitlab",
+            HostingStyle::S3 => "s3",
         };
         string.fmt(f)
     }
@@ -316,6 +354,8 @@ impl std::str::FromStr for HostingStyle {
         let res = match val {
             "github" => HostingStyle::Github,
             "axodotdev" => HostingStyle::Axodotdev,
+            "gitlab" => HostingStyle::Gitlab,
+            "s3" => HostingStyle::S3,
             s => {
                 return Err(DistError::UnrecognizedHostingStyle {
                     style: s.to_string(),
@@ -334,6 +374,16 @@ pub enum PublishStyle {
     Homebrew,
     /// Publish an npm pkg to the global npm registry
     Npm,
+    /// Publish a Scoop manifest to a bucket repository
+    Scoop,
+    /// Open a pull request with winget manifests against winget-pkgs
+    Winget,
+    /// Push a PKGBUILD to the AUR
+    Aur,
+    /// Publish a Nix derivation to a repository
+    Nix,
+    /// Upload Python wheels to PyPI
+    Pypi,
     /// User-supplied value
     User(String),
 }
@@ -347,6 +397,16 @@ impl std::str::FromStr for PublishStyle {
             Ok(Self::Homebrew)
         } else if s == "npm" {
             Ok(Self::Npm)
+        } else if s == "scoop" {
+            Ok(Self::Scoop)
+        } else if s == "winget" {
+            Ok(Self::Winget)
+        } else if s == "aur" {
+            Ok(Self::Aur)
+        } else if s == "nix" {
+            Ok(Self::Nix)
+        } else if s == "pypi" {
+            Ok(Self::Pypi)
         } else {
             Err(DistError::UnrecognizedJobStyle {
                 style: s.to_owned(),
@@ -372,6 +432,11 @@ impl std::fmt::Display for PublishStyle {
         match self {
             PublishStyle::Homebrew => write!(f, "homebrew"),
             PublishStyle::Npm => write!(f, "npm"),
+            PublishStyle::Scoop => write!(f, "scoop"),
+            PublishStyle::Winget => write!(f, "winget"),
+            PublishStyle::Aur => write!(f, "aur"),
+            PublishStyle::Nix => write!(f, "nix"),
+            PublishStyle::Pypi => write!(f, "pypi"),
             PublishStyle::User(s) => write!(f, "./{s}"),
         }
     }
@@ -516,6 +581,13 @@ pub enum InstallPathStrategy {
         /// The subdir to install to
         subdir: String,
     },
+    /// install to this absolute path, only when doing a system-wide install
+    ///
+    /// syntax: `/usr/local/bin` or `C:\Program Files\myapp`
+    Absolute {
+        /// The path to install to
+        path: String,
+    },
 }
 
 impl InstallPathStrategy {
@@ -523,6 +595,54 @@ impl InstallPathStrategy {
     pub fn default_list() -> Vec<Self> {
         vec![InstallPathStrategy::CargoHome]
     }
+
+    /// Whether this is only used for system-wide installs (as opposed to per-user ones)
+    pub fn is_system(&self) -> bool {
+        matches!(self, InstallPathStrategy::Absolute { .. })
+    }
+
+    /// Get the first absolute path for the given platform, to do system-wide installs to
+    ///
+    /// The same install-path list is shared by every installer, so it can contain
+    /// both unix paths (`/usr/local/bin`) and windows ones (`C:\Program Files\myapp`).
+    pub fn system_path(paths: &[Self], windows: bool) -> Option<String> {
+        paths.iter().find_map(|p| match p {
+            InstallPathStrategy::Absolute { path } if is_windows_path(path) == windows => {
+                Some(path.clone())
+            }
+            _ => None,
+        })
+    }
+
+    /// Check there's at most one absolute path for each platform
+    ///
+    /// Only the first would ever be used, so any more are surely a mistake.
+    pub fn validate_system_paths(paths: &[Self]) -> DistResult<()> {
+        for windows in [false, true] {
+            let mut system_paths = paths.iter().filter_map(|p| match p {
+                InstallPathStrategy::Absolute { path } if is_windows_path(path) == windows => {
+                    Some(path)
+                }
+                _ => None,
+            });
+            if let (Some(first), Some(second)) = (system_paths.next(), system_paths.next()) {
+                return Err(DistError::InstallPathMultipleSystem {
+                    first: first.clone(),
+                    second: second.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether an absolute path is a windows one, starting with a drive letter
+fn is_windows_path(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && (bytes[2] == b'\\' || bytes[2] == b'/')
 }
 
 impl std::str::FromStr for InstallPathStrategy {
@@ -541,6 +661,15 @@ impl std::str::FromStr for InstallPathStrategy {
                     subdir: subdir.strip_suffix('/').unwrap_or(subdir).to_owned(),
                 })
             }
+        } else if path.starts_with('/') || is_windows_path(path) {
+            Ok(InstallPathStrategy::Absolute {
+                // If there's a trailing slash, strip it to be uniform
+                path: if path.len() > 1 {
+                    path.trim_end_matches(['/', '\\']).to_owned()
+                } else {
+                    path.to_owned()
+                },
+            })
         } else if let Some(val) = path.strip_prefix('$') {
             if let Some((env_key, subdir)) = val.split_once('/') {
                 Ok(InstallPathStrategy::EnvSubdir {
@@ -567,6 +696,7 @@ impl std::fmt::Display for InstallPathStrategy {
             InstallPathStrategy::CargoHome => write!(f, "{CARGO_HOME_INSTALL_PATH}"),
             InstallPathStrategy::HomeSubdir { subdir } => write!(f, "~/{subdir}"),
             InstallPathStrategy::EnvSubdir { env_key, subdir } => write!(f, "${env_key}/{subdir}"),
+            InstallPathStrategy::Absolute { path } => write!(f, "{path}"),
         }
     }
 }
@@ -693,15 +823,19 @@ pub enum JinjaInstallPathStrategy {
 
 impl InstallPathStrategy {
     /// Convert this into a jinja-friendly form
-    pub fn into_jinja(self) -> JinjaInstallPathStrategy {
+    ///
+    /// Returns None for system install paths, which installers get separately
+    /// (see [`InstallPathStrategy::system_path`][]).
+    pub fn into_jinja(self) -> Option<JinjaInstallPathStrategy> {
         match self {
-            InstallPathStrategy::CargoHome => JinjaInstallPathStrategy::CargoHome,
+            InstallPathStrategy::CargoHome => Some(JinjaInstallPathStrategy::CargoHome),
             InstallPathStrategy::HomeSubdir { subdir } => {
-                JinjaInstallPathStrategy::HomeSubdir { subdir }
+                Some(JinjaInstallPathStrategy::HomeSubdir { subdir })
             }
             InstallPathStrategy::EnvSubdir { env_key, subdir } => {
-                JinjaInstallPathStrategy::EnvSubdir { env_key, subdir }
+                Some(JinjaInstallPathStrategy::EnvSubdir { env_key, subdir })
             }
+            InstallPathStrategy::Absolute { .. } => None,
         }
     }
 }
@@ -747,6 +881,85 @@ impl std::fmt::Display for ChecksumStyle {
     }
 }
 
+/// A style of detached signature to produce for release artifacts
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SignatureStyle {
+    /// An ASCII-armored OpenPGP signature (using gpg)
+    Gpg,
+    /// A minisign signature
+    Minisign,
+    /// A Sigstore bundle, signed keylessly with a certificate from Fulcio
+    /// and recorded in Rekor
+    Sigstore,
+}
+
+impl SignatureStyle {
+    /// Get the extension of a signature file
+    pub fn ext(self) -> &'static str {
+        match self {
+            SignatureStyle::Gpg => "asc",
+            SignatureStyle::Minisign => "minisig",
+            SignatureStyle::Sigstore => "sigstore.json",
+        }
+    }
+}
+
+impl std::fmt::Display for SignatureStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SignatureStyle::Gpg => "gpg".fmt(f),
+            SignatureStyle::Minisign => "minisign".fmt(f),
+            SignatureStyle::Sigstore => "sigstore".fmt(f),
+        }
+    }
+}
+
+/// The Sigstore instance to sign with (`sigstore`)
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SigstoreConfig {
+    /// The Fulcio instance to get signing certificates from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fulcio_url: Option<String>,
+    /// The Rekor instance to record signatures in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rekor_url: Option<String>,
+}
+
+impl SigstoreConfig {
+    /// Get the Fulcio URL, defaulting to the public good instance
+    pub fn fulcio_url(&self) -> &str {
+        self.fulcio_url
+            .as_deref()
+            .unwrap_or("https://fulcio.sigstore.dev")
+    }
+
+    /// Get the Rekor URL, defaulting to the public good instance
+    pub fn rekor_url(&self) -> &str {
+        self.rekor_url
+            .as_deref()
+            .unwrap_or("https://rekor.sigstore.dev")
+    }
+}
+
+/// A component to list in generated SBOMs (`sbom-components`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SbomComponent {
+    /// The name of the component
+    pub name: String,
+    /// The version of the component
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// The component's package URL (e.g. "pkg:github/madler/zlib@v1.3.1")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purl: Option<String>,
+    /// The component's license, as an SPDX expression
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+}
+
 /// Which style(s) of configuration to generate
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum GenerateMode {
@@ -998,6 +1211,180 @@ impl std::fmt::Display for ProductionMode {
     }
 }
 
+/// Which signing providers sign which files (`signing`)
+///
+/// Files are grouped by [`SigningKind`][], and each kind can be given a provider
+/// for every target, with overrides for specific targets. Anything without a
+/// provider is left to the built-in `ssldotcom` and `macos` signers, which sign
+/// what they always have if `ssldotcom-windows-sign`/`macos-sign` are enabled.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SigningConfig {
+    /// The provider to sign each kind of file with, on every target
+    #[serde(flatten)]
+    pub rules: SigningRules,
+    /// Overrides of `rules` for specific targets
+    #[serde(default)]
+    #[serde(skip_serializing_if = "SortedMap::is_empty")]
+    pub targets: SortedMap<TripleName, SigningRules>,
+    /// Signing providers, by the name the rules refer to them with
+    #[serde(default)]
+    #[serde(skip_serializing_if = "SortedMap::is_empty")]
+    pub providers: SortedMap<String, SigningProviderConfig>,
+}
+
+impl SigningConfig {
+    /// Get the name of the provider that should sign this kind of file for this target, if any
+    pub fn provider_for(&self, kind: SigningKind, target: Option<&TripleNameRef>) -> Option<&str> {
+        target
+            .and_then(|target| self.targets.get(target))
+            .and_then(|rules| rules.provider_for(kind))
+            .or_else(|| self.rules.provider_for(kind))
+    }
+
+    /// Make any paths in these settings relative to the given path
+    pub fn make_relative_to(&mut self, base_path: &Utf8Path) {
+        for provider in self.providers.values_mut() {
+            if let SigningProviderConfig::Osslsigncode(OsslsigncodeConfig {
+                pkcs12: Some(path),
+                ..
+            }) = provider
+            {
+                *path = base_path.join(&*path);
+            }
+        }
+    }
+}
+
+/// The name of the provider to sign each kind of file with
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SigningRules {
+    /// Executables, as they come out of the build (and powershell scripts)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binaries: Option<String>,
+    /// msi installers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub msi: Option<String>,
+    /// macOS pkg installers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pkg: Option<String>,
+    /// Archives (.zip, .tar.gz, ...) and source tarballs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archives: Option<String>,
+    /// Checksum files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksums: Option<String>,
+}
+
+impl SigningRules {
+    /// Get the name of the provider for this kind of file, if any
+    pub fn provider_for(&self, kind: SigningKind) -> Option<&str> {
+        match kind {
+            SigningKind::Binaries => self.binaries.as_deref(),
+            SigningKind::Msi => self.msi.as_deref(),
+            SigningKind::Pkg => self.pkg.as_deref(),
+            SigningKind::Archives => self.archives.as_deref(),
+            SigningKind::Checksums => self.checksums.as_deref(),
+        }
+    }
+
+    /// Get the names of every provider these rules refer to
+    pub fn providers(&self) -> impl Iterator<Item = &str> {
+        [
+            &self.binaries,
+            &self.msi,
+            &self.pkg,
+            &self.archives,
+            &self.checksums,
+        ]
+        .into_iter()
+        .filter_map(|name| name.as_deref())
+    }
+}
+
+/// A kind of file that can be signed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SigningKind {
+    /// Executables, as they come out of the build (and powershell scripts)
+    Binaries,
+    /// msi installers
+    Msi,
+    /// macOS pkg installers
+    Pkg,
+    /// Archives (.zip, .tar.gz, ...) and source tarballs
+    Archives,
+    /// Checksum files
+    Checksums,
+}
+
+impl std::fmt::Display for SigningKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let string = match self {
+            SigningKind::Binaries => "binaries",
+            SigningKind::Msi => "msi",
+            SigningKind::Pkg => "pkg",
+            SigningKind::Archives => "archives",
+            SigningKind::Checksums => "checksums",
+        };
+        string.fmt(f)
+    }
+}
+
+/// A signing provider (`signing.providers.<name>`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+#[serde(rename_all = "kebab-case")]
+pub enum SigningProviderConfig {
+    /// Azure Trusted Signing, using the `sign` dotnet tool
+    AzureTrustedSigning(AzureTrustedSigningConfig),
+    /// A local PKCS#12 certificate, using osslsigncode
+    Osslsigncode(OsslsigncodeConfig),
+    /// A command of your own
+    Command(CommandSignerConfig),
+}
+
+/// Settings for signing with Azure Trusted Signing
+///
+/// Credentials are picked up from the usual `AZURE_*` env vars.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AzureTrustedSigningConfig {
+    /// The endpoint of your account's region (e.g. `https://eus.codesigning.azure.net`)
+    pub endpoint: String,
+    /// The name of your Trusted Signing account
+    pub account: String,
+    /// The name of the certificate profile to sign with
+    pub certificate_profile: String,
+}
+
+/// Settings for signing with osslsigncode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct OsslsigncodeConfig {
+    /// Path to the PKCS#12 file to sign with
+    ///
+    /// If this isn't set, the base64-encoded contents of the file are
+    /// read from `OSSLSIGNCODE_PKCS12` instead.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pkcs12: Option<Utf8PathBuf>,
+    /// An RFC 3161 timestamp server to timestamp signatures with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp_url: Option<String>,
+}
+
+/// Settings for signing with a custom command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommandSignerConfig {
+    /// The command to run to sign a file, which should sign it in place
+    ///
+    /// Any `{file}` in the arguments is replaced with the path of the file
+    /// to sign, and if there isn't one the path is passed as the last argument.
+    pub command: Vec<String>,
+}
+
 /// Find the dist workspaces relative to the current directory
 pub fn get_project() -> Result<axoproject::WorkspaceGraph, axoproject::errors::ProjectError> {
     let start_dir = std::env::current_dir().expect("couldn't get current working dir!?");
@@ -9,6 +9,9 @@ use semver::Version;
 use serde::{Deserialize, Serialize};
 use tracing::log::warn;
 
+use super::v1::ci::gitlab::{GitlabIdTokens, GitlabRunnerConfigInput};
+use super::v1::hosts::s3::S3HostLayer;
+use super::v1::installers::homebrew::{HomebrewService, HomebrewTest};
 use super::*;
 use crate::platform::MinGlibcVersion;
 use crate::SortedMap;
@@ -189,6 +192,45 @@ pub struct DistMetadata {
     /// Create version-specific Homebrew formulas
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version_formulas: Option<bool>,
+    /// A `test do` block to add to the Homebrew formula, for `brew test`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub homebrew_test: Option<HomebrewTest>,
+    /// A `service do` block to add to the Homebrew formula, for `brew services`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub homebrew_service: Option<HomebrewService>,
+    /// Text for Homebrew to show after installing the formula
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub homebrew_caveats: Option<String>,
+    /// Repackage the archives as Homebrew bottles, and list them in the formula
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub homebrew_bottles: Option<bool>,
+    /// A Scoop bucket to push the Scoop manifest to, if built
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scoop_bucket: Option<String>,
+    /// The winget package identifier (e.g. "Publisher.App")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winget_identifier: Option<String>,
+    /// A fork of winget-pkgs to open pull requests from, if built
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winget_fork: Option<String>,
+    /// The git remote base to push AUR packages to, if published
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aur_remote: Option<String>,
+    /// A GitHub repo to push the Nix derivation to, if built
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nix_repo: Option<String>,
+    /// A png or svg icon to put in AppImages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appimage_icon: Option<Utf8PathBuf>,
+    /// A .desktop file to put in AppImages instead of the generated one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appimage_desktop_file: Option<Utf8PathBuf>,
+    /// The categories to list the app under in the AppImage's .desktop file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appimage_categories: Option<Vec<String>>,
+    /// Whether the app in AppImages should be run in a terminal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appimage_terminal: Option<bool>,
 
     /// A set of packages to install before building
     #[serde(rename = "dependencies")]
@@ -249,11 +291,24 @@ pub struct DistMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub npm_scope: Option<String>,
 
+    /// Publish a package per platform with the binaries in it, instead of
+    /// downloading them when the npm package is installed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub npm_platform_packages: Option<bool>,
+
     /// Which checksum algorithm to use, from: sha256, sha512, sha3-256,
     /// sha3-512, blake2s, blake2b, or false (to disable checksums)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub checksum: Option<ChecksumStyle>,
 
+    /// A minisign public key to sign the unified checksum file with (since 1.1.0)
+    ///
+    /// The matching secret key is read from the MINISIGN_SECRET_KEY environment
+    /// variable when building global artifacts, and the shell and powershell
+    /// installers embed this key to verify what they download.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minisign_public_key: Option<String>,
+
     /// Build only the required packages, and individually (since 0.1.0) (default: false)
     ///
     /// By default when we need to build anything in your workspace, we build your entire workspace
@@ -395,6 +450,37 @@ pub struct DistMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_tarball: Option<bool>,
 
+    /// Whether to dist an offline bundle of each app's installers, archives and checksums (since 1.1.0)
+    ///
+    /// (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offline_bundle: Option<bool>,
+
+    /// Detached signatures to produce for each archive, installer and source tarball,
+    /// from: gpg, minisign, sigstore (since 1.1.0)
+    ///
+    /// (defaults to none)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signatures: Option<Vec<SignatureStyle>>,
+
+    /// The Sigstore instance to make sigstore signatures with (since 1.1.0)
+    ///
+    /// (defaults to the public good instance)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sigstore: Option<SigstoreConfig>,
+
+    /// Whether to dist an in-toto SLSA provenance statement for each release (since 1.1.0)
+    ///
+    /// (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<bool>,
+
+    /// Whether to dist CycloneDX and SPDX SBOMs for each release (since 1.1.0)
+    ///
+    /// (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sbom: Option<bool>,
+
     /// Host jobs to run in CI
     ///
     /// The core build job is always run, but this allows additional hooks
@@ -467,6 +553,10 @@ pub struct DistMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub macos_sign: Option<bool>,
 
+    /// Which signing providers sign which files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing: Option<SigningConfig>,
+
     /// Whether GitHub Attestations is enabled (default false)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub github_attestations: Option<bool>,
@@ -480,6 +570,10 @@ pub struct DistMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extra_artifacts: Option<Vec<ExtraArtifact>>,
 
+    /// Components to add to the app's SBOMs, for things dist can't find on its own (since 1.1.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sbom_components: Option<Vec<SbomComponent>>,
+
     /// Custom GitHub runners, mapped by triple target
     #[serde(skip_serializing_if = "Option::is_none")]
     pub github_custom_runners:
@@ -489,6 +583,23 @@ pub struct DistMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub github_custom_job_permissions: Option<SortedMap<String, GithubPermissionMap>>,
 
+    /// Custom GitLab runners, mapped by triple target
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gitlab_custom_runners:
+        Option<SortedMap<TripleName, StringLikeOr<String, GitlabRunnerConfigInput>>>,
+
+    /// Custom OIDC ID tokens for GitLab jobs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gitlab_custom_job_id_tokens: Option<SortedMap<String, GitlabIdTokens>>,
+
+    /// Base URL of the GitLab API to use for GitLab hosting
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gitlab_api_url: Option<String>,
+
+    /// Where to upload artifacts for S3 hosting
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub s3_config: Option<S3HostLayer>,
+
     /// Aliases to install binaries as
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bin_aliases: Option<SortedMap<String, Vec<String>>>,
@@ -565,6 +676,9 @@ impl DistMetadata {
         let DistMetadata {
             include,
             extra_artifacts,
+            appimage_icon,
+            appimage_desktop_file,
+            signing,
             // The rest of these don't include relative paths
             cargo_dist_version: _,
             cargo_dist_url_override: _,
@@ -575,15 +689,29 @@ impl DistMetadata {
             install_success_msg: _,
             tap: _,
             version_formulas: _,
+            homebrew_test: _,
+            homebrew_service: _,
+            homebrew_caveats: _,
+            homebrew_bottles: _,
+            scoop_bucket: _,
+            winget_identifier: _,
+            winget_fork: _,
+            aur_remote: _,
+            nix_repo: _,
+            appimage_categories: _,
+            appimage_terminal: _,
             formula: _,
             system_dependencies: _,
+            sbom_components: _,
             targets: _,
             auto_includes: _,
             windows_archive: _,
             unix_archive: _,
             npm_package: _,
             npm_scope: _,
+            npm_platform_packages: _,
             checksum: _,
+            minisign_public_key: _,
             precise_builds: _,
             fail_fast: _,
             cache_builds: _,
@@ -599,6 +727,11 @@ impl DistMetadata {
             local_artifacts_jobs: _,
             global_artifacts_jobs: _,
             source_tarball: _,
+            offline_bundle: _,
+            signatures: _,
+            sigstore: _,
+            provenance: _,
+            sbom: _,
             host_jobs: _,
             publish_jobs: _,
             post_announce_jobs: _,
@@ -615,6 +748,10 @@ impl DistMetadata {
             hosting: _,
             github_custom_runners: _,
             github_custom_job_permissions: _,
+            gitlab_custom_runners: _,
+            gitlab_custom_job_id_tokens: _,
+            gitlab_api_url: _,
+            s3_config: _,
             bin_aliases: _,
             completion_cmds: _,
             tag_namespace: _,
@@ -647,14 +784,26 @@ impl DistMetadata {
                 extra.working_dir = base_path.join(&extra.working_dir);
             }
         }
+        if let Some(icon) = appimage_icon {
+            *icon = base_path.join(&*icon);
+        }
+        if let Some(desktop_file) = appimage_desktop_file {
+            *desktop_file = base_path.join(&*desktop_file);
+        }
+        if let Some(signing) = signing {
+            signing.make_relative_to(base_path);
+        }
     }
 
     /// Determines whether the configured install paths are compatible with each other
     pub fn validate_install_paths(&self) -> DistResult<()> {
         if let Some(paths) = &self.install_path {
-            if paths.len() > 1 && paths.contains(&InstallPathStrategy::CargoHome) {
+            // System install paths are only used with --system, so they don't conflict
+            let user_paths = paths.iter().filter(|p| !p.is_system()).count();
+            if user_paths > 1 && paths.contains(&InstallPathStrategy::CargoHome) {
                 return Err(DistError::IncompatibleInstallPathConfiguration {});
             }
+            InstallPathStrategy::validate_system_paths(paths)?;
         }
 
         Ok(())
@@ -678,6 +827,19 @@ impl DistMetadata {
             tap,
             formula,
             version_formulas,
+            homebrew_test,
+            homebrew_service,
+            homebrew_caveats,
+            homebrew_bottles,
+            scoop_bucket,
+            winget_identifier,
+            winget_fork,
+            aur_remote,
+            nix_repo,
+            appimage_icon,
+            appimage_desktop_file,
+            appimage_categories,
+            appimage_terminal,
             system_dependencies,
             targets,
             include,
@@ -686,7 +848,9 @@ impl DistMetadata {
             unix_archive,
             npm_package,
             npm_scope,
+            npm_platform_packages,
             checksum,
+            minisign_public_key,
             precise_builds,
             merge_tasks,
             fail_fast,
@@ -702,6 +866,11 @@ impl DistMetadata {
             local_artifacts_jobs,
             global_artifacts_jobs,
             source_tarball,
+            offline_bundle,
+            signatures,
+            sigstore,
+            provenance,
+            sbom,
             host_jobs,
             publish_jobs,
             post_announce_jobs,
@@ -713,12 +882,18 @@ impl DistMetadata {
             github_release,
             ssldotcom_windows_sign,
             macos_sign,
+            signing,
             github_attestations,
             msvc_crt_static,
             hosting,
             extra_artifacts,
+            sbom_components,
             github_custom_runners,
             github_custom_job_permissions,
+            gitlab_custom_runners,
+            gitlab_custom_job_id_tokens,
+            gitlab_api_url,
+            s3_config,
             bin_aliases,
             completion_cmds,
             tag_namespace,
@@ -801,6 +976,9 @@ impl DistMetadata {
         if macos_sign.is_some() {
             warn!("package.metadata.dist.macos-sign is set, but this is only accepted in workspace.metadata (value is being ignored): {package_manifest_path}");
         }
+        if signing.is_some() {
+            warn!("package.metadata.dist.signing is set, but this is only accepted in workspace.metadata (value is being ignored): {package_manifest_path}");
+        }
         if github_attestations.is_some() {
             warn!("package.metadata.dist.github-attestations is set, but this is only accepted in workspace.metadata (value is being ignored): {package_manifest_path}");
         }
@@ -822,6 +1000,21 @@ impl DistMetadata {
         if source_tarball.is_some() {
             warn!("package.metadata.dist.source-tarball is set, but this is only accepted in workspace.metadata (value is being ignored): {package_manifest_path}");
         }
+        if offline_bundle.is_some() {
+            warn!("package.metadata.dist.offline-bundle is set, but this is only accepted in workspace.metadata (value is being ignored): {package_manifest_path}");
+        }
+        if signatures.is_some() {
+            warn!("package.metadata.dist.signatures is set, but this is only accepted in workspace.metadata (value is being ignored): {package_manifest_path}");
+        }
+        if sigstore.is_some() {
+            warn!("package.metadata.dist.sigstore is set, but this is only accepted in workspace.metadata (value is being ignored): {package_manifest_path}");
+        }
+        if provenance.is_some() {
+            warn!("package.metadata.dist.provenance is set, but this is only accepted in workspace.metadata (value is being ignored): {package_manifest_path}");
+        }
+        if sbom.is_some() {
+            warn!("package.metadata.dist.sbom is set, but this is only accepted in workspace.metadata (value is being ignored): {package_manifest_path}");
+        }
         if host_jobs.is_some() {
             warn!("package.metadata.dist.host-jobs is set, but this is only accepted in workspace.metadata (value is being ignored): {package_manifest_path}");
         }
@@ -843,6 +1036,18 @@ impl DistMetadata {
         if github_custom_runners.is_some() {
             warn!("package.metadata.dist.github-custom-runners is set, but this is only accepted in workspace.metadata (value is being ignored): {package_manifest_path}");
         }
+        if gitlab_custom_job_id_tokens.is_some() {
+            warn!("package.metadata.dist.gitlab-custom-job-id-tokens is set, but this is only accepted in workspace.metadata (value is being ignored): {package_manifest_path}");
+        }
+        if gitlab_api_url.is_some() {
+            warn!("package.metadata.dist.gitlab-api-url is set, but this is only accepted in workspace.metadata (value is being ignored): {package_manifest_path}");
+        }
+        if s3_config.is_some() {
+            warn!("package.metadata.dist.s3-config is set, but this is only accepted in workspace.metadata (value is being ignored): {package_manifest_path}");
+        }
+        if gitlab_custom_runners.is_some() {
+            warn!("package.metadata.dist.gitlab-custom-runners is set, but this is only accepted in workspace.metadata (value is being ignored): {package_manifest_path}");
+        }
         if github_build_setup.is_some() {
             warn!("package.metadata.dist.github-build-setup is set, but this is only accepted in workspace.metadata (value is being ignored): {package_manifest_path}");
         }
@@ -872,9 +1077,15 @@ impl DistMetadata {
         if npm_scope.is_none() {
             npm_scope.clone_from(&workspace_config.npm_scope);
         }
+        if npm_platform_packages.is_none() {
+            *npm_platform_packages = workspace_config.npm_platform_packages;
+        }
         if checksum.is_none() {
             *checksum = workspace_config.checksum;
         }
+        if minisign_public_key.is_none() {
+            minisign_public_key.clone_from(&workspace_config.minisign_public_key);
+        }
         if install_path.is_none() {
             install_path.clone_from(&workspace_config.install_path);
         }
@@ -899,12 +1110,54 @@ impl DistMetadata {
         if version_formulas.is_none() {
             *version_formulas = workspace_config.version_formulas;
         }
+        if homebrew_test.is_none() {
+            homebrew_test.clone_from(&workspace_config.homebrew_test);
+        }
+        if homebrew_service.is_none() {
+            homebrew_service.clone_from(&workspace_config.homebrew_service);
+        }
+        if homebrew_caveats.is_none() {
+            homebrew_caveats.clone_from(&workspace_config.homebrew_caveats);
+        }
+        if homebrew_bottles.is_none() {
+            *homebrew_bottles = workspace_config.homebrew_bottles;
+        }
+        if scoop_bucket.is_none() {
+            scoop_bucket.clone_from(&workspace_config.scoop_bucket);
+        }
+        if winget_identifier.is_none() {
+            winget_identifier.clone_from(&workspace_config.winget_identifier);
+        }
+        if winget_fork.is_none() {
+            winget_fork.clone_from(&workspace_config.winget_fork);
+        }
+        if aur_remote.is_none() {
+            aur_remote.clone_from(&workspace_config.aur_remote);
+        }
+        if nix_repo.is_none() {
+            nix_repo.clone_from(&workspace_config.nix_repo);
+        }
+        if appimage_icon.is_none() {
+            appimage_icon.clone_from(&workspace_config.appimage_icon);
+        }
+        if appimage_desktop_file.is_none() {
+            appimage_desktop_file.clone_from(&workspace_config.appimage_desktop_file);
+        }
+        if appimage_categories.is_none() {
+            appimage_categories.clone_from(&workspace_config.appimage_categories);
+        }
+        if appimage_terminal.is_none() {
+            *appimage_terminal = workspace_config.appimage_terminal;
+        }
         if system_dependencies.is_none() {
             system_dependencies.clone_from(&workspace_config.system_dependencies);
         }
         if extra_artifacts.is_none() {
             extra_artifacts.clone_from(&workspace_config.extra_artifacts);
         }
+        if sbom_components.is_none() {
+            sbom_components.clone_from(&workspace_config.sbom_components);
+        }
         if bin_aliases.is_none() {
             bin_aliases.clone_from(&workspace_config.bin_aliases);
         }
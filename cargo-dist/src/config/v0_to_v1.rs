@@ -5,14 +5,22 @@ use artifacts::ArtifactLayer;
 use builds::cargo::CargoBuildLayer;
 use builds::{BuildLayer, CommonBuildLayer};
 use ci::github::GithubCiLayer;
+use ci::gitlab::GitlabCiLayer;
 use ci::{CiLayer, CommonCiLayer};
 use hosts::github::GithubHostLayer;
+use hosts::gitlab::GitlabHostLayer;
+use hosts::s3::S3HostLayer;
 use hosts::{CommonHostLayer, HostLayer};
+use installers::appimage::AppimageInstallerLayer;
 use installers::homebrew::HomebrewInstallerLayer;
+use installers::nix::NixInstallerLayer;
 use installers::npm::NpmInstallerLayer;
 use installers::pkg::PkgInstallerLayer;
+use installers::scoop::ScoopInstallerLayer;
+use installers::winget::WingetInstallerLayer;
 use installers::{CommonInstallerLayer, InstallerLayer};
 use layer::BoolOr;
+use publishers::aur::AurPublisherLayer;
 use publishers::{CommonPublisherLayer, PublisherLayer};
 
 use super::v0::DistMetadata;
@@ -34,6 +42,19 @@ impl DistMetadata {
             tap,
             formula,
             version_formulas,
+            homebrew_test,
+            homebrew_service,
+            homebrew_caveats,
+            homebrew_bottles,
+            scoop_bucket,
+            winget_identifier,
+            winget_fork,
+            aur_remote,
+            nix_repo,
+            appimage_icon,
+            appimage_desktop_file,
+            appimage_categories,
+            appimage_terminal,
             system_dependencies,
             targets,
             include,
@@ -43,7 +64,9 @@ impl DistMetadata {
             unix_archive,
             npm_package,
             npm_scope,
+            npm_platform_packages,
             checksum,
+            minisign_public_key,
             precise_builds,
             merge_tasks,
             fail_fast,
@@ -59,6 +82,11 @@ impl DistMetadata {
             local_artifacts_jobs,
             global_artifacts_jobs,
             source_tarball,
+            offline_bundle,
+            signatures,
+            sigstore,
+            provenance,
+            sbom,
             host_jobs,
             publish_jobs,
             post_announce_jobs,
@@ -70,12 +98,18 @@ impl DistMetadata {
             github_release,
             ssldotcom_windows_sign,
             macos_sign,
+            signing,
             mac_pkg_config,
             github_attestations,
             hosting,
             extra_artifacts,
+            sbom_components,
             github_custom_runners,
             github_custom_job_permissions,
+            gitlab_custom_runners,
+            gitlab_custom_job_id_tokens,
+            gitlab_api_url,
+            s3_config,
             bin_aliases,
             completion_cmds,
             tag_namespace,
@@ -107,13 +141,27 @@ impl DistMetadata {
         });
         let needs_artifacts = archive_layer.is_some()
             || source_tarball.is_some()
+            || offline_bundle.is_some()
             || extra_artifacts.is_some()
-            || checksum.is_some();
+            || checksum.is_some()
+            || minisign_public_key.is_some()
+            || signatures.is_some()
+            || sigstore.is_some()
+            || provenance.is_some()
+            || sbom.is_some()
+            || sbom_components.is_some();
         let artifacts_layer = needs_artifacts.then_some(ArtifactLayer {
             archives: archive_layer,
             source_tarball,
+            offline_bundle,
             extra: extra_artifacts,
             checksum,
+            minisign_public_key,
+            signatures,
+            sigstore,
+            provenance,
+            sbom,
+            sbom_components,
         });
 
         // Builds
@@ -139,6 +187,7 @@ impl DistMetadata {
             || system_dependencies.is_some()
             || ssldotcom_windows_sign.is_some()
             || macos_sign.is_some()
+            || signing.is_some()
             || msvc_crt_static.is_some()
             || min_glibc_version.is_some()
             || omnibor.is_some();
@@ -146,6 +195,7 @@ impl DistMetadata {
             common: CommonBuildLayer {},
             ssldotcom_windows_sign,
             macos_sign,
+            signing,
             system_dependencies,
             cargo: cargo_layer,
             generic: None,
@@ -169,7 +219,19 @@ impl DistMetadata {
                 None
             }
         });
+        let gitlab_ci_layer = list_to_bool_layer(is_global, &ci, CiStyle::Gitlab, || {
+            if gitlab_custom_runners.is_some() || gitlab_custom_job_id_tokens.is_some() {
+                Some(GitlabCiLayer {
+                    common: CommonCiLayer::default(),
+                    runners: gitlab_custom_runners,
+                    id_tokens: gitlab_custom_job_id_tokens,
+                })
+            } else {
+                None
+            }
+        });
         let has_github_ci = github_ci_layer.is_some();
+        let has_gitlab_ci = gitlab_ci_layer.is_some();
         let custom_publish_jobs = publish_jobs.as_ref().map(|jobs| {
             jobs.iter()
                 .filter_map(|p| {
@@ -191,6 +253,7 @@ impl DistMetadata {
             custom_publish_jobs
         };
         let needs_ci_layer = github_ci_layer.is_some()
+            || gitlab_ci_layer.is_some()
             || merge_tasks.is_some()
             || fail_fast.is_some()
             || cache_builds.is_some()
@@ -222,6 +285,7 @@ impl DistMetadata {
                 post_announce_jobs,
             },
             github: github_ci_layer,
+            gitlab: gitlab_ci_layer,
         });
 
         // hosts
@@ -247,12 +311,38 @@ impl DistMetadata {
             });
         let axodotdev_host_layer =
             list_to_bool_layer(is_global, &hosting, HostingStyle::Axodotdev, || None);
-        if github_host_layer.is_none() && axodotdev_host_layer.is_none() && has_github_ci {
+        let mut gitlab_host_layer =
+            list_to_bool_layer(is_global, &hosting, HostingStyle::Gitlab, || {
+                gitlab_api_url.map(|api_url| GitlabHostLayer {
+                    common: CommonHostLayer::default(),
+                    api_url: Some(api_url),
+                })
+            });
+        let s3_host_layer = list_to_bool_layer(is_global, &hosting, HostingStyle::S3, || {
+            s3_config.map(|config| S3HostLayer {
+                common: CommonHostLayer::default(),
+                ..config
+            })
+        });
+        // S3 is usually a mirror, so setting up a bucket shouldn't turn off the CI's
+        // native hosting unless `hosting` explicitly asks for it
+        let explicit_s3 = hosting
+            .as_ref()
+            .is_some_and(|hosting| hosting.contains(&HostingStyle::S3));
+        let no_host_layer = github_host_layer.is_none()
+            && axodotdev_host_layer.is_none()
+            && gitlab_host_layer.is_none()
+            && !explicit_s3;
+        if no_host_layer && has_github_ci {
             github_host_layer = Some(BoolOr::Bool(true));
+        } else if no_host_layer && has_gitlab_ci {
+            gitlab_host_layer = Some(BoolOr::Bool(true));
         }
 
         let needs_host_layer = github_host_layer.is_some()
             || axodotdev_host_layer.is_some()
+            || gitlab_host_layer.is_some()
+            || s3_host_layer.is_some()
             || force_latest.is_some()
             || display.is_some()
             || display_name.is_some();
@@ -260,6 +350,8 @@ impl DistMetadata {
             common: CommonHostLayer {},
             github: github_host_layer,
             axodotdev: axodotdev_host_layer,
+            gitlab: gitlab_host_layer,
+            s3: s3_host_layer,
             force_latest,
             display,
             display_name,
@@ -268,12 +360,22 @@ impl DistMetadata {
         // installers
         let homebrew_installer_layer =
             list_to_bool_layer(is_global, &installers, InstallerStyle::Homebrew, || {
-                if tap.is_some() || formula.is_some() {
+                if tap.is_some()
+                    || formula.is_some()
+                    || homebrew_test.is_some()
+                    || homebrew_service.is_some()
+                    || homebrew_caveats.is_some()
+                    || homebrew_bottles.is_some()
+                {
                     Some(HomebrewInstallerLayer {
                         common: CommonInstallerLayer::default(),
                         tap,
                         formula,
                         version_formulas,
+                        test: homebrew_test,
+                        service: homebrew_service,
+                        caveats: homebrew_caveats,
+                        bottles: homebrew_bottles,
                     })
                 } else {
                     None
@@ -281,11 +383,12 @@ impl DistMetadata {
             });
         let npm_installer_layer =
             list_to_bool_layer(is_global, &installers, InstallerStyle::Npm, || {
-                if npm_package.is_some() || npm_scope.is_some() {
+                if npm_package.is_some() || npm_scope.is_some() || npm_platform_packages.is_some() {
                     Some(NpmInstallerLayer {
                         common: CommonInstallerLayer::default(),
                         package: npm_package,
                         scope: npm_scope,
+                        platform_packages: npm_platform_packages,
                     })
                 } else {
                     None
@@ -305,6 +408,60 @@ impl DistMetadata {
                     install_location,
                 })
             });
+        let deb_installer_layer =
+            list_to_bool_layer(is_global, &installers, InstallerStyle::Deb, || None);
+        let rpm_installer_layer =
+            list_to_bool_layer(is_global, &installers, InstallerStyle::Rpm, || None);
+        let scoop_installer_layer =
+            list_to_bool_layer(is_global, &installers, InstallerStyle::Scoop, || {
+                scoop_bucket.map(|bucket| ScoopInstallerLayer {
+                    common: CommonInstallerLayer::default(),
+                    bucket: Some(bucket),
+                })
+            });
+        let winget_installer_layer =
+            list_to_bool_layer(is_global, &installers, InstallerStyle::Winget, || {
+                if winget_identifier.is_some() || winget_fork.is_some() {
+                    Some(WingetInstallerLayer {
+                        common: CommonInstallerLayer::default(),
+                        identifier: winget_identifier,
+                        fork: winget_fork,
+                    })
+                } else {
+                    None
+                }
+            });
+        let aur_installer_layer =
+            list_to_bool_layer(is_global, &installers, InstallerStyle::Aur, || None);
+        let nix_installer_layer =
+            list_to_bool_layer(is_global, &installers, InstallerStyle::Nix, || {
+                nix_repo.map(|repo| NixInstallerLayer {
+                    common: CommonInstallerLayer::default(),
+                    repo: Some(repo),
+                })
+            });
+        let pypi_installer_layer =
+            list_to_bool_layer(is_global, &installers, InstallerStyle::Pypi, || None);
+        let appimage_installer_layer =
+            list_to_bool_layer(is_global, &installers, InstallerStyle::Appimage, || {
+                if appimage_icon.is_some()
+                    || appimage_desktop_file.is_some()
+                    || appimage_categories.is_some()
+                    || appimage_terminal.is_some()
+                {
+                    Some(AppimageInstallerLayer {
+                        common: CommonInstallerLayer::default(),
+                        icon: appimage_icon,
+                        desktop_file: appimage_desktop_file,
+                        categories: appimage_categories,
+                        terminal: appimage_terminal,
+                    })
+                } else {
+                    None
+                }
+            });
+        let chocolatey_installer_layer =
+            list_to_bool_layer(is_global, &installers, InstallerStyle::Chocolatey, || None);
         let powershell_installer_layer =
             list_to_bool_layer(is_global, &installers, InstallerStyle::Powershell, || None);
         let shell_installer_layer =
@@ -315,6 +472,15 @@ impl DistMetadata {
             || powershell_installer_layer.is_some()
             || shell_installer_layer.is_some()
             || pkg_installer_layer.is_some()
+            || deb_installer_layer.is_some()
+            || rpm_installer_layer.is_some()
+            || scoop_installer_layer.is_some()
+            || winget_installer_layer.is_some()
+            || chocolatey_installer_layer.is_some()
+            || aur_installer_layer.is_some()
+            || nix_installer_layer.is_some()
+            || pypi_installer_layer.is_some()
+            || appimage_installer_layer.is_some()
             || install_path.is_some()
             || install_success_msg.is_some()
             || install_libraries.is_some()
@@ -335,6 +501,15 @@ impl DistMetadata {
             powershell: powershell_installer_layer,
             shell: shell_installer_layer,
             pkg: pkg_installer_layer,
+            deb: deb_installer_layer,
+            rpm: rpm_installer_layer,
+            scoop: scoop_installer_layer,
+            winget: winget_installer_layer,
+            chocolatey: chocolatey_installer_layer,
+            aur: aur_installer_layer,
+            nix: nix_installer_layer,
+            pypi: pypi_installer_layer,
+            appimage: appimage_installer_layer,
             updater: install_updater,
             always_use_latest_updater,
         });
@@ -344,8 +519,28 @@ impl DistMetadata {
             list_to_bool_layer(is_global, &publish_jobs, PublishStyle::Homebrew, || None);
         let npm_publisher_layer =
             list_to_bool_layer(is_global, &publish_jobs, PublishStyle::Npm, || None);
+        let scoop_publisher_layer =
+            list_to_bool_layer(is_global, &publish_jobs, PublishStyle::Scoop, || None);
+        let winget_publisher_layer =
+            list_to_bool_layer(is_global, &publish_jobs, PublishStyle::Winget, || None);
+        let aur_publisher_layer =
+            list_to_bool_layer(is_global, &publish_jobs, PublishStyle::Aur, || {
+                aur_remote.map(|remote| AurPublisherLayer {
+                    common: CommonPublisherLayer::default(),
+                    remote: Some(remote),
+                })
+            });
+        let nix_publisher_layer =
+            list_to_bool_layer(is_global, &publish_jobs, PublishStyle::Nix, || None);
+        let pypi_publisher_layer =
+            list_to_bool_layer(is_global, &publish_jobs, PublishStyle::Pypi, || None);
         let needs_publisher_layer = homebrew_publisher_layer.is_some()
             || npm_publisher_layer.is_some()
+            || scoop_publisher_layer.is_some()
+            || winget_publisher_layer.is_some()
+            || aur_publisher_layer.is_some()
+            || nix_publisher_layer.is_some()
+            || pypi_publisher_layer.is_some()
             || publish_prereleases.is_some();
         let publisher_layer = needs_publisher_layer.then_some(PublisherLayer {
             common: CommonPublisherLayer {
@@ -353,6 +548,11 @@ impl DistMetadata {
             },
             homebrew: homebrew_publisher_layer,
             npm: npm_publisher_layer,
+            scoop: scoop_publisher_layer,
+            winget: winget_publisher_layer,
+            aur: aur_publisher_layer,
+            nix: nix_publisher_layer,
+            pypi: pypi_publisher_layer,
         });
 
         // done!
@@ -12,6 +12,8 @@ pub struct AppArtifactConfig {
     pub archives: ArchiveConfig,
     /// Any extra artifacts and their buildscripts
     pub extra: Vec<ExtraArtifact>,
+    /// Components to add to the app's SBOMs
+    pub sbom_components: Vec<SbomComponent>,
 }
 
 /// workspace artifact config (final)
@@ -19,8 +21,20 @@ pub struct AppArtifactConfig {
 pub struct WorkspaceArtifactConfig {
     /// Whether to generate and dist a tarball containing your app's source code
     pub source_tarball: bool,
+    /// Whether to generate an offline bundle
+    pub offline_bundle: bool,
     /// How to checksum
     pub checksum: ChecksumStyle,
+    /// A minisign public key to sign the unified checksum file with
+    pub minisign_public_key: Option<String>,
+    /// Detached signatures to produce for each archive, installer and source tarball
+    pub signatures: Vec<SignatureStyle>,
+    /// The Sigstore instance to make sigstore signatures with
+    pub sigstore: SigstoreConfig,
+    /// Whether to generate a SLSA provenance statement for each release
+    pub provenance: bool,
+    /// Whether to generate CycloneDX and SPDX SBOMs for each release
+    pub sbom: bool,
 }
 /// artifact config (raw from file)
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -36,6 +50,12 @@ pub struct ArtifactLayer {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_tarball: Option<bool>,
 
+    /// Whether to dist an offline bundle of each app's installers, archives and checksums
+    ///
+    /// (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offline_bundle: Option<bool>,
+
     /// Any extra artifacts and their buildscripts
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extra: Option<Vec<ExtraArtifact>>,
@@ -43,6 +63,41 @@ pub struct ArtifactLayer {
     /// How to checksum
     #[serde(skip_serializing_if = "Option::is_none")]
     pub checksum: Option<ChecksumStyle>,
+
+    /// A minisign public key to sign the unified checksum file with
+    ///
+    /// The matching secret key is read from the MINISIGN_SECRET_KEY environment
+    /// variable, and the shell and powershell installers verify the signature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minisign_public_key: Option<String>,
+
+    /// Detached signatures to produce for each archive, installer and source tarball
+    ///
+    /// (defaults to none)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signatures: Option<Vec<SignatureStyle>>,
+
+    /// The Sigstore instance to make sigstore signatures with
+    ///
+    /// (defaults to the public good instance)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sigstore: Option<SigstoreConfig>,
+
+    /// Whether to dist an in-toto SLSA provenance statement for each release
+    ///
+    /// (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<bool>,
+
+    /// Whether to dist CycloneDX and SPDX SBOMs for each release
+    ///
+    /// (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sbom: Option<bool>,
+
+    /// Components to add to the app's SBOMs, for things dist can't find on its own
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sbom_components: Option<Vec<SbomComponent>>,
 }
 impl AppArtifactConfig {
     /// get the defaults for a package
@@ -50,6 +105,7 @@ impl AppArtifactConfig {
         Self {
             archives: ArchiveConfig::defaults_for_package(workspaces, pkg_idx),
             extra: vec![],
+            sbom_components: vec![],
         }
     }
 }
@@ -59,7 +115,13 @@ impl WorkspaceArtifactConfig {
     pub fn defaults_for_workspace(_workspaces: &WorkspaceGraph) -> Self {
         Self {
             source_tarball: true,
+            offline_bundle: false,
             checksum: ChecksumStyle::Sha256,
+            minisign_public_key: None,
+            signatures: vec![],
+            sigstore: SigstoreConfig::default(),
+            provenance: false,
+            sbom: false,
         }
     }
 }
@@ -71,13 +133,21 @@ impl ApplyLayer for AppArtifactConfig {
         Self::Layer {
             archives,
             extra,
+            sbom_components,
             // these are all workspace-only
             source_tarball: _,
+            offline_bundle: _,
             checksum: _,
+            minisign_public_key: _,
+            signatures: _,
+            sigstore: _,
+            provenance: _,
+            sbom: _,
         }: Self::Layer,
     ) {
         self.archives.apply_val_layer(archives);
         self.extra.apply_val(extra);
+        self.sbom_components.apply_val(sbom_components);
     }
 }
 
@@ -87,13 +157,26 @@ impl ApplyLayer for WorkspaceArtifactConfig {
         &mut self,
         Self::Layer {
             source_tarball,
+            offline_bundle,
             checksum,
+            minisign_public_key,
+            signatures,
+            sigstore,
+            provenance,
+            sbom,
             // these are all app-only
             archives: _,
             extra: _,
+            sbom_components: _,
         }: Self::Layer,
     ) {
         self.source_tarball.apply_val(source_tarball);
+        self.offline_bundle.apply_val(offline_bundle);
         self.checksum.apply_val(checksum);
+        self.minisign_public_key.apply_opt(minisign_public_key);
+        self.signatures.apply_val(signatures);
+        self.sigstore.apply_val(sigstore);
+        self.provenance.apply_val(provenance);
+        self.sbom.apply_val(sbom);
     }
 }
@@ -17,6 +17,8 @@ pub struct WorkspaceBuildConfig {
     pub ssldotcom_windows_sign: Option<ProductionMode>,
     /// whether to sign macos binaries with apple
     pub macos_sign: bool,
+    /// which signing providers sign which files
+    pub signing: SigningConfig,
     /// Overrides the minimum supported glibc version.
     pub min_glibc_version: Option<MinGlibcVersion>,
     /// Whether to generate OmniBOR artifact IDs.
@@ -47,6 +49,8 @@ pub struct BuildConfigInheritable {
     pub ssldotcom_windows_sign: Option<ProductionMode>,
     /// whether to sign macos binaries with apple
     pub macos_sign: Option<bool>,
+    /// which signing providers sign which files
+    pub signing: Option<SigningConfig>,
     /// cargo builds
     pub cargo: Option<CargoBuildLayer>,
     /// generic builds
@@ -75,6 +79,10 @@ pub struct BuildLayer {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub macos_sign: Option<bool>,
 
+    /// which signing providers sign which files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing: Option<SigningConfig>,
+
     /// cargo builds
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cargo: Option<BoolOr<CargoBuildLayer>>,
@@ -102,6 +110,7 @@ impl BuildConfigInheritable {
             system_dependencies: Default::default(),
             ssldotcom_windows_sign: None,
             macos_sign: None,
+            signing: None,
             min_glibc_version: None,
             omnibor: None,
         }
@@ -115,6 +124,7 @@ impl BuildConfigInheritable {
             system_dependencies: Default::default(),
             ssldotcom_windows_sign: None,
             macos_sign: None,
+            signing: None,
             min_glibc_version: None,
             omnibor: None,
         }
@@ -129,6 +139,7 @@ impl BuildConfigInheritable {
             cargo,
             ssldotcom_windows_sign,
             macos_sign,
+            signing,
             min_glibc_version,
             omnibor,
             // local-only
@@ -143,6 +154,7 @@ impl BuildConfigInheritable {
             cargo: cargo_out,
             macos_sign: macos_sign.unwrap_or(false),
             ssldotcom_windows_sign,
+            signing: signing.unwrap_or_default(),
             min_glibc_version,
             omnibor: omnibor.unwrap_or(false),
         }
@@ -163,6 +175,7 @@ impl BuildConfigInheritable {
             // local-only
             ssldotcom_windows_sign: _,
             macos_sign: _,
+            signing: _,
         } = self;
         let mut cargo_out = AppCargoBuildConfig::defaults_for_package(workspaces, pkg_idx, &common);
         if let Some(cargo) = cargo {
@@ -194,6 +207,7 @@ impl ApplyLayer for BuildConfigInheritable {
             system_dependencies,
             ssldotcom_windows_sign,
             macos_sign,
+            signing,
             min_glibc_version,
             omnibor,
         }: Self::Layer,
@@ -205,6 +219,7 @@ impl ApplyLayer for BuildConfigInheritable {
         self.ssldotcom_windows_sign
             .apply_opt(ssldotcom_windows_sign);
         self.macos_sign.apply_opt(macos_sign);
+        self.signing.apply_opt(signing);
         self.min_glibc_version.apply_opt(min_glibc_version);
         self.omnibor.apply_opt(omnibor);
     }
@@ -0,0 +1,142 @@
+//! gitlab ci config
+
+use dist_schema::{StringLikeOr, TripleName};
+
+use super::*;
+
+/// OIDC ID tokens GitLab should mint for a job, mapped by variable name to audience
+///
+/// This is the closest thing GitLab has to GitHub's per-job permissions.
+pub type GitlabIdTokens = SortedMap<String, String>;
+
+/// gitlab ci config (raw from file)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GitlabCiLayer {
+    /// Common options
+    #[serde(flatten)]
+    pub common: CommonCiLayer,
+
+    /// Custom GitLab runners, mapped by triple target
+    ///
+    /// A plain string is treated as a single runner tag.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runners: Option<SortedMap<TripleName, StringLikeOr<String, GitlabRunnerConfigInput>>>,
+
+    /// OIDC ID tokens to give custom jobs, mapped by job name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_tokens: Option<SortedMap<String, GitlabIdTokens>>,
+}
+
+/// gitlab ci config (final)
+#[derive(Debug, Default, Clone)]
+pub struct GitlabCiConfig {
+    /// Common options
+    pub common: CommonCiConfig,
+
+    /// Custom GitLab runners, mapped by triple target
+    pub runners: SortedMap<TripleName, GitlabRunnerConfig>,
+
+    /// OIDC ID tokens to give custom jobs, mapped by job name
+    pub id_tokens: SortedMap<String, GitlabIdTokens>,
+}
+
+/// A GitLab runner, as written in the config
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GitlabRunnerConfigInput {
+    /// Runner tags used to pick the runner
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+
+    /// Docker image to run the job in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+
+    /// Host triple of the runner (defaults to the target being built)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<TripleName>,
+}
+
+/// A GitLab runner (final)
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GitlabRunnerConfig {
+    /// Runner tags used to pick the runner
+    pub tags: Vec<String>,
+
+    /// Docker image to run the job in
+    pub image: Option<String>,
+
+    /// Host triple of the runner
+    pub host: TripleName,
+}
+
+impl GitlabCiConfig {
+    /// Get defaults for the given package
+    pub fn defaults_for_workspace(_workspaces: &WorkspaceGraph, common: &CommonCiConfig) -> Self {
+        Self {
+            common: common.clone(),
+            runners: Default::default(),
+            id_tokens: Default::default(),
+        }
+    }
+}
+
+impl ApplyLayer for GitlabCiConfig {
+    type Layer = GitlabCiLayer;
+    fn apply_layer(
+        &mut self,
+        Self::Layer {
+            common,
+            runners,
+            id_tokens,
+        }: Self::Layer,
+    ) {
+        self.common.apply_layer(common);
+        self.runners.apply_val(runners.map(|runners| {
+            runners
+                .into_iter()
+                .map(|(target_triple, runner)| {
+                    let runner = match runner {
+                        StringLikeOr::StringLike(tag) => GitlabRunnerConfig {
+                            tags: vec![tag],
+                            image: None,
+                            host: target_triple.clone(),
+                        },
+                        StringLikeOr::Val(runner) => GitlabRunnerConfig {
+                            tags: runner.tags.unwrap_or_default(),
+                            image: runner.image,
+                            // if not specified, then assume the custom gitlab runner is
+                            // the right platform (host == target)
+                            host: runner.host.unwrap_or_else(|| target_triple.clone()),
+                        },
+                    };
+                    (target_triple, runner)
+                })
+                .collect()
+        }));
+        self.id_tokens.apply_val(id_tokens);
+    }
+}
+impl ApplyLayer for GitlabCiLayer {
+    type Layer = GitlabCiLayer;
+    fn apply_layer(
+        &mut self,
+        Self::Layer {
+            common,
+            runners,
+            id_tokens,
+        }: Self::Layer,
+    ) {
+        self.common.apply_layer(common);
+        self.runners.apply_opt(runners);
+        self.id_tokens.apply_opt(id_tokens);
+    }
+}
+
+impl std::ops::Deref for GitlabCiConfig {
+    type Target = CommonCiConfig;
+    fn deref(&self) -> &Self::Target {
+        &self.common
+    }
+}
@@ -0,0 +1,60 @@
+//! gitlab host
+
+use super::*;
+
+/// gitlab host config (raw)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GitlabHostLayer {
+    /// Common options
+    #[serde(flatten)]
+    pub common: CommonHostLayer,
+
+    /// Base URL of the GitLab API to host releases with
+    ///
+    /// Defaults to the `/api/v4` endpoint of the instance your repository is on.
+    /// Required if your repository isn't on GitLab.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+}
+/// gitlab host config (final)
+#[derive(Debug, Default, Clone)]
+pub struct GitlabHostConfig {
+    /// Common options
+    pub common: CommonHostConfig,
+    /// Base URL of the GitLab API to host releases with
+    /// (None means "derive it from the repository URL")
+    pub api_url: Option<String>,
+}
+
+impl GitlabHostConfig {
+    /// Get defaults for the given workspace
+    pub fn defaults_for_workspace(_workspaces: &WorkspaceGraph, common: &CommonHostConfig) -> Self {
+        Self {
+            common: common.clone(),
+            api_url: None,
+        }
+    }
+}
+
+impl ApplyLayer for GitlabHostConfig {
+    type Layer = GitlabHostLayer;
+    fn apply_layer(&mut self, Self::Layer { common, api_url }: Self::Layer) {
+        self.common.apply_layer(common);
+        self.api_url.apply_opt(api_url);
+    }
+}
+impl ApplyLayer for GitlabHostLayer {
+    type Layer = GitlabHostLayer;
+    fn apply_layer(&mut self, Self::Layer { common, api_url }: Self::Layer) {
+        self.common.apply_layer(common);
+        self.api_url.apply_opt(api_url);
+    }
+}
+
+impl std::ops::Deref for GitlabHostConfig {
+    type Target = CommonHostConfig;
+    fn deref(&self) -> &Self::Target {
+        &self.common
+    }
+}
@@ -2,11 +2,15 @@
 
 pub mod axodotdev;
 pub mod github;
+pub mod gitlab;
+pub mod s3;
 
 use super::*;
 
 use axodotdev::*;
 use github::*;
+use gitlab::*;
+use s3::*;
 
 #[derive(Debug, Clone)]
 /// package-specific host config (final)
@@ -26,6 +30,10 @@ pub struct WorkspaceHostConfig {
     pub github: Option<GithubHostConfig>,
     /// axodotdev host config (axo releases)
     pub axodotdev: Option<AxodotdevHostConfig>,
+    /// gitlab host config (gitlab releases + generic package registry)
+    pub gitlab: Option<GitlabHostConfig>,
+    /// s3 host config (s3-compatible object storage)
+    pub s3: Option<S3HostConfig>,
 }
 /// host config (inheritance not folded in yet)
 #[derive(Debug, Clone)]
@@ -42,6 +50,10 @@ pub struct HostConfigInheritable {
     pub github: Option<GithubHostLayer>,
     /// axodotdev hosting
     pub axodotdev: Option<AxodotdevHostLayer>,
+    /// gitlab hosting
+    pub gitlab: Option<GitlabHostLayer>,
+    /// s3 hosting
+    pub s3: Option<S3HostLayer>,
 }
 
 /// host config (raw from file)
@@ -78,6 +90,12 @@ pub struct HostLayer {
     /// axodotdev hosting
     #[serde(skip_serializing_if = "Option::is_none")]
     pub axodotdev: Option<BoolOr<AxodotdevHostLayer>>,
+    /// gitlab hosting
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gitlab: Option<BoolOr<GitlabHostLayer>>,
+    /// s3 hosting
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub s3: Option<BoolOr<S3HostLayer>>,
 }
 impl HostConfigInheritable {
     /// get defaults for a package
@@ -86,6 +104,8 @@ impl HostConfigInheritable {
             common: CommonHostConfig::defaults_for_package(workspaces, pkg_idx),
             github: None,
             axodotdev: None,
+            gitlab: None,
+            s3: None,
             force_latest: None,
             display: None,
             display_name: None,
@@ -97,6 +117,8 @@ impl HostConfigInheritable {
             common: CommonHostConfig::defaults_for_workspace(workspaces),
             github: None,
             axodotdev: None,
+            gitlab: None,
+            s3: None,
             force_latest: None,
             display: None,
             display_name: None,
@@ -112,6 +134,8 @@ impl HostConfigInheritable {
             common: _,
             github: _,
             axodotdev: _,
+            gitlab: _,
+            s3: _,
             force_latest: _,
             display,
             display_name,
@@ -132,6 +156,8 @@ impl HostConfigInheritable {
             common,
             github,
             axodotdev,
+            gitlab,
+            s3,
             force_latest,
             display: _,
             display_name: _,
@@ -146,9 +172,21 @@ impl HostConfigInheritable {
             default.apply_layer(axodotdev);
             default
         });
+        let gitlab = gitlab.map(|gitlab| {
+            let mut default = GitlabHostConfig::defaults_for_workspace(workspaces, &common);
+            default.apply_layer(gitlab);
+            default
+        });
+        let s3 = s3.map(|s3| {
+            let mut default = S3HostConfig::defaults_for_workspace(workspaces, &common);
+            default.apply_layer(s3);
+            default
+        });
         WorkspaceHostConfig {
             github,
             axodotdev,
+            gitlab,
+            s3,
             force_latest: force_latest.unwrap_or(false),
         }
     }
@@ -162,6 +200,8 @@ impl ApplyLayer for HostConfigInheritable {
             common,
             github,
             axodotdev,
+            gitlab,
+            s3,
             force_latest,
             display,
             display_name,
@@ -170,6 +210,8 @@ impl ApplyLayer for HostConfigInheritable {
         self.common.apply_layer(common);
         self.github.apply_bool_layer(github);
         self.axodotdev.apply_bool_layer(axodotdev);
+        self.gitlab.apply_bool_layer(gitlab);
+        self.s3.apply_bool_layer(s3);
         self.force_latest.apply_opt(force_latest);
         self.display.apply_opt(display);
         self.display_name.apply_opt(display_name);
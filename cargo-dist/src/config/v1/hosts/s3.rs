@@ -0,0 +1,146 @@
+//! s3 host
+
+use super::*;
+
+/// s3 host config (raw)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct S3HostLayer {
+    /// Common options
+    #[serde(flatten)]
+    pub common: CommonHostLayer,
+
+    /// The bucket to upload artifacts to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bucket: Option<String>,
+
+    /// A path in the bucket to put releases under
+    ///
+    /// Each release's files go in `<prefix>/<app-name>/<version>/`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+
+    /// The S3 API endpoint to talk to
+    ///
+    /// Defaults to AWS's endpoint for the region. Set this to use other
+    /// S3-compatible storage like MinIO.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+
+    /// The region the bucket is in
+    ///
+    /// (defaults to "us-east-1")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+
+    /// The URL the bucket can be publicly downloaded from
+    ///
+    /// `{bucket}`, `{region}`, and `{endpoint}` are replaced with their values.
+    /// Defaults to `{endpoint}/{bucket}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_url: Option<String>,
+}
+/// s3 host config (final)
+#[derive(Debug, Default, Clone)]
+pub struct S3HostConfig {
+    /// Common options
+    pub common: CommonHostConfig,
+    /// The bucket to upload artifacts to
+    pub bucket: Option<String>,
+    /// A path in the bucket to put releases under
+    pub prefix: Option<String>,
+    /// The S3 API endpoint to talk to
+    /// (None means "AWS's endpoint for the region")
+    pub endpoint: Option<String>,
+    /// The region the bucket is in
+    pub region: String,
+    /// The URL the bucket can be publicly downloaded from
+    /// (None means "`{endpoint}/{bucket}`")
+    pub public_url: Option<String>,
+}
+
+impl S3HostConfig {
+    /// Get defaults for the given workspace
+    pub fn defaults_for_workspace(_workspaces: &WorkspaceGraph, common: &CommonHostConfig) -> Self {
+        Self {
+            common: common.clone(),
+            bucket: None,
+            prefix: None,
+            endpoint: None,
+            region: "us-east-1".to_owned(),
+            public_url: None,
+        }
+    }
+
+    /// The S3 API endpoint to talk to
+    pub fn endpoint(&self) -> String {
+        self.endpoint
+            .clone()
+            .unwrap_or_else(|| format!("https://s3.{}.amazonaws.com", self.region))
+            .trim_end_matches('/')
+            .to_owned()
+    }
+
+    /// The URL the bucket can be publicly downloaded from
+    pub fn public_url(&self, bucket: &str) -> String {
+        let endpoint = self.endpoint();
+        self.public_url
+            .as_deref()
+            .unwrap_or("{endpoint}/{bucket}")
+            .replace("{bucket}", bucket)
+            .replace("{region}", &self.region)
+            .replace("{endpoint}", &endpoint)
+            .trim_end_matches('/')
+            .to_owned()
+    }
+}
+
+impl ApplyLayer for S3HostConfig {
+    type Layer = S3HostLayer;
+    fn apply_layer(
+        &mut self,
+        Self::Layer {
+            common,
+            bucket,
+            prefix,
+            endpoint,
+            region,
+            public_url,
+        }: Self::Layer,
+    ) {
+        self.common.apply_layer(common);
+        self.bucket.apply_opt(bucket);
+        self.prefix.apply_opt(prefix);
+        self.endpoint.apply_opt(endpoint);
+        self.region.apply_val(region);
+        self.public_url.apply_opt(public_url);
+    }
+}
+impl ApplyLayer for S3HostLayer {
+    type Layer = S3HostLayer;
+    fn apply_layer(
+        &mut self,
+        Self::Layer {
+            common,
+            bucket,
+            prefix,
+            endpoint,
+            region,
+            public_url,
+        }: Self::Layer,
+    ) {
+        self.common.apply_layer(common);
+        self.bucket.apply_opt(bucket);
+        self.prefix.apply_opt(prefix);
+        self.endpoint.apply_opt(endpoint);
+        self.region.apply_opt(region);
+        self.public_url.apply_opt(public_url);
+    }
+}
+
+impl std::ops::Deref for S3HostConfig {
+    type Target = CommonHostConfig;
+    fn deref(&self) -> &Self::Target {
+        &self.common
+    }
+}
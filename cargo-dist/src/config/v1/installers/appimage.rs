@@ -0,0 +1,109 @@
+//! appimage installer config
+
+use super::*;
+
+/// Options for appimage installer
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AppimageInstallerLayer {
+    /// Common options
+    #[serde(flatten)]
+    pub common: CommonInstallerLayer,
+
+    /// A png or svg icon for the app
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<Utf8PathBuf>,
+
+    /// A .desktop file to use instead of the generated one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desktop_file: Option<Utf8PathBuf>,
+
+    /// The categories to list the app under in the generated .desktop file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub categories: Option<Vec<String>>,
+
+    /// Whether the app should be run in a terminal (true for CLI apps)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terminal: Option<bool>,
+}
+/// Options for appimage installer
+#[derive(Debug, Default, Clone)]
+pub struct AppimageInstallerConfig {
+    /// Common options
+    pub common: CommonInstallerConfig,
+
+    /// A png or svg icon for the app
+    pub icon: Option<Utf8PathBuf>,
+
+    /// A .desktop file to use instead of the generated one
+    pub desktop_file: Option<Utf8PathBuf>,
+
+    /// The categories to list the app under in the generated .desktop file
+    pub categories: Vec<String>,
+
+    /// Whether the app should be run in a terminal (true for CLI apps)
+    pub terminal: bool,
+}
+
+impl AppimageInstallerConfig {
+    /// Get defaults for the given package
+    pub fn defaults_for_package(
+        _workspaces: &WorkspaceGraph,
+        _pkg_idx: PackageIdx,
+        common: &CommonInstallerConfig,
+    ) -> Self {
+        Self {
+            common: common.clone(),
+            icon: None,
+            desktop_file: None,
+            categories: vec!["Utility".to_owned()],
+            terminal: true,
+        }
+    }
+}
+
+impl ApplyLayer for AppimageInstallerConfig {
+    type Layer = AppimageInstallerLayer;
+    fn apply_layer(
+        &mut self,
+        Self::Layer {
+            common,
+            icon,
+            desktop_file,
+            categories,
+            terminal,
+        }: Self::Layer,
+    ) {
+        self.common.apply_layer(common);
+        self.icon.apply_opt(icon);
+        self.desktop_file.apply_opt(desktop_file);
+        self.categories.apply_val(categories);
+        self.terminal.apply_val(terminal);
+    }
+}
+impl ApplyLayer for AppimageInstallerLayer {
+    type Layer = AppimageInstallerLayer;
+    fn apply_layer(
+        &mut self,
+        Self::Layer {
+            common,
+            icon,
+            desktop_file,
+            categories,
+            terminal,
+        }: Self::Layer,
+    ) {
+        self.common.apply_layer(common);
+        self.icon.apply_opt(icon);
+        self.desktop_file.apply_opt(desktop_file);
+        self.categories.apply_opt(categories);
+        self.terminal.apply_opt(terminal);
+    }
+}
+
+impl std::ops::Deref for AppimageInstallerConfig {
+    type Target = CommonInstallerConfig;
+    fn deref(&self) -> &Self::Target {
+        &self.common
+    }
+}
@@ -0,0 +1,51 @@
+//! chocolatey installer config
+
+use super::*;
+
+/// Options for chocolatey installer
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ChocolateyInstallerLayer {
+    /// Common options
+    #[serde(flatten)]
+    pub common: CommonInstallerLayer,
+}
+/// Options for chocolatey installer
+#[derive(Debug, Default, Clone)]
+pub struct ChocolateyInstallerConfig {
+    /// Common options
+    pub common: CommonInstallerConfig,
+}
+
+impl ChocolateyInstallerConfig {
+    /// Get defaults for the given package
+    pub fn defaults_for_package(
+        _workspaces: &WorkspaceGraph,
+        _pkg_idx: PackageIdx,
+        common: &CommonInstallerConfig,
+    ) -> Self {
+        Self {
+            common: common.clone(),
+        }
+    }
+}
+
+impl ApplyLayer for ChocolateyInstallerConfig {
+    type Layer = ChocolateyInstallerLayer;
+    fn apply_layer(&mut self, Self::Layer { common }: Self::Layer) {
+        self.common.apply_layer(common);
+    }
+}
+impl ApplyLayer for ChocolateyInstallerLayer {
+    type Layer = ChocolateyInstallerLayer;
+    fn apply_layer(&mut self, Self::Layer { common }: Self::Layer) {
+        self.common.apply_layer(common);
+    }
+}
+
+impl std::ops::Deref for ChocolateyInstallerConfig {
+    type Target = CommonInstallerConfig;
+    fn deref(&self) -> &Self::Target {
+        &self.common
+    }
+}
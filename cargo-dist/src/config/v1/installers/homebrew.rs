@@ -15,6 +15,14 @@ pub struct HomebrewInstallerLayer {
     pub formula: Option<String>,
     /// Create version-specific Homebrew formulas
     pub version_formulas: Option<bool>,
+    /// A `test do` block to add to the formula, for `brew test`
+    pub test: Option<HomebrewTest>,
+    /// A `service do` block to add to the formula, for `brew services`
+    pub service: Option<HomebrewService>,
+    /// Text for Homebrew to show after installing the formula
+    pub caveats: Option<String>,
+    /// Repackage the archives as bottles, and list them in the formula
+    pub bottles: Option<bool>,
 }
 /// Options for homebrew installer
 #[derive(Debug, Default, Clone)]
@@ -27,6 +35,14 @@ pub struct HomebrewInstallerConfig {
     pub formula: Option<String>,
     /// Create version-specific Homebrew formulas
     pub version_formulas: Option<bool>,
+    /// A `test do` block to add to the formula, for `brew test`
+    pub test: Option<HomebrewTest>,
+    /// A `service do` block to add to the formula, for `brew services`
+    pub service: Option<HomebrewService>,
+    /// Text for Homebrew to show after installing the formula
+    pub caveats: Option<String>,
+    /// Repackage the archives as bottles, and list them in the formula
+    pub bottles: bool,
 }
 
 impl HomebrewInstallerConfig {
@@ -41,6 +57,10 @@ impl HomebrewInstallerConfig {
             tap: None,
             formula: None,
             version_formulas: None,
+            test: None,
+            service: None,
+            caveats: None,
+            bottles: false,
         }
     }
 }
@@ -54,12 +74,20 @@ impl ApplyLayer for HomebrewInstallerConfig {
             tap,
             formula,
             version_formulas,
+            test,
+            service,
+            caveats,
+            bottles,
         }: Self::Layer,
     ) {
         self.common.apply_layer(common);
         self.tap.apply_opt(tap);
         self.formula.apply_opt(formula);
         self.version_formulas.apply_opt(version_formulas);
+        self.test.apply_opt(test);
+        self.service.apply_opt(service);
+        self.caveats.apply_opt(caveats);
+        self.bottles.apply_val(bottles);
     }
 }
 impl ApplyLayer for HomebrewInstallerLayer {
@@ -71,15 +99,46 @@ impl ApplyLayer for HomebrewInstallerLayer {
             tap,
             formula,
             version_formulas,
+            test,
+            service,
+            caveats,
+            bottles,
         }: Self::Layer,
     ) {
         self.common.apply_layer(common);
         self.tap.apply_opt(tap);
         self.formula.apply_opt(formula);
         self.version_formulas.apply_opt(version_formulas);
+        self.test.apply_opt(test);
+        self.service.apply_opt(service);
+        self.caveats.apply_opt(caveats);
+        self.bottles.apply_opt(bottles);
     }
 }
 
+/// A `test do` block for a Homebrew formula
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct HomebrewTest {
+    /// The command to run, starting with one of the formula's binaries (e.g. "my-app --version")
+    pub command: String,
+    /// Text the command's output has to contain (`{version}` is replaced with the formula's version)
+    pub output: String,
+}
+
+/// A `service do` block for a Homebrew formula
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct HomebrewService {
+    /// The command to run, starting with one of the formula's binaries
+    pub run: Vec<String>,
+    /// Whether to restart the service whenever it exits
+    #[serde(default)]
+    pub keep_alive: bool,
+    /// The dir to run the service in
+    pub working_dir: Option<String>,
+}
+
 impl std::ops::Deref for HomebrewInstallerConfig {
     type Target = CommonInstallerConfig;
     fn deref(&self) -> &Self::Target {
@@ -1,20 +1,38 @@
 //! installer config
 
+pub mod appimage;
+pub mod aur;
+pub mod chocolatey;
+pub mod deb;
 pub mod homebrew;
 pub mod msi;
+pub mod nix;
 pub mod npm;
 pub mod pkg;
 pub mod powershell;
+pub mod pypi;
+pub mod rpm;
+pub mod scoop;
 pub mod shell;
+pub mod winget;
 
 use super::*;
 
+use appimage::*;
+use aur::*;
+use chocolatey::*;
+use deb::*;
 use homebrew::*;
 use msi::*;
+use nix::*;
 use npm::*;
 use pkg::*;
 use powershell::*;
+use pypi::*;
+use rpm::*;
+use scoop::*;
 use shell::*;
+use winget::*;
 
 /// workspace installer config (final)
 #[derive(Debug, Default, Clone)]
@@ -39,6 +57,24 @@ pub struct AppInstallerConfig {
     pub shell: Option<ShellInstallerConfig>,
     /// shell installer
     pub pkg: Option<PkgInstallerConfig>,
+    /// deb installer
+    pub deb: Option<DebInstallerConfig>,
+    /// rpm installer
+    pub rpm: Option<RpmInstallerConfig>,
+    /// scoop installer
+    pub scoop: Option<ScoopInstallerConfig>,
+    /// winget installer
+    pub winget: Option<WingetInstallerConfig>,
+    /// chocolatey installer
+    pub chocolatey: Option<ChocolateyInstallerConfig>,
+    /// aur installer
+    pub aur: Option<AurInstallerConfig>,
+    /// nix installer
+    pub nix: Option<NixInstallerConfig>,
+    /// pypi installer
+    pub pypi: Option<PypiInstallerConfig>,
+    /// appimage installer
+    pub appimage: Option<AppimageInstallerConfig>,
 }
 
 /// installer config (inheritance not yet applied)
@@ -58,6 +94,24 @@ pub struct InstallerConfigInheritable {
     pub shell: Option<ShellInstallerLayer>,
     /// pkg installer
     pub pkg: Option<PkgInstallerLayer>,
+    /// deb installer
+    pub deb: Option<DebInstallerLayer>,
+    /// rpm installer
+    pub rpm: Option<RpmInstallerLayer>,
+    /// scoop installer
+    pub scoop: Option<ScoopInstallerLayer>,
+    /// winget installer
+    pub winget: Option<WingetInstallerLayer>,
+    /// chocolatey installer
+    pub chocolatey: Option<ChocolateyInstallerLayer>,
+    /// aur installer
+    pub aur: Option<AurInstallerLayer>,
+    /// nix installer
+    pub nix: Option<NixInstallerLayer>,
+    /// pypi installer
+    pub pypi: Option<PypiInstallerLayer>,
+    /// appimage installer
+    pub appimage: Option<AppimageInstallerLayer>,
     /// Whether to install an updater program alongside the software
     pub updater: bool,
     /// Whether to always use the latest version instead of a fixed version
@@ -83,6 +137,24 @@ pub struct InstallerLayer {
     pub shell: Option<BoolOr<ShellInstallerLayer>>,
     /// pkg installer
     pub pkg: Option<BoolOr<PkgInstallerLayer>>,
+    /// deb installer
+    pub deb: Option<BoolOr<DebInstallerLayer>>,
+    /// rpm installer
+    pub rpm: Option<BoolOr<RpmInstallerLayer>>,
+    /// scoop installer
+    pub scoop: Option<BoolOr<ScoopInstallerLayer>>,
+    /// winget installer
+    pub winget: Option<BoolOr<WingetInstallerLayer>>,
+    /// chocolatey installer
+    pub chocolatey: Option<BoolOr<ChocolateyInstallerLayer>>,
+    /// aur installer
+    pub aur: Option<BoolOr<AurInstallerLayer>>,
+    /// nix installer
+    pub nix: Option<BoolOr<NixInstallerLayer>>,
+    /// pypi installer
+    pub pypi: Option<BoolOr<PypiInstallerLayer>>,
+    /// appimage installer
+    pub appimage: Option<BoolOr<AppimageInstallerLayer>>,
     /// Whether to install an updater program alongside the software
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updater: Option<bool>,
@@ -109,6 +181,15 @@ impl InstallerConfigInheritable {
             powershell: None,
             shell: None,
             pkg: None,
+            deb: None,
+            rpm: None,
+            scoop: None,
+            winget: None,
+            chocolatey: None,
+            aur: None,
+            nix: None,
+            pypi: None,
+            appimage: None,
             updater: false,
             always_use_latest_updater: false,
         }
@@ -130,6 +211,15 @@ impl InstallerConfigInheritable {
             powershell: _,
             shell: _,
             pkg: _,
+            deb: _,
+            rpm: _,
+            scoop: _,
+            winget: _,
+            chocolatey: _,
+            aur: _,
+            nix: _,
+            pypi: _,
+            appimage: _,
         } = self;
 
         WorkspaceInstallerConfig {
@@ -151,6 +241,15 @@ impl InstallerConfigInheritable {
             powershell,
             shell,
             pkg,
+            deb,
+            rpm,
+            scoop,
+            winget,
+            chocolatey,
+            aur,
+            nix,
+            pypi,
+            appimage,
             // global-only
             updater: _,
             always_use_latest_updater: _,
@@ -191,6 +290,60 @@ impl InstallerConfigInheritable {
             default.apply_layer(pkg);
             default
         });
+        let deb = deb.map(|deb| {
+            let mut default =
+                DebInstallerConfig::defaults_for_package(workspaces, pkg_idx, &common);
+            default.apply_layer(deb);
+            default
+        });
+        let rpm = rpm.map(|rpm| {
+            let mut default =
+                RpmInstallerConfig::defaults_for_package(workspaces, pkg_idx, &common);
+            default.apply_layer(rpm);
+            default
+        });
+        let scoop = scoop.map(|scoop| {
+            let mut default =
+                ScoopInstallerConfig::defaults_for_package(workspaces, pkg_idx, &common);
+            default.apply_layer(scoop);
+            default
+        });
+        let winget = winget.map(|winget| {
+            let mut default =
+                WingetInstallerConfig::defaults_for_package(workspaces, pkg_idx, &common);
+            default.apply_layer(winget);
+            default
+        });
+        let chocolatey = chocolatey.map(|chocolatey| {
+            let mut default =
+                ChocolateyInstallerConfig::defaults_for_package(workspaces, pkg_idx, &common);
+            default.apply_layer(chocolatey);
+            default
+        });
+        let aur = aur.map(|aur| {
+            let mut default =
+                AurInstallerConfig::defaults_for_package(workspaces, pkg_idx, &common);
+            default.apply_layer(aur);
+            default
+        });
+        let nix = nix.map(|nix| {
+            let mut default =
+                NixInstallerConfig::defaults_for_package(workspaces, pkg_idx, &common);
+            default.apply_layer(nix);
+            default
+        });
+        let pypi = pypi.map(|pypi| {
+            let mut default =
+                PypiInstallerConfig::defaults_for_package(workspaces, pkg_idx, &common);
+            default.apply_layer(pypi);
+            default
+        });
+        let appimage = appimage.map(|appimage| {
+            let mut default =
+                AppimageInstallerConfig::defaults_for_package(workspaces, pkg_idx, &common);
+            default.apply_layer(appimage);
+            default
+        });
         AppInstallerConfig {
             homebrew,
             msi,
@@ -198,6 +351,15 @@ impl InstallerConfigInheritable {
             powershell,
             shell,
             pkg,
+            deb,
+            rpm,
+            scoop,
+            winget,
+            chocolatey,
+            aur,
+            nix,
+            pypi,
+            appimage,
         }
     }
 }
@@ -213,6 +375,15 @@ impl ApplyLayer for InstallerConfigInheritable {
             powershell,
             shell,
             pkg,
+            deb,
+            rpm,
+            scoop,
+            winget,
+            chocolatey,
+            aur,
+            nix,
+            pypi,
+            appimage,
             updater,
             always_use_latest_updater,
         }: Self::Layer,
@@ -224,6 +395,15 @@ impl ApplyLayer for InstallerConfigInheritable {
         self.powershell.apply_bool_layer(powershell);
         self.shell.apply_bool_layer(shell);
         self.pkg.apply_bool_layer(pkg);
+        self.deb.apply_bool_layer(deb);
+        self.rpm.apply_bool_layer(rpm);
+        self.scoop.apply_bool_layer(scoop);
+        self.winget.apply_bool_layer(winget);
+        self.chocolatey.apply_bool_layer(chocolatey);
+        self.aur.apply_bool_layer(aur);
+        self.nix.apply_bool_layer(nix);
+        self.pypi.apply_bool_layer(pypi);
+        self.appimage.apply_bool_layer(appimage);
         self.updater.apply_val(updater);
         self.always_use_latest_updater
             .apply_val(always_use_latest_updater);
@@ -0,0 +1,58 @@
+//! nix installer config
+
+use super::*;
+
+/// Options for nix installer
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NixInstallerLayer {
+    /// Common options
+    #[serde(flatten)]
+    pub common: CommonInstallerLayer,
+    /// A GitHub repo to push the Nix derivation to, if built
+    pub repo: Option<String>,
+}
+/// Options for nix installer
+#[derive(Debug, Default, Clone)]
+pub struct NixInstallerConfig {
+    /// Common options
+    pub common: CommonInstallerConfig,
+    /// A GitHub repo to push the Nix derivation to, if built
+    pub repo: Option<String>,
+}
+
+impl NixInstallerConfig {
+    /// Get defaults for the given package
+    pub fn defaults_for_package(
+        _workspaces: &WorkspaceGraph,
+        _pkg_idx: PackageIdx,
+        common: &CommonInstallerConfig,
+    ) -> Self {
+        Self {
+            common: common.clone(),
+            repo: None,
+        }
+    }
+}
+
+impl ApplyLayer for NixInstallerConfig {
+    type Layer = NixInstallerLayer;
+    fn apply_layer(&mut self, Self::Layer { common, repo }: Self::Layer) {
+        self.common.apply_layer(common);
+        self.repo.apply_opt(repo);
+    }
+}
+impl ApplyLayer for NixInstallerLayer {
+    type Layer = NixInstallerLayer;
+    fn apply_layer(&mut self, Self::Layer { common, repo }: Self::Layer) {
+        self.common.apply_layer(common);
+        self.repo.apply_opt(repo);
+    }
+}
+
+impl std::ops::Deref for NixInstallerConfig {
+    type Target = CommonInstallerConfig;
+    fn deref(&self) -> &Self::Target {
+        &self.common
+    }
+}
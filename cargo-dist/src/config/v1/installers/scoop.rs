@@ -0,0 +1,58 @@
+//! scoop installer config
+
+use super::*;
+
+/// Options for scoop installer
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ScoopInstallerLayer {
+    /// Common options
+    #[serde(flatten)]
+    pub common: CommonInstallerLayer,
+    /// A Scoop bucket to push the Scoop manifest to, if built
+    pub bucket: Option<String>,
+}
+/// Options for scoop installer
+#[derive(Debug, Default, Clone)]
+pub struct ScoopInstallerConfig {
+    /// Common options
+    pub common: CommonInstallerConfig,
+    /// A Scoop bucket to push the Scoop manifest to, if built
+    pub bucket: Option<String>,
+}
+
+impl ScoopInstallerConfig {
+    /// Get defaults for the given package
+    pub fn defaults_for_package(
+        _workspaces: &WorkspaceGraph,
+        _pkg_idx: PackageIdx,
+        common: &CommonInstallerConfig,
+    ) -> Self {
+        Self {
+            common: common.clone(),
+            bucket: None,
+        }
+    }
+}
+
+impl ApplyLayer for ScoopInstallerConfig {
+    type Layer = ScoopInstallerLayer;
+    fn apply_layer(&mut self, Self::Layer { common, bucket }: Self::Layer) {
+        self.common.apply_layer(common);
+        self.bucket.apply_opt(bucket);
+    }
+}
+impl ApplyLayer for ScoopInstallerLayer {
+    type Layer = ScoopInstallerLayer;
+    fn apply_layer(&mut self, Self::Layer { common, bucket }: Self::Layer) {
+        self.common.apply_layer(common);
+        self.bucket.apply_opt(bucket);
+    }
+}
+
+impl std::ops::Deref for ScoopInstallerConfig {
+    type Target = CommonInstallerConfig;
+    fn deref(&self) -> &Self::Target {
+        &self.common
+    }
+}
@@ -0,0 +1,79 @@
+//! winget installer config
+
+use super::*;
+
+/// Options for winget installer
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct WingetInstallerLayer {
+    /// Common options
+    #[serde(flatten)]
+    pub common: CommonInstallerLayer,
+    /// The winget package identifier (e.g. "Publisher.App")
+    pub identifier: Option<String>,
+    /// A fork of winget-pkgs to open pull requests from, if built
+    pub fork: Option<String>,
+}
+/// Options for winget installer
+#[derive(Debug, Default, Clone)]
+pub struct WingetInstallerConfig {
+    /// Common options
+    pub common: CommonInstallerConfig,
+    /// The winget package identifier (e.g. "Publisher.App")
+    pub identifier: Option<String>,
+    /// A fork of winget-pkgs to open pull requests from, if built
+    pub fork: Option<String>,
+}
+
+impl WingetInstallerConfig {
+    /// Get defaults for the given package
+    pub fn defaults_for_package(
+        _workspaces: &WorkspaceGraph,
+        _pkg_idx: PackageIdx,
+        common: &CommonInstallerConfig,
+    ) -> Self {
+        Self {
+            common: common.clone(),
+            identifier: None,
+            fork: None,
+        }
+    }
+}
+
+impl ApplyLayer for WingetInstallerConfig {
+    type Layer = WingetInstallerLayer;
+    fn apply_layer(
+        &mut self,
+        Self::Layer {
+            common,
+            identifier,
+            fork,
+        }: Self::Layer,
+    ) {
+        self.common.apply_layer(common);
+        self.identifier.apply_opt(identifier);
+        self.fork.apply_opt(fork);
+    }
+}
+impl ApplyLayer for WingetInstallerLayer {
+    type Layer = WingetInstallerLayer;
+    fn apply_layer(
+        &mut self,
+        Self::Layer {
+            common,
+            identifier,
+            fork,
+        }: Self::Layer,
+    ) {
+        self.common.apply_layer(common);
+        self.identifier.apply_opt(identifier);
+        self.fork.apply_opt(fork);
+    }
+}
+
+impl std::ops::Deref for WingetInstallerConfig {
+    type Target = CommonInstallerConfig;
+    fn deref(&self) -> &Self::Target {
+        &self.common
+    }
+}
@@ -505,6 +505,11 @@ impl TomlLayer {
                 }
             }
         }
+        if let Some(builds) = &mut self.builds {
+            if let Some(signing) = &mut builds.signing {
+                signing.make_relative_to(base_path);
+            }
+        }
         if let Some(hosts) = &mut self.hosts {
             if let Some(BoolOr::Val(github)) = &mut hosts.github {
                 if let Some(path) = &mut github.submodule_path {
@@ -512,15 +517,28 @@ impl TomlLayer {
                 }
             }
         }
+        if let Some(installers) = &mut self.installers {
+            if let Some(BoolOr::Val(appimage)) = &mut installers.appimage {
+                if let Some(path) = &mut appimage.icon {
+                    make_path_relative_to(path, base_path);
+                }
+                if let Some(path) = &mut appimage.desktop_file {
+                    make_path_relative_to(path, base_path);
+                }
+            }
+        }
     }
 
     /// Determines whether the configured install paths are compatible with each other
     pub fn validate_install_paths(&self) -> DistResult<()> {
         if let Some(installers) = &self.installers {
             if let Some(paths) = &installers.common.install_path {
-                if paths.len() > 1 && paths.contains(&InstallPathStrategy::CargoHome) {
+                // System install paths are only used with --system, so they don't conflict
+                let user_paths = paths.iter().filter(|p| !p.is_system()).count();
+                if user_paths > 1 && paths.contains(&InstallPathStrategy::CargoHome) {
                     return Err(DistError::IncompatibleInstallPathConfiguration {});
                 }
+                InstallPathStrategy::validate_system_paths(paths)?;
             }
         }
 
@@ -572,6 +590,24 @@ impl TomlLayer {
             if artifacts.source_tarball.is_some() {
                 Self::merge_warn("artifacts.source-tarball", package_manifest_path);
             }
+            if artifacts.offline_bundle.is_some() {
+                Self::merge_warn("artifacts.offline-bundle", package_manifest_path);
+            }
+            if artifacts.minisign_public_key.is_some() {
+                Self::merge_warn("artifacts.minisign-public-key", package_manifest_path);
+            }
+            if artifacts.signatures.is_some() {
+                Self::merge_warn("artifacts.signatures", package_manifest_path);
+            }
+            if artifacts.sigstore.is_some() {
+                Self::merge_warn("artifacts.sigstore", package_manifest_path);
+            }
+            if artifacts.provenance.is_some() {
+                Self::merge_warn("artifacts.provenance", package_manifest_path);
+            }
+            if artifacts.sbom.is_some() {
+                Self::merge_warn("artifacts.sbom", package_manifest_path);
+            }
         }
 
         if let Some(builds) = builds {
@@ -585,6 +621,10 @@ impl TomlLayer {
                 }
             }
 
+            if builds.signing.is_some() {
+                Self::merge_warn("builds.signing", package_manifest_path);
+            }
+
             if let Some(BoolOr::Val(cargo)) = &builds.cargo {
                 if cargo.features.is_some() {
                     Self::merge_warn("cargo.features", package_manifest_path);
@@ -0,0 +1,62 @@
+//! AUR publisher config
+
+use super::*;
+
+/// The default git remote base to push AUR packages to
+pub const DEFAULT_AUR_REMOTE: &str = "ssh://aur@aur.archlinux.org";
+
+/// Options for AUR publishes
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AurPublisherLayer {
+    /// Common options
+    #[serde(flatten)]
+    pub common: CommonPublisherLayer,
+    /// The git remote base to push packages to (`/{pkgname}.git` is appended)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+}
+/// Options for AUR publishes
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AurPublisherConfig {
+    /// Common options
+    pub common: CommonPublisherConfig,
+    /// The git remote base to push packages to (`/{pkgname}.git` is appended)
+    pub remote: String,
+}
+
+impl AurPublisherConfig {
+    /// Get defaults for the given package
+    pub fn defaults_for_package(
+        _workspaces: &WorkspaceGraph,
+        _pkg_idx: PackageIdx,
+        common: &CommonPublisherConfig,
+    ) -> Self {
+        Self {
+            common: common.clone(),
+            remote: DEFAULT_AUR_REMOTE.to_owned(),
+        }
+    }
+}
+
+impl ApplyLayer for AurPublisherConfig {
+    type Layer = AurPublisherLayer;
+    fn apply_layer(&mut self, Self::Layer { common, remote }: Self::Layer) {
+        self.common.apply_layer(common);
+        self.remote.apply_val(remote);
+    }
+}
+impl ApplyLayer for AurPublisherLayer {
+    type Layer = AurPublisherLayer;
+    fn apply_layer(&mut self, Self::Layer { common, remote }: Self::Layer) {
+        self.common.apply_layer(common);
+        self.remote.apply_opt(remote);
+    }
+}
+
+impl std::ops::Deref for AurPublisherConfig {
+    type Target = CommonPublisherConfig;
+    fn deref(&self) -> &Self::Target {
+        &self.common
+    }
+}
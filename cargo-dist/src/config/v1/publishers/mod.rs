@@ -1,12 +1,22 @@
 //! publisher config
 
+pub mod aur;
 pub mod homebrew;
+pub mod nix;
 pub mod npm;
+pub mod pypi;
+pub mod scoop;
+pub mod winget;
 
 use super::*;
 
+use aur::*;
 use homebrew::*;
+use nix::*;
 use npm::*;
+use pypi::*;
+use scoop::*;
+use winget::*;
 
 /// the final publisher config
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -15,6 +25,16 @@ pub struct PublisherConfig {
     pub homebrew: Option<HomebrewPublisherConfig>,
     /// npm publisher
     pub npm: Option<NpmPublisherConfig>,
+    /// scoop publisher
+    pub scoop: Option<ScoopPublisherConfig>,
+    /// winget publisher
+    pub winget: Option<WingetPublisherConfig>,
+    /// aur publisher
+    pub aur: Option<AurPublisherConfig>,
+    /// nix publisher
+    pub nix: Option<NixPublisherConfig>,
+    /// pypi publisher
+    pub pypi: Option<PypiPublisherConfig>,
 }
 
 /// the publisher config
@@ -28,6 +48,16 @@ pub struct PublisherConfigInheritable {
     pub homebrew: Option<HomebrewPublisherLayer>,
     /// npm publisher
     pub npm: Option<NpmPublisherLayer>,
+    /// scoop publisher
+    pub scoop: Option<ScoopPublisherLayer>,
+    /// winget publisher
+    pub winget: Option<WingetPublisherLayer>,
+    /// aur publisher
+    pub aur: Option<AurPublisherLayer>,
+    /// nix publisher
+    pub nix: Option<NixPublisherLayer>,
+    /// pypi publisher
+    pub pypi: Option<PypiPublisherLayer>,
 }
 
 /// "raw" publisher config from presum
@@ -40,6 +70,16 @@ pub struct PublisherLayer {
     pub homebrew: Option<BoolOr<HomebrewPublisherLayer>>,
     /// npm publisher
     pub npm: Option<BoolOr<NpmPublisherLayer>>,
+    /// scoop publisher
+    pub scoop: Option<BoolOr<ScoopPublisherLayer>>,
+    /// winget publisher
+    pub winget: Option<BoolOr<WingetPublisherLayer>>,
+    /// aur publisher
+    pub aur: Option<BoolOr<AurPublisherLayer>>,
+    /// nix publisher
+    pub nix: Option<BoolOr<NixPublisherLayer>>,
+    /// pypi publisher
+    pub pypi: Option<BoolOr<PypiPublisherLayer>>,
 }
 impl PublisherConfigInheritable {
     /// get the defaults for a given package
@@ -48,6 +88,11 @@ impl PublisherConfigInheritable {
             common: CommonPublisherConfig::defaults_for_package(workspaces, pkg_idx),
             homebrew: None,
             npm: None,
+            scoop: None,
+            winget: None,
+            aur: None,
+            nix: None,
+            pypi: None,
         }
     }
     /// fold the inherited fields in to get the final publisher config
@@ -60,6 +105,11 @@ impl PublisherConfigInheritable {
             common,
             homebrew,
             npm,
+            scoop,
+            winget,
+            aur,
+            nix,
+            pypi,
         } = self;
         let homebrew = homebrew.map(|homebrew| {
             let mut default =
@@ -73,7 +123,45 @@ impl PublisherConfigInheritable {
             default.apply_layer(npm);
             default
         });
-        PublisherConfig { homebrew, npm }
+        let scoop = scoop.map(|scoop| {
+            let mut default =
+                ScoopPublisherConfig::defaults_for_package(workspaces, pkg_idx, &common);
+            default.apply_layer(scoop);
+            default
+        });
+        let winget = winget.map(|winget| {
+            let mut default =
+                WingetPublisherConfig::defaults_for_package(workspaces, pkg_idx, &common);
+            default.apply_layer(winget);
+            default
+        });
+        let aur = aur.map(|aur| {
+            let mut default =
+                AurPublisherConfig::defaults_for_package(workspaces, pkg_idx, &common);
+            default.apply_layer(aur);
+            default
+        });
+        let nix = nix.map(|nix| {
+            let mut default =
+                NixPublisherConfig::defaults_for_package(workspaces, pkg_idx, &common);
+            default.apply_layer(nix);
+            default
+        });
+        let pypi = pypi.map(|pypi| {
+            let mut default =
+                PypiPublisherConfig::defaults_for_package(workspaces, pkg_idx, &common);
+            default.apply_layer(pypi);
+            default
+        });
+        PublisherConfig {
+            homebrew,
+            npm,
+            scoop,
+            winget,
+            aur,
+            nix,
+            pypi,
+        }
     }
 }
 impl ApplyLayer for PublisherConfigInheritable {
@@ -84,11 +172,21 @@ impl ApplyLayer for PublisherConfigInheritable {
             common,
             homebrew,
             npm,
+            scoop,
+            winget,
+            aur,
+            nix,
+            pypi,
         }: Self::Layer,
     ) {
         self.common.apply_layer(common);
         self.homebrew.apply_bool_layer(homebrew);
         self.npm.apply_bool_layer(npm);
+        self.scoop.apply_bool_layer(scoop);
+        self.winget.apply_bool_layer(winget);
+        self.aur.apply_bool_layer(aur);
+        self.nix.apply_bool_layer(nix);
+        self.pypi.apply_bool_layer(pypi);
     }
 }
 
@@ -0,0 +1,49 @@
+//! nix publisher config
+
+use super::*;
+
+/// Options for nix publishes
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct NixPublisherLayer {
+    /// Common options
+    pub common: CommonPublisherLayer,
+}
+/// Options for nix publishes
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NixPublisherConfig {
+    /// Common options
+    pub common: CommonPublisherConfig,
+}
+
+impl NixPublisherConfig {
+    /// Get defaults for the given package
+    pub fn defaults_for_package(
+        _workspaces: &WorkspaceGraph,
+        _pkg_idx: PackageIdx,
+        common: &CommonPublisherConfig,
+    ) -> Self {
+        Self {
+            common: common.clone(),
+        }
+    }
+}
+
+impl ApplyLayer for NixPublisherConfig {
+    type Layer = NixPublisherLayer;
+    fn apply_layer(&mut self, Self::Layer { common }: Self::Layer) {
+        self.common.apply_layer(common);
+    }
+}
+impl ApplyLayer for NixPublisherLayer {
+    type Layer = NixPublisherLayer;
+    fn apply_layer(&mut self, Self::Layer { common }: Self::Layer) {
+        self.common.apply_layer(common);
+    }
+}
+
+impl std::ops::Deref for NixPublisherConfig {
+    type Target = CommonPublisherConfig;
+    fn deref(&self) -> &Self::Target {
+        &self.common
+    }
+}
@@ -0,0 +1,49 @@
+//! winget publisher config
+
+use super::*;
+
+/// Options for winget publishes
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct WingetPublisherLayer {
+    /// Common options
+    pub common: CommonPublisherLayer,
+}
+/// Options for winget publishes
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WingetPublisherConfig {
+    /// Common options
+    pub common: CommonPublisherConfig,
+}
+
+impl WingetPublisherConfig {
+    /// Get defaults for the given package
+    pub fn defaults_for_package(
+        _workspaces: &WorkspaceGraph,
+        _pkg_idx: PackageIdx,
+        common: &CommonPublisherConfig,
+    ) -> Self {
+        Self {
+            common: common.clone(),
+        }
+    }
+}
+
+impl ApplyLayer for WingetPublisherConfig {
+    type Layer = WingetPublisherLayer;
+    fn apply_layer(&mut self, Self::Layer { common }: Self::Layer) {
+        self.common.apply_layer(common);
+    }
+}
+impl ApplyLayer for WingetPublisherLayer {
+    type Layer = WingetPublisherLayer;
+    fn apply_layer(&mut self, Self::Layer { common }: Self::Layer) {
+        self.common.apply_layer(common);
+    }
+}
+
+impl std::ops::Deref for WingetPublisherConfig {
+    type Target = CommonPublisherConfig;
+    fn deref(&self) -> &Self::Target {
+        &self.common
+    }
+}
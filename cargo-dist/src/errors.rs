@@ -45,6 +45,10 @@ pub enum DistError {
     #[diagnostic(transparent)]
     Project(#[from] axoproject::errors::ProjectError),
 
+    /// random reqwest error
+    #[error(transparent)]
+    Reqwest(#[from] axoasset::reqwest::Error),
+
     /// random string error
     #[error(transparent)]
     FromUtf8Error(#[from] std::string::FromUtf8Error),
@@ -53,6 +57,10 @@ pub enum DistError {
     #[error(transparent)]
     Goblin(#[from] goblin::error::Error),
 
+    /// random zip error
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+
     /// random camino conversion error
     #[error(transparent)]
     FromPathBufError(#[from] camino::FromPathBufError),
@@ -164,6 +172,78 @@ pub enum DistError {
         inner: AxoprojectError,
     },
 
+    /// We got a repository URL but couldn't interpret it as a GitLab project
+    #[error("GitLab hosting requires a GitLab repository")]
+    CantEnableGitlabUrlNotGitlab {
+        /// inner error that caught this
+        #[diagnostic_source]
+        inner: AxoprojectError,
+    },
+
+    /// GitLab hosting was requested for a repository that isn't on GitLab
+    #[error("GitLab hosting needs to know which GitLab instance to use, but your repository isn't on GitLab: {url}")]
+    #[diagnostic(help("set gitlab-api-url to the API of the GitLab instance to upload to (e.g. \"https://gitlab.com/api/v4\")"))]
+    CantEnableGitlabNoApiUrl {
+        /// the repository url
+        url: String,
+    },
+
+    /// We were asked to make a GitLab Release for a manifest that isn't announcing anything
+    #[error("can't create a GitLab Release because the dist-manifest has no announcement tag")]
+    #[diagnostic(help("make sure the manifest comes from dist plan or dist build with a tag"))]
+    GitlabReleaseNoTag {},
+
+    /// We need to talk to the GitLab API but don't have any credentials
+    #[error("GitLab hosting needs a token to talk to the GitLab API")]
+    #[diagnostic(help(
+        "set GITLAB_TOKEN to a token with the api scope, or run this in GitLab CI so CI_JOB_TOKEN is available"
+    ))]
+    GitlabNoToken {},
+
+    /// The GitLab API rejected a request
+    #[error("GitLab API request to {url} failed with status {status}")]
+    #[diagnostic(help("the response was: {body}"))]
+    GitlabApiError {
+        /// The URL we requested
+        url: String,
+        /// The HTTP status we got back
+        status: u16,
+        /// The body of the response
+        body: String,
+    },
+
+    /// S3 hosting was enabled without saying where to put things
+    #[error("S3 hosting is enabled, but no bucket was specified")]
+    #[diagnostic(help("set s3-config.bucket in your dist config"))]
+    S3NoBucket {},
+
+    /// We need to talk to an S3 API but don't have any credentials
+    #[error("S3 hosting needs credentials to upload to the bucket")]
+    #[diagnostic(help("set AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY (and AWS_SESSION_TOKEN if your credentials are temporary)"))]
+    S3NoCredentials {},
+
+    /// The configured S3 endpoint isn't a usable URL
+    #[error("S3 endpoint {endpoint} isn't a valid URL")]
+    #[diagnostic(help(
+        "set s3-config.endpoint to something like https://s3.us-east-1.amazonaws.com"
+    ))]
+    S3BadEndpoint {
+        /// The endpoint we were given
+        endpoint: String,
+    },
+
+    /// The S3 API rejected a request
+    #[error("S3 request to {url} failed with status {status}")]
+    #[diagnostic(help("the response was: {body}"))]
+    S3ApiError {
+        /// The URL we requested
+        url: String,
+        /// The HTTP status we got back
+        status: u16,
+        /// The body of the response
+        body: String,
+    },
+
     /// User supplied an illegal npm scope
     #[error("The npm-scope field must be an all-lowercase value; the supplied value was {scope}")]
     ScopeMustBeLowercase {
@@ -175,7 +255,7 @@ pub enum DistError {
     ///
     /// NOTE: we can't use `diagnostic(help)` here because this will get crammed into
     /// a serde_json error, reducing it to a String. So we inline the help!
-    #[error(r#"install-path = "{path}" has an unknown format (it can either be "CARGO_HOME", "~/subdir/", or "$ENV_VAR/subdir/")"#)]
+    #[error(r#"install-path = "{path}" has an unknown format (it can either be "CARGO_HOME", "~/subdir/", "$ENV_VAR/subdir/", or an absolute path)"#)]
     InstallPathInvalid {
         /// The full value passed to install-path
         path: String,
@@ -201,6 +281,18 @@ pub enum DistError {
         path: String,
     },
 
+    /// Two absolute install-paths for the same platform, so the second would never be used
+    #[error(r#"install-path has both "{first}" and "{second}" for system-wide installs"#)]
+    #[diagnostic(help(
+        "only the first absolute path for each platform is used, so remove the other"
+    ))]
+    InstallPathMultipleSystem {
+        /// The absolute path that would be used
+        first: String,
+        /// The absolute path that would be ignored
+        second: String,
+    },
+
     /// explicitly requested workspace builds, but had packages with custom feature settings
     #[error("precise-builds = false was set, but some packages have custom build features, making it impossible")]
     #[diagnostic(help("these packages customized either features, no-default-features, or all-features:\n{packages:#?}"))]
@@ -217,6 +309,30 @@ pub enum DistError {
         packages: Vec<camino::Utf8PathBuf>,
     },
 
+    /// packages disagreed on scoop buckets
+    #[error("different scoop buckets were set in your workspace, this is currently unsupported")]
+    #[diagnostic(help("these packages disagree:\n{packages:#?}"))]
+    MismatchedScoopBuckets {
+        /// paths of problem manifests
+        packages: Vec<camino::Utf8PathBuf>,
+    },
+
+    /// packages disagreed on winget forks
+    #[error("different winget forks were set in your workspace, this is currently unsupported")]
+    #[diagnostic(help("these packages disagree:\n{packages:#?}"))]
+    MismatchedWingetForks {
+        /// paths of problem manifests
+        packages: Vec<camino::Utf8PathBuf>,
+    },
+
+    /// packages disagreed on nix repos
+    #[error("different nix repos were set in your workspace, this is currently unsupported")]
+    #[diagnostic(help("these packages disagree:\n{packages:#?}"))]
+    MismatchedNixRepos {
+        /// paths of problem manifests
+        packages: Vec<camino::Utf8PathBuf>,
+    },
+
     /// packages disagreed on publishers
     #[error("different publisher settings were in your workspace, this is currently unuspported")]
     #[diagnostic(help("these packages disagree:\n{packages:#?}"))]
@@ -605,6 +721,118 @@ pub enum DistError {
     #[diagnostic(help("Is the value of this environment variable valid base64?"))]
     CertificateDecodeError {},
 
+    /// Failure to decode a base64-encoded PKCS#12 file
+    #[error("We failed to decode the PKCS#12 file stored in the OSSLSIGNCODE_PKCS12 environment variable.")]
+    #[diagnostic(help("Is the value of this environment variable valid base64?"))]
+    Pkcs12DecodeError {},
+
+    /// A minisign key couldn't be parsed
+    #[error("The minisign {kind} isn't valid: {reason}")]
+    #[diagnostic(help(
+        "minisign-public-key should be the second line of the .pub file generated by `minisign -G`, and MINISIGN_SECRET_KEY the contents of the .key file"
+    ))]
+    MinisignInvalidKey {
+        /// Which key this was
+        kind: String,
+        /// What was wrong with it
+        reason: String,
+    },
+
+    /// The minisign secret key is encrypted but there's no password
+    #[error(
+        "The minisign secret key in MINISIGN_SECRET_KEY is encrypted, but no password was provided"
+    )]
+    #[diagnostic(help("Set MINISIGN_SECRET_KEY_PASSWORD to the key's password"))]
+    MinisignPasswordMissing {},
+
+    /// The minisign secret key couldn't be decrypted
+    #[error("We failed to decrypt the minisign secret key in MINISIGN_SECRET_KEY")]
+    #[diagnostic(help("Is MINISIGN_SECRET_KEY_PASSWORD set to the key's password?"))]
+    MinisignWrongPassword {},
+
+    /// The minisign secret key doesn't go with the configured public key
+    #[error("The minisign secret key in MINISIGN_SECRET_KEY (key id {secret_key_id}) doesn't match minisign-public-key (key id {public_key_id})")]
+    #[diagnostic(help(
+        "The installers will only accept signatures from the key in your config, so these need to be a pair"
+    ))]
+    MinisignKeyMismatch {
+        /// id of the configured public key
+        public_key_id: String,
+        /// id of the secret key from the environment
+        secret_key_id: String,
+    },
+
+    /// The installers can't check signed checksums of this kind
+    #[error("minisign-public-key requires sha256 or sha512 checksums, but checksum is set to {checksum}")]
+    #[diagnostic(help(
+        "The powershell installer can only verify sha256 and sha512 checksums, please pick one of those"
+    ))]
+    MinisignUnsupportedChecksum {
+        /// The configured checksum style
+        checksum: crate::config::ChecksumStyle,
+    },
+
+    /// The signing rules refer to a provider that doesn't exist
+    #[error("signing.{kind} is set to {name}, but there's no signing provider with that name")]
+    #[diagnostic(help(
+        "Add it under signing.providers, or use one of the built-in ssldotcom or macos providers"
+    ))]
+    UnknownSigningProvider {
+        /// The kind of file the provider was going to sign
+        kind: crate::config::SigningKind,
+        /// The name of the provider
+        name: String,
+    },
+
+    /// A signing provider needs a secret from the environment
+    #[error("{provider} signing requires {var} to be set")]
+    #[diagnostic(help("This should be one of your CI secrets"))]
+    SigningSecretMissing {
+        /// The kind of provider
+        provider: String,
+        /// The env var it needs
+        var: String,
+    },
+
+    /// Sigstore signing failed
+    #[error("Sigstore signing failed: {reason}")]
+    #[diagnostic(help(
+        "Sigstore signing needs an OIDC token in SIGSTORE_ID_TOKEN, or a GitHub Actions job with the id-token: write permission"
+    ))]
+    SigstoreError {
+        /// What went wrong
+        reason: String,
+    },
+
+    /// A Sigstore service rejected a request
+    #[error("Sigstore request to {url} failed with status {status}")]
+    #[diagnostic(help("the response was: {body}"))]
+    SigstoreApiError {
+        /// The URL we requested
+        url: String,
+        /// The HTTP status we got back
+        status: u16,
+        /// The body of the response
+        body: String,
+    },
+
+    /// A custom signing command was empty
+    #[error("signing provider {name} has an empty command")]
+    SigningCommandEmpty {
+        /// The name of the provider
+        name: String,
+    },
+
+    /// cargo metadata failed while generating an SBOM
+    #[error("failed to get the dependencies of {manifest_path} for its SBOM")]
+    SbomCargoMetadata {
+        /// The Cargo.toml of the package
+        manifest_path: Utf8PathBuf,
+        /// The underlying error
+        #[source]
+        details: cargo_metadata::Error,
+    },
+
     /// Missing configuration for a .pkg
     #[error("A Mac .pkg installer was requested, but the config is missing")]
     #[diagnostic(help("Please ensure a dist.mac-pkg-config section is present in your config. For more details see: https://example.com"))]
@@ -615,6 +843,76 @@ pub enum DistError {
     #[diagnostic(help("Please either enter a bundle identifier, or disable the Mac .pkg"))]
     MacPkgBundleIdentifierMissing {},
 
+    /// No maintainer for a .deb
+    #[error("A .deb installer was requested for {package}, but it has no maintainer")]
+    #[diagnostic(help(
+        "Please add `authors = [...]` to your Cargo.toml, the first entry is used as the maintainer"
+    ))]
+    DebMaintainerMissing {
+        /// The package
+        package: String,
+    },
+
+    /// No authors for a Chocolatey package
+    #[error("A Chocolatey package was requested for {package}, but it has no authors")]
+    #[diagnostic(help("Please add `authors = [...]` to your Cargo.toml"))]
+    ChocolateyAuthorsMissing {
+        /// The package
+        package: String,
+    },
+
+    /// No publisher for winget manifests
+    #[error("winget manifests were requested for {package}, but it has no publisher")]
+    #[diagnostic(help(
+        "Please add `authors = [...]` to your Cargo.toml, the first entry is used as the publisher"
+    ))]
+    WingetPublisherMissing {
+        /// The package
+        package: String,
+    },
+
+    /// No license for winget manifests
+    #[error("winget manifests were requested for {package}, but it has no license")]
+    #[diagnostic(help("Please add `license = \"...\"` to your Cargo.toml"))]
+    WingetLicenseMissing {
+        /// The package
+        package: String,
+    },
+
+    /// winget needs the sha256 of everything it installs
+    #[error("winget manifests can't be written because {artifact} has no sha256 checksum")]
+    #[diagnostic(help(
+        "winget only accepts sha256 checksums, and this artifact wasn't given one"
+    ))]
+    WingetNoSha256 {
+        /// The artifact
+        artifact: String,
+    },
+
+    /// Chocolatey needs the hash of every archive its install script downloads
+    #[error(
+        "a Chocolatey package can't be written because {artifact} has no sha256 or sha512 checksum"
+    )]
+    #[diagnostic(help(
+        "Chocolatey only verifies sha256 and sha512 checksums, so set `checksum = \"sha256\"` or `checksum = \"sha512\"`"
+    ))]
+    ChocolateyNoChecksum {
+        /// The archive
+        artifact: String,
+    },
+
+    /// Nix needs the hash of every archive a derivation fetches
+    #[error(
+        "a Nix derivation can't be written because {artifact} has no sha256 or sha512 checksum"
+    )]
+    #[diagnostic(help(
+        "the derivation needs the archives' checksums, so build them first (or merge in their dist-manifests)"
+    ))]
+    NixNoChecksum {
+        /// The archive
+        artifact: String,
+    },
+
     /// Project depends on a too-old axoupdater
     #[error("Your project ({package_name}) uses axoupdater as a library, but the version specified ({your_version}) is older than the minimum supported version ({minimum}). (The dependency comes via {source_name} in the dependency tree.)")]
     #[diagnostic(help(
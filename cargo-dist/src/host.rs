@@ -1,5 +1,8 @@
 //! Details for hosting artifacts
 
+pub mod gitlab;
+pub mod s3;
+
 use crate::{
     announce::{announcement_axodotdev, announcement_github, AnnouncementTag},
     check_integrity,
@@ -10,12 +13,16 @@ use crate::{
     errors::DistResult,
     gather_work,
     manifest::save_manifest,
-    net::create_gazenot_client,
+    net::{create_gazenot_client, create_gitlab_client, create_s3_client},
     DistError, DistGraph, DistGraphBuilder, HostingInfo,
 };
 use axoproject::WorkspaceGraph;
-use dist_schema::{ArtifactIdRef, DistManifest, Hosting};
+use dist_schema::{ArtifactIdRef, DistManifest, GitlabHosting, Hosting, S3Hosting};
 use gazenot::{AnnouncementKey, Gazenot};
+use gitlab::{encode_project, GitlabClient, GitlabRelease, GitlabReleaseAssets, GitlabReleaseLink};
+use s3::{S3Bucket, S3Client};
+
+const DIST_MANIFEST_ARTIFACT_ID: &ArtifactIdRef = ArtifactIdRef::from_str("dist-manifest.json");
 
 /// Do hosting
 pub fn do_host(cfg: &Config, host_args: HostArgs) -> DistResult<DistManifest> {
@@ -63,6 +70,32 @@ pub fn do_host(cfg: &Config, host_args: HostArgs) -> DistResult<DistManifest> {
                 HostingStyle::Github => {
                     // implemented in CI backend
                 }
+                HostingStyle::Gitlab => {
+                    // There's nothing to create ahead of time: generic packages spring into
+                    // existence when the first file is uploaded to them.
+                    let upload = host_args.steps.contains(&HostStyle::Upload);
+                    let release = host_args.steps.contains(&HostStyle::Release);
+                    if upload || release {
+                        let gitlab = create_gitlab_client(&dist.client_settings)?;
+                        if upload {
+                            // pre-save the hosting info so that it will be found on the FS to upload.
+                            save_manifest(&dist.dist_dir.join("dist-manifest.json"), &manifest)?;
+                            upload_to_gitlab(&dist, &manifest, &gitlab)?;
+                        }
+                        if release {
+                            release_gitlab(&dist, &manifest, &gitlab)?;
+                        }
+                    }
+                }
+                HostingStyle::S3 => {
+                    // A bucket has no notion of releases, uploading is all there is
+                    if host_args.steps.contains(&HostStyle::Upload) {
+                        let s3 = create_s3_client(&dist.client_settings)?;
+                        // pre-save the hosting info so that it will be found on the FS to upload.
+                        save_manifest(&dist.dist_dir.join("dist-manifest.json"), &manifest)?;
+                        upload_to_s3(&dist, &manifest, &s3)?;
+                    }
+                }
             }
         }
     }
@@ -81,10 +114,13 @@ impl<'a> DistGraphBuilder<'a> {
     ) -> DistResult<()> {
         let mut ci = vec![];
         {
-            let CiConfig { github } = &self.inner.config.ci;
+            let CiConfig { github, gitlab } = &self.inner.config.ci;
             if github.is_some() {
                 ci.push(CiStyle::Github);
             }
+            if gitlab.is_some() {
+                ci.push(CiStyle::Gitlab);
+            }
         }
 
         let mut hosting = vec![];
@@ -92,6 +128,8 @@ impl<'a> DistGraphBuilder<'a> {
             let WorkspaceHostConfig {
                 github,
                 axodotdev,
+                gitlab,
+                s3,
                 force_latest: _,
             } = &self.inner.config.hosts;
             if github.is_some() {
@@ -100,13 +138,32 @@ impl<'a> DistGraphBuilder<'a> {
             if axodotdev.is_some() {
                 hosting.push(HostingStyle::Axodotdev);
             }
+            if gitlab.is_some() {
+                hosting.push(HostingStyle::Gitlab);
+            }
+            if s3.is_some() {
+                hosting.push(HostingStyle::S3);
+            }
         }
         let hosting = if hosting.is_empty() {
             None
         } else {
             Some(hosting)
         };
-        self.inner.hosting = select_hosting(self.workspaces, announcing, hosting, Some(&ci))?;
+        let gitlab_api_url = self
+            .inner
+            .config
+            .hosts
+            .gitlab
+            .as_ref()
+            .and_then(|gitlab| gitlab.api_url.as_deref());
+        self.inner.hosting = select_hosting(
+            self.workspaces,
+            announcing,
+            hosting,
+            Some(&ci),
+            gitlab_api_url,
+        )?;
         // If we don't think we can host things, don't bother
         let Some(hosting) = &self.inner.hosting else {
             return Ok(());
@@ -198,6 +255,60 @@ impl<'a> DistGraphBuilder<'a> {
                         })
                     }
                 }
+                HostingStyle::Gitlab => {
+                    // Artifacts go in a generic package named after the app, so all we need to
+                    // know is the URL to download from
+                    let Some(api_url) = &hosting.gitlab_api_url else {
+                        continue;
+                    };
+                    let project = format!("{}/{}", hosting.owner, hosting.project);
+                    let project_id = encode_project(&project);
+                    for (name, version) in &releases_without_hosting {
+                        self.manifest
+                            .ensure_release(name.clone(), version.clone())
+                            .hosting
+                            .gitlab = Some(GitlabHosting {
+                            api_url: api_url.clone(),
+                            artifact_download_path: format!(
+                                "/projects/{project_id}/packages/generic/{name}/{version}"
+                            ),
+                            project: project.clone(),
+                        })
+                    }
+                }
+                HostingStyle::S3 => {
+                    // Nothing to create, the URLs are entirely determined by the config
+                    let Some(config) = &self.inner.config.hosts.s3 else {
+                        continue;
+                    };
+                    let Some(bucket) = &config.bucket else {
+                        return Err(DistError::S3NoBucket {});
+                    };
+                    let prefix = config
+                        .prefix
+                        .as_deref()
+                        .unwrap_or_default()
+                        .trim_matches('/');
+                    for (name, version) in &releases_without_hosting {
+                        let mut path = String::new();
+                        for part in [prefix, name, version] {
+                            if !part.is_empty() {
+                                path.push('/');
+                                path.push_str(part);
+                            }
+                        }
+                        self.manifest
+                            .ensure_release(name.clone(), version.clone())
+                            .hosting
+                            .s3 = Some(S3Hosting {
+                            artifact_base_url: config.public_url(bucket),
+                            artifact_download_path: path,
+                            endpoint: config.endpoint(),
+                            region: config.region.clone(),
+                            bucket: bucket.clone(),
+                        })
+                    }
+                }
             }
         }
 
@@ -212,14 +323,14 @@ fn check_hosting(_dist: &DistGraph, _manifest: &DistManifest, _abyss: &Gazenot)
 }
 
 fn upload_to_hosting(dist: &DistGraph, manifest: &DistManifest, abyss: &Gazenot) -> DistResult<()> {
-    const DIST_MANIFEST_ARTIFACT_ID: &ArtifactIdRef = ArtifactIdRef::from_str("dist-manifest.json");
-
     // Gather up the files to upload for each release
     let files = manifest.releases.iter().filter_map(|release| {
         // Github Releases only has semantics on Announce
         let Hosting {
             axodotdev,
             github: _,
+            gitlab: _,
+            s3: _,
         } = &release.hosting;
         if let Some(set) = axodotdev {
             // Upload all files associated with this Release, plus the dist-manifest.json
@@ -251,6 +362,8 @@ fn release_hosting(
         let Hosting {
             axodotdev,
             github: _,
+            gitlab: _,
+            s3: _,
         } = &release.hosting;
         if let Some(set) = axodotdev {
             let release = gazenot::ReleaseKey {
@@ -292,6 +405,8 @@ fn announce_hosting(_dist: &DistGraph, manifest: &DistManifest, abyss: &Gazenot)
             let Hosting {
                 axodotdev,
                 github: _,
+                gitlab: _,
+                s3: _,
             } = &release.hosting;
             axodotdev
                 .as_ref()
@@ -308,11 +423,140 @@ fn announce_hosting(_dist: &DistGraph, manifest: &DistManifest, abyss: &Gazenot)
     Ok(())
 }
 
+fn upload_to_gitlab(
+    dist: &DistGraph,
+    manifest: &DistManifest,
+    gitlab: &GitlabClient,
+) -> DistResult<()> {
+    // Gather up the files to upload for each release
+    let mut files = vec![];
+    for release in &manifest.releases {
+        // Build the URL from the GitLab hosting itself, other hosts may be preferred for downloads
+        let Some(hosting) = &release.hosting.gitlab else {
+            continue;
+        };
+        let download_url = format!("{}{}", hosting.api_url, hosting.artifact_download_path);
+        // Upload all files associated with this Release, plus the dist-manifest.json
+        let names = manifest
+            .artifacts_for_release(release)
+            .filter_map(|(_id, artifact)| artifact.name.as_deref())
+            .chain(Some(DIST_MANIFEST_ARTIFACT_ID));
+        for name in names {
+            files.push((
+                format!("{download_url}/{name}"),
+                dist.dist_dir.join(name.as_str()),
+            ));
+        }
+    }
+
+    let handle = tokio::runtime::Handle::current();
+    for (url, path) in &files {
+        handle.block_on(gitlab.upload_file(url, path))?;
+    }
+    eprintln!("all artifacts uploaded to GitLab!");
+    Ok(())
+}
+
+fn upload_to_s3(dist: &DistGraph, manifest: &DistManifest, s3: &S3Client) -> DistResult<()> {
+    // Gather up the files to upload for each release
+    let mut files = vec![];
+    for release in &manifest.releases {
+        let Some(hosting) = &release.hosting.s3 else {
+            continue;
+        };
+        let dir = hosting.artifact_download_path.trim_start_matches('/');
+        // Upload all files associated with this Release, plus the dist-manifest.json
+        let names = manifest
+            .artifacts_for_release(release)
+            .filter_map(|(_id, artifact)| artifact.name.as_deref())
+            .chain(Some(DIST_MANIFEST_ARTIFACT_ID));
+        for name in names {
+            let key = if dir.is_empty() {
+                name.to_string()
+            } else {
+                format!("{dir}/{name}")
+            };
+            files.push((hosting, key, dist.dist_dir.join(name.as_str())));
+        }
+    }
+
+    let handle = tokio::runtime::Handle::current();
+    for (hosting, key, path) in &files {
+        let bucket = S3Bucket {
+            endpoint: &hosting.endpoint,
+            region: &hosting.region,
+            name: &hosting.bucket,
+        };
+        handle.block_on(s3.upload_file(bucket, key, path))?;
+    }
+    eprintln!("all artifacts uploaded to S3!");
+    Ok(())
+}
+
+fn release_gitlab(
+    _dist: &DistGraph,
+    manifest: &DistManifest,
+    gitlab: &GitlabClient,
+) -> DistResult<()> {
+    // GitLab only allows one Release per tag, so all our releases go in one,
+    // just like the GitHub Release our GitHub CI makes
+    let mut target = None;
+    let mut links = vec![];
+    for release in &manifest.releases {
+        let Some(GitlabHosting {
+            api_url,
+            artifact_download_path,
+            project,
+        }) = &release.hosting.gitlab
+        else {
+            continue;
+        };
+        let download_url = format!("{api_url}{artifact_download_path}");
+        target.get_or_insert_with(|| (api_url.clone(), project.clone()));
+        for (_id, artifact) in manifest.artifacts_for_release(release) {
+            let Some(name) = &artifact.name else {
+                continue;
+            };
+            links.push(GitlabReleaseLink {
+                name: name.to_string(),
+                url: format!("{download_url}/{name}"),
+                link_type: "package".to_owned(),
+            });
+        }
+    }
+    let Some((api_url, project)) = target else {
+        return Ok(());
+    };
+
+    let Some(tag) = manifest.announcement_tag.clone() else {
+        return Err(DistError::GitlabReleaseNoTag {});
+    };
+    let release = GitlabRelease {
+        name: manifest
+            .announcement_title
+            .clone()
+            .unwrap_or_else(|| tag.clone()),
+        tag_name: tag,
+        description: manifest
+            .announcement_github_body
+            .clone()
+            .unwrap_or_default(),
+        // If the tag doesn't exist yet (e.g. release-branch mode), create it from the commit CI is building
+        git_ref: std::env::var("CI_COMMIT_SHA").ok(),
+        assets: GitlabReleaseAssets { links },
+    };
+    tokio::runtime::Handle::current()
+        .block_on(gitlab.create_release(&api_url, &project, &release))?;
+    eprintln!("GitLab release published!");
+    Ok(())
+}
+
 pub(crate) fn select_hosting(
     workspaces: &WorkspaceGraph,
     announcing: &AnnouncementTag,
     hosting: Option<Vec<HostingStyle>>,
     ci: Option<&[CiStyle]>,
+    gitlab_api_url: Option<&str>,
 ) -> DistResult<Option<HostingInfo>> {
     // Either use the explicit one, or default to the CI provider's native solution
     let Some(hosting_providers) = hosting
@@ -349,7 +593,50 @@ pub(crate) fn select_hosting(
         }
     };
 
-    // Currently there's only one supported sourcehost provider
+    // GitLab hosting lives on the GitLab instance the repository is on; everything else
+    // is tied to GitHub, except S3 which doesn't care where the repository is
+    let needs_github = hosting_providers
+        .iter()
+        .any(|host| matches!(host, HostingStyle::Github | HostingStyle::Axodotdev));
+    let needs_gitlab = hosting_providers.contains(&HostingStyle::Gitlab);
+    let on_github = raw_repository_url.github_repo().is_ok();
+    let use_gitlab =
+        !needs_github && !on_github && (needs_gitlab || raw_repository_url.gitlab_repo().is_ok());
+    if use_gitlab {
+        let repo = raw_repository_url
+            .gitlab_repo()
+            .map_err(|e| DistError::CantEnableGitlabUrlNotGitlab { inner: e })?;
+        let domain = repo.domain();
+        let repo_path = repo.web_path();
+        let gitlab_api_url = needs_gitlab.then(|| {
+            gitlab_api_url
+                .map(|url| url.trim_end_matches('/').to_owned())
+                .unwrap_or_else(|| format!("{domain}/api/v4"))
+        });
+        return Ok(Some(HostingInfo {
+            hosts: hosting_providers,
+            domain,
+            repo_path,
+            source_host: "gitlab".to_owned(),
+            owner: repo.namespace,
+            project: repo.name,
+            gitlab_api_url,
+        }));
+    }
+
+    // GitLab hosting alongside a GitHub repository (e.g. a mirror) can't guess
+    // which GitLab instance to use, so it must be told
+    let gitlab_api_url = if needs_gitlab {
+        let Some(url) = gitlab_api_url else {
+            return Err(DistError::CantEnableGitlabNoApiUrl {
+                url: raw_repository_url.0.clone(),
+            });
+        };
+        Some(url.trim_end_matches('/').to_owned())
+    } else {
+        None
+    };
+
     let repo = raw_repository_url
         .github_repo()
         .map_err(|e| DistError::CantEnableGithubUrlNotGithub { inner: e })?;
@@ -363,5 +650,6 @@ pub(crate) fn select_hosting(
         source_host: "github".to_owned(),
         owner: repo.owner,
         project: repo.name,
+        gitlab_api_url,
     }))
 }
@@ -0,0 +1,144 @@
+//! A minimal client for the parts of the GitLab API we use for hosting
+//!
+//! Artifacts get uploaded to the project's generic package registry, and
+//! then a GitLab Release is created that links to them.
+
+use axoasset::reqwest;
+use camino::Utf8Path;
+use serde::Serialize;
+
+use crate::{errors::DistResult, DistError};
+
+/// Env var holding a personal/project access token
+pub const GITLAB_TOKEN_ENV_VAR: &str = "GITLAB_TOKEN";
+/// Env var GitLab CI sets to the job's own token
+pub const CI_JOB_TOKEN_ENV_VAR: &str = "CI_JOB_TOKEN";
+
+/// Credentials for the GitLab API
+#[derive(Debug, Clone)]
+pub enum GitlabToken {
+    /// A personal, project, or group access token
+    Private(String),
+    /// A CI job token
+    Job(String),
+}
+
+impl GitlabToken {
+    /// Get a token from the environment, preferring an explicit access token
+    pub fn from_env() -> DistResult<Self> {
+        if let Ok(token) = std::env::var(GITLAB_TOKEN_ENV_VAR) {
+            Ok(Self::Private(token))
+        } else if let Ok(token) = std::env::var(CI_JOB_TOKEN_ENV_VAR) {
+            Ok(Self::Job(token))
+        } else {
+            Err(DistError::GitlabNoToken {})
+        }
+    }
+
+    fn header(&self) -> (&'static str, &str) {
+        match self {
+            Self::Private(token) => ("PRIVATE-TOKEN", token),
+            Self::Job(token) => ("JOB-TOKEN", token),
+        }
+    }
+}
+
+/// A release to create with the GitLab Releases API
+#[derive(Debug, Clone, Serialize)]
+pub struct GitlabRelease {
+    /// The tag to release
+    pub tag_name: String,
+    /// The title of the release
+    pub name: String,
+    /// The body of the release (markdown)
+    pub description: String,
+    /// The commit to create the tag from, if it doesn't exist yet
+    #[serde(rename = "ref")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    /// Files to link from the release
+    pub assets: GitlabReleaseAssets,
+}
+
+/// The assets of a [`GitlabRelease`][]
+#[derive(Debug, Clone, Serialize)]
+pub struct GitlabReleaseAssets {
+    /// Links to the artifacts
+    pub links: Vec<GitlabReleaseLink>,
+}
+
+/// A link from a [`GitlabRelease`][] to one of its artifacts
+#[derive(Debug, Clone, Serialize)]
+pub struct GitlabReleaseLink {
+    /// Name of the artifact
+    pub name: String,
+    /// Where to download the artifact
+    pub url: String,
+    /// What kind of link this is ("package", "other", ...)
+    pub link_type: String,
+}
+
+/// A client for a particular GitLab instance
+#[derive(Debug, Clone)]
+pub struct GitlabClient {
+    client: reqwest::Client,
+    token: GitlabToken,
+}
+
+impl GitlabClient {
+    /// Create a new client with the given http client and credentials
+    pub fn new(client: reqwest::Client, token: GitlabToken) -> Self {
+        Self { client, token }
+    }
+
+    /// Upload a file to the given generic package registry URL
+    pub async fn upload_file(&self, url: &str, path: &Utf8Path) -> DistResult<()> {
+        let contents = axoasset::LocalAsset::load_bytes(path)?;
+        let (header, token) = self.token.header();
+        let response = self
+            .client
+            .put(url)
+            .header(header, token)
+            .body(contents)
+            .send()
+            .await?;
+        check_response(url, response).await
+    }
+
+    /// Create a release for the given project, using the API at `api_url`
+    pub async fn create_release(
+        &self,
+        api_url: &str,
+        project: &str,
+        release: &GitlabRelease,
+    ) -> DistResult<()> {
+        let url = format!("{api_url}/projects/{}/releases", encode_project(project));
+        let (header, token) = self.token.header();
+        let response = self
+            .client
+            .post(&url)
+            .header(header, token)
+            .json(release)
+            .send()
+            .await?;
+        check_response(&url, response).await
+    }
+}
+
+/// Encode a project path (e.g. `"group/project"`) to be used as a project id in an API path
+pub fn encode_project(project: &str) -> String {
+    project.replace('/', "%2F")
+}
+
+async fn check_response(url: &str, response: reqwest::Response) -> DistResult<()> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(DistError::GitlabApiError {
+        url: url.to_owned(),
+        status: status.as_u16(),
+        body,
+    })
+}
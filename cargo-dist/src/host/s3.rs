@@ -0,0 +1,341 @@
+//! A minimal client for uploading to S3-compatible object storage
+//!
+//! This only knows how to sign (AWS Signature Version 4) and send a single
+//! PutObject request, which is all we need to mirror artifacts into a bucket.
+
+use axoasset::reqwest;
+use camino::Utf8Path;
+use sha2::{Digest, Sha256};
+
+use crate::{errors::DistResult, DistError};
+
+/// Env var holding the access key id
+pub const ACCESS_KEY_ID_ENV_VAR: &str = "AWS_ACCESS_KEY_ID";
+/// Env var holding the secret access key
+pub const SECRET_ACCESS_KEY_ENV_VAR: &str = "AWS_SECRET_ACCESS_KEY";
+/// Env var holding the session token for temporary credentials
+pub const SESSION_TOKEN_ENV_VAR: &str = "AWS_SESSION_TOKEN";
+
+/// Credentials for an S3 API
+#[derive(Debug, Clone)]
+pub struct S3Credentials {
+    /// The access key id
+    pub access_key_id: String,
+    /// The secret access key
+    pub secret_access_key: String,
+    /// A session token, if these are temporary credentials
+    pub session_token: Option<String>,
+}
+
+impl S3Credentials {
+    /// Get credentials from the standard AWS env vars
+    pub fn from_env() -> DistResult<Self> {
+        let (Ok(access_key_id), Ok(secret_access_key)) = (
+            std::env::var(ACCESS_KEY_ID_ENV_VAR),
+            std::env::var(SECRET_ACCESS_KEY_ENV_VAR),
+        ) else {
+            return Err(DistError::S3NoCredentials {});
+        };
+        Ok(Self {
+            access_key_id,
+            secret_access_key,
+            session_token: std::env::var(SESSION_TOKEN_ENV_VAR).ok(),
+        })
+    }
+}
+
+/// A bucket to upload to
+#[derive(Debug, Clone, Copy)]
+pub struct S3Bucket<'a> {
+    /// The S3 API endpoint, e.g. `"https://s3.us-east-1.amazonaws.com"`
+    pub endpoint: &'a str,
+    /// The region of the bucket
+    pub region: &'a str,
+    /// The name of the bucket
+    pub name: &'a str,
+}
+
+/// A client for S3-compatible APIs
+#[derive(Debug, Clone)]
+pub struct S3Client {
+    client: reqwest::Client,
+    credentials: S3Credentials,
+}
+
+impl S3Client {
+    /// Create a new client with the given http client and credentials
+    pub fn new(client: reqwest::Client, credentials: S3Credentials) -> Self {
+        Self {
+            client,
+            credentials,
+        }
+    }
+
+    /// Upload a file to the given key of the bucket
+    pub async fn upload_file(
+        &self,
+        bucket: S3Bucket<'_>,
+        key: &str,
+        path: &Utf8Path,
+    ) -> DistResult<()> {
+        self.upload_file_at(bucket, key, path, std::time::SystemTime::now())
+            .await
+    }
+
+    /// Upload a file to the given key of the bucket, signing the request as if it's `time`
+    pub(crate) async fn upload_file_at(
+        &self,
+        bucket: S3Bucket<'_>,
+        key: &str,
+        path: &Utf8Path,
+        time: std::time::SystemTime,
+    ) -> DistResult<()> {
+        let contents = axoasset::LocalAsset::load_bytes(path)?;
+        // Always use path-style addressing, it's the one every S3 implementation supports
+        let url = format!(
+            "{}/{}/{}",
+            bucket.endpoint,
+            uri_encode(bucket.name),
+            key.split('/').map(uri_encode).collect::<Vec<_>>().join("/")
+        );
+        let bad_endpoint = || DistError::S3BadEndpoint {
+            endpoint: bucket.endpoint.to_owned(),
+        };
+        let parsed = reqwest::Url::parse(&url).map_err(|_| bad_endpoint())?;
+        let host = match (parsed.host_str(), parsed.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_owned(),
+            (None, _) => return Err(bad_endpoint()),
+        };
+        let payload_hash = format!("{:x}", Sha256::digest(&contents));
+        let (amz_date, date) = amz_timestamp(time);
+
+        let mut headers = vec![
+            ("host", host),
+            ("x-amz-content-sha256", payload_hash.clone()),
+            ("x-amz-date", amz_date.clone()),
+        ];
+        if let Some(token) = &self.credentials.session_token {
+            headers.push(("x-amz-security-token", token.clone()));
+        }
+        let authorization = sigv4_authorization(
+            &self.credentials,
+            &SigningRequest {
+                method: "PUT",
+                path: parsed.path(),
+                headers: &headers,
+                payload_hash: &payload_hash,
+                region: bucket.region,
+                service: "s3",
+                amz_date: &amz_date,
+                date: &date,
+            },
+        );
+
+        let mut request = self
+            .client
+            .put(parsed)
+            .header("authorization", authorization)
+            .body(contents);
+        // reqwest fills in the host header itself
+        for (name, value) in headers.into_iter().skip(1) {
+            request = request.header(name, value);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let body = response.text().await.unwrap_or_default();
+        Err(DistError::S3ApiError {
+            url,
+            status: status.as_u16(),
+            body,
+        })
+    }
+}
+
+/// Percent-encode everything but the characters S3 considers unreserved
+fn uri_encode(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+/// The parts of a request that go into its signature
+struct SigningRequest<'a> {
+    method: &'a str,
+    /// The (already uri-encoded) path, with no query
+    path: &'a str,
+    /// Headers to sign, with lowercase names, in sorted order
+    headers: &'a [(&'a str, String)],
+    payload_hash: &'a str,
+    region: &'a str,
+    service: &'a str,
+    /// `YYYYMMDD'T'HHMMSS'Z'`
+    amz_date: &'a str,
+    /// `YYYYMMDD`
+    date: &'a str,
+}
+
+/// Compute the Authorization header for a request, as described in
+/// https://docs.aws.amazon.com/AmazonS3/latest/API/sig-v4-header-based-auth.html
+fn sigv4_authorization(credentials: &S3Credentials, request: &SigningRequest) -> String {
+    let canonical_headers = request
+        .headers
+        .iter()
+        .map(|(name, value)| format!("{name}:{}\n", value.trim()))
+        .collect::<String>();
+    let signed_headers = request
+        .headers
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical_request = format!(
+        "{}\n{}\n\n{canonical_headers}\n{signed_headers}\n{}",
+        request.method, request.path, request.payload_hash
+    );
+    let scope = format!(
+        "{}/{}/{}/aws4_request",
+        request.date, request.region, request.service
+    );
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{scope}\n{:x}",
+        request.amz_date,
+        Sha256::digest(canonical_request.as_bytes())
+    );
+    let signing_key = signing_key(
+        &credentials.secret_access_key,
+        request.date,
+        request.region,
+        request.service,
+    );
+    let signature = crate::encode_hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+        credentials.access_key_id
+    )
+}
+
+/// Derive the key that signs requests for the given day, region, and service
+fn signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let mut key = format!("AWS4{secret_access_key}").into_bytes();
+    for part in [date, region, service, "aws4_request"] {
+        key = hmac_sha256(&key, part.as_bytes());
+    }
+    key
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, key);
+    ring::hmac::sign(&key, message).as_ref().to_vec()
+}
+
+/// Get the `YYYYMMDD'T'HHMMSS'Z'` and `YYYYMMDD` forms of the given time (in UTC)
+fn amz_timestamp(time: std::time::SystemTime) -> (String, String) {
+    let ((year, month, day), (hours, minutes, seconds)) = crate::utc_date_time(time);
+    let date = format!("{year:04}{month:02}{day:02}");
+    let amz_date = format!("{date}T{hours:02}{minutes:02}{seconds:02}Z");
+    (amz_date, date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(hex: &str) -> Vec<u8> {
+        crate::decode_hex(hex).unwrap()
+    }
+
+    #[test]
+    fn test_hmac_sha256() {
+        // Test cases 1, 2, and 6 from RFC 4231
+        assert_eq!(
+            crate::encode_hex(&hmac_sha256(&[0x0b; 20], b"Hi There")),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+        assert_eq!(
+            crate::encode_hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            crate::encode_hex(&hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn test_signing_key() {
+        // The example from AWS's "Examples of how to derive a signing key for Signature Version 4"
+        assert_eq!(
+            signing_key(
+                "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+                "20120215",
+                "us-east-1",
+                "iam"
+            ),
+            decode("f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d")
+        );
+    }
+
+    #[test]
+    fn test_sigv4_get_vanilla() {
+        // "get-vanilla" from the AWS Signature Version 4 test suite
+        let credentials = S3Credentials {
+            access_key_id: "AKIDEXAMPLE".to_owned(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_owned(),
+            session_token: None,
+        };
+        let (amz_date, date) =
+            amz_timestamp(std::time::UNIX_EPOCH + std::time::Duration::from_secs(1440938160));
+        let headers = [
+            ("host", "example.amazonaws.com".to_owned()),
+            ("x-amz-date", amz_date.clone()),
+        ];
+        let authorization = sigv4_authorization(
+            &credentials,
+            &SigningRequest {
+                method: "GET",
+                path: "/",
+                headers: &headers,
+                payload_hash: "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+                region: "us-east-1",
+                service: "service",
+                amz_date: &amz_date,
+                date: &date,
+            },
+        );
+        assert_eq!(
+            authorization,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, SignedHeaders=host;x-amz-date, Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+
+    #[test]
+    fn test_amz_timestamp() {
+        let at = |secs| amz_timestamp(std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs));
+        assert_eq!(
+            at(0),
+            ("19700101T000000Z".to_owned(), "19700101".to_owned())
+        );
+        assert_eq!(
+            at(1440938160),
+            ("20150830T123600Z".to_owned(), "20150830".to_owned())
+        );
+        // leap days, and the last second of a day
+        assert_eq!(
+            at(951868799),
+            ("20000229T235959Z".to_owned(), "20000229".to_owned())
+        );
+    }
+}
@@ -1,6 +1,10 @@
 use crate::config::v1::installers::{
-    homebrew::HomebrewInstallerLayer, msi::MsiInstallerLayer, npm::NpmInstallerLayer,
-    pkg::PkgInstallerLayer, powershell::PowershellInstallerLayer, shell::ShellInstallerLayer,
+    appimage::AppimageInstallerLayer, aur::AurInstallerLayer,
+    chocolatey::ChocolateyInstallerLayer, deb::DebInstallerLayer,
+    homebrew::HomebrewInstallerLayer, msi::MsiInstallerLayer, nix::NixInstallerLayer, npm::NpmInstallerLayer,
+    pkg::PkgInstallerLayer, powershell::PowershellInstallerLayer, pypi::PypiInstallerLayer,
+    rpm::RpmInstallerLayer,
+    scoop::ScoopInstallerLayer, shell::ShellInstallerLayer, winget::WingetInstallerLayer,
     CommonInstallerLayer, InstallerLayer,
 };
 use crate::config::v1::{
@@ -340,6 +344,34 @@ fn apply_artifacts(table: &mut toml_edit::Table, artifacts: &Option<ArtifactLaye
         artifacts.source_tarball,
     );
 
+    apply_optional_value(
+        artifacts_table,
+        "offline-bundle",
+        "# Generate and dist an offline bundle of the installers and archives\n",
+        artifacts.offline_bundle,
+    );
+
+    apply_string_list(
+        artifacts_table,
+        "signatures",
+        "# Detached signatures to generate for each archive, installer and source tarball\n",
+        artifacts.signatures.as_ref(),
+    );
+
+    apply_optional_value(
+        artifacts_table,
+        "provenance",
+        "# Generate and dist a SLSA provenance statement for each release\n",
+        artifacts.provenance,
+    );
+
+    apply_optional_value(
+        artifacts_table,
+        "sbom",
+        "# Generate and dist CycloneDX and SPDX SBOMs for each release\n",
+        artifacts.sbom,
+    );
+
     // TODO(migration): implement dist.artifacts.extra.
     /*
     apply_optional_value(
@@ -705,6 +737,150 @@ fn apply_installers(table: &mut toml_edit::Table, installers: &Option<InstallerL
         }
     }
 
+    if let Some(deb) = &installers.deb {
+        match deb {
+            BoolOr::Bool(b) => {
+                apply_optional_value(
+                    installers_table,
+                    "deb",
+                    "\n# Configuration for the Debian .deb installer\n",
+                    Some(*b),
+                );
+            }
+            BoolOr::Val(v) => {
+                apply_installers_deb(installers_table, v);
+            }
+        }
+    }
+
+    if let Some(rpm) = &installers.rpm {
+        match rpm {
+            BoolOr::Bool(b) => {
+                apply_optional_value(
+                    installers_table,
+                    "rpm",
+                    "\n# Configuration for the RPM .rpm installer\n",
+                    Some(*b),
+                );
+            }
+            BoolOr::Val(v) => {
+                apply_installers_rpm(installers_table, v);
+            }
+        }
+    }
+
+    if let Some(scoop) = &installers.scoop {
+        match scoop {
+            BoolOr::Bool(b) => {
+                apply_optional_value(
+                    installers_table,
+                    "scoop",
+                    "\n# Configuration for the Scoop manifest\n",
+                    Some(*b),
+                );
+            }
+            BoolOr::Val(v) => {
+                apply_installers_scoop(installers_table, v);
+            }
+        }
+    }
+
+    if let Some(winget) = &installers.winget {
+        match winget {
+            BoolOr::Bool(b) => {
+                apply_optional_value(
+                    installers_table,
+                    "winget",
+                    "\n# Configuration for the winget manifests\n",
+                    Some(*b),
+                );
+            }
+            BoolOr::Val(v) => {
+                apply_installers_winget(installers_table, v);
+            }
+        }
+    }
+
+    if let Some(chocolatey) = &installers.chocolatey {
+        match chocolatey {
+            BoolOr::Bool(b) => {
+                apply_optional_value(
+                    installers_table,
+                    "chocolatey",
+                    "\n# Configuration for the Chocolatey package\n",
+                    Some(*b),
+                );
+            }
+            BoolOr::Val(v) => {
+                apply_installers_chocolatey(installers_table, v);
+            }
+        }
+    }
+
+    if let Some(aur) = &installers.aur {
+        match aur {
+            BoolOr::Bool(b) => {
+                apply_optional_value(
+                    installers_table,
+                    "aur",
+                    "\n# Configuration for the AUR PKGBUILD\n",
+                    Some(*b),
+                );
+            }
+            BoolOr::Val(v) => {
+                apply_installers_aur(installers_table, v);
+            }
+        }
+    }
+
+    if let Some(nix) = &installers.nix {
+        match nix {
+            BoolOr::Bool(b) => {
+                apply_optional_value(
+                    installers_table,
+                    "nix",
+                    "\n# Configuration for the Nix derivation\n",
+                    Some(*b),
+                );
+            }
+            BoolOr::Val(v) => {
+                apply_installers_nix(installers_table, v);
+            }
+        }
+    }
+
+    if let Some(pypi) = &installers.pypi {
+        match pypi {
+            BoolOr::Bool(b) => {
+                apply_optional_value(
+                    installers_table,
+                    "pypi",
+                    "\n# Configuration for the Python wheels\n",
+                    Some(*b),
+                );
+            }
+            BoolOr::Val(v) => {
+                apply_installers_pypi(installers_table, v);
+            }
+        }
+    }
+
+    if let Some(appimage) = &installers.appimage {
+        match appimage {
+            BoolOr::Bool(b) => {
+                apply_optional_value(
+                    installers_table,
+                    "appimage",
+                    "\n# Configuration for the AppImages\n",
+                    Some(*b),
+                );
+            }
+            BoolOr::Val(v) => {
+                apply_installers_appimage(installers_table, v);
+            }
+        }
+    }
+
     // installer.updater: Option<Bool>
     // installer.always_use_latest_updater: Option<bool>
     apply_optional_value(
@@ -787,6 +963,20 @@ fn apply_installers_homebrew(
         *homebrew.version_formulas,
     );
 
+    apply_optional_value(
+        homebrew_table,
+        "caveats",
+        "# Text for Homebrew to show after installing the formula\n",
+        homebrew.caveats.clone(),
+    );
+
+    apply_optional_value(
+        homebrew_table,
+        "bottles",
+        "# Whether to repackage the archives as Homebrew bottles\n",
+        homebrew.bottles,
+    );
+
     // Finalize the table
     homebrew_table
         .decor_mut()
@@ -833,6 +1023,13 @@ fn apply_installers_npm(installers_table: &mut toml_edit::Table, npm: &NpmInstal
         "# A namespace to use when publishing this package to the npm registry\n",
         npm.scope.as_deref(),
     );
+
+    apply_optional_value(
+        npm_table,
+        "platform-packages",
+        "# Whether to publish a package per platform with the binaries in it\n",
+        npm.platform_packages,
+    );
 }
 
 fn apply_installers_powershell(
@@ -894,6 +1091,212 @@ fn apply_installers_pkg(installers_table: &mut toml_edit::Table, pkg: &PkgInstal
         .set_prefix("\n# Configuration for the Mac .pkg installer\n");
 }
 
+fn apply_installers_deb(installers_table: &mut toml_edit::Table, deb: &DebInstallerLayer) {
+    let Some(deb_table) = installers_table.get_mut("deb") else {
+        return;
+    };
+    let toml_edit::Item::Table(deb_table) = deb_table else {
+        panic!("Expected [dist.installers.deb] to be a table");
+    };
+
+    apply_installers_common(deb_table, &deb.common);
+
+    // Finalize the table
+    deb_table
+        .decor_mut()
+        .set_prefix("\n# Configuration for the Debian .deb installer\n");
+}
+
+fn apply_installers_rpm(installers_table: &mut toml_edit::Table, rpm: &RpmInstallerLayer) {
+    let Some(rpm_table) = installers_table.get_mut("rpm") else {
+        return;
+    };
+    let toml_edit::Item::Table(rpm_table) = rpm_table else {
+        panic!("Expected [dist.installers.rpm] to be a table");
+    };
+
+    apply_installers_common(rpm_table, &rpm.common);
+
+    // Finalize the table
+    rpm_table
+        .decor_mut()
+        .set_prefix("\n# Configuration for the RPM .rpm installer\n");
+}
+
+fn apply_installers_scoop(installers_table: &mut toml_edit::Table, scoop: &ScoopInstallerLayer) {
+    let Some(scoop_table) = installers_table.get_mut("scoop") else {
+        return;
+    };
+    let toml_edit::Item::Table(scoop_table) = scoop_table else {
+        panic!("Expected [dist.installers.scoop] to be a table");
+    };
+
+    apply_installers_common(scoop_table, &scoop.common);
+
+    apply_optional_value(
+        scoop_table,
+        "bucket",
+        "# A GitHub repo to push Scoop manifests to\n",
+        scoop.bucket.clone(),
+    );
+
+    // Finalize the table
+    scoop_table
+        .decor_mut()
+        .set_prefix("\n# Configuration for the Scoop manifest\n");
+}
+
+fn apply_installers_nix(installers_table: &mut toml_edit::Table, nix: &NixInstallerLayer) {
+    let Some(nix_table) = installers_table.get_mut("nix") else {
+        return;
+    };
+    let toml_edit::Item::Table(nix_table) = nix_table else {
+        panic!("Expected [dist.installers.nix] to be a table");
+    };
+
+    apply_installers_common(nix_table, &nix.common);
+
+    apply_optional_value(
+        nix_table,
+        "repo",
+        "# A GitHub repo to push Nix derivations to\n",
+        nix.repo.clone(),
+    );
+
+    // Finalize the table
+    nix_table
+        .decor_mut()
+        .set_prefix("\n# Configuration for the Nix derivation\n");
+}
+
+fn apply_installers_pypi(installers_table: &mut toml_edit::Table, pypi: &PypiInstallerLayer) {
+    let Some(pypi_table) = installers_table.get_mut("pypi") else {
+        return;
+    };
+    let toml_edit::Item::Table(pypi_table) = pypi_table else {
+        panic!("Expected [dist.installers.pypi] to be a table");
+    };
+
+    apply_installers_common(pypi_table, &pypi.common);
+
+    // Finalize the table
+    pypi_table
+        .decor_mut()
+        .set_prefix("\n# Configuration for the Python wheels\n");
+}
+
+fn apply_installers_appimage(
+    installers_table: &mut toml_edit::Table,
+    appimage: &AppimageInstallerLayer,
+) {
+    let Some(appimage_table) = installers_table.get_mut("appimage") else {
+        return;
+    };
+    let toml_edit::Item::Table(appimage_table) = appimage_table else {
+        panic!("Expected [dist.installers.appimage] to be a table");
+    };
+
+    apply_installers_common(appimage_table, &appimage.common);
+
+    apply_optional_value(
+        appimage_table,
+        "icon",
+        "# A png or svg icon for the app\n",
+        appimage.icon.as_ref().map(|p| p.to_string()),
+    );
+
+    apply_optional_value(
+        appimage_table,
+        "desktop-file",
+        "# A .desktop file to use instead of the generated one\n",
+        appimage.desktop_file.as_ref().map(|p| p.to_string()),
+    );
+
+    apply_string_list(
+        appimage_table,
+        "categories",
+        "# The categories to list the app under in the .desktop file\n",
+        appimage.categories.as_ref(),
+    );
+
+    apply_optional_value(
+        appimage_table,
+        "terminal",
+        "# Whether the app should be run in a terminal\n",
+        appimage.terminal,
+    );
+
+    // Finalize the table
+    appimage_table
+        .decor_mut()
+        .set_prefix("\n# Configuration for the AppImages\n");
+}
+
+fn apply_installers_winget(installers_table: &mut toml_edit::Table, winget: &WingetInstallerLayer) {
+    let Some(winget_table) = installers_table.get_mut("winget") else {
+        return;
+    };
+    let toml_edit::Item::Table(winget_table) = winget_table else {
+        panic!("Expected [dist.installers.winget] to be a table");
+    };
+
+    apply_installers_common(winget_table, &winget.common);
+
+    apply_optional_value(
+        winget_table,
+        "identifier",
+        "# The winget package identifier\n",
+        winget.identifier.clone(),
+    );
+
+    apply_optional_value(
+        winget_table,
+        "fork",
+        "# A GitHub fork of winget-pkgs to open pull requests from\n",
+        winget.fork.clone(),
+    );
+
+    // Finalize the table
+    winget_table
+        .decor_mut()
+        .set_prefix("\n# Configuration for the winget manifests\n");
+}
+
+fn apply_installers_chocolatey(
+    installers_table: &mut toml_edit::Table,
+    chocolatey: &ChocolateyInstallerLayer,
+) {
+    let Some(chocolatey_table) = installers_table.get_mut("chocolatey") else {
+        return;
+    };
+    let toml_edit::Item::Table(chocolatey_table) = chocolatey_table else {
+        panic!("Expected [dist.installers.chocolatey] to be a table");
+    };
+
+    apply_installers_common(chocolatey_table, &chocolatey.common);
+
+    // Finalize the table
+    chocolatey_table
+        .decor_mut()
+        .set_prefix("\n# Configuration for the Chocolatey package\n");
+}
+
+fn apply_installers_aur(installers_table: &mut toml_edit::Table, aur: &AurInstallerLayer) {
+    let Some(aur_table) = installers_table.get_mut("aur") else {
+        return;
+    };
+    let toml_edit::Item::Table(aur_table) = aur_table else {
+        panic!("Expected [dist.installers.aur] to be a table");
+    };
+
+    apply_installers_common(aur_table, &aur.common);
+
+    // Finalize the table
+    aur_table
+        .decor_mut()
+        .set_prefix("\n# Configuration for the AUR PKGBUILD\n");
+}
+
 fn apply_publishers(table: &mut toml_edit::Table, publishers: &Option<PublisherLayer>) {
     let Some(publishers_table) = table.get_mut("publishers") else {
         return;
@@ -196,6 +196,19 @@ fn get_new_dist_metadata(
             tap: None,
             formula: None,
             version_formulas: None,
+            homebrew_test: None,
+            homebrew_service: None,
+            homebrew_caveats: None,
+            homebrew_bottles: None,
+            scoop_bucket: None,
+            winget_identifier: None,
+            winget_fork: None,
+            aur_remote: None,
+            nix_repo: None,
+            appimage_icon: None,
+            appimage_desktop_file: None,
+            appimage_categories: None,
+            appimage_terminal: None,
             system_dependencies: None,
             targets: None,
             dist: None,
@@ -205,7 +218,9 @@ fn get_new_dist_metadata(
             unix_archive: None,
             npm_scope: None,
             npm_package: None,
+            npm_platform_packages: None,
             checksum: None,
+            minisign_public_key: None,
             precise_builds: None,
             merge_tasks: None,
             fail_fast: None,
@@ -221,6 +236,11 @@ fn get_new_dist_metadata(
             local_artifacts_jobs: None,
             global_artifacts_jobs: None,
             source_tarball: None,
+            offline_bundle: None,
+            signatures: None,
+            sigstore: None,
+            provenance: None,
+            sbom: None,
             host_jobs: None,
             publish_jobs: None,
             post_announce_jobs: None,
@@ -234,12 +254,18 @@ fn get_new_dist_metadata(
             allow_dirty: None,
             ssldotcom_windows_sign: None,
             macos_sign: None,
+            signing: None,
             github_attestations: None,
             msvc_crt_static: None,
             hosting: None,
             extra_artifacts: None,
+            sbom_components: None,
             github_custom_runners: None,
             github_custom_job_permissions: None,
+            gitlab_custom_runners: None,
+            gitlab_custom_job_id_tokens: None,
+            gitlab_api_url: None,
+            s3_config: None,
             bin_aliases: None,
             completion_cmds: None,
             tag_namespace: None,
@@ -368,61 +394,52 @@ fn get_new_dist_metadata(
     // Enable CI backends
     // FIXME: when there is more than one option we maybe shouldn't hide this
     // once the user has any one enabled, right now it's just annoying to always
-    // prompt for Github CI support.
+    // prompt for CI support.
     if meta.ci.as_deref().unwrap_or_default().is_empty() {
-        // FIXME: when there is more than one option this should be a proper
-        // multiselect like the installer selector is! For now we do
-        // most of the multi-select logic and then just give a prompt.
-        let known = &[CiStyle::Github];
+        let known = &[CiStyle::Github, CiStyle::Gitlab];
+        // If they passed a CI style on the CLI, respect that, otherwise default
+        // to Github CI, which is still the best supported one
+        let cli_picked_ci = known.iter().any(|item| cfg.ci.contains(item));
         let mut defaults = vec![];
         let mut keys = vec![];
-        let mut github_key = 0;
         for item in known {
             // If this CI style is in their config, keep it
             // If they passed it on the CLI, flip it on
-            let mut default = meta
+            let default = meta
                 .ci
                 .as_ref()
                 .map(|ci| ci.contains(item))
                 .unwrap_or(false)
-                || cfg.ci.contains(item);
-
-            // Currently default to enabling github CI because we don't
-            // support anything else and we can give a good error later
-            #[allow(irrefutable_let_patterns)]
-            if let CiStyle::Github = item {
-                github_key = 0;
-                default = true;
-            }
+                || cfg.ci.contains(item)
+                || (!cli_picked_ci && *item == CiStyle::Github);
             defaults.push(default);
             // This match is here to remind you to add new CiStyles
             // to `known` above!
             keys.push(match item {
                 CiStyle::Github => "github",
+                CiStyle::Gitlab => "gitlab",
             });
         }
 
         // Prompt the user
-        let prompt = r#"enable Github CI and Releases?"#;
-        let default = defaults[github_key];
-
-        let github_selected = if args.yes {
-            default
+        let prompt = r#"what CI do you want to generate?
+    (select with arrow keys and space, submit with enter)"#;
+        let selected = if args.yes {
+            defaults
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, enabled)| enabled.then_some(idx))
+                .collect()
         } else {
-            let res = Confirm::with_theme(&theme)
+            let res = MultiSelect::with_theme(&theme)
+                .items(&keys)
+                .defaults(&defaults)
                 .with_prompt(prompt)
-                .default(default)
                 .interact()?;
             eprintln!();
             res
         };
 
-        let selected = if github_selected {
-            vec![github_key]
-        } else {
-            vec![]
-        };
-
         // Apply the results
         let ci: Vec<_> = selected.into_iter().map(|i| known[i]).collect();
         meta.ci = if ci.is_empty() { None } else { Some(ci) };
@@ -441,10 +458,24 @@ fn get_new_dist_metadata(
                 InstallerStyle::Npm,
                 InstallerStyle::Homebrew,
                 InstallerStyle::Msi,
+                InstallerStyle::Deb,
+                InstallerStyle::Rpm,
+                InstallerStyle::Scoop,
+                InstallerStyle::Winget,
+                InstallerStyle::Chocolatey,
+                InstallerStyle::Aur,
+                InstallerStyle::Nix,
+                InstallerStyle::Pypi,
+                InstallerStyle::Appimage,
             ]
         } else {
             eprintln!("{notice} no CI backends enabled, most installers have been hidden");
-            &[InstallerStyle::Msi]
+            &[
+                InstallerStyle::Msi,
+                InstallerStyle::Deb,
+                InstallerStyle::Rpm,
+                InstallerStyle::Appimage,
+            ]
         };
         let mut defaults = vec![];
         let mut keys = vec![];
@@ -470,6 +501,15 @@ fn get_new_dist_metadata(
                 InstallerStyle::Homebrew => "homebrew",
                 InstallerStyle::Msi => "msi",
                 InstallerStyle::Pkg => "pkg",
+                InstallerStyle::Deb => "deb",
+                InstallerStyle::Rpm => "rpm",
+                InstallerStyle::Scoop => "scoop",
+                InstallerStyle::Winget => "winget",
+                InstallerStyle::Chocolatey => "chocolatey",
+                InstallerStyle::Aur => "aur",
+                InstallerStyle::Nix => "nix",
+                InstallerStyle::Pypi => "pypi",
+                InstallerStyle::Appimage => "appimage",
             });
         }
 
@@ -557,6 +597,183 @@ fn get_new_dist_metadata(
         }
     }
 
+    // Special handling of the Scoop installer
+    if meta
+        .installers
+        .as_deref()
+        .unwrap_or_default()
+        .contains(&InstallerStyle::Scoop)
+    {
+        let scoop_is_new = !orig_meta
+            .installers
+            .as_deref()
+            .unwrap_or_default()
+            .contains(&InstallerStyle::Scoop);
+
+        if scoop_is_new {
+            let prompt = r#"you've enabled Scoop support; if you want dist
+    to automatically push manifest updates to a bucket (repository) for you,
+    please enter the bucket name (in GitHub owner/name format)"#;
+            let default = "".to_string();
+
+            let bucket: String = if args.yes {
+                default
+            } else {
+                let res = Input::with_theme(&theme)
+                    .with_prompt(prompt)
+                    .allow_empty(true)
+                    .interact_text()?;
+                eprintln!();
+                res
+            };
+            let bucket = bucket.trim();
+            if bucket.is_empty() {
+                eprintln!("Scoop manifests will not be automatically published");
+                meta.scoop_bucket = None;
+            } else {
+                meta.scoop_bucket = Some(bucket.to_owned());
+                publish_jobs.push(PublishStyle::Scoop);
+
+                eprintln!("{check} Scoop manifest will be published to {bucket}");
+
+                eprintln!(
+                    r#"{check} You must provision a GitHub token and expose it as a secret named
+    SCOOP_BUCKET_TOKEN in GitHub Actions. For more information,
+    see the documentation:
+    https://opensource.axo.dev/cargo-dist/book/installers/scoop.html"#
+                );
+            }
+        }
+    } else {
+        let scoop_toggled_off = orig_meta
+            .installers
+            .as_deref()
+            .unwrap_or_default()
+            .contains(&InstallerStyle::Scoop);
+        if scoop_toggled_off {
+            meta.scoop_bucket = None;
+            publish_jobs.retain(|job| job != &PublishStyle::Scoop);
+        }
+    }
+
+    // Special handling of the Nix installer
+    if meta
+        .installers
+        .as_deref()
+        .unwrap_or_default()
+        .contains(&InstallerStyle::Nix)
+    {
+        let nix_is_new = !orig_meta
+            .installers
+            .as_deref()
+            .unwrap_or_default()
+            .contains(&InstallerStyle::Nix);
+
+        if nix_is_new {
+            let prompt = r#"you've enabled Nix support; if you want dist
+    to automatically push derivation updates to a repository for you,
+    please enter the repository name (in GitHub owner/name format)"#;
+            let default = "".to_string();
+
+            let repo: String = if args.yes {
+                default
+            } else {
+                let res = Input::with_theme(&theme)
+                    .with_prompt(prompt)
+                    .allow_empty(true)
+                    .interact_text()?;
+                eprintln!();
+                res
+            };
+            let repo = repo.trim();
+            if repo.is_empty() {
+                eprintln!("Nix derivations will not be automatically published");
+                meta.nix_repo = None;
+            } else {
+                meta.nix_repo = Some(repo.to_owned());
+                publish_jobs.push(PublishStyle::Nix);
+
+                eprintln!("{check} Nix derivation will be published to {repo}");
+
+                eprintln!(
+                    r#"{check} You must provision a GitHub token and expose it as a secret named
+    NIX_REPO_TOKEN in GitHub Actions. For more information,
+    see the documentation:
+    https://opensource.axo.dev/cargo-dist/book/installers/nix.html"#
+                );
+            }
+        }
+    } else {
+        let nix_toggled_off = orig_meta
+            .installers
+            .as_deref()
+            .unwrap_or_default()
+            .contains(&InstallerStyle::Nix);
+        if nix_toggled_off {
+            meta.nix_repo = None;
+            publish_jobs.retain(|job| job != &PublishStyle::Nix);
+        }
+    }
+
+    // Special handling of the winget installer
+    if meta
+        .installers
+        .as_deref()
+        .unwrap_or_default()
+        .contains(&InstallerStyle::Winget)
+    {
+        let winget_is_new = !orig_meta
+            .installers
+            .as_deref()
+            .unwrap_or_default()
+            .contains(&InstallerStyle::Winget);
+
+        if winget_is_new {
+            let prompt = r#"you've enabled winget support; if you want dist
+    to automatically open pull requests against winget-pkgs for you,
+    please enter your fork of it (in GitHub owner/name format)"#;
+            let default = "".to_string();
+
+            let fork: String = if args.yes {
+                default
+            } else {
+                let res = Input::with_theme(&theme)
+                    .with_prompt(prompt)
+                    .allow_empty(true)
+                    .interact_text()?;
+                eprintln!();
+                res
+            };
+            let fork = fork.trim();
+            if fork.is_empty() {
+                eprintln!("winget manifests will not be automatically published");
+                meta.winget_fork = None;
+            } else {
+                meta.winget_fork = Some(fork.to_owned());
+                publish_jobs.push(PublishStyle::Winget);
+
+                eprintln!("{check} winget pull requests will be opened from {fork}");
+
+                eprintln!(
+                    r#"{check} You must provision a GitHub token and expose it as a secret named
+    WINGET_TOKEN in GitHub Actions. For more information,
+    see the documentation:
+    https://opensource.axo.dev/cargo-dist/book/installers/winget.html"#
+                );
+            }
+        }
+    } else {
+        let winget_toggled_off = orig_meta
+            .installers
+            .as_deref()
+            .unwrap_or_default()
+            .contains(&InstallerStyle::Winget);
+        if winget_toggled_off {
+            meta.winget_fork = None;
+            publish_jobs.retain(|job| job != &PublishStyle::Winget);
+        }
+    }
+
     // Special handling of the npm installer
     if meta
         .installers
@@ -624,6 +841,38 @@ fn get_new_dist_metadata(
         }
     }
 
+    // Special handling of the PyPI installer
+    if meta
+        .installers
+        .as_deref()
+        .unwrap_or_default()
+        .contains(&InstallerStyle::Pypi)
+    {
+        let pypi_is_new = !orig_meta
+            .installers
+            .as_deref()
+            .unwrap_or_default()
+            .contains(&InstallerStyle::Pypi);
+        if pypi_is_new && !publish_jobs.contains(&PublishStyle::Pypi) {
+            publish_jobs.push(PublishStyle::Pypi);
+
+            eprintln!(
+                r#"{check} You must provision a PyPI API token and expose it as a secret named
+    PYPI_TOKEN in your CI. For more information, see the documentation:
+    https://opensource.axo.dev/cargo-dist/book/installers/pypi.html"#
+            );
+        }
+    } else {
+        let pypi_toggled_off = orig_meta
+            .installers
+            .as_deref()
+            .unwrap_or_default()
+            .contains(&InstallerStyle::Pypi);
+        if pypi_toggled_off {
+            publish_jobs.retain(|job| job != &PublishStyle::Pypi);
+        }
+    }
+
     meta.publish_jobs = if publish_jobs.is_empty() {
         None
     } else {
@@ -700,6 +949,17 @@ fn apply_dist_to_metadata(metadata: &mut toml_edit::Item, meta: &DistMetadata) {
         tap,
         formula,
         version_formulas,
+        homebrew_caveats,
+        homebrew_bottles,
+        scoop_bucket,
+        winget_identifier,
+        winget_fork,
+        aur_remote,
+        nix_repo,
+        appimage_icon,
+        appimage_desktop_file,
+        appimage_categories,
+        appimage_terminal,
         targets,
         include,
         auto_includes,
@@ -707,7 +967,9 @@ fn apply_dist_to_metadata(metadata: &mut toml_edit::Item, meta: &DistMetadata) {
         unix_archive,
         npm_scope,
         npm_package,
+        npm_platform_packages,
         checksum,
+        minisign_public_key,
         precise_builds,
         merge_tasks,
         fail_fast,
@@ -723,6 +985,11 @@ fn apply_dist_to_metadata(metadata: &mut toml_edit::Item, meta: &DistMetadata) {
         local_artifacts_jobs,
         global_artifacts_jobs,
         source_tarball,
+        offline_bundle,
+        signatures,
+        sigstore: _,
+        provenance,
+        sbom,
         host_jobs,
         publish_jobs,
         post_announce_jobs,
@@ -753,12 +1020,20 @@ fn apply_dist_to_metadata(metadata: &mut toml_edit::Item, meta: &DistMetadata) {
         omnibor,
         // These settings are complex enough that we don't support editing them in init
         extra_artifacts: _,
+        sbom_components: _,
         github_custom_runners: _,
+        homebrew_test: _,
+        homebrew_service: _,
         github_custom_job_permissions: _,
+        gitlab_custom_runners: _,
+        gitlab_custom_job_id_tokens: _,
+        gitlab_api_url: _,
+        s3_config: _,
         bin_aliases: _,
         completion_cmds: _,
         system_dependencies: _,
         github_build_setup: _,
+        signing: _,
     } = &meta;
 
     // Forcibly inline the default install_path if not specified,
@@ -835,6 +1110,83 @@ fn apply_dist_to_metadata(metadata: &mut toml_edit::Item, meta: &DistMetadata) {
         *version_formulas,
     );
 
+    apply_optional_value(
+        table,
+        "homebrew-caveats",
+        "# Text for Homebrew to show after installing the formula\n",
+        homebrew_caveats.clone(),
+    );
+
+    apply_optional_value(
+        table,
+        "homebrew-bottles",
+        "# Whether to repackage the archives as Homebrew bottles\n",
+        *homebrew_bottles,
+    );
+
+    apply_optional_value(
+        table,
+        "scoop-bucket",
+        "# A GitHub repo to push Scoop manifests to\n",
+        scoop_bucket.clone(),
+    );
+
+    apply_optional_value(
+        table,
+        "winget-identifier",
+        "# The winget package identifier\n",
+        winget_identifier.clone(),
+    );
+
+    apply_optional_value(
+        table,
+        "winget-fork",
+        "# A GitHub fork of winget-pkgs to open pull requests from\n",
+        winget_fork.clone(),
+    );
+
+    apply_optional_value(
+        table,
+        "aur-remote",
+        "# The git remote to push AUR packages to\n",
+        aur_remote.clone(),
+    );
+
+    apply_optional_value(
+        table,
+        "nix-repo",
+        "# A GitHub repo to push Nix derivations to\n",
+        nix_repo.clone(),
+    );
+
+    apply_optional_value(
+        table,
+        "appimage-icon",
+        "# A png or svg icon to put in AppImages (path relative to this Cargo.toml's dir)\n",
+        appimage_icon.as_ref().map(|p| p.to_string()),
+    );
+
+    apply_optional_value(
+        table,
+        "appimage-desktop-file",
+        "# A .desktop file to put in AppImages (path relative to this Cargo.toml's dir)\n",
+        appimage_desktop_file.as_ref().map(|p| p.to_string()),
+    );
+
+    apply_string_list(
+        table,
+        "appimage-categories",
+        "# The categories to list the app under in the AppImage's .desktop file\n",
+        appimage_categories.as_ref(),
+    );
+
+    apply_optional_value(
+        table,
+        "appimage-terminal",
+        "# Whether the app in AppImages should be run in a terminal\n",
+        *appimage_terminal,
+    );
+
     apply_string_list(
         table,
         "targets",
@@ -898,6 +1250,13 @@ fn apply_dist_to_metadata(metadata: &mut toml_edit::Item, meta: &DistMetadata) {
         npm_scope.as_deref(),
     );
 
+    apply_optional_value(
+        table,
+        "npm-platform-packages",
+        "# Whether to publish an npm package per platform with the binaries in it\n",
+        *npm_platform_packages,
+    );
+
     apply_optional_value(
         table,
         "checksum",
@@ -905,6 +1264,13 @@ fn apply_dist_to_metadata(metadata: &mut toml_edit::Item, meta: &DistMetadata) {
         checksum.map(|c| c.ext().as_str()),
     );
 
+    apply_optional_value(
+        table,
+        "minisign-public-key",
+        "# A minisign public key to verify the signed checksums with\n",
+        minisign_public_key.as_deref(),
+    );
+
     apply_optional_value(
         table,
         "precise-builds",
@@ -1040,6 +1406,34 @@ fn apply_dist_to_metadata(metadata: &mut toml_edit::Item, meta: &DistMetadata) {
         *source_tarball,
     );
 
+    apply_optional_value(
+        table,
+        "offline-bundle",
+        "# Generate and dist an offline bundle of the installers and archives\n",
+        *offline_bundle,
+    );
+
+    apply_string_list(
+        table,
+        "signatures",
+        "# Detached signatures to generate for each archive, installer and source tarball\n",
+        signatures.as_ref(),
+    );
+
+    apply_optional_value(
+        table,
+        "provenance",
+        "# Generate and dist a SLSA provenance statement for each release\n",
+        *provenance,
+    );
+
+    apply_optional_value(
+        table,
+        "sbom",
+        "# Generate and dist CycloneDX and SPDX SBOMs for each release\n",
+        *sbom,
+    );
+
     apply_string_list(
         table,
         "host-jobs",
@@ -18,7 +18,8 @@ use axoprocess::Cmd;
 use backend::{
     ci::CiInfo,
     installer::{
-        self, macpkg::PkgInstallerInfo, msi::MsiInstallerInfo, HomebrewImpl, InstallerImpl,
+        self, appimage::AppimageInstallerInfo, deb::DebInstallerInfo, macpkg::PkgInstallerInfo,
+        msi::MsiInstallerInfo, rpm::RpmInstallerInfo, HomebrewImpl, InstallerImpl,
     },
 };
 use build::generic::{build_generic_target, run_extra_artifacts_build};
@@ -54,6 +55,8 @@ pub mod manifest;
 mod migrate;
 pub mod net;
 pub mod platform;
+pub mod provenance;
+pub mod sbom;
 pub mod sign;
 pub mod tasks;
 #[cfg(test)]
@@ -74,6 +77,14 @@ pub fn do_env_test(cfg: &Config) -> DistResult<()> {
     let need_cargo_auditable = builds.cargo.cargo_auditable && local_builds;
     // omnibor is used in both local and global builds
     let need_omnibor = builds.omnibor;
+    // appimagetool is only used to build AppImages for real
+    let need_appimagetool = cfg.artifact_mode != ArtifactMode::Lies
+        && dist.local_build_steps.iter().any(|step| {
+            matches!(
+                step,
+                BuildStep::GenerateInstaller(InstallerImpl::Appimage(_))
+            )
+        });
     let mut need_xwin = false;
     let mut need_zigbuild = false;
 
@@ -114,6 +125,7 @@ pub fn do_env_test(cfg: &Config) -> DistResult<()> {
         need_omnibor.then(|| tools.omnibor()),
         need_xwin.then(|| tools.cargo_xwin()),
         need_zigbuild.then(|| tools.cargo_zigbuild()),
+        need_appimagetool.then(|| tools.appimagetool()),
     ];
 
     // Drop `None`s, then extract the values from the remaining `Option`s.
@@ -240,10 +252,25 @@ fn run_build_step(
             checksum,
             dest_path,
         }) => generate_unified_checksum(manifest, *checksum, dest_path)?,
+        BuildStep::MinisignSignature(MinisignSignatureStep {
+            src_path,
+            dest_path,
+        }) => dist_graph.signer.minisign(src_path, dest_path)?,
+        BuildStep::DetachedSignature(DetachedSignatureStep {
+            style,
+            src_path,
+            dest_path,
+            for_artifact: _,
+        }) => dist_graph
+            .signer
+            .detached_sign(*style, src_path, dest_path)?,
+        BuildStep::Sign(step) => sign_artifact(dist_graph, manifest, step)?,
         BuildStep::OmniborArtifactId(OmniborArtifactIdImpl {
             src_path,
             dest_path,
         }) => generate_omnibor_artifact_id(dist_graph, src_path, dest_path)?,
+        BuildStep::Provenance(step) => provenance::generate_provenance(dist_graph, manifest, step)?,
+        BuildStep::GenerateSbom(step) => sbom::generate_sbom(dist_graph, manifest, step)?,
         BuildStep::GenerateSourceTarball(SourceTarballStep {
             committish,
             prefix,
@@ -314,6 +341,51 @@ pub fn create_tmp() -> DistResult<(TempDir, Utf8PathBuf)> {
     Ok((tmp_dir, tmp_root))
 }
 
+/// Encodes bytes as lowercase hex
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Decodes a hex string, returning None if it's malformed
+pub(crate) fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Splits a time into its UTC date (year, month, day) and time of day (hours, minutes, seconds)
+pub(crate) fn utc_date_time(time: std::time::SystemTime) -> ((i64, i64, i64), (u64, u64, u64)) {
+    let secs = time
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (days, secs_of_day) = (secs / 86400, secs % 86400);
+    // Convert days since the epoch to a civil date
+    // (see http://howardhinnant.github.io/date_algorithms.html#civil_from_days)
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (
+        (year, month, day),
+        (
+            secs_of_day / 3600,
+            secs_of_day % 3600 / 60,
+            secs_of_day % 60,
+        ),
+    )
+}
+
 /// Fetches an installer executable from a preexisting binary and installs it in the expected target path.
 fn fetch_updater_from_binary(
     dist_graph: &DistGraph,
@@ -392,6 +464,13 @@ fn build_fake(
             // MSI and pkg, unlike other installers, aren't safe to generate on any platform
             InstallerImpl::Msi(msi) => generate_fake_msi(dist_graph, msi, manifest)?,
             InstallerImpl::Pkg(pkg) => generate_fake_pkg(dist_graph, pkg, manifest)?,
+            // debs and rpms package up the binaries, which we didn't really build
+            InstallerImpl::Deb(deb) => generate_fake_deb(dist_graph, deb, manifest)?,
+            InstallerImpl::Rpm(rpm) => generate_fake_rpm(dist_graph, rpm, manifest)?,
+            // AppImages need appimagetool, which we can't expect to have
+            InstallerImpl::Appimage(appimage) => {
+                generate_fake_appimage(dist_graph, appimage, manifest)?
+            }
             _ => generate_installer(dist_graph, installer, manifest)?,
         },
         BuildStep::Checksum(ChecksumImpl {
@@ -410,10 +489,19 @@ fn build_fake(
             checksum,
             dest_path,
         }) => generate_unified_checksum(manifest, *checksum, dest_path)?,
+        BuildStep::MinisignSignature(MinisignSignatureStep { dest_path, .. })
+        | BuildStep::DetachedSignature(DetachedSignatureStep { dest_path, .. }) => {
+            generate_fake_signature(dest_path)?
+        }
+        // Never run signing providers, they need real files and real secrets
+        BuildStep::Sign(_) => {}
         BuildStep::OmniborArtifactId(OmniborArtifactIdImpl {
             src_path,
             dest_path,
         }) => generate_omnibor_artifact_id(dist_graph, src_path, dest_path)?,
+        BuildStep::Provenance(step) => provenance::generate_provenance(dist_graph, manifest, step)?,
+        // SBOMs may need to run cargo metadata against real dependencies
+        BuildStep::GenerateSbom(step) => generate_fake_sbom(step)?,
         // Except source tarballs, which are definitely not okay
         // We mock these because it requires:
         // 1. git to be installed;
@@ -463,6 +551,60 @@ fn generate_fake_pkg(
     Ok(())
 }
 
+fn generate_fake_deb(
+    _dist: &DistGraph,
+    deb: &DebInstallerInfo,
+    _manifest: &DistManifest,
+) -> DistResult<()> {
+    LocalAsset::write_new_all(
+        "this is a fake .deb made by `dist build --artifacts=lies`\n",
+        &deb.file_path,
+    )?;
+
+    Ok(())
+}
+
+fn generate_fake_rpm(
+    _dist: &DistGraph,
+    rpm: &RpmInstallerInfo,
+    _manifest: &DistManifest,
+) -> DistResult<()> {
+    LocalAsset::write_new_all(
+        "this is a fake .rpm made by `dist build --artifacts=lies`\n",
+        &rpm.file_path,
+    )?;
+
+    Ok(())
+}
+
+fn generate_fake_signature(dest_path: &Utf8Path) -> DistResult<()> {
+    // Signing needs real secrets, so just mark where the signature would go
+    LocalAsset::write_new_all(
+        "this is a fake signature made by `dist build --artifacts=lies`\n",
+        dest_path,
+    )?;
+    Ok(())
+}
+
+fn generate_fake_sbom(step: &GenerateSbomStep) -> DistResult<()> {
+    LocalAsset::write_new_all("", &step.dest_path)?;
+
+    Ok(())
+}
+
+fn generate_fake_appimage(
+    _dist: &DistGraph,
+    appimage: &AppimageInstallerInfo,
+    _manifest: &DistManifest,
+) -> DistResult<()> {
+    LocalAsset::write_new_all(
+        "this is a fake .AppImage made by `dist build --artifacts=lies`\n",
+        &appimage.file_path,
+    )?;
+
+    Ok(())
+}
+
 fn generate_omnibor_artifact_id(
     dist_graph: &DistGraph,
     src_path: &Utf8Path,
@@ -506,6 +648,21 @@ fn generate_and_write_checksum(
     Ok(())
 }
 
+/// Sign an artifact in place and record who signed it in the manifest
+fn sign_artifact(dist: &DistGraph, manifest: &mut DistManifest, step: &SignStep) -> DistResult<()> {
+    let SignStep {
+        kind,
+        target,
+        src_path,
+        for_artifact,
+    } = step;
+    let signed_by = dist.signer.sign(*kind, target.as_deref(), src_path)?;
+    if let Some(artifact) = manifest.artifacts.get_mut(for_artifact) {
+        artifact.signed_by.extend(signed_by);
+    }
+    Ok(())
+}
+
 /// Collect all checksums for all artifacts and write them to a unified checksum file
 fn generate_unified_checksum(
     manifest: &DistManifest,
@@ -537,7 +694,6 @@ fn generate_unified_checksum(
 fn generate_checksum(checksum: &ChecksumStyle, src_path: &Utf8Path) -> DistResult<ChecksumValue> {
     info!("generating {checksum:?} for {src_path}");
     use sha2::Digest;
-    use std::fmt::Write;
 
     let file_bytes = axoasset::LocalAsset::load_bytes(src_path.as_str())?;
 
@@ -576,11 +732,7 @@ fn generate_checksum(checksum: &ChecksumStyle, src_path: &Utf8Path) -> DistResul
             unreachable!()
         }
     };
-    let mut output = String::with_capacity(hash.len() * 2);
-    for byte in hash {
-        write!(&mut output, "{byte:02x}").unwrap();
-    }
-    Ok(ChecksumValue::new(output))
+    Ok(ChecksumValue::new(encode_hex(&hash)))
 }
 
 /// Creates a source code tarball from the git archive from
@@ -794,7 +946,7 @@ pub fn run_generate(dist: &DistGraph, args: &GenerateArgs) -> DistResult<()> {
             match mode {
                 GenerateMode::Ci => {
                     // If you add a CI backend, call it here
-                    let CiInfo { github } = &dist.ci;
+                    let CiInfo { github, gitlab } = &dist.ci;
                     if let Some(github) = github {
                         if args.check {
                             github.check(dist)?;
@@ -802,6 +954,13 @@ pub fn run_generate(dist: &DistGraph, args: &GenerateArgs) -> DistResult<()> {
                             github.write_to_disk(dist)?;
                         }
                     }
+                    if let Some(gitlab) = gitlab {
+                        if args.check {
+                            gitlab.check(dist)?;
+                        } else {
+                            gitlab.write_to_disk(dist)?;
+                        }
+                    }
                 }
                 GenerateMode::Msi => {
                     for artifact in &dist.artifacts {
@@ -885,11 +1044,22 @@ fn generate_installer(
             installer::powershell::write_install_ps_script(dist, info)?
         }
         InstallerImpl::Npm(info) => installer::npm::write_npm_project(dist, info)?,
+        InstallerImpl::NpmPlatform(info) => installer::npm::write_npm_platform_package(info)?,
         InstallerImpl::Homebrew(HomebrewImpl { info, fragments }) => {
             installer::homebrew::write_homebrew_formula(dist, info, fragments, manifest)?
         }
-        InstallerImpl::Msi(info) => info.build(dist)?,
+        InstallerImpl::HomebrewBottle(info) => info.build()?,
+        InstallerImpl::Msi(info) => info.build()?,
         InstallerImpl::Pkg(info) => info.build()?,
+        InstallerImpl::Deb(info) => info.build(manifest)?,
+        InstallerImpl::Rpm(info) => info.build()?,
+        InstallerImpl::Scoop(info) => info.build(manifest)?,
+        InstallerImpl::Winget(info) => info.build(manifest)?,
+        InstallerImpl::Chocolatey(info) => info.build(dist, manifest)?,
+        InstallerImpl::Aur(info) => info.build(manifest)?,
+        InstallerImpl::Nix(info) => info.build(dist, manifest)?,
+        InstallerImpl::Pypi(info) => info.build()?,
+        InstallerImpl::Appimage(info) => info.build(dist, manifest)?,
     }
     Ok(())
 }
@@ -92,6 +92,7 @@ fn compute_linkage_assuming_local_build(
                             system: dist.system_id.clone(),
                             linkage: Some(linkage),
                             target_triples: vec![target.clone()],
+                            signed_by: vec![],
                         },
                     );
                 }
@@ -47,9 +47,16 @@ use crate::{
     announce::AnnouncementTag,
     backend::{
         installer::{
-            homebrew::HomebrewInstallerInfo, npm::NpmInstallerInfo, HomebrewImpl, InstallerImpl,
+            aur::{AurFileType, AurInstallerInfo},
+            chocolatey::ChocolateyInstallerInfo,
+            homebrew::HomebrewInstallerInfo,
+            nix::NixInstallerInfo,
+            npm::NpmInstallerInfo,
+            scoop::ScoopInstallerInfo,
+            winget::{WingetInstallerInfo, WingetManifestType},
+            HomebrewImpl, InstallerImpl,
         },
-        templates::{TemplateEntry, TEMPLATE_INSTALLER_NPM},
+        templates::{TemplateEntry, TEMPLATE_INSTALLER_NPM, TEMPLATE_INSTALLER_NPM_PLATFORMS},
     },
     config::Config,
     errors::DistResult,
@@ -104,13 +111,24 @@ pub(crate) fn load_and_merge_manifests(
             let out_release =
                 output.ensure_release(release.app_name.clone(), release.app_version.clone());
             // If the input has hosting info, apply it
-            let Hosting { axodotdev, github } = release.hosting;
+            let Hosting {
+                axodotdev,
+                github,
+                gitlab,
+                s3,
+            } = release.hosting;
             if let Some(hosting) = axodotdev {
                 out_release.hosting.axodotdev = Some(hosting);
             }
             if let Some(hosting) = github {
                 out_release.hosting.github = Some(hosting);
             }
+            if let Some(hosting) = gitlab {
+                out_release.hosting.gitlab = Some(hosting);
+            }
+            if let Some(hosting) = s3 {
+                out_release.hosting.s3 = Some(hosting);
+            }
             // If the input has a list of artifacts for this release, merge them
             for artifact in release.artifacts {
                 if !out_release.artifacts.contains(&artifact) {
@@ -154,6 +172,20 @@ fn merge_artifact(output: &mut DistManifest, artifact_id: ArtifactId, artifact:
             // Merge checksums
             out_artifact.checksums.extend(artifact.checksums);
 
+            // Merge signatures
+            for signing in artifact.signed_by {
+                if !out_artifact.signed_by.contains(&signing) {
+                    out_artifact.signed_by.push(signing);
+                }
+            }
+
+            // Merge detached signatures
+            for signature in artifact.signatures {
+                if !out_artifact.signatures.contains(&signature) {
+                    out_artifact.signatures.push(signature);
+                }
+            }
+
             // Merge assets
             for asset in artifact.assets {
                 if let Some(out_asset) = out_artifact
@@ -298,10 +330,15 @@ fn add_manifest_artifact(
     //
     // These can't be pre-included in the normal static assets list above because
     // they're generated from templates, and not copied from the user's project.
-    if let ArtifactKind::Installer(InstallerImpl::Npm(..)) = &artifact.kind {
+    if let ArtifactKind::Installer(InstallerImpl::Npm(info)) = &artifact.kind {
+        let template = if info.platform_packages.is_some() {
+            TEMPLATE_INSTALLER_NPM_PLATFORMS
+        } else {
+            TEMPLATE_INSTALLER_NPM
+        };
         let root_dir = dist
             .templates
-            .get_template_dir(TEMPLATE_INSTALLER_NPM)
+            .get_template_dir(template)
             .expect("npm template missing!?");
         let mut queue = vec![root_dir];
         while let Some(dir) = queue.pop() {
@@ -356,6 +393,16 @@ fn add_manifest_artifact(
             description = Some(info.desc.clone());
             kind = dist_schema::ArtifactKind::Installer;
         }
+        ArtifactKind::Installer(InstallerImpl::NpmPlatform(..)) => {
+            install_hint = None;
+            description = Some("install via npm".to_owned());
+            kind = dist_schema::ArtifactKind::Installer;
+        }
+        ArtifactKind::Installer(InstallerImpl::HomebrewBottle(..)) => {
+            install_hint = None;
+            description = Some("install via Homebrew".to_owned());
+            kind = dist_schema::ArtifactKind::Installer;
+        }
         ArtifactKind::Installer(InstallerImpl::Msi(..)) => {
             install_hint = None;
             description = Some("install via msi".to_owned());
@@ -366,6 +413,77 @@ fn add_manifest_artifact(
             description = Some("install via pkg".to_owned());
             kind = dist_schema::ArtifactKind::Installer;
         }
+        ArtifactKind::Installer(InstallerImpl::Deb(..)) => {
+            install_hint = None;
+            description = Some("install via deb".to_owned());
+            kind = dist_schema::ArtifactKind::Installer;
+        }
+        ArtifactKind::Installer(InstallerImpl::Rpm(..)) => {
+            install_hint = None;
+            description = Some("install via rpm".to_owned());
+            kind = dist_schema::ArtifactKind::Installer;
+        }
+        ArtifactKind::Installer(InstallerImpl::Pypi(..)) => {
+            install_hint = None;
+            description = Some("install via pip".to_owned());
+            kind = dist_schema::ArtifactKind::Installer;
+        }
+        ArtifactKind::Installer(InstallerImpl::Appimage(..)) => {
+            install_hint = None;
+            description = Some("portable AppImage".to_owned());
+            kind = dist_schema::ArtifactKind::Installer;
+        }
+        ArtifactKind::Installer(InstallerImpl::Scoop(ScoopInstallerInfo { hint, .. })) => {
+            install_hint = Some(hint.clone());
+            description = Some("Install prebuilt binaries via Scoop".to_owned());
+            kind = dist_schema::ArtifactKind::Installer;
+        }
+        ArtifactKind::Installer(InstallerImpl::Winget(WingetInstallerInfo {
+            manifest_type,
+            identifier,
+            ..
+        })) => {
+            // Only one manifest in the set needs to advertise itself
+            if *manifest_type == WingetManifestType::Installer {
+                install_hint = Some(format!("winget install --id {identifier}"));
+                description = Some("Install prebuilt binaries via winget".to_owned());
+            } else {
+                install_hint = None;
+                description = None;
+            }
+            kind = dist_schema::ArtifactKind::Installer;
+        }
+        ArtifactKind::Installer(InstallerImpl::Chocolatey(ChocolateyInstallerInfo {
+            package_id,
+            ..
+        })) => {
+            install_hint = Some(format!("choco install {package_id} --source ."));
+            description = Some("Install prebuilt binaries via Chocolatey".to_owned());
+            kind = dist_schema::ArtifactKind::Installer;
+        }
+        ArtifactKind::Installer(InstallerImpl::Aur(AurInstallerInfo {
+            file_type,
+            pkgname,
+            ..
+        })) => {
+            // Only one file in the package needs to advertise itself
+            if *file_type == AurFileType::Pkgbuild {
+                install_hint = Some(format!("yay -S {pkgname}"));
+                description = Some("Install prebuilt binaries via the AUR".to_owned());
+            } else {
+                install_hint = None;
+                description = None;
+            }
+            kind = dist_schema::ArtifactKind::Installer;
+        }
+        ArtifactKind::Installer(InstallerImpl::Nix(NixInstallerInfo { file_path, .. })) => {
+            let file_name = file_path.file_name().unwrap_or_default();
+            install_hint = Some(format!(
+                "nix-build -E 'with import <nixpkgs> {{ }}; callPackage ./{file_name} {{ }}'"
+            ));
+            description = Some("Install prebuilt binaries via Nix".to_owned());
+            kind = dist_schema::ArtifactKind::Installer;
+        }
         ArtifactKind::Checksum(_) => {
             install_hint = None;
             description = None;
@@ -376,11 +494,21 @@ fn add_manifest_artifact(
             description = None;
             kind = dist_schema::ArtifactKind::UnifiedChecksum;
         }
+        ArtifactKind::MinisignSignature(_) | ArtifactKind::DetachedSignature(_) => {
+            install_hint = None;
+            description = None;
+            kind = dist_schema::ArtifactKind::Signature;
+        }
         ArtifactKind::SourceTarball(_) => {
             install_hint = None;
             description = None;
             kind = dist_schema::ArtifactKind::SourceTarball;
         }
+        ArtifactKind::OfflineBundle(_) => {
+            install_hint = None;
+            description = Some("install without internet access".to_owned());
+            kind = dist_schema::ArtifactKind::OfflineBundle;
+        }
         ArtifactKind::ExtraArtifact(_) => {
             install_hint = None;
             description = None;
@@ -401,9 +529,25 @@ fn add_manifest_artifact(
             description = None;
             kind = dist_schema::ArtifactKind::OmniborArtifactId;
         }
+        ArtifactKind::Provenance(_) => {
+            install_hint = None;
+            description = None;
+            kind = dist_schema::ArtifactKind::Provenance;
+        }
     };
 
     let checksum = artifact.checksum.map(|idx| dist.artifact(idx).id.clone());
+    let signatures = dist
+        .artifacts
+        .iter()
+        .filter(|signature| {
+            matches!(
+                &signature.kind,
+                ArtifactKind::DetachedSignature(step) if step.for_artifact == artifact.id
+            )
+        })
+        .map(|signature| signature.id.clone())
+        .collect();
 
     let out_artifact = dist_schema::Artifact {
         name: Some(artifact.id.clone()),
@@ -419,6 +563,8 @@ fn add_manifest_artifact(
         kind,
         checksum,
         checksums: Default::default(),
+        signed_by: Default::default(),
+        signatures,
     };
 
     if !cfg.no_local_paths {
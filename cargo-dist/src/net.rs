@@ -1,7 +1,11 @@
 //! Centralized logic for initializing http clients to
 //! ensure uniform configuration.
 
+use crate::config::SigstoreConfig;
 use crate::errors::DistResult;
+use crate::host::gitlab::{GitlabClient, GitlabToken};
+use crate::host::s3::{S3Client, S3Credentials};
+use crate::sign::SigstoreClient;
 use axoasset::reqwest;
 
 /// Settings for http clients
@@ -52,3 +56,36 @@ pub fn create_gazenot_client(
     let client = gazenot::Gazenot::into_the_abyss(source_host, owner)?;
     Ok(client)
 }
+
+/// Create a GitLab client
+///
+/// Like Gazenot clients, creating one will error out if the environment
+/// doesn't have the credentials it needs, so this should only be called
+/// when we know for sure we HAVE to talk to the GitLab API.
+pub fn create_gitlab_client(settings: &ClientSettings) -> DistResult<GitlabClient> {
+    let token = GitlabToken::from_env()?;
+    let client = create_reqwest_client(settings)?;
+    Ok(GitlabClient::new(client, token))
+}
+
+/// Create an S3 client
+///
+/// Errors out if the environment doesn't have credentials for the bucket,
+/// so this should only be called when we're actually going to upload.
+pub fn create_s3_client(settings: &ClientSettings) -> DistResult<S3Client> {
+    let credentials = S3Credentials::from_env()?;
+    let client = create_reqwest_client(settings)?;
+    Ok(S3Client::new(client, credentials))
+}
+
+/// Create a Sigstore client for the given instance
+///
+/// This doesn't need any credentials up front, the OIDC token is only
+/// fetched when something actually gets signed.
+pub fn create_sigstore_client(
+    settings: &ClientSettings,
+    config: &SigstoreConfig,
+) -> DistResult<SigstoreClient> {
+    let client = create_reqwest_client(settings)?;
+    Ok(SigstoreClient::new(client, config.clone()))
+}
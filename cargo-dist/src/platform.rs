@@ -568,30 +568,83 @@ impl PlatformSupport {
                 continue;
             };
             let archive = &self.archives[option.archive_idx];
-            let updater = if let Some(updater_idx) = archive.updater {
-                let updater = &self.updaters[updater_idx];
-                Some(UpdaterFragment {
-                    id: updater.id.clone(),
-                    binary: updater.binary.clone(),
-                })
-            } else {
-                None
-            };
-            let fragment = ExecutableZipFragment {
-                id: archive.id.clone(),
-                target_triple: target.clone(),
-                zip_style: archive.zip_style,
-                executables: archive.executables.clone(),
-                cdylibs: archive.cdylibs.clone(),
-                cstaticlibs: archive.cstaticlibs.clone(),
-                runtime_conditions: option.runtime_conditions.clone(),
-                updater,
+            fragments.push(self.fragment(
+                archive,
+                target.clone(),
+                option.runtime_conditions.clone(),
+            ));
+        }
+        fragments
+    }
+
+    /// Like [`Self::fragments`][] but only for platforms that have a natively built archive
+    ///
+    /// Use this for installers that can't express fallbacks themselves, so that they don't
+    /// e.g. hand x64 binaries to arm64 Windows, which the platform would then claim to support.
+    pub fn native_fragments(&self) -> Vec<ExecutableZipFragment> {
+        let mut fragments = vec![];
+        for (target, options) in &self.platforms {
+            let Some(option) = options.first() else {
+                continue;
             };
-            fragments.push(fragment);
+            if !matches!(
+                option.quality,
+                SupportQuality::HostNative | SupportQuality::BulkyNative
+            ) {
+                continue;
+            }
+            let archive = &self.archives[option.archive_idx];
+            fragments.push(self.fragment(
+                archive,
+                target.clone(),
+                option.runtime_conditions.clone(),
+            ));
         }
         fragments
     }
 
+    /// Get the fragment for the archive that was built for the given target
+    ///
+    /// Unlike looking the target up in [`Self::fragments`][], this finds archives
+    /// whose platforms are keyed by a more specific name (e.g. musl archives,
+    /// which are listed as "-musl-static" or "-musl-dynamic").
+    pub fn fragment_for_target(&self, target: &TripleNameRef) -> Option<ExecutableZipFragment> {
+        let archive = self
+            .archives
+            .iter()
+            .find(|archive| archive.target_triples.iter().any(|t| t == target))?;
+        Some(self.fragment(
+            archive,
+            target.to_owned(),
+            archive.native_runtime_conditions.clone(),
+        ))
+    }
+
+    fn fragment(
+        &self,
+        archive: &FetchableArchive,
+        target_triple: TripleName,
+        runtime_conditions: RuntimeConditions,
+    ) -> ExecutableZipFragment {
+        let updater = archive.updater.map(|updater_idx| {
+            let updater = &self.updaters[updater_idx];
+            UpdaterFragment {
+                id: updater.id.clone(),
+                binary: updater.binary.clone(),
+            }
+        });
+        ExecutableZipFragment {
+            id: archive.id.clone(),
+            target_triple,
+            zip_style: archive.zip_style,
+            executables: archive.executables.clone(),
+            cdylibs: archive.cdylibs.clone(),
+            cstaticlibs: archive.cstaticlibs.clone(),
+            runtime_conditions,
+            updater,
+        }
+    }
+
     /// Conflate all the options that `fragments` suggests to create a single unified
     /// RuntimeConditions that can be used in installers while we transition to implementations
     /// that more granularly factor in these details.
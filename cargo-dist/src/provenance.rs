@@ -0,0 +1,453 @@
+//! SLSA provenance statements
+//!
+//! A provenance statement is an [in-toto statement][statement] whose predicate
+//! is [SLSA v1 provenance][slsa]: it lists every file in a release with its
+//! digests, and describes what they were built from (the source commit and
+//! the dist-manifest build plan) and what they were built with (the tools on
+//! this machine, and the environment of every machine that did a build).
+//!
+//! Everything in it comes from the merged dist-manifest, so it works the same
+//! no matter which CI (if any) is running the build. CI-specific details like
+//! the run's URL are only added when we recognize the CI.
+//!
+//! [statement]: https://github.com/in-toto/attestation/blob/main/spec/v1/statement.md
+//! [slsa]: https://slsa.dev/spec/v1.0/provenance
+
+use std::collections::BTreeMap;
+
+use axoasset::LocalAsset;
+use camino::Utf8Path;
+use dist_schema::{DistManifest, SystemId, SystemInfo};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::{
+    config::ChecksumStyle, errors::*, generate_checksum, DistGraph, ProvenanceStep, Tools,
+};
+
+const STATEMENT_TYPE: &str = "https://in-toto.io/Statement/v1";
+const PREDICATE_TYPE: &str = "https://slsa.dev/provenance/v1";
+/// The build type, which is documented at this url
+const BUILD_TYPE: &str =
+    "https://opensource.axo.dev/cargo-dist/book/supplychain-security/attestations/slsa-provenance.html";
+/// The builder to claim when we don't recognize the CI (or there's no CI)
+const LOCAL_BUILDER_ID: &str = "https://opensource.axo.dev/cargo-dist/local-build";
+
+/// An in-toto statement
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Statement<'a> {
+    #[serde(rename = "_type")]
+    type_: &'static str,
+    subject: Vec<ResourceDescriptor>,
+    predicate_type: &'static str,
+    predicate: Provenance<'a>,
+}
+
+/// A SLSA v1 provenance predicate
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Provenance<'a> {
+    build_definition: BuildDefinition<'a>,
+    run_details: RunDetails,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BuildDefinition<'a> {
+    build_type: &'static str,
+    external_parameters: ExternalParameters<'a>,
+    internal_parameters: InternalParameters<'a>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    resolved_dependencies: Vec<ResourceDescriptor>,
+}
+
+/// The inputs to the build that the user controls
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExternalParameters<'a> {
+    /// The tag being released
+    tag: &'a str,
+    /// The app being released
+    release: ReleaseParameters<'a>,
+    /// The build plan
+    plan: &'a DistManifest,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReleaseParameters<'a> {
+    name: &'a str,
+    version: &'a str,
+}
+
+/// The inputs to the build that dist picked
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InternalParameters<'a> {
+    /// Every machine that built part of the release, and its environment
+    systems: &'a BTreeMap<SystemId, SystemInfo>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RunDetails {
+    builder: Builder,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<BuildMetadata>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Builder {
+    id: String,
+    /// The tools that were found on this machine, and their versions
+    version: BTreeMap<&'static str, String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BuildMetadata {
+    invocation_id: String,
+}
+
+/// An in-toto resource descriptor (a file, or some source code)
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResourceDescriptor {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uri: Option<String>,
+    digest: BTreeMap<String, String>,
+}
+
+/// What we know about the CI running this build
+#[derive(Debug, Default)]
+struct CiRun {
+    builder_id: Option<String>,
+    invocation_id: Option<String>,
+}
+
+impl CiRun {
+    /// Detect the CI from its environment variables
+    fn from_env() -> Self {
+        let var = |name| std::env::var(name).ok().filter(|val| !val.is_empty());
+
+        if var("GITHUB_ACTIONS").as_deref() == Some("true") {
+            let server =
+                var("GITHUB_SERVER_URL").unwrap_or_else(|| "https://github.com".to_owned());
+            let builder_id =
+                var("GITHUB_WORKFLOW_REF").map(|workflow| format!("{server}/{workflow}"));
+            let invocation_id = match (
+                var("GITHUB_REPOSITORY"),
+                var("GITHUB_RUN_ID"),
+                var("GITHUB_RUN_ATTEMPT"),
+            ) {
+                (Some(repo), Some(run), Some(attempt)) => Some(format!(
+                    "{server}/{repo}/actions/runs/{run}/attempts/{attempt}"
+                )),
+                _ => None,
+            };
+            Self {
+                builder_id,
+                invocation_id,
+            }
+        } else if var("GITLAB_CI").as_deref() == Some("true") {
+            let builder_id = match (
+                var("CI_SERVER_URL"),
+                var("CI_PROJECT_PATH"),
+                var("CI_RUNNER_ID"),
+            ) {
+                (Some(server), Some(project), Some(runner)) => {
+                    Some(format!("{server}/{project}/-/runners/{runner}"))
+                }
+                _ => None,
+            };
+            Self {
+                builder_id,
+                invocation_id: var("CI_JOB_URL"),
+            }
+        } else {
+            Self::default()
+        }
+    }
+}
+
+/// Generate the provenance statement for a release, from the merged dist-manifest
+pub fn generate_provenance(
+    dist: &DistGraph,
+    manifest: &DistManifest,
+    step: &ProvenanceStep,
+) -> DistResult<()> {
+    info!("generating provenance statement for {}", step.app_name);
+    let statement = provenance_statement(
+        manifest,
+        step,
+        &dist.dist_dir,
+        tool_versions(&dist.tools),
+        CiRun::from_env(),
+    )?;
+    // in-toto statements are stored one per line, so there can be several in a file
+    let mut contents =
+        serde_json::to_string(&statement).expect("failed to serialize provenance statement");
+    contents.push('\n');
+    LocalAsset::write_new_all(&contents, &step.dest_path)?;
+
+    Ok(())
+}
+
+fn provenance_statement<'a>(
+    manifest: &'a DistManifest,
+    step: &'a ProvenanceStep,
+    dist_dir: &Utf8Path,
+    tools: BTreeMap<&'static str, String>,
+    ci: CiRun,
+) -> DistResult<Statement<'a>> {
+    let subject = subjects(manifest, step, dist_dir)?;
+
+    let resolved_dependencies = step
+        .source_commit
+        .iter()
+        .map(|commit| ResourceDescriptor {
+            name: None,
+            uri: step
+                .source_repo
+                .as_ref()
+                .map(|repo| format!("git+{repo}@refs/tags/{}", step.tag)),
+            digest: BTreeMap::from_iter([("gitCommit".to_owned(), commit.clone())]),
+        })
+        .collect();
+
+    Ok(Statement {
+        type_: STATEMENT_TYPE,
+        subject,
+        predicate_type: PREDICATE_TYPE,
+        predicate: Provenance {
+            build_definition: BuildDefinition {
+                build_type: BUILD_TYPE,
+                external_parameters: ExternalParameters {
+                    tag: &step.tag,
+                    release: ReleaseParameters {
+                        name: &step.app_name,
+                        version: &step.app_version,
+                    },
+                    plan: manifest,
+                },
+                internal_parameters: InternalParameters {
+                    systems: &manifest.systems,
+                },
+                resolved_dependencies,
+            },
+            run_details: RunDetails {
+                builder: Builder {
+                    id: ci.builder_id.unwrap_or_else(|| LOCAL_BUILDER_ID.to_owned()),
+                    version: tools,
+                },
+                metadata: ci
+                    .invocation_id
+                    .map(|invocation_id| BuildMetadata { invocation_id }),
+            },
+        },
+    })
+}
+
+/// Every file in the release, with its digests
+///
+/// Digests come from the checksums in the manifest (which were computed by
+/// whichever machine built the file), and if there's no sha256 among them we
+/// hash the file ourselves, if we have it.
+fn subjects(
+    manifest: &DistManifest,
+    step: &ProvenanceStep,
+    dist_dir: &Utf8Path,
+) -> DistResult<Vec<ResourceDescriptor>> {
+    let own_name = step.dest_path.file_name();
+    let Some(release) = manifest
+        .releases
+        .iter()
+        .find(|r| r.app_name == step.app_name && r.app_version == step.app_version)
+    else {
+        return Ok(vec![]);
+    };
+
+    let mut subjects = vec![];
+    for artifact_id in &release.artifacts {
+        let Some(name) = manifest
+            .artifacts
+            .get(artifact_id)
+            .and_then(|artifact| artifact.name.as_ref())
+        else {
+            continue;
+        };
+        if Some(name.as_str()) == own_name {
+            continue;
+        }
+
+        // in-toto spells the sha3 digests with underscores, but otherwise agrees with us
+        let mut digest = manifest.artifacts[artifact_id]
+            .checksums
+            .iter()
+            .map(|(ext, value)| (ext.as_str().replace('-', "_"), value.as_str().to_owned()))
+            .collect::<BTreeMap<_, _>>();
+        let path = dist_dir.join(name.as_str());
+        if !digest.contains_key("sha256") && path.exists() {
+            let value = generate_checksum(&ChecksumStyle::Sha256, &path)?;
+            digest.insert("sha256".to_owned(), value.as_str().to_owned());
+        }
+        if digest.is_empty() {
+            warn!("{name} isn't in the dist dir and has no checksums, leaving it out of the provenance statement");
+            continue;
+        }
+
+        subjects.push(ResourceDescriptor {
+            name: Some(name.to_string()),
+            uri: None,
+            digest,
+        });
+    }
+
+    Ok(subjects)
+}
+
+/// The versions of the tools on this machine
+fn tool_versions(tools: &Tools) -> BTreeMap<&'static str, String> {
+    let Tools {
+        host_target: _,
+        cargo,
+        rustup,
+        brew,
+        git,
+        omnibor,
+        code_sign_tool,
+        cargo_auditable,
+        cargo_cyclonedx,
+        cargo_xwin,
+        cargo_zigbuild,
+        appimagetool,
+    } = tools;
+
+    let mut versions = BTreeMap::from_iter([("dist", env!("CARGO_PKG_VERSION").to_owned())]);
+    if let Some(version_line) = cargo.as_ref().and_then(|cargo| cargo.version_line.as_ref()) {
+        versions.insert("cargo", version_line.clone());
+    }
+    for (name, tool) in [
+        ("rustup", rustup),
+        ("brew", brew),
+        ("git", git),
+        ("omnibor-cli", omnibor),
+        ("CodeSignTool", code_sign_tool),
+        ("cargo-auditable", cargo_auditable),
+        ("cargo-cyclonedx", cargo_cyclonedx),
+        ("cargo-xwin", cargo_xwin),
+        ("cargo-zigbuild", cargo_zigbuild),
+        ("appimagetool", appimagetool),
+    ] {
+        if let Some(tool) = tool {
+            versions.insert(name, tool.version.clone());
+        }
+    }
+    versions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dist_schema::{Artifact, ArtifactId, ArtifactKind, ChecksumExtension, ChecksumValue};
+
+    fn artifact(name: &str) -> Artifact {
+        Artifact {
+            name: Some(ArtifactId::new(name.to_owned())),
+            kind: ArtifactKind::ExecutableZip,
+            target_triples: vec![],
+            path: None,
+            assets: vec![],
+            install_hint: None,
+            description: None,
+            checksum: None,
+            checksums: Default::default(),
+            signed_by: Default::default(),
+            signatures: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_provenance_statement() {
+        let (_tmp, dist_dir) = crate::create_tmp().unwrap();
+
+        let mut manifest = DistManifest::new(vec![], Default::default());
+        let mut archive = artifact("app-x86_64-unknown-linux-gnu.tar.xz");
+        archive.checksums.insert(
+            ChecksumExtension::new("sha3-256".to_owned()),
+            ChecksumValue::new("abcd".to_owned()),
+        );
+        // sha256 of the empty string
+        LocalAsset::write_new_all("", dist_dir.join("app-installer.sh")).unwrap();
+        for artifact in [
+            archive,
+            artifact("app-installer.sh"),
+            artifact("app.intoto.jsonl"),
+            artifact("missing.tar.gz"),
+        ] {
+            let id = artifact.name.clone().unwrap();
+            manifest.artifacts.insert(id.clone(), artifact);
+            manifest
+                .ensure_release("app".to_owned(), "1.0.0".to_owned())
+                .artifacts
+                .push(id);
+        }
+
+        let step = ProvenanceStep {
+            app_name: "app".to_owned(),
+            app_version: "1.0.0".to_owned(),
+            tag: "v1.0.0".to_owned(),
+            source_repo: Some("https://github.com/owner/app".to_owned()),
+            source_commit: Some("0123456789abcdef".to_owned()),
+            dest_path: dist_dir.join("app.intoto.jsonl"),
+        };
+        let ci = CiRun {
+            builder_id: None,
+            invocation_id: Some("https://ci.example.com/runs/1".to_owned()),
+        };
+        let statement =
+            provenance_statement(&manifest, &step, &dist_dir, Default::default(), ci).unwrap();
+        let json = serde_json::to_value(&statement).unwrap();
+
+        assert_eq!(json["_type"], STATEMENT_TYPE);
+        assert_eq!(json["predicateType"], PREDICATE_TYPE);
+        assert_eq!(
+            json["subject"],
+            serde_json::json!([
+                {
+                    "name": "app-x86_64-unknown-linux-gnu.tar.xz",
+                    "digest": { "sha3_256": "abcd" },
+                },
+                {
+                    "name": "app-installer.sh",
+                    "digest": {
+                        "sha256": "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+                    },
+                },
+            ])
+        );
+        let definition = &json["predicate"]["buildDefinition"];
+        assert_eq!(
+            definition["resolvedDependencies"],
+            serde_json::json!([{
+                "uri": "git+https://github.com/owner/app@refs/tags/v1.0.0",
+                "digest": { "gitCommit": "0123456789abcdef" },
+            }])
+        );
+        assert_eq!(definition["externalParameters"]["tag"], "v1.0.0");
+        assert_eq!(
+            definition["externalParameters"]["plan"]["releases"][0]["app_name"],
+            "app"
+        );
+        let run = &json["predicate"]["runDetails"];
+        assert_eq!(run["builder"]["id"], LOCAL_BUILDER_ID);
+        assert_eq!(
+            run["metadata"]["invocationId"],
+            "https://ci.example.com/runs/1"
+        );
+    }
+}
@@ -0,0 +1,277 @@
+//! Finding the dependencies of a cargo package
+
+use std::collections::{BTreeMap, VecDeque};
+
+use camino::Utf8Path;
+use cargo_metadata::{CargoOpt, DependencyKind, Metadata, MetadataCommand, Package, PackageId};
+
+use super::{normalize_license, Bom, Component};
+use crate::{errors::*, CargoTargetFeatureList, CargoTargetFeatures};
+
+/// Add everything the package depends on to the SBOM
+///
+/// Only normal dependencies end up in the SBOM (not dev or build ones), but for
+/// every platform, as a single SBOM describes the release for all of its targets.
+pub(crate) fn add_dependencies(
+    bom: &mut Bom,
+    cargo: &str,
+    manifest_path: &Utf8Path,
+    package_id: &str,
+    features: &CargoTargetFeatures,
+) -> DistResult<()> {
+    let mut command = MetadataCommand::new();
+    command.cargo_path(cargo).manifest_path(manifest_path);
+    if !features.default_features {
+        command.features(CargoOpt::NoDefaultFeatures);
+    }
+    match &features.features {
+        CargoTargetFeatureList::All => {
+            command.features(CargoOpt::AllFeatures);
+        }
+        CargoTargetFeatureList::List(list) if !list.is_empty() => {
+            command.features(CargoOpt::SomeFeatures(list.clone()));
+        }
+        CargoTargetFeatureList::List(_) => {}
+    }
+    let metadata = command
+        .exec()
+        .map_err(|details| DistError::SbomCargoMetadata {
+            manifest_path: manifest_path.to_owned(),
+            details,
+        })?;
+    add_metadata(bom, &metadata, package_id);
+
+    Ok(())
+}
+
+/// Add everything the package depends on to the SBOM, according to `cargo metadata`
+fn add_metadata(bom: &mut Bom, metadata: &Metadata, package_id: &str) {
+    let Some(resolve) = &metadata.resolve else {
+        return;
+    };
+
+    let packages = metadata
+        .packages
+        .iter()
+        .map(|package| (&package.id, package))
+        .collect::<BTreeMap<_, _>>();
+    let nodes = resolve
+        .nodes
+        .iter()
+        .map(|node| (&node.id, node))
+        .collect::<BTreeMap<_, _>>();
+    let normal_deps = |id: &PackageId| {
+        nodes
+            .get(id)
+            .into_iter()
+            .flat_map(|node| &node.deps)
+            .filter(|dep| {
+                dep.dep_kinds
+                    .iter()
+                    .any(|info| info.kind == DependencyKind::Normal)
+            })
+            .map(|dep| &dep.pkg)
+            .collect::<Vec<_>>()
+    };
+
+    let root = PackageId {
+        repr: package_id.to_owned(),
+    };
+    bom.root.depends_on = normal_deps(&root)
+        .into_iter()
+        .map(|id| id.repr.clone())
+        .collect();
+    if let Some(package) = packages.get(&root) {
+        bom.root.purl = Some(purl(package));
+    }
+
+    let mut queue = normal_deps(&root).into_iter().collect::<VecDeque<_>>();
+    let mut seen = queue.iter().copied().collect::<Vec<_>>();
+    seen.push(&root);
+    while let Some(id) = queue.pop_front() {
+        let Some(package) = packages.get(id) else {
+            continue;
+        };
+        let deps = normal_deps(id);
+        for dep in &deps {
+            if !seen.contains(dep) {
+                seen.push(dep);
+                queue.push_back(dep);
+            }
+        }
+        bom.components.push(component(package, deps));
+    }
+}
+
+fn component(package: &Package, deps: Vec<&PackageId>) -> Component {
+    let download_url = package
+        .source
+        .as_ref()
+        .filter(|source| source.is_crates_io())
+        .map(|_| {
+            format!(
+                "https://crates.io/api/v1/crates/{}/{}/download",
+                package.name, package.version
+            )
+        });
+    Component {
+        version: Some(package.version.to_string()),
+        description: package.description.clone(),
+        license: package.license.as_deref().map(normalize_license),
+        purl: Some(purl(package)),
+        download_url,
+        depends_on: deps.into_iter().map(|id| id.repr.clone()).collect(),
+        ..Component::library(package.id.repr.clone(), package.name.to_string())
+    }
+}
+
+fn purl(package: &Package) -> String {
+    format!("pkg:cargo/{}@{}", package.name, package.version)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::sbom::ComponentKind;
+
+    const CRATES_IO: &str = "registry+https://github.com/rust-lang/crates.io-index";
+
+    fn package(name: &str, version: &str, source: Option<&str>) -> serde_json::Value {
+        json!({
+            "name": name,
+            "version": version,
+            "id": format!("{name} {version}"),
+            "source": source,
+            "license": "MIT/Apache-2.0",
+            "dependencies": [],
+            "targets": [],
+            "features": {},
+            "manifest_path": format!("/src/{name}/Cargo.toml"),
+        })
+    }
+
+    fn node(id: &str, deps: &[(&str, Option<&str>)]) -> serde_json::Value {
+        let deps = deps
+            .iter()
+            .map(|(pkg, kind)| {
+                json!({
+                    "name": pkg.split(' ').next().unwrap(),
+                    "pkg": pkg,
+                    "dep_kinds": [{ "kind": kind, "target": null }],
+                })
+            })
+            .collect::<Vec<_>>();
+        let dependencies = deps
+            .iter()
+            .map(|dep| dep["pkg"].clone())
+            .collect::<Vec<_>>();
+        json!({ "id": id, "deps": deps, "dependencies": dependencies })
+    }
+
+    fn metadata() -> Metadata {
+        serde_json::from_value(json!({
+            "packages": [
+                package("app", "1.0.0", None),
+                package("serde", "1.0.0", Some(CRATES_IO)),
+                package("serde_derive", "1.0.0", Some(CRATES_IO)),
+                package("itoa", "1.0.0", Some(CRATES_IO)),
+                package("winapi", "0.3.9", Some("git+https://github.com/retep998/winapi-rs#1234")),
+                package("cc", "1.0.0", Some(CRATES_IO)),
+                package("insta", "1.0.0", Some(CRATES_IO)),
+            ],
+            "workspace_members": ["app 1.0.0"],
+            "resolve": {
+                "nodes": [
+                    node("app 1.0.0", &[
+                        ("serde 1.0.0", None),
+                        ("winapi 0.3.9", None),
+                        ("cc 1.0.0", Some("build")),
+                        ("insta 1.0.0", Some("dev")),
+                    ]),
+                    node("serde 1.0.0", &[("serde_derive 1.0.0", None), ("itoa 1.0.0", None)]),
+                    node("serde_derive 1.0.0", &[("itoa 1.0.0", None)]),
+                    node("itoa 1.0.0", &[]),
+                    node("winapi 0.3.9", &[]),
+                    node("cc 1.0.0", &[]),
+                    node("insta 1.0.0", &[]),
+                ],
+                "root": "app 1.0.0",
+            },
+            "workspace_root": "/src",
+            "target_directory": "/src/target",
+            "version": 1,
+        }))
+        .unwrap()
+    }
+
+    fn bom() -> Bom {
+        Bom {
+            root: Component {
+                kind: ComponentKind::Application,
+                ..Component::library("app".to_owned(), "app".to_owned())
+            },
+            components: vec![],
+        }
+    }
+
+    #[test]
+    fn test_normal_dependencies() {
+        let mut bom = bom();
+        add_metadata(&mut bom, &metadata(), "app 1.0.0");
+
+        assert_eq!(bom.root.purl.as_deref(), Some("pkg:cargo/app@1.0.0"));
+        // Build and dev dependencies don't ship in the app, and itoa is only listed once
+        assert_eq!(bom.root.depends_on, vec!["serde 1.0.0", "winapi 0.3.9"]);
+        let ids = bom
+            .components
+            .iter()
+            .map(|component| component.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                "serde 1.0.0",
+                "winapi 0.3.9",
+                "serde_derive 1.0.0",
+                "itoa 1.0.0"
+            ]
+        );
+        assert_eq!(
+            bom.components[0].depends_on,
+            vec!["serde_derive 1.0.0", "itoa 1.0.0"]
+        );
+    }
+
+    #[test]
+    fn test_component() {
+        let mut bom = bom();
+        add_metadata(&mut bom, &metadata(), "app 1.0.0");
+
+        let serde = &bom.components[0];
+        assert_eq!(serde.name, "serde");
+        assert_eq!(serde.version.as_deref(), Some("1.0.0"));
+        assert_eq!(serde.license.as_deref(), Some("MIT OR Apache-2.0"));
+        assert_eq!(serde.purl.as_deref(), Some("pkg:cargo/serde@1.0.0"));
+        assert_eq!(
+            serde.download_url.as_deref(),
+            Some("https://crates.io/api/v1/crates/serde/1.0.0/download")
+        );
+        // Only crates.io gets a download url
+        let winapi = &bom.components[1];
+        assert_eq!(winapi.purl.as_deref(), Some("pkg:cargo/winapi@0.3.9"));
+        assert_eq!(winapi.download_url, None);
+    }
+
+    #[test]
+    fn test_no_resolve() {
+        let mut metadata = metadata();
+        metadata.resolve = None;
+        let mut bom = bom();
+        add_metadata(&mut bom, &metadata, "app 1.0.0");
+
+        assert!(bom.root.depends_on.is_empty());
+        assert!(bom.components.is_empty());
+    }
+}
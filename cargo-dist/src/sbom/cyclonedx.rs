@@ -0,0 +1,198 @@
+//! Writing SBOMs as CycloneDX 1.5 JSON
+//!
+//! See <https://cyclonedx.org/docs/1.5/json/>
+
+use serde_json::{json, Map, Value};
+
+use super::{Bom, Component, ComponentKind, HashAlg};
+
+/// Write the SBOM out as CycloneDX JSON
+pub(crate) fn write(bom: &Bom, timestamp: &str, serial: uuid::Uuid) -> String {
+    let dependencies = std::iter::once(&bom.root)
+        .chain(&bom.components)
+        .map(|component| {
+            let depends_on = bom
+                .dependencies(component)
+                .map(|dep| dep.id.as_str())
+                .collect::<Vec<_>>();
+            json!({ "ref": component.id, "dependsOn": depends_on })
+        })
+        .collect::<Vec<_>>();
+
+    let document = json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "serialNumber": format!("urn:uuid:{serial}"),
+        "version": 1,
+        "metadata": {
+            "timestamp": timestamp,
+            "tools": {
+                "components": [{
+                    "type": "application",
+                    "name": "dist",
+                    "version": env!("CARGO_PKG_VERSION"),
+                }],
+            },
+            "component": component(&bom.root),
+        },
+        "components": bom.components.iter().map(component).collect::<Vec<_>>(),
+        "dependencies": dependencies,
+    });
+    serde_json::to_string_pretty(&document).expect("failed to serialize CycloneDX SBOM")
+}
+
+fn component(component: &Component) -> Value {
+    let mut out = Map::new();
+    let kind = match component.kind {
+        ComponentKind::Application => "application",
+        ComponentKind::Library => "library",
+    };
+    out.insert("type".to_owned(), kind.into());
+    out.insert("bom-ref".to_owned(), component.id.clone().into());
+    out.insert("name".to_owned(), component.name.clone().into());
+    if let Some(version) = &component.version {
+        out.insert("version".to_owned(), version.clone().into());
+    }
+    if let Some(description) = &component.description {
+        out.insert("description".to_owned(), description.clone().into());
+    }
+    if let Some(license) = component.license_expression() {
+        out.insert("licenses".to_owned(), json!([{ "expression": license }]));
+    } else if let Some(license) = &component.license {
+        out.insert(
+            "licenses".to_owned(),
+            json!([{ "license": { "name": license } }]),
+        );
+    }
+    if let Some(purl) = &component.purl {
+        out.insert("purl".to_owned(), purl.clone().into());
+    }
+    if !component.hashes.is_empty() {
+        let hashes = component
+            .hashes
+            .iter()
+            .map(|hash| {
+                let alg = match hash.alg {
+                    HashAlg::Sha1 => "SHA-1",
+                    HashAlg::Sha256 => "SHA-256",
+                    HashAlg::Sha384 => "SHA-384",
+                    HashAlg::Sha512 => "SHA-512",
+                };
+                json!({ "alg": alg, "content": hash.value })
+            })
+            .collect::<Vec<_>>();
+        out.insert("hashes".to_owned(), hashes.into());
+    }
+    if let Some(url) = &component.download_url {
+        // The app's url is its repo, everything else's is where we got it from
+        let kind = match component.kind {
+            ComponentKind::Application => "vcs",
+            ComponentKind::Library => "distribution",
+        };
+        out.insert(
+            "externalReferences".to_owned(),
+            json!([{ "type": kind, "url": url }]),
+        );
+    }
+    Value::Object(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sbom::{tests::bom, Hash};
+
+    #[test]
+    fn test_cyclonedx() {
+        let json = write(&bom(), "2024-01-01T00:00:00Z", uuid::Uuid::nil());
+        let json: Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(json["bomFormat"], "CycloneDX");
+        assert_eq!(json["specVersion"], "1.5");
+        assert_eq!(
+            json["serialNumber"],
+            "urn:uuid:00000000-0000-0000-0000-000000000000"
+        );
+        assert_eq!(json["metadata"]["timestamp"], "2024-01-01T00:00:00Z");
+        assert_eq!(
+            json["metadata"]["component"],
+            json!({
+                "type": "application",
+                "bom-ref": "app",
+                "name": "app",
+                "version": "1.0.0",
+                "licenses": [{ "expression": "MIT OR Apache-2.0" }],
+            })
+        );
+        assert_eq!(
+            json["components"],
+            json!([{
+                "type": "library",
+                "bom-ref": "pkg:npm/left-pad@1.3.0",
+                "name": "left-pad",
+                "version": "1.3.0",
+                "purl": "pkg:npm/left-pad@1.3.0",
+                "hashes": [{ "alg": "SHA-512", "content": "abcd" }],
+            }])
+        );
+        assert_eq!(
+            json["dependencies"],
+            json!([
+                { "ref": "app", "dependsOn": ["pkg:npm/left-pad@1.3.0"] },
+                { "ref": "pkg:npm/left-pad@1.3.0", "dependsOn": [] },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_licenses_and_references() {
+        let mut bom = bom();
+        bom.root.description = Some("it pads".to_owned());
+        bom.root.download_url = Some("https://github.com/example/app".to_owned());
+        let left_pad = &mut bom.components[0];
+        left_pad.license = Some("see LICENSE.txt".to_owned());
+        left_pad.download_url =
+            Some("https://registry.npmjs.org/left-pad/-/left-pad-1.3.0.tgz".to_owned());
+        left_pad.hashes = vec![
+            Hash {
+                alg: HashAlg::Sha1,
+                value: "1234".to_owned(),
+            },
+            Hash {
+                alg: HashAlg::Sha256,
+                value: "5678".to_owned(),
+            },
+        ];
+
+        let json = write(&bom, "2024-01-01T00:00:00Z", uuid::Uuid::nil());
+        let json: Value = serde_json::from_str(&json).unwrap();
+
+        let app = &json["metadata"]["component"];
+        assert_eq!(app["description"], "it pads");
+        // The app's url is its repo
+        assert_eq!(
+            app["externalReferences"],
+            json!([{ "type": "vcs", "url": "https://github.com/example/app" }])
+        );
+        let left_pad = &json["components"][0];
+        // Licenses that aren't SPDX expressions are kept by name
+        assert_eq!(
+            left_pad["licenses"],
+            json!([{ "license": { "name": "see LICENSE.txt" } }])
+        );
+        assert_eq!(
+            left_pad["externalReferences"],
+            json!([{
+                "type": "distribution",
+                "url": "https://registry.npmjs.org/left-pad/-/left-pad-1.3.0.tgz",
+            }])
+        );
+        assert_eq!(
+            left_pad["hashes"],
+            json!([
+                { "alg": "SHA-1", "content": "1234" },
+                { "alg": "SHA-256", "content": "5678" },
+            ])
+        );
+    }
+}
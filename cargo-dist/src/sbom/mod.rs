@@ -0,0 +1,429 @@
+//! Generating SBOMs (Software Bills Of Materials) without external tools
+//!
+//! Each release gets a CycloneDX 1.5 and an SPDX 2.3 SBOM (both JSON), listing
+//! what went into the app. Where that list comes from depends on the project:
+//!
+//! * cargo: every crate the app (transitively) depends on, from `cargo metadata`
+//! * npm: every package the app (transitively) depends on, from `package-lock.json`
+//! * generic: the system libraries its binaries were found to link against
+//!
+//! Components declared with `sbom-components` are added to all of these, as
+//! that's how users can list things we can't see (like vendored C libraries).
+
+mod cargo;
+mod cyclonedx;
+mod npm;
+mod spdx;
+
+use axoasset::LocalAsset;
+use camino::{Utf8Path, Utf8PathBuf};
+use dist_schema::DistManifest;
+use sha2::{Digest, Sha256};
+use tracing::info;
+
+use crate::{
+    backend::installer::source_date_epoch, config::SbomComponent, errors::*, CargoTargetFeatures,
+    DistGraph, GenerateSbomStep,
+};
+
+/// A format to write SBOMs in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SbomFormat {
+    /// CycloneDX 1.5 JSON
+    CycloneDx,
+    /// SPDX 2.3 JSON
+    Spdx,
+}
+
+impl SbomFormat {
+    /// Get the file extension for this format
+    pub fn ext(self) -> &'static str {
+        match self {
+            SbomFormat::CycloneDx => "cdx.json",
+            SbomFormat::Spdx => "spdx.json",
+        }
+    }
+}
+
+/// Where to find an app's dependencies
+#[derive(Debug, Clone)]
+pub enum SbomSource {
+    /// A cargo package, whose dependencies come from `cargo metadata`
+    Cargo {
+        /// The package's Cargo.toml
+        manifest_path: Utf8PathBuf,
+        /// The package's id, as cargo reports it
+        package_id: String,
+        /// The features the app is built with
+        features: CargoTargetFeatures,
+    },
+    /// An npm package, whose dependencies come from its package-lock.json
+    Npm {
+        /// The package-lock.json, if there is one
+        lockfile: Option<Utf8PathBuf>,
+        /// The dir of the package itself (which is not the lockfile's in npm workspaces)
+        package_root: Utf8PathBuf,
+    },
+    /// Something else, that we can only see the linkage of
+    Generic,
+}
+
+/// A bill of materials, before it's written out in some format
+#[derive(Debug)]
+struct Bom {
+    /// The app itself
+    root: Component,
+    /// Everything in it
+    components: Vec<Component>,
+}
+
+impl Bom {
+    /// Get the components that `component` depends on
+    fn dependencies<'a>(&'a self, component: &'a Component) -> impl Iterator<Item = &'a Component> {
+        component
+            .depends_on
+            .iter()
+            .filter_map(|id| self.components.iter().find(|component| &component.id == id))
+    }
+}
+
+/// A piece of software in an SBOM
+#[derive(Debug, Clone)]
+struct Component {
+    /// An id for the component, unique within the SBOM
+    id: String,
+    kind: ComponentKind,
+    name: String,
+    version: Option<String>,
+    description: Option<String>,
+    /// An SPDX license expression
+    license: Option<String>,
+    purl: Option<String>,
+    /// Where the component can be downloaded from
+    download_url: Option<String>,
+    hashes: Vec<Hash>,
+    /// The ids of the components this one depends on
+    depends_on: Vec<String>,
+}
+
+impl Component {
+    /// A library with only a name, to fill in the rest of
+    fn library(id: String, name: String) -> Self {
+        Self {
+            id,
+            kind: ComponentKind::Library,
+            name,
+            version: None,
+            description: None,
+            license: None,
+            purl: None,
+            download_url: None,
+            hashes: vec![],
+            depends_on: vec![],
+        }
+    }
+
+    /// Get the license, if it's a valid SPDX expression
+    fn license_expression(&self) -> Option<&str> {
+        self.license
+            .as_deref()
+            .filter(|license| ::spdx::Expression::parse(license).is_ok())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ComponentKind {
+    Application,
+    Library,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Hash {
+    alg: HashAlg,
+    /// The hex-encoded digest
+    value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HashAlg {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+/// Generate an SBOM for a release
+pub fn generate_sbom(
+    dist: &DistGraph,
+    manifest: &DistManifest,
+    step: &GenerateSbomStep,
+) -> DistResult<()> {
+    info!("generating {} for {}", step.format.ext(), step.app_name);
+    let bom = collect_bom(dist, manifest, step)?;
+
+    // Like the packages we build, rebuilding a release should give an identical SBOM
+    let time = std::time::UNIX_EPOCH + std::time::Duration::from_secs(source_date_epoch());
+    let ((year, month, day), (hours, minutes, seconds)) = crate::utc_date_time(time);
+    let timestamp = format!("{year:04}-{month:02}-{day:02}T{hours:02}:{minutes:02}:{seconds:02}Z");
+    let serial = serial_number(&step.app_name, &step.app_version, &timestamp);
+    let contents = match step.format {
+        SbomFormat::CycloneDx => cyclonedx::write(&bom, &timestamp, serial),
+        SbomFormat::Spdx => spdx::write(&bom, &timestamp, serial),
+    };
+    LocalAsset::write_new_all(&contents, &step.dest_path)?;
+
+    Ok(())
+}
+
+/// Get the serial number (or SPDX document namespace) of a release's SBOM
+///
+/// This has to be unique to the release, and is derived from it rather than random
+/// so that it's reproducible.
+fn serial_number(app_name: &str, app_version: &str, timestamp: &str) -> uuid::Uuid {
+    let digest = Sha256::digest(format!("{app_name}\0{app_version}\0{timestamp}"));
+    let bytes = digest[..16]
+        .try_into()
+        .expect("sha256 digest was too short!?");
+    uuid::Builder::from_custom_bytes(bytes).into_uuid()
+}
+
+fn collect_bom(
+    dist: &DistGraph,
+    manifest: &DistManifest,
+    step: &GenerateSbomStep,
+) -> DistResult<Bom> {
+    let mut bom = Bom {
+        root: Component {
+            id: step.app_name.clone(),
+            kind: ComponentKind::Application,
+            name: step.app_name.clone(),
+            version: Some(step.app_version.clone()),
+            description: step.app_desc.clone(),
+            license: step.app_license.as_deref().map(normalize_license),
+            purl: None,
+            download_url: step.app_repository_url.clone(),
+            hashes: vec![],
+            depends_on: vec![],
+        },
+        components: vec![],
+    };
+
+    match &step.source {
+        SbomSource::Cargo {
+            manifest_path,
+            package_id,
+            features,
+        } => {
+            let cargo = dist.tools.cargo()?;
+            cargo::add_dependencies(&mut bom, &cargo.cmd, manifest_path, package_id, features)?;
+        }
+        SbomSource::Npm {
+            lockfile: Some(lockfile),
+            package_root,
+        } => npm::add_dependencies(&mut bom, lockfile, package_root)?,
+        SbomSource::Npm { lockfile: None, .. } => {}
+        SbomSource::Generic => add_system_libraries(&mut bom, manifest, step),
+    }
+    add_declared_components(&mut bom, &step.components);
+
+    Ok(bom)
+}
+
+/// Add the system libraries the release's binaries link against
+fn add_system_libraries(bom: &mut Bom, manifest: &DistManifest, step: &GenerateSbomStep) {
+    let Some(release) = manifest
+        .releases
+        .iter()
+        .find(|r| r.app_name == step.app_name && r.app_version == step.app_version)
+    else {
+        return;
+    };
+
+    let libraries = release
+        .artifacts
+        .iter()
+        .filter_map(|id| manifest.artifacts.get(id))
+        .flat_map(|artifact| &artifact.assets)
+        .filter_map(|asset| manifest.assets.get(asset.id.as_ref()?)?.linkage.as_ref())
+        .flat_map(|linkage| &linkage.system);
+    for library in libraries {
+        // Prefer the package the library comes from, which is the same on every target
+        let name = library.source.clone().unwrap_or_else(|| {
+            Utf8Path::new(&library.path)
+                .file_name()
+                .unwrap_or(&library.path)
+                .to_owned()
+        });
+        let id = format!("system:{name}");
+        if bom.components.iter().any(|component| component.id == id) {
+            continue;
+        }
+        bom.root.depends_on.push(id.clone());
+        bom.components.push(Component::library(id, name));
+    }
+}
+
+/// Add the components the user declared
+fn add_declared_components(bom: &mut Bom, declared: &[SbomComponent]) {
+    for SbomComponent {
+        name,
+        version,
+        purl,
+        license,
+    } in declared
+    {
+        let id = match (purl, version) {
+            (Some(purl), _) => purl.clone(),
+            (None, Some(version)) => format!("{name}@{version}"),
+            (None, None) => name.clone(),
+        };
+        bom.root.depends_on.push(id.clone());
+        bom.components.push(Component {
+            version: version.clone(),
+            license: license.as_deref().map(normalize_license),
+            purl: purl.clone(),
+            ..Component::library(id, name.clone())
+        });
+    }
+}
+
+/// Turn old-style license lists like "MIT/Apache-2.0" into SPDX expressions
+fn normalize_license(license: &str) -> String {
+    license
+        .split('/')
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(" OR ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An app that depends on left-pad
+    pub(super) fn bom() -> Bom {
+        let mut root = Component {
+            kind: ComponentKind::Application,
+            version: Some("1.0.0".to_owned()),
+            license: Some("MIT OR Apache-2.0".to_owned()),
+            ..Component::library("app".to_owned(), "app".to_owned())
+        };
+        root.depends_on.push("pkg:npm/left-pad@1.3.0".to_owned());
+        let left_pad = Component {
+            version: Some("1.3.0".to_owned()),
+            purl: Some("pkg:npm/left-pad@1.3.0".to_owned()),
+            hashes: vec![Hash {
+                alg: HashAlg::Sha512,
+                value: "abcd".to_owned(),
+            }],
+            ..Component::library("pkg:npm/left-pad@1.3.0".to_owned(), "left-pad".to_owned())
+        };
+        Bom {
+            root,
+            components: vec![left_pad],
+        }
+    }
+
+    #[test]
+    fn test_normalize_license() {
+        assert_eq!(normalize_license("MIT/Apache-2.0"), "MIT OR Apache-2.0");
+        assert_eq!(normalize_license("MIT OR Apache-2.0"), "MIT OR Apache-2.0");
+    }
+
+    #[test]
+    fn test_system_libraries() {
+        let executable = |id: &str| {
+            serde_json::json!({
+                "kind": "executable-zip",
+                "assets": [{ "id": id, "name": "app", "path": "app", "kind": "executable" }],
+            })
+        };
+        let asset = |id: &str, libraries: serde_json::Value| {
+            serde_json::json!({
+                "id": id,
+                "name": "app",
+                "system": "build:local",
+                "target_triples": [],
+                "linkage": { "system": libraries },
+            })
+        };
+        let manifest: DistManifest = serde_json::from_value(serde_json::json!({
+            "releases": [{
+                "app_name": "app",
+                "app_version": "1.0.0",
+                "artifacts": ["app-linux.tar.gz", "app-mac.tar.gz"],
+            }],
+            "artifacts": {
+                "app-linux.tar.gz": executable("app-linux-exe"),
+                "app-mac.tar.gz": executable("app-mac-exe"),
+            },
+            "assets": {
+                "app-linux-exe": asset("app-linux-exe", serde_json::json!([
+                    { "path": "/lib/x86_64-linux-gnu/libc.so.6", "source": "libc6", "package_manager": "apt" },
+                    { "path": "/lib/x86_64-linux-gnu/libgcc_s.so.1", "source": "libgcc-s1", "package_manager": "apt" },
+                ])),
+                "app-mac-exe": asset("app-mac-exe", serde_json::json!([
+                    { "path": "/usr/lib/libSystem.B.dylib", "package_manager": null },
+                    // The same package on another target is only listed once
+                    { "path": "/lib/libc.so.6", "source": "libc6", "package_manager": "apt" },
+                ])),
+            },
+        }))
+        .unwrap();
+        let step = GenerateSbomStep {
+            format: SbomFormat::CycloneDx,
+            source: SbomSource::Generic,
+            app_name: "app".to_owned(),
+            app_version: "1.0.0".to_owned(),
+            app_desc: None,
+            app_license: None,
+            app_repository_url: None,
+            components: vec![],
+            dest_path: Utf8PathBuf::from("target/distrib/app.cdx.json"),
+        };
+        let mut bom = bom();
+        bom.root.depends_on.clear();
+        bom.components.clear();
+        add_system_libraries(&mut bom, &manifest, &step);
+
+        // Libraries are named after their package, or else their file
+        assert_eq!(
+            bom.root.depends_on,
+            vec![
+                "system:libc6",
+                "system:libgcc-s1",
+                "system:libSystem.B.dylib"
+            ]
+        );
+        let names = bom
+            .components
+            .iter()
+            .map(|component| component.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["libc6", "libgcc-s1", "libSystem.B.dylib"]);
+    }
+
+    #[test]
+    fn test_serial_number() {
+        let serial = serial_number("app", "1.0.0", "1980-01-01T00:00:00Z");
+        assert_eq!(
+            serial,
+            serial_number("app", "1.0.0", "1980-01-01T00:00:00Z")
+        );
+        assert_eq!(serial.get_version(), Some(uuid::Version::Custom));
+        assert_eq!(serial.get_variant(), uuid::Variant::RFC4122);
+
+        assert_ne!(
+            serial,
+            serial_number("app", "1.0.1", "1980-01-01T00:00:00Z")
+        );
+        assert_ne!(
+            serial,
+            serial_number("other", "1.0.0", "1980-01-01T00:00:00Z")
+        );
+        assert_ne!(
+            serial,
+            serial_number("app", "1.0.0", "2024-01-01T00:00:00Z")
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//! Finding the dependencies of an npm package, from its package-lock.json
+
+use std::collections::{BTreeMap, VecDeque};
+
+use axoasset::SourceFile;
+use base64::Engine;
+use camino::Utf8Path;
+use serde::Deserialize;
+use tracing::warn;
+
+use super::{normalize_license, Bom, Component, Hash, HashAlg};
+use crate::encode_hex;
+use crate::errors::*;
+
+/// The parts of a package-lock.json we care about
+#[derive(Debug, Deserialize)]
+struct PackageLock {
+    /// Every package, keyed by the path it's installed at (v2 and v3 lockfiles only)
+    #[serde(default)]
+    packages: Option<BTreeMap<String, LockedPackage>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct LockedPackage {
+    name: Option<String>,
+    version: Option<String>,
+    resolved: Option<String>,
+    integrity: Option<String>,
+    license: Option<String>,
+    description: Option<String>,
+    /// Whether this is a symlink to the package at `resolved`
+    #[serde(default)]
+    link: bool,
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+    #[serde(default, rename = "optionalDependencies")]
+    optional_dependencies: BTreeMap<String, String>,
+}
+
+/// Add everything the package depends on to the SBOM
+///
+/// devDependencies aren't part of what we ship, so they're left out.
+pub(crate) fn add_dependencies(
+    bom: &mut Bom,
+    lockfile: &Utf8Path,
+    package_root: &Utf8Path,
+) -> DistResult<()> {
+    let lock: PackageLock = SourceFile::load_local(lockfile)?.deserialize_json()?;
+    let Some(packages) = lock.packages else {
+        warn!("{lockfile} is too old to generate an SBOM from, you may want to update npm");
+        return Ok(());
+    };
+    // The root package is keyed by its path relative to the lockfile ("" unless it's in an npm workspace)
+    let root_key = lockfile
+        .parent()
+        .and_then(|dir| package_root.strip_prefix(dir).ok())
+        .map(|path| path.as_str().replace('\\', "/"))
+        .unwrap_or_default();
+
+    let root_deps = resolve_dependencies(&packages, &root_key);
+    bom.root.depends_on = root_deps
+        .iter()
+        .map(|key| purl(key, &packages[key]))
+        .collect();
+    dedup(&mut bom.root.depends_on);
+
+    let mut queue = root_deps.into_iter().collect::<VecDeque<_>>();
+    let mut seen = queue.clone().into_iter().collect::<Vec<_>>();
+    while let Some(key) = queue.pop_front() {
+        let deps = resolve_dependencies(&packages, &key);
+        let mut depends_on = deps
+            .iter()
+            .map(|dep| purl(dep, &packages[dep]))
+            .collect::<Vec<_>>();
+        dedup(&mut depends_on);
+        for dep in deps {
+            if !seen.contains(&dep) {
+                seen.push(dep.clone());
+                queue.push_back(dep);
+            }
+        }
+
+        let component = component(&key, &packages[&key], depends_on);
+        if !bom.components.iter().any(|c| c.id == component.id) {
+            bom.components.push(component);
+        }
+    }
+
+    Ok(())
+}
+
+/// Find the keys of the packages that the package at `key` depends on
+fn resolve_dependencies(packages: &BTreeMap<String, LockedPackage>, key: &str) -> Vec<String> {
+    let Some(package) = packages.get(key) else {
+        return vec![];
+    };
+    package
+        .dependencies
+        .keys()
+        .chain(package.optional_dependencies.keys())
+        .filter_map(|name| resolve(packages, key, name))
+        .collect()
+}
+
+/// Find the package `name` like node would from the package at `key`, by walking up node_modules dirs
+fn resolve(packages: &BTreeMap<String, LockedPackage>, key: &str, name: &str) -> Option<String> {
+    let mut base = key;
+    loop {
+        let candidate = if base.is_empty() {
+            format!("node_modules/{name}")
+        } else {
+            format!("{base}/node_modules/{name}")
+        };
+        if let Some(package) = packages.get(&candidate) {
+            // Workspace packages are symlinked in, so go to the real thing
+            return match (package.link, &package.resolved) {
+                (true, Some(target)) => packages.contains_key(target).then(|| target.clone()),
+                _ => Some(candidate),
+            };
+        }
+        if base.is_empty() {
+            return None;
+        }
+        base = if let Some(idx) = base.rfind("/node_modules/") {
+            &base[..idx]
+        } else if base.starts_with("node_modules/") {
+            ""
+        } else {
+            base.rsplit_once('/')
+                .map(|(parent, _)| parent)
+                .unwrap_or("")
+        };
+    }
+}
+
+/// Get the name of a package, which for non-workspace packages is only in its key
+fn name<'a>(key: &'a str, package: &'a LockedPackage) -> &'a str {
+    package.name.as_deref().unwrap_or_else(|| {
+        key.rsplit_once("node_modules/")
+            .map(|(_, name)| name)
+            .unwrap_or(key)
+    })
+}
+
+fn purl(key: &str, package: &LockedPackage) -> String {
+    let name = name(key, package).replace('@', "%40");
+    match &package.version {
+        Some(version) => format!("pkg:npm/{name}@{version}"),
+        None => format!("pkg:npm/{name}"),
+    }
+}
+
+fn component(key: &str, package: &LockedPackage, depends_on: Vec<String>) -> Component {
+    let hashes = package
+        .integrity
+        .as_deref()
+        .map(integrity_hashes)
+        .unwrap_or_default();
+    // For workspace packages this is a path, which is no use to anyone else
+    let download_url = package
+        .resolved
+        .clone()
+        .filter(|resolved| resolved.starts_with("http"));
+    Component {
+        version: package.version.clone(),
+        description: package.description.clone(),
+        license: package.license.as_deref().map(normalize_license),
+        purl: Some(purl(key, package)),
+        download_url,
+        hashes,
+        depends_on,
+        ..Component::library(purl(key, package), name(key, package).to_owned())
+    }
+}
+
+/// Decode a subresource integrity string (e.g. `sha512-<base64>`) into hex hashes
+fn integrity_hashes(integrity: &str) -> Vec<Hash> {
+    integrity
+        .split_whitespace()
+        .filter_map(|entry| {
+            let (alg, digest) = entry.split_once('-')?;
+            let alg = match alg {
+                "sha1" => HashAlg::Sha1,
+                "sha256" => HashAlg::Sha256,
+                "sha384" => HashAlg::Sha384,
+                "sha512" => HashAlg::Sha512,
+                _ => return None,
+            };
+            let digest = base64::engine::general_purpose::STANDARD
+                .decode(digest)
+                .ok()?;
+            Some(Hash {
+                alg,
+                value: encode_hex(&digest),
+            })
+        })
+        .collect()
+}
+
+fn dedup(ids: &mut Vec<String>) {
+    ids.sort();
+    ids.dedup();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_npm_dependencies() {
+        let lock = r#"{
+          "name": "workspace",
+          "lockfileVersion": 3,
+          "packages": {
+            "": { "name": "workspace", "workspaces": ["packages/app"] },
+            "packages/app": {
+              "name": "@acme/app",
+              "version": "1.0.0",
+              "dependencies": { "@acme/lib": "*", "left-pad": "^1.3.0" },
+              "devDependencies": { "typescript": "^5.0.0" }
+            },
+            "packages/lib": {
+              "name": "@acme/lib",
+              "version": "0.1.0",
+              "dependencies": { "left-pad": "^1.0.0" }
+            },
+            "packages/lib/node_modules/left-pad": {
+              "version": "1.0.0",
+              "resolved": "https://registry.npmjs.org/left-pad/-/left-pad-1.0.0.tgz",
+              "integrity": "sha1-q7jYiY1AJnS1fMNP2kyMvj+6+BE="
+            },
+            "node_modules/@acme/app": { "resolved": "packages/app", "link": true },
+            "node_modules/@acme/lib": { "resolved": "packages/lib", "link": true },
+            "node_modules/left-pad": {
+              "version": "1.3.0",
+              "resolved": "https://registry.npmjs.org/left-pad/-/left-pad-1.3.0.tgz",
+              "license": "WTFPL"
+            },
+            "node_modules/typescript": { "version": "5.4.0", "dev": true }
+          }
+        }"#;
+        let (_tmp, root) = crate::create_tmp().unwrap();
+        axoasset::LocalAsset::write_new_all(lock, root.join("package-lock.json")).unwrap();
+
+        let mut bom = Bom {
+            root: Component::library("@acme/app".to_owned(), "@acme/app".to_owned()),
+            components: vec![],
+        };
+        add_dependencies(
+            &mut bom,
+            &root.join("package-lock.json"),
+            &root.join("packages/app"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            bom.root.depends_on,
+            ["pkg:npm/%40acme/lib@0.1.0", "pkg:npm/left-pad@1.3.0"]
+        );
+        let ids = bom
+            .components
+            .iter()
+            .map(|c| c.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            [
+                "pkg:npm/%40acme/lib@0.1.0",
+                "pkg:npm/left-pad@1.3.0",
+                "pkg:npm/left-pad@1.0.0"
+            ]
+        );
+        assert_eq!(bom.components[0].depends_on, ["pkg:npm/left-pad@1.0.0"]);
+        assert_eq!(bom.components[1].license.as_deref(), Some("WTFPL"));
+        assert_eq!(
+            bom.components[2].hashes,
+            [Hash {
+                alg: HashAlg::Sha1,
+                value: "abb8d8898d402674b57cc34fda4c8cbe3fbaf811".to_owned()
+            }]
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! Writing SBOMs as SPDX 2.3 JSON
+//!
+//! See <https://spdx.github.io/spdx-spec/v2.3/>
+
+use serde_json::{json, Map, Value};
+
+use super::{Bom, Component, ComponentKind, HashAlg};
+
+const NOASSERTION: &str = "NOASSERTION";
+
+/// Write the SBOM out as SPDX JSON
+pub(crate) fn write(bom: &Bom, timestamp: &str, serial: uuid::Uuid) -> String {
+    let components = std::iter::once(&bom.root)
+        .chain(&bom.components)
+        .collect::<Vec<_>>();
+    let spdx_id = |component: &Component| {
+        let idx = components
+            .iter()
+            .position(|c| c.id == component.id)
+            .expect("SBOM component went missing");
+        // SPDX ids may only contain letters, numbers, `.` and `-`
+        let name = component
+            .name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect::<String>();
+        format!("SPDXRef-Package-{idx}-{name}")
+    };
+
+    let mut relationships = vec![json!({
+        "spdxElementId": "SPDXRef-DOCUMENT",
+        "relationshipType": "DESCRIBES",
+        "relatedSpdxElement": spdx_id(&bom.root),
+    })];
+    for component in &components {
+        for dep in bom.dependencies(component) {
+            relationships.push(json!({
+                "spdxElementId": spdx_id(component),
+                "relationshipType": "DEPENDS_ON",
+                "relatedSpdxElement": spdx_id(dep),
+            }));
+        }
+    }
+
+    let name = match &bom.root.version {
+        Some(version) => format!("{}-{version}", bom.root.name),
+        None => bom.root.name.clone(),
+    };
+    let document = json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": name,
+        "documentNamespace": format!("https://spdx.org/spdxdocs/{name}-{serial}"),
+        "creationInfo": {
+            "created": timestamp,
+            "creators": [format!("Tool: dist-{}", env!("CARGO_PKG_VERSION"))],
+        },
+        "packages": components
+            .iter()
+            .map(|component| package(component, spdx_id(component)))
+            .collect::<Vec<_>>(),
+        "relationships": relationships,
+    });
+    serde_json::to_string_pretty(&document).expect("failed to serialize SPDX SBOM")
+}
+
+fn package(component: &Component, spdx_id: String) -> Value {
+    let mut out = Map::new();
+    out.insert("SPDXID".to_owned(), spdx_id.into());
+    out.insert("name".to_owned(), component.name.clone().into());
+    if let Some(version) = &component.version {
+        out.insert("versionInfo".to_owned(), version.clone().into());
+    }
+    if let Some(description) = &component.description {
+        out.insert("description".to_owned(), description.clone().into());
+    }
+    let download_location = component.download_url.as_deref().unwrap_or(NOASSERTION);
+    out.insert("downloadLocation".to_owned(), download_location.into());
+    out.insert("filesAnalyzed".to_owned(), false.into());
+    let license = component.license_expression().unwrap_or(NOASSERTION);
+    out.insert("licenseDeclared".to_owned(), license.into());
+    out.insert("licenseConcluded".to_owned(), NOASSERTION.into());
+    out.insert("copyrightText".to_owned(), NOASSERTION.into());
+    if !component.hashes.is_empty() {
+        let checksums = component
+            .hashes
+            .iter()
+            .map(|hash| {
+                let alg = match hash.alg {
+                    HashAlg::Sha1 => "SHA1",
+                    HashAlg::Sha256 => "SHA256",
+                    HashAlg::Sha384 => "SHA384",
+                    HashAlg::Sha512 => "SHA512",
+                };
+                json!({ "algorithm": alg, "checksumValue": hash.value })
+            })
+            .collect::<Vec<_>>();
+        out.insert("checksums".to_owned(), checksums.into());
+    }
+    if let Some(purl) = &component.purl {
+        out.insert(
+            "externalRefs".to_owned(),
+            json!([{
+                "referenceCategory": "PACKAGE-MANAGER",
+                "referenceType": "purl",
+                "referenceLocator": purl,
+            }]),
+        );
+    }
+    let purpose = match component.kind {
+        ComponentKind::Application => "APPLICATION",
+        ComponentKind::Library => "LIBRARY",
+    };
+    out.insert("primaryPackagePurpose".to_owned(), purpose.into());
+    Value::Object(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sbom::tests::bom;
+
+    #[test]
+    fn test_spdx() {
+        let json = write(&bom(), "2024-01-01T00:00:00Z", uuid::Uuid::nil());
+        let json: Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(json["spdxVersion"], "SPDX-2.3");
+        assert_eq!(json["name"], "app-1.0.0");
+        assert_eq!(
+            json["documentNamespace"],
+            "https://spdx.org/spdxdocs/app-1.0.0-00000000-0000-0000-0000-000000000000"
+        );
+        assert_eq!(json["creationInfo"]["created"], "2024-01-01T00:00:00Z");
+        assert_eq!(json["packages"][0]["licenseDeclared"], "MIT OR Apache-2.0");
+        assert_eq!(json["packages"][0]["primaryPackagePurpose"], "APPLICATION");
+        assert_eq!(json["packages"][1]["licenseDeclared"], "NOASSERTION");
+        assert_eq!(
+            json["packages"][1]["checksums"],
+            json!([{ "algorithm": "SHA512", "checksumValue": "abcd" }])
+        );
+        assert_eq!(
+            json["relationships"],
+            json!([
+                {
+                    "spdxElementId": "SPDXRef-DOCUMENT",
+                    "relationshipType": "DESCRIBES",
+                    "relatedSpdxElement": "SPDXRef-Package-0-app",
+                },
+                {
+                    "spdxElementId": "SPDXRef-Package-0-app",
+                    "relationshipType": "DEPENDS_ON",
+                    "relatedSpdxElement": "SPDXRef-Package-1-left-pad",
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_package() {
+        let mut bom = bom();
+        bom.root.version = None;
+        let left_pad = &mut bom.components[0];
+        left_pad.name = "@scope/left_pad".to_owned();
+        left_pad.description = Some("it pads".to_owned());
+        left_pad.download_url =
+            Some("https://registry.npmjs.org/left-pad/-/left-pad-1.3.0.tgz".to_owned());
+
+        let json = write(&bom, "2024-01-01T00:00:00Z", uuid::Uuid::nil());
+        let json: Value = serde_json::from_str(&json).unwrap();
+
+        // Without a version, the document is just named after the app
+        assert_eq!(json["name"], "app");
+        assert_eq!(json["packages"][0]["downloadLocation"], "NOASSERTION");
+        assert_eq!(
+            json["packages"][1],
+            json!({
+                // Characters SPDX ids can't contain are replaced
+                "SPDXID": "SPDXRef-Package-1--scope-left-pad",
+                "name": "@scope/left_pad",
+                "versionInfo": "1.3.0",
+                "description": "it pads",
+                "downloadLocation": "https://registry.npmjs.org/left-pad/-/left-pad-1.3.0.tgz",
+                "filesAnalyzed": false,
+                "licenseDeclared": "NOASSERTION",
+                "licenseConcluded": "NOASSERTION",
+                "copyrightText": "NOASSERTION",
+                "checksums": [{ "algorithm": "SHA512", "checksumValue": "abcd" }],
+                "externalRefs": [{
+                    "referenceCategory": "PACKAGE-MANAGER",
+                    "referenceType": "purl",
+                    "referenceLocator": "pkg:npm/left-pad@1.3.0",
+                }],
+                "primaryPackagePurpose": "LIBRARY",
+            })
+        );
+    }
+}
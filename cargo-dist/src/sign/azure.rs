@@ -0,0 +1,98 @@
+//! Codesigning using Azure Trusted Signing
+//!
+//! This uses the `sign` dotnet tool (`dotnet tool install --global sign`),
+//! which picks up credentials from the usual `AZURE_CLIENT_ID`/`AZURE_TENANT_ID`/
+//! `AZURE_CLIENT_SECRET` env vars (or anything else `DefaultAzureCredential` accepts).
+use axoprocess::Cmd;
+use camino::Utf8Path;
+use tracing::info;
+
+use super::Signer;
+use crate::config::AzureTrustedSigningConfig;
+use crate::errors::*;
+
+/// The env vars the `sign` tool reads credentials from
+pub const SECRET_VARS: &[&str] = &["AZURE_CLIENT_ID", "AZURE_TENANT_ID", "AZURE_CLIENT_SECRET"];
+
+/// A signer using an Azure Trusted Signing certificate profile
+#[derive(Debug)]
+pub struct TrustedSigning {
+    config: AzureTrustedSigningConfig,
+}
+
+impl TrustedSigning {
+    pub fn new(config: AzureTrustedSigningConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the arguments to sign this file with
+    fn args_for(&self, file: &Utf8Path) -> Vec<String> {
+        let AzureTrustedSigningConfig {
+            endpoint,
+            account,
+            certificate_profile,
+        } = &self.config;
+        let dir = file.parent().expect("signing a file with no parent dir!?");
+        let name = file.file_name().expect("signing a file with no name!?");
+        vec![
+            "code".to_owned(),
+            "trusted-signing".to_owned(),
+            "--trusted-signing-endpoint".to_owned(),
+            endpoint.clone(),
+            "--trusted-signing-account".to_owned(),
+            account.clone(),
+            "--trusted-signing-certificate-profile".to_owned(),
+            certificate_profile.clone(),
+            "--base-directory".to_owned(),
+            dir.to_string(),
+            name.to_owned(),
+        ]
+    }
+}
+
+impl Signer for TrustedSigning {
+    fn provider(&self) -> &'static str {
+        "azure-trusted-signing"
+    }
+
+    fn sign(&self, file: &Utf8Path) -> DistResult<()> {
+        info!("azure trusted signing {file}");
+        let mut cmd = Cmd::new("sign", "sign with azure trusted signing");
+        for arg in self.args_for(file) {
+            cmd.arg(arg);
+        }
+        cmd.stdout_to_stderr();
+        cmd.run()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_args() {
+        let signer = TrustedSigning::new(AzureTrustedSigningConfig {
+            endpoint: "https://eus.codesigning.azure.net".to_owned(),
+            account: "my-account".to_owned(),
+            certificate_profile: "my-profile".to_owned(),
+        });
+        assert_eq!(
+            signer.args_for(Utf8Path::new("target/distrib/app.exe")),
+            vec![
+                "code",
+                "trusted-signing",
+                "--trusted-signing-endpoint",
+                "https://eus.codesigning.azure.net",
+                "--trusted-signing-account",
+                "my-account",
+                "--trusted-signing-certificate-profile",
+                "my-profile",
+                "--base-directory",
+                "target/distrib",
+                "app.exe",
+            ]
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! Signing with a command of the user's choosing
+//!
+//! This is the escape hatch for signing services we don't know about:
+//! the command is run once per file, and is expected to sign it in place.
+use axoprocess::Cmd;
+use camino::Utf8Path;
+use tracing::info;
+
+use super::Signer;
+use crate::config::CommandSignerConfig;
+use crate::errors::*;
+
+/// The placeholder in the command that's replaced with the file to sign
+const FILE_PLACEHOLDER: &str = "{file}";
+
+/// A signer that runs a custom command
+#[derive(Debug)]
+pub struct CommandSigner {
+    command: String,
+    args: Vec<String>,
+}
+
+impl CommandSigner {
+    pub fn new(name: &str, config: CommandSignerConfig) -> DistResult<Self> {
+        let mut parts = config.command.into_iter();
+        let Some(command) = parts.next() else {
+            return Err(DistError::SigningCommandEmpty {
+                name: name.to_owned(),
+            });
+        };
+        Ok(Self {
+            command,
+            args: parts.collect(),
+        })
+    }
+
+    /// Get the arguments to sign this file with
+    fn args_for(&self, file: &Utf8Path) -> Vec<String> {
+        let mut args = self
+            .args
+            .iter()
+            .map(|arg| arg.replace(FILE_PLACEHOLDER, file.as_str()))
+            .collect::<Vec<_>>();
+        if !self.args.iter().any(|arg| arg.contains(FILE_PLACEHOLDER)) {
+            args.push(file.to_string());
+        }
+        args
+    }
+}
+
+impl Signer for CommandSigner {
+    fn provider(&self) -> &'static str {
+        "command"
+    }
+
+    fn sign(&self, file: &Utf8Path) -> DistResult<()> {
+        info!("signing {file} with {}", self.command);
+        let mut cmd = Cmd::new(&self.command, "sign with a custom command");
+        for arg in self.args_for(file) {
+            cmd.arg(arg);
+        }
+        cmd.stdout_to_stderr();
+        cmd.run()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signer(command: &[&str]) -> DistResult<CommandSigner> {
+        let command = command.iter().map(|part| (*part).to_owned()).collect();
+        CommandSigner::new("my-signer", CommandSignerConfig { command })
+    }
+
+    #[test]
+    fn test_placeholder_replaced() {
+        let signer = signer(&["sign-tool", "--in={file}", "--out", "{file}"]).unwrap();
+        assert_eq!(signer.command, "sign-tool");
+        assert_eq!(
+            signer.args_for(Utf8Path::new("dist/app.exe")),
+            vec!["--in=dist/app.exe", "--out", "dist/app.exe"]
+        );
+    }
+
+    #[test]
+    fn test_file_appended_without_placeholder() {
+        let signer = signer(&["sign-tool", "--quiet"]).unwrap();
+        assert_eq!(
+            signer.args_for(Utf8Path::new("dist/app.exe")),
+            vec!["--quiet", "dist/app.exe"]
+        );
+    }
+
+    #[test]
+    fn test_empty_command() {
+        assert!(matches!(
+            signer(&[]),
+            Err(DistError::SigningCommandEmpty { name }) if name == "my-signer"
+        ));
+    }
+}
@@ -0,0 +1,405 @@
+//! Detached signatures using minisign keys
+//!
+//! We produce "legacy" (not prehashed) minisign signatures, which are a plain
+//! ed25519 signature over the file. `minisign -V` still accepts these, and
+//! unlike prehashed signatures they can be checked with nothing but an ed25519
+//! implementation, which is what lets the shell installer fall back to
+//! `openssl pkeyutl` and the powershell installer check them itself.
+//!
+//! The formats are documented at <https://jedisct1.github.io/minisign/>.
+
+use axoasset::LocalAsset;
+use base64::Engine;
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use camino::{Utf8Path, Utf8PathBuf};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use tracing::info;
+
+use crate::errors::*;
+
+/// The env var the secret key is read from
+pub const SECRET_KEY_VAR: &str = "MINISIGN_SECRET_KEY";
+/// The env var the secret key's password is read from
+pub const PASSWORD_VAR: &str = "MINISIGN_SECRET_KEY_PASSWORD";
+
+/// The algorithm id for ed25519 keys and legacy signatures
+const SIGALG: &[u8; 2] = b"Ed";
+/// The algorithm id for scrypt-encrypted secret keys
+const KDFALG: &[u8; 2] = b"Sc";
+/// The algorithm id for the secret key's blake2b checksum
+const CHKALG: &[u8; 2] = b"B2";
+
+/// A minisign signer, configured from the environment
+#[derive(Debug)]
+pub struct Minisign {
+    secret_key: MinisignSecretKey,
+}
+
+impl Minisign {
+    /// Setup signing with the secret key for the given public key
+    pub fn new(public_key: Option<&str>) -> DistResult<Option<Self>> {
+        // Feature must be enabled
+        let Some(public_key) = public_key else {
+            return Ok(None);
+        };
+        let public_key = MinisignPublicKey::parse(public_key)?;
+
+        // We only complain about this when there's something to sign
+        let Ok(secret_key) = std::env::var(SECRET_KEY_VAR) else {
+            return Ok(None);
+        };
+        let password = std::env::var(PASSWORD_VAR).ok();
+        let secret_key = MinisignSecretKey::parse(&secret_key, password.as_deref())?;
+
+        if secret_key.key_id != public_key.key_id
+            || secret_key.keypair.public_key().as_ref() != public_key.key
+        {
+            return Err(DistError::MinisignKeyMismatch {
+                public_key_id: key_id_string(&public_key.key_id),
+                secret_key_id: key_id_string(&secret_key.key_id),
+            });
+        }
+
+        Ok(Some(Self { secret_key }))
+    }
+
+    /// Setup signing with whatever secret key is available, from the environment
+    /// or else the local minisign keyring
+    pub fn from_env_or_keyring() -> DistResult<Option<Self>> {
+        let secret_key = if let Ok(secret_key) = std::env::var(SECRET_KEY_VAR) {
+            secret_key
+        } else {
+            match keyring_path() {
+                Some(path) if path.exists() => LocalAsset::load_string(&path)?,
+                _ => return Ok(None),
+            }
+        };
+        let password = std::env::var(PASSWORD_VAR).ok();
+        let secret_key = MinisignSecretKey::parse(&secret_key, password.as_deref())?;
+        Ok(Some(Self { secret_key }))
+    }
+
+    /// Sign `file`, writing the signature to `dest`
+    pub fn sign(&self, file: &Utf8Path, dest: &Utf8Path) -> DistResult<()> {
+        info!("minisign signing {file}");
+        let contents = LocalAsset::load_bytes(file)?;
+        let file_name = file.file_name().unwrap_or_default();
+        let signature = self
+            .secret_key
+            .sign(&contents, &format!("file:{file_name}"));
+        LocalAsset::write_new(&signature, dest)?;
+        Ok(())
+    }
+}
+
+/// Get the path `minisign` keeps its secret key at by default
+fn keyring_path() -> Option<Utf8PathBuf> {
+    if let Ok(dir) = std::env::var("MINISIGN_CONFIG_DIR") {
+        return Some(Utf8PathBuf::from(dir).join("minisign.key"));
+    }
+    let home = homedir::my_home().ok()??;
+    let home = Utf8PathBuf::from_path_buf(home).ok()?;
+    Some(home.join(".minisign").join("minisign.key"))
+}
+
+/// Get just the base64 line of a minisign public key, checking that it's valid
+pub fn minisign_public_key_line(input: &str) -> DistResult<String> {
+    let key = MinisignPublicKey::parse(input)?;
+    let mut bytes = SIGALG.to_vec();
+    bytes.extend_from_slice(&key.key_id);
+    bytes.extend_from_slice(&key.key);
+    Ok(base64::prelude::BASE64_STANDARD.encode(bytes))
+}
+
+/// A parsed minisign public key
+struct MinisignPublicKey {
+    key_id: [u8; 8],
+    key: [u8; 32],
+}
+
+impl MinisignPublicKey {
+    /// Parse a public key, either as just the base64 line or the whole `.pub` file
+    fn parse(input: &str) -> DistResult<Self> {
+        let bytes = decode_key_line(input, "public key")?;
+        let Some((alg, rest)) = bytes.split_first_chunk::<2>() else {
+            return Err(invalid_key("public key", "it's too short"));
+        };
+        if alg != SIGALG {
+            return Err(invalid_key("public key", "it isn't an ed25519 key"));
+        }
+        let (Some((key_id, key)), 40) = (rest.split_first_chunk::<8>(), rest.len()) else {
+            return Err(invalid_key("public key", "it's the wrong length"));
+        };
+        Ok(Self {
+            key_id: *key_id,
+            key: key.try_into().expect("length was checked"),
+        })
+    }
+}
+
+/// A decrypted minisign secret key
+struct MinisignSecretKey {
+    key_id: [u8; 8],
+    keypair: Ed25519KeyPair,
+}
+
+// manual debug impl to prevent anyone adding derive(Debug) and leaking SECRETS
+impl std::fmt::Debug for MinisignSecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MinisignSecretKey")
+            .field("key_id", &key_id_string(&self.key_id))
+            .field("keypair", &"<hidden>")
+            .finish()
+    }
+}
+
+impl MinisignSecretKey {
+    /// Parse a secret key, either as just the base64 line or the whole `.key` file,
+    /// decrypting it with `password` if it's encrypted
+    fn parse(input: &str, password: Option<&str>) -> DistResult<Self> {
+        let bytes = decode_key_line(input, "secret key")?;
+        // sig_alg, kdf_alg, chk_alg, kdf_salt, kdf_opslimit, kdf_memlimit,
+        // and then the (maybe encrypted) key_id, secret key, and checksum
+        if bytes.len() != 2 + 2 + 2 + 32 + 8 + 8 + 8 + 64 + 32 {
+            return Err(invalid_key("secret key", "it's the wrong length"));
+        }
+        let (sig_alg, rest) = bytes.split_at(2);
+        let (kdf_alg, rest) = rest.split_at(2);
+        let (chk_alg, rest) = rest.split_at(2);
+        let (salt, rest) = rest.split_at(32);
+        let (opslimit, rest) = rest.split_at(8);
+        let (memlimit, rest) = rest.split_at(8);
+        if sig_alg != SIGALG || chk_alg != CHKALG {
+            return Err(invalid_key("secret key", "it isn't an ed25519 key"));
+        }
+
+        let mut keynum_sk = rest.to_owned();
+        let encrypted = kdf_alg == KDFALG;
+        if encrypted {
+            let Some(password) = password else {
+                return Err(DistError::MinisignPasswordMissing {});
+            };
+            let (log_n, r, p) = scrypt_params(
+                u64::from_le_bytes(opslimit.try_into().expect("length was checked")),
+                u64::from_le_bytes(memlimit.try_into().expect("length was checked")),
+            );
+            let params = scrypt::Params::new(log_n, r, p, scrypt::Params::RECOMMENDED_LEN)
+                .map_err(|_| invalid_key("secret key", "its scrypt parameters are invalid"))?;
+            let mut stream = vec![0; keynum_sk.len()];
+            scrypt::scrypt(password.as_bytes(), salt, &params, &mut stream)
+                .expect("output length is valid");
+            for (byte, mask) in keynum_sk.iter_mut().zip(stream) {
+                *byte ^= mask;
+            }
+        } else if kdf_alg != [0, 0] {
+            return Err(invalid_key(
+                "secret key",
+                "it's encrypted in an unknown way",
+            ));
+        }
+
+        let (key_id, rest) = keynum_sk.split_at(8);
+        let (secret_key, chk) = rest.split_at(64);
+        let mut hasher = Blake2b::<U32>::new();
+        hasher.update(sig_alg);
+        hasher.update(key_id);
+        hasher.update(secret_key);
+        if hasher.finalize().as_slice() != chk {
+            return Err(if encrypted {
+                DistError::MinisignWrongPassword {}
+            } else {
+                invalid_key("secret key", "its checksum doesn't match")
+            });
+        }
+
+        // libsodium-style secret keys are the seed followed by the public key
+        let (seed, public_key) = secret_key.split_at(32);
+        let keypair = Ed25519KeyPair::from_seed_and_public_key(seed, public_key)
+            .map_err(|e| invalid_key("secret key", &e.to_string()))?;
+        Ok(Self {
+            key_id: key_id.try_into().expect("length was checked"),
+            keypair,
+        })
+    }
+
+    /// Produce the contents of a `.minisig`-style signature file for `message`
+    fn sign(&self, message: &[u8], trusted_comment: &str) -> String {
+        let signature = self.keypair.sign(message);
+        let mut sig_line = SIGALG.to_vec();
+        sig_line.extend_from_slice(&self.key_id);
+        sig_line.extend_from_slice(signature.as_ref());
+
+        // The "global" signature covers the signature and the trusted comment
+        let mut global = signature.as_ref().to_vec();
+        global.extend_from_slice(trusted_comment.as_bytes());
+        let global_signature = self.keypair.sign(&global);
+
+        let base64 = base64::prelude::BASE64_STANDARD;
+        format!(
+            "untrusted comment: signature from minisign secret key {}\n{}\ntrusted comment: {trusted_comment}\n{}\n",
+            key_id_string(&self.key_id),
+            base64.encode(sig_line),
+            base64.encode(global_signature),
+        )
+    }
+}
+
+/// Get the base64 line out of a key file (or a bare key) and decode it
+fn decode_key_line(input: &str, kind: &str) -> DistResult<Vec<u8>> {
+    let line = input
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with("untrusted comment:"))
+        .ok_or_else(|| invalid_key(kind, "it's empty"))?;
+    base64::prelude::BASE64_STANDARD
+        .decode(line)
+        .map_err(|_| invalid_key(kind, "it isn't valid base64"))
+}
+
+fn invalid_key(kind: &str, reason: &str) -> DistError {
+    DistError::MinisignInvalidKey {
+        kind: kind.to_owned(),
+        reason: reason.to_owned(),
+    }
+}
+
+/// Format a key id the way minisign displays it (a little-endian u64 in hex)
+fn key_id_string(key_id: &[u8; 8]) -> String {
+    format!("{:016X}", u64::from_le_bytes(*key_id))
+}
+
+/// Convert libsodium's scrypt opslimit/memlimit into (log2(N), r, p),
+/// the same way `crypto_pwhash_scryptsalsa208sha256` does
+fn scrypt_params(opslimit: u64, memlimit: u64) -> (u8, u32, u32) {
+    let opslimit = opslimit.max(32768);
+    let r = 8;
+    let log_n_for = |max_n: u64| {
+        (1..63)
+            .find(|log_n| 1u64 << log_n > max_n / 2)
+            .unwrap_or(63)
+    };
+    if opslimit < memlimit / 32 {
+        let log_n = log_n_for(opslimit / (r as u64 * 4));
+        (log_n, r, 1)
+    } else {
+        let log_n = log_n_for(memlimit / (r as u64 * 128));
+        let max_rp = ((opslimit / 4) >> log_n).min(0x3fffffff);
+        (log_n, r, max_rp as u32 / r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{UnparsedPublicKey, ED25519};
+
+    // A real key and signature made by minisign, over the message "test"
+    const PUBLIC_KEY: &str = "untrusted comment: minisign public key E7620F1842B4E81F
+RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+    const SIGNATURE: &str =
+        "RWQf6LRCGA9i59SLOFxz6NxvASXDJeRtuZykwQepbDEGt87ig1BNpWaVWuNrm73YiIiJbq71Wi+dP9eKL8OC351vwIasSSbXxwA=";
+
+    /// Make an unencrypted secret key file from a seed
+    fn secret_key_file(key_id: [u8; 8], seed: [u8; 32]) -> String {
+        let keypair = Ed25519KeyPair::from_seed_unchecked(&seed).unwrap();
+        let mut secret_key = seed.to_vec();
+        secret_key.extend_from_slice(keypair.public_key().as_ref());
+        let mut hasher = Blake2b::<U32>::new();
+        hasher.update(SIGALG);
+        hasher.update(key_id);
+        hasher.update(&secret_key);
+
+        let mut bytes = SIGALG.to_vec();
+        bytes.extend_from_slice(&[0, 0]);
+        bytes.extend_from_slice(CHKALG);
+        bytes.extend_from_slice(&[0; 32 + 8 + 8]);
+        bytes.extend_from_slice(&key_id);
+        bytes.extend_from_slice(&secret_key);
+        bytes.extend_from_slice(&hasher.finalize());
+        format!(
+            "untrusted comment: minisign encrypted secret key\n{}\n",
+            base64::prelude::BASE64_STANDARD.encode(bytes)
+        )
+    }
+
+    #[test]
+    fn test_parse_public_key() {
+        let key = MinisignPublicKey::parse(PUBLIC_KEY).unwrap();
+        assert_eq!(key_id_string(&key.key_id), "E7620F1842B4E81F");
+
+        // The key verifies minisign's own signature
+        let sig_line = base64::prelude::BASE64_STANDARD.decode(SIGNATURE).unwrap();
+        assert_eq!(&sig_line[..2], SIGALG);
+        assert_eq!(sig_line[2..10], key.key_id);
+        UnparsedPublicKey::new(&ED25519, key.key)
+            .verify(b"test", &sig_line[10..])
+            .unwrap();
+
+        // Just the key line works too
+        let bare = MinisignPublicKey::parse(PUBLIC_KEY.lines().last().unwrap()).unwrap();
+        assert_eq!(bare.key, key.key);
+
+        assert!(MinisignPublicKey::parse("not a key").is_err());
+        assert!(MinisignPublicKey::parse("RWQf6LRCGA9i").is_err());
+    }
+
+    #[test]
+    fn test_sign() {
+        let key_id = *b"\x1f\xe8\xb4\x42\x18\x0f\x62\xe7";
+        let key = MinisignSecretKey::parse(&secret_key_file(key_id, [7; 32]), None).unwrap();
+        assert_eq!(key_id_string(&key.key_id), "E7620F1842B4E81F");
+
+        let signature = key.sign(b"hello\n", "file:sha256.sum");
+        let lines = signature.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "untrusted comment: signature from minisign secret key E7620F1842B4E81F"
+        );
+        assert_eq!(lines[2], "trusted comment: file:sha256.sum");
+
+        let base64 = base64::prelude::BASE64_STANDARD;
+        let sig_line = base64.decode(lines[1]).unwrap();
+        assert_eq!(&sig_line[..2], SIGALG);
+        assert_eq!(sig_line[2..10], key_id);
+        let public_key = UnparsedPublicKey::new(&ED25519, key.keypair.public_key().as_ref());
+        public_key.verify(b"hello\n", &sig_line[10..]).unwrap();
+
+        let mut global = sig_line[10..].to_vec();
+        global.extend_from_slice(b"file:sha256.sum");
+        public_key
+            .verify(&global, &base64.decode(lines[3]).unwrap())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_parse_encrypted_secret_key() {
+        // Encrypted independently of dist (with OpenSSL's scrypt and BLAKE2b) following
+        // minisign's key format, with the password "hunter2". It uses cheap scrypt
+        // parameters, as minisign's defaults need a gigabyte of memory.
+        const ENCRYPTED: &str = "untrusted comment: minisign encrypted secret key
+RWRTY0IyCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkAgAAAAAAAAAAAEAAAAAAAeqHfoYyJNmCduaYltgV4rk+9CM5Jf5a0rgpPeKfAneL5hFRZFKYEunlQQUW/d1IKuC16yusL19Oopa1rpouG0cR4TgtYaQnxR8cEHe7kVmkw45vXA5VkEsXE7ZRrwUY5TrsnyZeiYRA=
+";
+        const ENCRYPTED_PUBLIC_KEY: &str =
+            "RWQBAgMEBQYHCBl/ayPhbIUyxqvIOPrNXqeJvgx2spIDNAOb+os9No1h";
+
+        let key = MinisignSecretKey::parse(ENCRYPTED, Some("hunter2")).unwrap();
+        let public_key = MinisignPublicKey::parse(ENCRYPTED_PUBLIC_KEY).unwrap();
+        assert_eq!(key.key_id, public_key.key_id);
+        assert_eq!(key.keypair.public_key().as_ref(), public_key.key);
+        assert!(matches!(
+            MinisignSecretKey::parse(ENCRYPTED, Some("hunter3")),
+            Err(DistError::MinisignWrongPassword {})
+        ));
+        assert!(matches!(
+            MinisignSecretKey::parse(ENCRYPTED, None),
+            Err(DistError::MinisignPasswordMissing {})
+        ));
+    }
+
+    #[test]
+    fn test_scrypt_params() {
+        // minisign's defaults
+        assert_eq!(scrypt_params(33554432, 1073741824), (20, 8, 1));
+        assert_eq!(scrypt_params(1, 1u64 << 30), (10, 8, 1));
+    }
+}
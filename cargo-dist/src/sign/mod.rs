@@ -1,22 +1,75 @@
 //! Code/artifact signing support
+//!
+//! Signing is done by [`Signer`][]s, which are registered by name in [`Signing`][].
+//! The `signing` config decides which of them signs each kind of file, on each target.
+//! There are two built-in signers, `ssldotcom` and `macos`, which are set up by
+//! `ssldotcom-windows-sign` and `macos-sign`, and are used for anything the config
+//! doesn't cover (which keeps them signing what they always have).
 
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
+use std::sync::OnceLock;
 
 use axoasset::AxoClient;
 use camino::Utf8Path;
-use dist_schema::TripleNameRef;
+use dist_schema::{SigningInfo, TripleNameRef};
 
-use crate::{config::ProductionMode, DistResult};
+use crate::{
+    config::{
+        v1::artifacts::WorkspaceArtifactConfig, ProductionMode, SignatureStyle, SigningConfig,
+        SigningKind, SigningProviderConfig,
+    },
+    errors::DistError,
+    net::ClientSettings,
+    DistResult, SortedMap,
+};
 
+mod azure;
+mod command;
+mod gpg;
 mod macos;
+mod minisign;
+mod osslsigncode;
+mod sigstore;
+
+pub use minisign::minisign_public_key_line;
+pub use sigstore::SigstoreClient;
 mod ssldotcom;
 
+/// The name of the built-in ssl.com signer
+const SSLDOTCOM: &str = "ssldotcom";
+/// The name of the built-in Apple codesign signer
+const MACOS: &str = "macos";
+
+/// Something that can sign files
+pub trait Signer: std::fmt::Debug {
+    /// The kind of provider this is, to record in the manifest (e.g. "osslsigncode")
+    fn provider(&self) -> &'static str;
+
+    /// Whether this signer knows how to sign this file
+    ///
+    /// Signers the user configured themselves are trusted to sign whatever
+    /// the config sends their way, so by default everything is accepted.
+    fn can_sign(&self, _file: &Utf8Path) -> DistResult<bool> {
+        Ok(true)
+    }
+
+    /// Sign a file in place
+    fn sign(&self, file: &Utf8Path) -> DistResult<()>;
+}
+
 /// Code/artifact signing providers
 #[derive(Debug)]
 pub struct Signing {
-    macos: Option<macos::Codesign>,
-    ssldotcom: Option<ssldotcom::CodeSignTool>,
+    signers: SortedMap<String, Box<dyn Signer>>,
+    config: SigningConfig,
+    /// The minisign public key signatures must verify against, if any
+    minisign_public_key: Option<String>,
+    /// The minisign signer, set up the first time something is signed
+    minisign: OnceLock<Option<minisign::Minisign>>,
+    /// The gpg signer, set up the first time something is signed
+    gpg: OnceLock<Option<gpg::Gpg>>,
+    sigstore: Option<SigstoreClient>,
 }
 
 impl Signing {
@@ -27,40 +80,441 @@ impl Signing {
         dist_dir: &Utf8Path,
         ssldotcom_windows_sign: Option<ProductionMode>,
         macos_sign: bool,
+        signing: &SigningConfig,
+        artifacts: &WorkspaceArtifactConfig,
     ) -> DistResult<Self> {
-        let ssldotcom =
-            ssldotcom::CodeSignTool::new(client, host_target, dist_dir, ssldotcom_windows_sign)?;
-        let macos = if macos_sign {
-            macos::Codesign::new(host_target)?
+        let mut signers = SortedMap::<String, Box<dyn Signer>>::new();
+        if let Some(signer) =
+            ssldotcom::CodeSignTool::new(client, host_target, dist_dir, ssldotcom_windows_sign)?
+        {
+            signers.insert(SSLDOTCOM.to_owned(), Box::new(signer));
+        }
+        if macos_sign {
+            if let Some(signer) = macos::Codesign::new(host_target)? {
+                signers.insert(MACOS.to_owned(), Box::new(signer));
+            }
+        }
+        // Providers from the config take precedence over the built-in ones
+        for (name, provider) in &signing.providers {
+            let signer: Box<dyn Signer> = match provider {
+                SigningProviderConfig::AzureTrustedSigning(config) => {
+                    Box::new(azure::TrustedSigning::new(config.clone()))
+                }
+                SigningProviderConfig::Osslsigncode(config) => {
+                    Box::new(osslsigncode::Osslsigncode::new(config.clone()))
+                }
+                SigningProviderConfig::Command(config) => {
+                    Box::new(command::CommandSigner::new(name, config.clone())?)
+                }
+            };
+            signers.insert(name.clone(), signer);
+        }
+
+        // Make sure the rules only refer to providers that exist
+        // (the built-in ones may legitimately be missing on this machine)
+        for rules in std::iter::once(&signing.rules).chain(signing.targets.values()) {
+            for kind in [
+                SigningKind::Binaries,
+                SigningKind::Msi,
+                SigningKind::Pkg,
+                SigningKind::Archives,
+                SigningKind::Checksums,
+            ] {
+                let Some(name) = rules.provider_for(kind) else {
+                    continue;
+                };
+                if !signing.providers.contains_key(name) && name != SSLDOTCOM && name != MACOS {
+                    return Err(DistError::UnknownSigningProvider {
+                        kind,
+                        name: name.to_owned(),
+                    });
+                }
+            }
+        }
+
+        // The gpg and minisign keys are only looked for when something gets signed,
+        // so planning doesn't go poking at secrets (or shelling out to gpg)
+        let sigstore = if artifacts.signatures.contains(&SignatureStyle::Sigstore) {
+            Some(crate::net::create_sigstore_client(
+                &ClientSettings::new(),
+                &artifacts.sigstore,
+            )?)
         } else {
             None
         };
-        Ok(Self { macos, ssldotcom })
+        Ok(Self {
+            signers,
+            config: signing.clone(),
+            minisign_public_key: artifacts.minisign_public_key.clone(),
+            minisign: OnceLock::new(),
+            gpg: OnceLock::new(),
+            sigstore,
+        })
+    }
+
+    /// Get the minisign signer, setting it up if this is the first signature
+    fn minisign_signer(&self) -> DistResult<Option<&minisign::Minisign>> {
+        get_or_try_init(&self.minisign, || {
+            // With a public key configured, signatures had better come from its secret key,
+            // so only fall back to whatever key is lying around if there isn't one
+            match &self.minisign_public_key {
+                Some(public_key) => minisign::Minisign::new(Some(public_key)),
+                None => minisign::Minisign::from_env_or_keyring(),
+            }
+        })
+    }
+
+    /// Get the gpg signer, setting it up if this is the first signature
+    fn gpg_signer(&self) -> DistResult<Option<&gpg::Gpg>> {
+        get_or_try_init(&self.gpg, gpg::Gpg::new)
+    }
+
+    /// Get the names of the signers that should sign this kind of file for this target
+    fn signers_for(&self, kind: SigningKind, target: Option<&TripleNameRef>) -> Vec<&str> {
+        if let Some(name) = self.config.provider_for(kind, target) {
+            return vec![name];
+        }
+        // Without a rule, fall back to what the built-in signers have always signed
+        match kind {
+            SigningKind::Binaries => vec![SSLDOTCOM, MACOS],
+            SigningKind::Msi => vec![SSLDOTCOM],
+            SigningKind::Pkg | SigningKind::Archives | SigningKind::Checksums => vec![],
+        }
     }
 
-    /// Sign a file
-    pub fn sign(&self, file: &Utf8Path) -> DistResult<()> {
-        if let Some(signer) = &self.ssldotcom {
-            let extension = file.extension().unwrap_or_default();
-            if let "exe" | "msi" | "ps1" = extension {
-                signer.sign(file)?;
+    /// Whether any signer is set up to sign this kind of file for this target
+    pub fn signs(&self, kind: SigningKind, target: Option<&TripleNameRef>) -> bool {
+        self.signers_for(kind, target)
+            .into_iter()
+            .any(|name| self.signers.contains_key(name))
+    }
+
+    /// Sign a file, returning a record of each signer that signed it
+    ///
+    /// `target` is the target the file was built for, if it's specific to one.
+    pub fn sign(
+        &self,
+        kind: SigningKind,
+        target: Option<&TripleNameRef>,
+        file: &Utf8Path,
+    ) -> DistResult<Vec<SigningInfo>> {
+        let mut signed_by = vec![];
+        for name in self.signers_for(kind, target) {
+            let Some(signer) = self.signers.get(name) else {
+                continue;
+            };
+            if !signer.can_sign(file)? {
+                continue;
             }
+            signer.sign(file)?;
+            signed_by.push(SigningInfo {
+                signer: name.to_owned(),
+                provider: signer.provider().to_owned(),
+            });
         }
-        if let Some(signer) = &self.macos {
-            // TODO: restructure, this is just to keep Windows
-            // from flagging dead code
-            #[cfg(unix)]
-            let is_executable = file.metadata()?.permissions().mode() & 0o111 != 0;
-            #[cfg(windows)]
-            let is_executable = true;
-
-            // At the moment, we're exclusively signing executables.
-            // In the future, we may need to sign app bundles (which are
-            // directories) or certain other metadata files.
-            if file.is_file() && is_executable {
-                signer.sign(file)?;
+        Ok(signed_by)
+    }
+
+    /// Produce a detached minisign signature of a file with the configured key
+    ///
+    /// The installers refuse to run without this signature, so it's an error
+    /// if the secret key isn't available.
+    pub fn minisign(&self, file: &Utf8Path, dest: &Utf8Path) -> DistResult<()> {
+        let signer = if self.minisign_public_key.is_some() {
+            self.minisign_signer()?
+        } else {
+            None
+        };
+        let Some(signer) = signer else {
+            return Err(DistError::SigningSecretMissing {
+                provider: "minisign".to_owned(),
+                var: minisign::SECRET_KEY_VAR.to_owned(),
+            });
+        };
+        signer.sign(file, dest)
+    }
+
+    /// Produce a detached signature of a file
+    ///
+    /// The signature is already listed as an artifact, so not having a key
+    /// (or token) to sign with is an error.
+    pub fn detached_sign(
+        &self,
+        style: SignatureStyle,
+        file: &Utf8Path,
+        dest: &Utf8Path,
+    ) -> DistResult<()> {
+        match style {
+            SignatureStyle::Gpg => {
+                let Some(signer) = self.gpg_signer()? else {
+                    return Err(DistError::SigningSecretMissing {
+                        provider: "gpg".to_owned(),
+                        var: gpg::PRIVATE_KEY_VAR.to_owned(),
+                    });
+                };
+                signer.sign(file, dest)?;
+            }
+            SignatureStyle::Minisign => {
+                let Some(signer) = self.minisign_signer()? else {
+                    return Err(DistError::SigningSecretMissing {
+                        provider: "minisign".to_owned(),
+                        var: minisign::SECRET_KEY_VAR.to_owned(),
+                    });
+                };
+                signer.sign(file, dest)?;
             }
+            SignatureStyle::Sigstore => match &self.sigstore {
+                Some(client) if client.has_token() => {
+                    let handle = tokio::runtime::Handle::current();
+                    handle.block_on(client.sign(file, dest))?;
+                }
+                _ => {
+                    return Err(DistError::SigningSecretMissing {
+                        provider: "sigstore".to_owned(),
+                        var: sigstore::ID_TOKEN_VAR.to_owned(),
+                    });
+                }
+            },
         }
         Ok(())
     }
 }
+
+/// Get the signer in a cell, setting it up if the cell is empty
+///
+/// If setting up fails the cell is left empty, so the error comes up again next time.
+fn get_or_try_init<T>(
+    cell: &OnceLock<Option<T>>,
+    init: impl FnOnce() -> DistResult<Option<T>>,
+) -> DistResult<Option<&T>> {
+    if let Some(signer) = cell.get() {
+        return Ok(signer.as_ref());
+    }
+    let signer = init()?;
+    Ok(cell.get_or_init(|| signer).as_ref())
+}
+
+/// Get the env vars the configured signing providers and detached signatures
+/// read their secrets from
+///
+/// CI passes these through from the secrets of the same name.
+pub fn signing_secret_vars(
+    signing: &SigningConfig,
+    signatures: &[SignatureStyle],
+) -> Vec<&'static str> {
+    let mut vars = vec![];
+    let provider_vars = signing.providers.values().map(|provider| match provider {
+        SigningProviderConfig::AzureTrustedSigning(_) => azure::SECRET_VARS,
+        SigningProviderConfig::Osslsigncode(config) => osslsigncode::secret_vars(config),
+        SigningProviderConfig::Command(_) => &[],
+    });
+    let signature_vars = signatures.iter().map(|style| match style {
+        SignatureStyle::Gpg => &[gpg::PRIVATE_KEY_VAR, gpg::PASSPHRASE_VAR, gpg::KEY_ID_VAR][..],
+        SignatureStyle::Minisign => &[minisign::SECRET_KEY_VAR, minisign::PASSWORD_VAR][..],
+        // OIDC tokens come from the job itself, not secrets
+        SignatureStyle::Sigstore => &[],
+    });
+    for provider_vars in provider_vars.chain(signature_vars) {
+        for var in provider_vars {
+            if !vars.contains(var) {
+                vars.push(*var);
+            }
+        }
+    }
+    vars
+}
+
+impl Signer for ssldotcom::CodeSignTool {
+    fn provider(&self) -> &'static str {
+        SSLDOTCOM
+    }
+
+    fn can_sign(&self, file: &Utf8Path) -> DistResult<bool> {
+        let extension = file.extension().unwrap_or_default();
+        Ok(matches!(extension, "exe" | "msi" | "ps1"))
+    }
+
+    fn sign(&self, file: &Utf8Path) -> DistResult<()> {
+        ssldotcom::CodeSignTool::sign(self, file)
+    }
+}
+
+impl Signer for macos::Codesign {
+    fn provider(&self) -> &'static str {
+        MACOS
+    }
+
+    fn can_sign(&self, file: &Utf8Path) -> DistResult<bool> {
+        // TODO: restructure, this is just to keep Windows
+        // from flagging dead code
+        #[cfg(unix)]
+        let is_executable = file.metadata()?.permissions().mode() & 0o111 != 0;
+        #[cfg(windows)]
+        let is_executable = true;
+
+        // At the moment, we're exclusively signing executables.
+        // In the future, we may need to sign app bundles (which are
+        // directories) or certain other metadata files.
+        Ok(file.is_file() && is_executable)
+    }
+
+    fn sign(&self, file: &Utf8Path) -> DistResult<()> {
+        macos::Codesign::sign(self, file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dist_schema::TripleName;
+
+    use super::*;
+    use crate::config::{ChecksumStyle, CommandSignerConfig, SigningRules};
+
+    fn artifacts() -> WorkspaceArtifactConfig {
+        WorkspaceArtifactConfig {
+            source_tarball: true,
+            offline_bundle: false,
+            checksum: ChecksumStyle::Sha256,
+            minisign_public_key: None,
+            signatures: vec![],
+            sigstore: Default::default(),
+            provenance: false,
+            sbom: false,
+        }
+    }
+
+    fn signing(config: &str) -> Signing {
+        let config: SigningConfig = axoasset::SourceFile::new("signing.toml", config.to_owned())
+            .deserialize_toml()
+            .unwrap();
+        let client = crate::net::create_axoasset_client(&Default::default()).unwrap();
+        Signing::new(
+            &client,
+            &TripleName::new("x86_64-unknown-linux-gnu".to_owned()),
+            Utf8Path::new("target/distrib"),
+            None,
+            false,
+            &config,
+            &artifacts(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn target_rules_override_global_rules() {
+        let signing = signing(
+            r#"
+binaries = "everywhere"
+checksums = "everywhere"
+
+[targets.x86_64-pc-windows-msvc]
+binaries = "windows"
+
+[providers.everywhere]
+kind = "command"
+command = ["true"]
+
+[providers.windows]
+kind = "command"
+command = ["true"]
+"#,
+        );
+        let windows = TripleName::new("x86_64-pc-windows-msvc".to_owned());
+        let linux = TripleName::new("x86_64-unknown-linux-gnu".to_owned());
+
+        assert_eq!(
+            signing.signers_for(SigningKind::Binaries, Some(&windows)),
+            vec!["windows"]
+        );
+        assert_eq!(
+            signing.signers_for(SigningKind::Binaries, Some(&linux)),
+            vec!["everywhere"]
+        );
+        assert_eq!(
+            signing.signers_for(SigningKind::Checksums, Some(&windows)),
+            vec!["everywhere"]
+        );
+        assert!(signing.signs(SigningKind::Checksums, None));
+        assert!(!signing.signs(SigningKind::Archives, None));
+    }
+
+    #[test]
+    fn builtin_signers_are_the_fallback() {
+        let signing = signing("");
+
+        assert_eq!(
+            signing.signers_for(SigningKind::Binaries, None),
+            vec![SSLDOTCOM, MACOS]
+        );
+        assert_eq!(signing.signers_for(SigningKind::Msi, None), vec![SSLDOTCOM]);
+        assert!(signing.signers_for(SigningKind::Pkg, None).is_empty());
+        // Neither is set up, so nothing is signed
+        assert!(!signing.signs(SigningKind::Binaries, None));
+    }
+
+    #[test]
+    fn detached_signers_are_set_up_lazily() {
+        let client = crate::net::create_axoasset_client(&Default::default()).unwrap();
+        let signing = Signing::new(
+            &client,
+            &TripleName::new("x86_64-unknown-linux-gnu".to_owned()),
+            Utf8Path::new("target/distrib"),
+            None,
+            false,
+            &SigningConfig::default(),
+            &WorkspaceArtifactConfig {
+                // Not a valid key, but nothing should look at it until it's used
+                minisign_public_key: Some("not a key".to_owned()),
+                signatures: vec![SignatureStyle::Gpg, SignatureStyle::Minisign],
+                ..artifacts()
+            },
+        )
+        .unwrap();
+
+        assert!(signing.minisign.get().is_none());
+        assert!(signing.gpg.get().is_none());
+        // Setting up fails, and keeps failing, once something is actually signed
+        let file = Utf8Path::new("target/distrib/app.tar.gz");
+        let dest = Utf8Path::new("target/distrib/app.tar.gz.minisig");
+        for _ in 0..2 {
+            assert!(matches!(
+                signing.detached_sign(SignatureStyle::Minisign, file, dest),
+                Err(DistError::MinisignInvalidKey { .. })
+            ));
+        }
+        assert!(signing.minisign.get().is_none());
+    }
+
+    #[test]
+    fn unknown_providers_are_rejected() {
+        let config = SigningConfig {
+            rules: SigningRules {
+                msi: Some("nope".to_owned()),
+                ..Default::default()
+            },
+            targets: Default::default(),
+            providers: SortedMap::from_iter([(
+                "yep".to_owned(),
+                SigningProviderConfig::Command(CommandSignerConfig {
+                    command: vec!["true".to_owned()],
+                }),
+            )]),
+        };
+        let client = crate::net::create_axoasset_client(&Default::default()).unwrap();
+        let err = Signing::new(
+            &client,
+            &TripleName::new("x86_64-unknown-linux-gnu".to_owned()),
+            Utf8Path::new("target/distrib"),
+            None,
+            false,
+            &config,
+            &artifacts(),
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            err,
+            DistError::UnknownSigningProvider { kind: SigningKind::Msi, name } if name == "nope"
+        ));
+    }
+}
@@ -0,0 +1,207 @@
+//! Codesigning with a local PKCS#12 certificate using osslsigncode
+//!
+//! osslsigncode can produce Authenticode signatures for Windows executables,
+//! msis and cabs from any platform, which makes it handy for teams that hold
+//! their own certificate.
+use axoprocess::Cmd;
+use base64::Engine;
+use camino::{Utf8Path, Utf8PathBuf};
+use tracing::info;
+
+use super::Signer;
+use crate::config::OsslsigncodeConfig;
+use crate::{create_tmp, errors::*};
+
+/// The env var the base64 PKCS#12 file is read from, if there's no path to it
+const PKCS12_VAR: &str = "OSSLSIGNCODE_PKCS12";
+/// The env var the PKCS#12 file's password is read from
+const PASSWORD_VAR: &str = "OSSLSIGNCODE_PASSWORD";
+
+/// A signer using osslsigncode
+#[derive(Debug)]
+pub struct Osslsigncode {
+    config: OsslsigncodeConfig,
+}
+
+impl Osslsigncode {
+    pub fn new(config: OsslsigncodeConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the arguments to sign this file with, writing the result to `signed`
+    fn args_for(
+        &self,
+        pkcs12: &Utf8Path,
+        password: Option<&Utf8Path>,
+        file: &Utf8Path,
+        signed: &Utf8Path,
+    ) -> Vec<String> {
+        let mut args = vec!["sign".to_owned(), "-pkcs12".to_owned(), pkcs12.to_string()];
+        if let Some(password) = password {
+            args.extend(["-readpass".to_owned(), password.to_string()]);
+        }
+        args.extend(["-h".to_owned(), "sha256".to_owned()]);
+        if let Some(url) = &self.config.timestamp_url {
+            args.extend(["-ts".to_owned(), url.clone()]);
+        }
+        args.extend([
+            "-in".to_owned(),
+            file.to_string(),
+            "-out".to_owned(),
+            signed.to_string(),
+        ]);
+        args
+    }
+}
+
+/// Get the env vars signing with these settings reads secrets from
+pub fn secret_vars(config: &OsslsigncodeConfig) -> &'static [&'static str] {
+    if config.pkcs12.is_some() {
+        &[PASSWORD_VAR]
+    } else {
+        &[PKCS12_VAR, PASSWORD_VAR]
+    }
+}
+
+impl Signer for Osslsigncode {
+    fn provider(&self) -> &'static str {
+        "osslsigncode"
+    }
+
+    fn sign(&self, file: &Utf8Path) -> DistResult<()> {
+        info!("osslsigncode signing {file}");
+        let (_tmp, tmp_dir) = create_tmp()?;
+        let pkcs12 = if let Some(path) = &self.config.pkcs12 {
+            path.clone()
+        } else {
+            write_pkcs12(&var(PKCS12_VAR)?, &tmp_dir)?
+        };
+        // Hand over the password in a file rather than on the command line, where
+        // other users on the machine could see it
+        let password = if let Ok(password) = std::env::var(PASSWORD_VAR) {
+            let path = tmp_dir.join("password");
+            std::fs::write(&path, password)?;
+            Some(path)
+        } else {
+            None
+        };
+        // osslsigncode won't sign in place, so sign to a temp file and move it back
+        let signed = Utf8PathBuf::from(format!("{file}.signed"));
+
+        let mut cmd = Cmd::new("osslsigncode", "sign with osslsigncode");
+        for arg in self.args_for(&pkcs12, password.as_deref(), file, &signed) {
+            cmd.arg(arg);
+        }
+        cmd.stdout_to_stderr();
+        cmd.run()?;
+
+        std::fs::rename(&signed, file)?;
+        Ok(())
+    }
+}
+
+/// Decode a base64 PKCS#12 file into the given dir, returning its path
+fn write_pkcs12(encoded: &str, dir: &Utf8Path) -> DistResult<Utf8PathBuf> {
+    let decoded = base64::prelude::BASE64_STANDARD
+        .decode(encoded.trim())
+        .map_err(|_| DistError::Pkcs12DecodeError {})?;
+    let path = dir.join("cert.p12");
+    std::fs::write(&path, decoded)?;
+    Ok(path)
+}
+
+fn var(var: &str) -> DistResult<String> {
+    std::env::var(var).map_err(|_| DistError::SigningSecretMissing {
+        provider: "osslsigncode".to_owned(),
+        var: var.to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signer(pkcs12: Option<&str>, timestamp_url: Option<&str>) -> Osslsigncode {
+        Osslsigncode::new(OsslsigncodeConfig {
+            pkcs12: pkcs12.map(Utf8PathBuf::from),
+            timestamp_url: timestamp_url.map(str::to_owned),
+        })
+    }
+
+    #[test]
+    fn test_args() {
+        let signer = signer(Some("cert.p12"), None);
+        assert_eq!(
+            signer.args_for(
+                Utf8Path::new("cert.p12"),
+                None,
+                Utf8Path::new("dist/app.exe"),
+                Utf8Path::new("dist/app.exe.signed"),
+            ),
+            vec![
+                "sign",
+                "-pkcs12",
+                "cert.p12",
+                "-h",
+                "sha256",
+                "-in",
+                "dist/app.exe",
+                "-out",
+                "dist/app.exe.signed",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_args_with_password_and_timestamp() {
+        let signer = signer(Some("cert.p12"), Some("http://timestamp.digicert.com"));
+        assert_eq!(
+            signer.args_for(
+                Utf8Path::new("cert.p12"),
+                Some(Utf8Path::new("/tmp/password")),
+                Utf8Path::new("dist/app.exe"),
+                Utf8Path::new("dist/app.exe.signed"),
+            ),
+            vec![
+                "sign",
+                "-pkcs12",
+                "cert.p12",
+                "-readpass",
+                "/tmp/password",
+                "-h",
+                "sha256",
+                "-ts",
+                "http://timestamp.digicert.com",
+                "-in",
+                "dist/app.exe",
+                "-out",
+                "dist/app.exe.signed",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_pkcs12() {
+        let (_tmp, tmp_dir) = create_tmp().unwrap();
+        let path = write_pkcs12(" aGVsbG8=\n", &tmp_dir).unwrap();
+        assert_eq!(path, tmp_dir.join("cert.p12"));
+        assert_eq!(std::fs::read(&path).unwrap(), b"hello");
+
+        assert!(matches!(
+            write_pkcs12("not base64!", &tmp_dir),
+            Err(DistError::Pkcs12DecodeError {})
+        ));
+    }
+
+    #[test]
+    fn test_secret_vars() {
+        assert_eq!(
+            secret_vars(&signer(Some("cert.p12"), None).config),
+            &[PASSWORD_VAR]
+        );
+        assert_eq!(
+            secret_vars(&signer(None, None).config),
+            &[PKCS12_VAR, PASSWORD_VAR]
+        );
+    }
+}
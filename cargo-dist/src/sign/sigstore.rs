@@ -0,0 +1,463 @@
+//! Keyless signing with Sigstore
+//!
+//! For each file we make a throwaway ECDSA P-256 key, trade an OIDC token for
+//! a short-lived certificate for it from Fulcio, sign the file with it, and
+//! record the signature in Rekor's transparency log. Everything needed to
+//! verify that (e.g. with `cosign verify-blob --bundle`) goes in a
+//! `.sigstore.json` bundle next to the file.
+//!
+//! The APIs and the bundle format are documented at <https://docs.sigstore.dev/>.
+
+use axoasset::{reqwest, LocalAsset};
+use base64::Engine;
+use camino::Utf8Path;
+use ring::rand::SystemRandom;
+use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+use tracing::info;
+
+use crate::{config::SigstoreConfig, decode_hex, encode_hex, errors::*, SortedMap};
+
+/// The env var an OIDC token for Sigstore is read from
+pub const ID_TOKEN_VAR: &str = "SIGSTORE_ID_TOKEN";
+/// The env vars GitHub Actions sets when a job can request OIDC tokens
+const GITHUB_TOKEN_URL_VAR: &str = "ACTIONS_ID_TOKEN_REQUEST_URL";
+const GITHUB_TOKEN_TOKEN_VAR: &str = "ACTIONS_ID_TOKEN_REQUEST_TOKEN";
+
+/// The DER prefix of a SubjectPublicKeyInfo for an uncompressed P-256 point
+const P256_SPKI_PREFIX: &[u8] = &[
+    0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+    0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+];
+
+/// A client for a particular Sigstore instance
+#[derive(Debug, Clone)]
+pub struct SigstoreClient {
+    client: reqwest::Client,
+    config: SigstoreConfig,
+}
+
+impl SigstoreClient {
+    /// Create a new client with the given http client and instance
+    pub fn new(client: reqwest::Client, config: SigstoreConfig) -> Self {
+        Self { client, config }
+    }
+
+    /// Whether there's an OIDC token to sign with in the environment
+    pub fn has_token(&self) -> bool {
+        std::env::var(ID_TOKEN_VAR).is_ok()
+            || (std::env::var(GITHUB_TOKEN_URL_VAR).is_ok()
+                && std::env::var(GITHUB_TOKEN_TOKEN_VAR).is_ok())
+    }
+
+    /// Sign `file`, writing the Sigstore bundle to `dest`
+    pub async fn sign(&self, file: &Utf8Path, dest: &Utf8Path) -> DistResult<()> {
+        let token = self.id_token().await?;
+        self.sign_with_token(file, dest, &token).await
+    }
+
+    /// Sign `file` with the identity of the given OIDC token, writing the Sigstore bundle to `dest`
+    pub(crate) async fn sign_with_token(
+        &self,
+        file: &Utf8Path,
+        dest: &Utf8Path,
+        token: &str,
+    ) -> DistResult<()> {
+        info!("sigstore signing {file}");
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng)
+            .map_err(|_| sigstore_error("couldn't generate a signing key"))?;
+        let keypair =
+            EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng)
+                .map_err(|_| sigstore_error("couldn't load the signing key"))?;
+
+        let certificate = self.signing_certificate(&keypair, &rng, token).await?;
+
+        let contents = LocalAsset::load_bytes(file)?;
+        let digest = sha2::Sha256::digest(&contents);
+        let signature = keypair
+            .sign(&rng, &contents)
+            .map_err(|_| sigstore_error("couldn't sign the file"))?;
+        let signature = b64(signature.as_ref());
+
+        let tlog_entry = self
+            .upload_to_rekor(&certificate, &signature, &digest)
+            .await?;
+
+        let bundle = Bundle {
+            media_type: "application/vnd.dev.sigstore.bundle.v0.3+json",
+            verification_material: VerificationMaterial {
+                certificate: Certificate {
+                    raw_bytes: pem_to_der_base64(&certificate),
+                },
+                tlog_entries: vec![tlog_entry],
+            },
+            message_signature: MessageSignature {
+                message_digest: MessageDigest {
+                    algorithm: "SHA2_256",
+                    digest: b64(&digest),
+                },
+                signature,
+            },
+        };
+        let json = serde_json::to_string_pretty(&bundle).expect("bundles are serializable");
+        LocalAsset::write_new(&json, dest)?;
+        Ok(())
+    }
+
+    /// Get an OIDC token, either directly from the environment or from GitHub Actions
+    async fn id_token(&self) -> DistResult<String> {
+        if let Ok(token) = std::env::var(ID_TOKEN_VAR) {
+            return Ok(token);
+        }
+        let (Ok(url), Ok(request_token)) = (
+            std::env::var(GITHUB_TOKEN_URL_VAR),
+            std::env::var(GITHUB_TOKEN_TOKEN_VAR),
+        ) else {
+            return Err(sigstore_error(&format!("{ID_TOKEN_VAR} isn't set")));
+        };
+
+        #[derive(Deserialize)]
+        struct GithubToken {
+            value: String,
+        }
+        let url = format!("{url}&audience=sigstore");
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(request_token)
+            .send()
+            .await?;
+        let token: GithubToken = check_response(&url, response).await?;
+        Ok(token.value)
+    }
+
+    /// Get a certificate for our key from Fulcio, returning the PEM of the leaf certificate
+    async fn signing_certificate(
+        &self,
+        keypair: &EcdsaKeyPair,
+        rng: &SystemRandom,
+        token: &str,
+    ) -> DistResult<String> {
+        // Fulcio wants proof we hold the key, in the form of a signature over
+        // the identity the token is for
+        let claim = token_identity(token)?;
+        let proof = keypair
+            .sign(rng, claim.as_bytes())
+            .map_err(|_| sigstore_error("couldn't sign the proof of possession"))?;
+        let mut spki = P256_SPKI_PREFIX.to_vec();
+        spki.extend_from_slice(keypair.public_key().as_ref());
+
+        let request = serde_json::json!({
+            "credentials": { "oidcIdentityToken": token },
+            "publicKeyRequest": {
+                "publicKey": {
+                    "algorithm": "ECDSA",
+                    "content": to_pem("PUBLIC KEY", &spki),
+                },
+                "proofOfPossession": b64(proof.as_ref()),
+            },
+        });
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct SigningCertificate {
+            signed_certificate_embedded_sct: Option<CertificateChainHolder>,
+            signed_certificate_detached_sct: Option<CertificateChainHolder>,
+        }
+        #[derive(Deserialize)]
+        struct CertificateChainHolder {
+            chain: CertificateChain,
+        }
+        #[derive(Deserialize)]
+        struct CertificateChain {
+            certificates: Vec<String>,
+        }
+
+        let url = format!("{}/api/v2/signingCert", self.config.fulcio_url());
+        let response = self.client.post(&url).json(&request).send().await?;
+        let response: SigningCertificate = check_response(&url, response).await?;
+        response
+            .signed_certificate_embedded_sct
+            .or(response.signed_certificate_detached_sct)
+            .and_then(|holder| holder.chain.certificates.into_iter().next())
+            .ok_or_else(|| sigstore_error("Fulcio didn't return a certificate"))
+    }
+
+    /// Record the signature in Rekor, returning the log entry for the bundle
+    async fn upload_to_rekor(
+        &self,
+        certificate: &str,
+        signature: &str,
+        digest: &[u8],
+    ) -> DistResult<TlogEntry> {
+        let request = serde_json::json!({
+            "apiVersion": "0.0.1",
+            "kind": "hashedrekord",
+            "spec": {
+                "signature": {
+                    "content": signature,
+                    "publicKey": { "content": b64(certificate.as_bytes()) },
+                },
+                "data": {
+                    "hash": { "algorithm": "sha256", "value": encode_hex(digest) },
+                },
+            },
+        });
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct LogEntry {
+            body: String,
+            integrated_time: i64,
+            #[serde(rename = "logID")]
+            log_id: String,
+            log_index: i64,
+            verification: Verification,
+        }
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Verification {
+            inclusion_proof: RekorInclusionProof,
+            signed_entry_timestamp: String,
+        }
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct RekorInclusionProof {
+            checkpoint: String,
+            hashes: Vec<String>,
+            log_index: i64,
+            root_hash: String,
+            tree_size: i64,
+        }
+
+        let url = format!("{}/api/v1/log/entries", self.config.rekor_url());
+        let response = self.client.post(&url).json(&request).send().await?;
+        let entries: SortedMap<String, LogEntry> = check_response(&url, response).await?;
+        let entry = entries
+            .into_values()
+            .next()
+            .ok_or_else(|| sigstore_error("Rekor didn't return a log entry"))?;
+        let proof = entry.verification.inclusion_proof;
+
+        Ok(TlogEntry {
+            log_index: entry.log_index.to_string(),
+            log_id: LogId {
+                key_id: b64(&hex_to_bytes(&entry.log_id)?),
+            },
+            kind_version: KindVersion {
+                kind: "hashedrekord",
+                version: "0.0.1",
+            },
+            integrated_time: entry.integrated_time.to_string(),
+            inclusion_promise: InclusionPromise {
+                signed_entry_timestamp: entry.verification.signed_entry_timestamp,
+            },
+            inclusion_proof: InclusionProof {
+                log_index: proof.log_index.to_string(),
+                root_hash: b64(&hex_to_bytes(&proof.root_hash)?),
+                tree_size: proof.tree_size.to_string(),
+                hashes: proof
+                    .hashes
+                    .iter()
+                    .map(|hash| Ok(b64(&hex_to_bytes(hash)?)))
+                    .collect::<DistResult<_>>()?,
+                checkpoint: Checkpoint {
+                    envelope: proof.checkpoint,
+                },
+            },
+            canonicalized_body: entry.body,
+        })
+    }
+}
+
+/// A Sigstore bundle (v0.3)
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Bundle {
+    media_type: &'static str,
+    verification_material: VerificationMaterial,
+    message_signature: MessageSignature,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VerificationMaterial {
+    certificate: Certificate,
+    tlog_entries: Vec<TlogEntry>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Certificate {
+    raw_bytes: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TlogEntry {
+    log_index: String,
+    log_id: LogId,
+    kind_version: KindVersion,
+    integrated_time: String,
+    inclusion_promise: InclusionPromise,
+    inclusion_proof: InclusionProof,
+    canonicalized_body: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogId {
+    key_id: String,
+}
+
+#[derive(Serialize)]
+struct KindVersion {
+    kind: &'static str,
+    version: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InclusionPromise {
+    signed_entry_timestamp: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InclusionProof {
+    log_index: String,
+    root_hash: String,
+    tree_size: String,
+    hashes: Vec<String>,
+    checkpoint: Checkpoint,
+}
+
+#[derive(Serialize)]
+struct Checkpoint {
+    envelope: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MessageSignature {
+    message_digest: MessageDigest,
+    signature: String,
+}
+
+#[derive(Serialize)]
+struct MessageDigest {
+    algorithm: &'static str,
+    digest: String,
+}
+
+/// Get the identity an OIDC token is for, which Fulcio wants signed as proof
+/// of possession (the email for tokens that have one, otherwise the subject)
+fn token_identity(token: &str) -> DistResult<String> {
+    let payload = token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| sigstore_error("the OIDC token isn't a JWT"))?;
+    let payload = base64::prelude::BASE64_URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|_| sigstore_error("the OIDC token isn't a JWT"))?;
+    let claims: serde_json::Value = serde_json::from_slice(&payload)
+        .map_err(|_| sigstore_error("the OIDC token isn't a JWT"))?;
+    claims
+        .get("email")
+        .or_else(|| claims.get("sub"))
+        .and_then(|claim| claim.as_str())
+        .map(|claim| claim.to_owned())
+        .ok_or_else(|| sigstore_error("the OIDC token has no subject"))
+}
+
+fn to_pem(label: &str, der: &[u8]) -> String {
+    let encoded = b64(der);
+    let mut pem = format!("-----BEGIN {label}-----\n");
+    for line in encoded.as_bytes().chunks(64) {
+        pem.push_str(std::str::from_utf8(line).expect("base64 is ascii"));
+        pem.push('\n');
+    }
+    pem.push_str(&format!("-----END {label}-----\n"));
+    pem
+}
+
+/// The body of a PEM is already the base64 of its DER, so just strip the rest
+fn pem_to_der_base64(pem: &str) -> String {
+    pem.lines()
+        .filter(|line| !line.starts_with("-----"))
+        .map(|line| line.trim())
+        .collect()
+}
+
+fn b64(bytes: &[u8]) -> String {
+    base64::prelude::BASE64_STANDARD.encode(bytes)
+}
+
+fn hex_to_bytes(hex: &str) -> DistResult<Vec<u8>> {
+    decode_hex(hex).ok_or_else(|| sigstore_error("Rekor returned a malformed hash"))
+}
+
+fn sigstore_error(reason: &str) -> DistError {
+    DistError::SigstoreError {
+        reason: reason.to_owned(),
+    }
+}
+
+async fn check_response<T: serde::de::DeserializeOwned>(
+    url: &str,
+    response: reqwest::Response,
+) -> DistResult<T> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response.json().await?);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(DistError::SigstoreApiError {
+        url: url.to_owned(),
+        status: status.as_u16(),
+        body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_identity() {
+        let token = |claims: &str| {
+            format!(
+                "e30.{}.c2ln",
+                base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(claims)
+            )
+        };
+
+        assert_eq!(
+            token_identity(&token(r#"{"sub":"repo:owner/app:ref:refs/tags/v1.0.0"}"#)).unwrap(),
+            "repo:owner/app:ref:refs/tags/v1.0.0"
+        );
+        assert_eq!(
+            token_identity(&token(r#"{"sub":"1234","email":"me@example.com"}"#)).unwrap(),
+            "me@example.com"
+        );
+        assert!(token_identity("not a jwt").is_err());
+    }
+
+    #[test]
+    fn test_public_key_pem() {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+        let keypair =
+            EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng)
+                .unwrap();
+        let mut spki = P256_SPKI_PREFIX.to_vec();
+        spki.extend_from_slice(keypair.public_key().as_ref());
+        let pem = to_pem("PUBLIC KEY", &spki);
+
+        assert!(pem.starts_with("-----BEGIN PUBLIC KEY-----\nMFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAE"));
+        assert_eq!(pem_to_der_base64(&pem), b64(&spki));
+        assert_eq!(hex_to_bytes(&encode_hex(&spki)).unwrap(), spki);
+    }
+}
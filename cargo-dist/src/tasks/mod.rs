@@ -50,7 +50,9 @@
 
 use std::collections::BTreeMap;
 
-use crate::backend::installer::{ExecutableZipFragment, HomebrewImpl};
+use crate::backend::installer::{
+    ChecksumSignatureInfo, ExecutableZipFragment, HomebrewImpl, ReleaseSelection,
+};
 use crate::platform::targets::{
     TARGET_ARM64_LINUX_GNU, TARGET_ARM64_MAC, TARGET_X64_LINUX_GNU, TARGET_X64_MAC,
 };
@@ -60,8 +62,8 @@ use axoproject::{PackageId, PackageIdx, WorkspaceGraph};
 use camino::{Utf8Path, Utf8PathBuf};
 use dist_schema::target_lexicon::{OperatingSystem, Triple};
 use dist_schema::{
-    ArtifactId, BuildEnvironment, DistManifest, HomebrewPackageName, SystemId, SystemInfo,
-    TripleName, TripleNameRef,
+    ArtifactId, BuildEnvironment, DistManifest, HomebrewPackageName, PipPackageName, SystemId,
+    SystemInfo, TripleName, TripleNameRef,
 };
 use semver::Version;
 use serde::Serialize;
@@ -69,34 +71,65 @@ use tracing::{info, warn};
 
 use crate::announce::{self, AnnouncementTag, TagMode};
 use crate::backend::ci::github::GithubCiInfo;
+use crate::backend::ci::gitlab::GitlabCiInfo;
 use crate::backend::ci::CiInfo;
-use crate::backend::installer::homebrew::{to_homebrew_license_format, HomebrewFragments};
+use crate::backend::installer::appimage::{appimage_architecture, AppimageInstallerInfo};
+use crate::backend::installer::aur::{
+    aur_architecture, aur_package_description, aur_package_name, aur_package_version,
+    to_aur_license_format, AurFileType, AurInstallerInfo, AurSource,
+};
+use crate::backend::installer::chocolatey::{
+    chocolatey_package_id, ChocolateyArchive, ChocolateyInstallerInfo,
+};
+use crate::backend::installer::deb::{
+    deb_architecture, deb_package_name, deb_version, DebInstallerInfo,
+};
+use crate::backend::installer::homebrew::{
+    homebrew_bottle_name, homebrew_bottle_tag, to_homebrew_license_format, HomebrewBottle,
+    HomebrewBottleInfo, HomebrewFragments,
+};
 use crate::backend::installer::macpkg::PkgInstallerInfo;
+use crate::backend::installer::nix::{nix_licenses, nix_system, NixInstallerInfo, NixSource};
+use crate::backend::installer::pypi::{
+    pep440_version, wheel_filename, wheel_platform_tag, PypiInstallerInfo,
+};
+use crate::backend::installer::rpm::{
+    rpm_architecture, rpm_lib_dir, rpm_version, RpmInstallerInfo,
+};
+use crate::backend::installer::scoop::{scoop_architecture, ScoopInstallerInfo};
+use crate::backend::installer::winget::{
+    winget_architecture, winget_identifier, winget_publisher, WingetInstallerInfo,
+    WingetInstallerSource, WingetManifestType,
+};
 use crate::config::v1::builds::cargo::AppCargoBuildConfig;
 use crate::config::v1::ci::CiConfig;
-use crate::config::v1::installers::CommonInstallerConfig;
+use crate::config::v1::installers::{npm::NpmInstallerConfig, CommonInstallerConfig};
 use crate::config::v1::publishers::PublisherConfig;
 use crate::config::v1::{app_config, workspace_config, AppConfig, WorkspaceConfig};
-use crate::config::{DependencyKind, DirtyMode, LibraryStyle};
+use crate::config::{DependencyKind, DirtyMode, InstallPathStrategy, LibraryStyle};
 use crate::linkage::determine_build_environment;
 use crate::net::ClientSettings;
-use crate::platform::{PlatformSupport, RuntimeConditions};
+use crate::platform::{FetchableArchive, PlatformSupport, RuntimeConditions};
 use crate::sign::Signing;
 use crate::{
     backend::{
         installer::{
             homebrew::{to_class_case, HomebrewInstallerInfo},
             msi::MsiInstallerInfo,
-            npm::NpmInstallerInfo,
+            npm::{
+                npm_libc, npm_os_cpu, npm_platform_package_name, npm_platform_packages,
+                NpmInstallerInfo, NpmPlatformInstallerInfo,
+            },
             InstallerImpl, InstallerInfo,
         },
         templates::Templates,
     },
     config::{
         self, ArtifactMode, ChecksumStyle, CompressionImpl, Config, HostingStyle, InstallerStyle,
-        ZipStyle,
+        SbomComponent, SignatureStyle, SigningKind, ZipStyle,
     },
     errors::{DistError, DistResult},
+    sbom::{SbomFormat, SbomSource},
 };
 
 /// Key in workspace.metadata or package.metadata for our config
@@ -199,6 +232,12 @@ pub struct DistGraph {
     pub allow_dirty: DirtyMode,
     /// Homebrew tap all packages agree on
     pub global_homebrew_tap: Option<String>,
+    /// Scoop bucket all packages agree on
+    pub global_scoop_bucket: Option<String>,
+    /// winget-pkgs fork all packages agree on
+    pub global_winget_fork: Option<String>,
+    /// Nix repo all packages agree on
+    pub global_nix_repo: Option<String>,
     /// builtin publish jobs all packages agree on
     pub global_publishers: Option<PublisherConfig>,
     /// Whether we can just build the workspace or need to build each package
@@ -260,6 +299,8 @@ pub struct HostingInfo {
     pub owner: String,
     /// Project name
     pub project: String,
+    /// Base URL of the GitLab API to host artifacts on, if GitLab hosting is enabled
+    pub gitlab_api_url: Option<String>,
 }
 
 /// Various tools we have found installed on the system
@@ -289,6 +330,8 @@ pub struct Tools {
     pub cargo_xwin: Option<Tool>,
     /// cargo-zigbuild, for some cross builds
     pub cargo_zigbuild: Option<Tool>,
+    /// appimagetool, for building AppImages
+    pub appimagetool: Option<Tool>,
 }
 
 impl Tools {
@@ -299,6 +342,13 @@ impl Tools {
         })
     }
 
+    /// Returns appimagetool info or an error
+    pub fn appimagetool(&self) -> DistResult<&Tool> {
+        self.appimagetool.as_ref().ok_or(DistError::ToolMissing {
+            tool: "appimagetool".to_owned(),
+        })
+    }
+
     /// Returns the omnibor info or an error
     pub fn omnibor(&self) -> DistResult<&Tool> {
         self.omnibor.as_ref().ok_or(DistError::ToolMissing {
@@ -431,8 +481,18 @@ pub enum BuildStep {
     Checksum(ChecksumImpl),
     /// Generate a unified checksum file, containing multiple entries
     UnifiedChecksum(UnifiedChecksumStep),
+    /// Generate a detached minisign signature of a file
+    MinisignSignature(MinisignSignatureStep),
+    /// Generate a detached signature of a release artifact
+    DetachedSignature(DetachedSignatureStep),
+    /// Sign a file in place with the configured signing providers
+    Sign(SignStep),
     /// Generate an OmniBOR Artifact ID
     OmniborArtifactId(OmniborArtifactIdImpl),
+    /// Generate a SLSA provenance statement for a release
+    Provenance(ProvenanceStep),
+    /// Generate an SBOM for a release
+    GenerateSbom(GenerateSbomStep),
     /// Fetch or build an updater binary
     Updater(UpdaterStep),
     // FIXME: For macos universal builds we'll want
@@ -632,6 +692,45 @@ pub struct UnifiedChecksumStep {
     pub dest_path: Utf8PathBuf,
 }
 
+/// Create a detached minisign signature of a file
+///
+/// This is used to sign the unified checksum file, so the shell and
+/// powershell installers can check that what they download is authentic.
+#[derive(Debug, Clone)]
+pub struct MinisignSignatureStep {
+    /// the file to sign
+    pub src_path: Utf8PathBuf,
+
+    /// record the signature to this path
+    pub dest_path: Utf8PathBuf,
+}
+
+/// Create a detached signature (like `.asc` or `.minisig`) of a release artifact
+#[derive(Debug, Clone)]
+pub struct DetachedSignatureStep {
+    /// the kind of signature to make
+    pub style: SignatureStyle,
+    /// the file to sign
+    pub src_path: Utf8PathBuf,
+    /// record the signature to this path
+    pub dest_path: Utf8PathBuf,
+    /// the artifact this is a signature of
+    pub for_artifact: ArtifactId,
+}
+
+/// Sign an artifact in place with whichever signing providers are configured for it
+#[derive(Debug, Clone)]
+pub struct SignStep {
+    /// the kind of file this is, for picking providers
+    pub kind: SigningKind,
+    /// the target the file was built for, if it's specific to one
+    pub target: Option<TripleName>,
+    /// the file to sign
+    pub src_path: Utf8PathBuf,
+    /// the artifact to record the signatures on
+    pub for_artifact: ArtifactId,
+}
+
 /// Create a file containing the OmniBOR Artifact ID for a specific file.
 #[derive(Debug, Clone)]
 pub struct OmniborArtifactIdImpl {
@@ -641,6 +740,52 @@ pub struct OmniborArtifactIdImpl {
     pub dest_path: Utf8PathBuf,
 }
 
+/// Create an in-toto SLSA provenance statement for a release
+///
+/// This is assembled from the (merged) dist-manifest, so it has to run
+/// after everything it describes has been built.
+#[derive(Debug, Clone)]
+pub struct ProvenanceStep {
+    /// the name of the app the release is for
+    pub app_name: String,
+    /// the version of the app the release is for
+    pub app_version: String,
+    /// the tag being announced
+    pub tag: String,
+    /// the url of the app's git repository, if known
+    pub source_repo: Option<String>,
+    /// the commit the release was built from, if known
+    pub source_commit: Option<String>,
+    /// record the statement to this path
+    pub dest_path: Utf8PathBuf,
+}
+
+/// Create an SBOM for a release
+///
+/// Like provenance, this is assembled from the (merged) dist-manifest,
+/// as that's where the linkage of every build ends up.
+#[derive(Debug, Clone)]
+pub struct GenerateSbomStep {
+    /// the format to write the SBOM in
+    pub format: SbomFormat,
+    /// where to find the app's dependencies
+    pub source: SbomSource,
+    /// the name of the app
+    pub app_name: String,
+    /// the version of the app
+    pub app_version: String,
+    /// a brief description of the app
+    pub app_desc: Option<String>,
+    /// the license of the app
+    pub app_license: Option<String>,
+    /// the url of the app's repository
+    pub app_repository_url: Option<String>,
+    /// components the user declared with `sbom-components`
+    pub components: Vec<SbomComponent>,
+    /// record the SBOM to this path
+    pub dest_path: Utf8PathBuf,
+}
+
 /// Create a source tarball
 #[derive(Debug, Clone)]
 pub struct SourceTarballStep {
@@ -752,8 +897,14 @@ pub enum ArtifactKind {
     Checksum(ChecksumImpl),
     /// A unified checksum file, like `sha256.sum`
     UnifiedChecksum(UnifiedChecksumStep),
+    /// A detached minisign signature, like `sha256.sum.sig`
+    MinisignSignature(MinisignSignatureStep),
+    /// A detached signature of a release artifact, like `app.tar.xz.asc`
+    DetachedSignature(DetachedSignatureStep),
     /// A source tarball
     SourceTarball(SourceTarball),
+    /// A bundle of the script installers and everything they'd download
+    OfflineBundle(OfflineBundle),
     /// An extra artifact specified via config
     ExtraArtifact(ExtraArtifactImpl),
     /// An updater executable
@@ -762,6 +913,8 @@ pub enum ArtifactKind {
     SBOM(SBOMImpl),
     /// An OmniBOR Artifact ID.
     OmniborArtifactId(OmniborArtifactIdImpl),
+    /// A SLSA provenance statement for a release
+    Provenance(ProvenanceStep),
 }
 
 /// An Archive containing binaries (aka ExecutableZip)
@@ -777,6 +930,13 @@ pub struct Symbols {
     kind: SymbolKind,
 }
 
+/// A bundle of the script installers and everything they'd download,
+/// for installing on machines without internet access
+#[derive(Clone, Debug)]
+pub struct OfflineBundle {
+    // everything important is already part of Artifact
+}
+
 /// A source tarball artifact
 #[derive(Clone, Debug)]
 pub struct SourceTarball {
@@ -811,7 +971,10 @@ pub struct UpdaterImpl {
 
 /// A file containing a Software Bill Of Materials
 #[derive(Clone, Debug)]
-pub struct SBOMImpl {}
+pub struct SBOMImpl {
+    /// How to generate it, if some other tool (like cargo-cyclonedx) doesn't
+    pub generate: Option<GenerateSbomStep>,
+}
 
 /// A logical release of an application that artifacts are grouped under
 #[derive(Clone, Debug)]
@@ -1103,6 +1266,96 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
             });
         }
 
+        // check scoop buckets for global publish jobs, same as homebrew taps
+        let mut global_scoop_bucket = None;
+        let mut packages_with_mismatched_buckets = vec![];
+        for ((_idx, package), package_config) in workspaces.all_packages().zip(&package_configs) {
+            if let Some(new_bucket) = package_config
+                .installers
+                .scoop
+                .as_ref()
+                .and_then(|scoop| scoop.bucket.as_ref())
+            {
+                let manifest_path = package
+                    .dist_manifest_path
+                    .clone()
+                    .unwrap_or(package.manifest_path.clone());
+                if let Some(current_bucket) = &global_scoop_bucket {
+                    if current_bucket != new_bucket {
+                        packages_with_mismatched_buckets.push(manifest_path);
+                    }
+                } else {
+                    packages_with_mismatched_buckets.push(manifest_path);
+                    global_scoop_bucket = Some(new_bucket.clone());
+                }
+            }
+        }
+        if packages_with_mismatched_buckets.len() > 1 {
+            return Err(DistError::MismatchedScoopBuckets {
+                packages: packages_with_mismatched_buckets,
+            });
+        }
+
+        // check winget forks for global publish jobs, same as homebrew taps
+        let mut global_winget_fork = None;
+        let mut packages_with_mismatched_forks = vec![];
+        for ((_idx, package), package_config) in workspaces.all_packages().zip(&package_configs) {
+            if let Some(new_fork) = package_config
+                .installers
+                .winget
+                .as_ref()
+                .and_then(|winget| winget.fork.as_ref())
+            {
+                let manifest_path = package
+                    .dist_manifest_path
+                    .clone()
+                    .unwrap_or(package.manifest_path.clone());
+                if let Some(current_fork) = &global_winget_fork {
+                    if current_fork != new_fork {
+                        packages_with_mismatched_forks.push(manifest_path);
+                    }
+                } else {
+                    packages_with_mismatched_forks.push(manifest_path);
+                    global_winget_fork = Some(new_fork.clone());
+                }
+            }
+        }
+        if packages_with_mismatched_forks.len() > 1 {
+            return Err(DistError::MismatchedWingetForks {
+                packages: packages_with_mismatched_forks,
+            });
+        }
+
+        // check nix repos for global publish jobs, same as homebrew taps
+        let mut global_nix_repo = None;
+        let mut packages_with_mismatched_nix_repos = vec![];
+        for ((_idx, package), package_config) in workspaces.all_packages().zip(&package_configs) {
+            if let Some(new_repo) = package_config
+                .installers
+                .nix
+                .as_ref()
+                .and_then(|nix| nix.repo.as_ref())
+            {
+                let manifest_path = package
+                    .dist_manifest_path
+                    .clone()
+                    .unwrap_or(package.manifest_path.clone());
+                if let Some(current_repo) = &global_nix_repo {
+                    if current_repo != new_repo {
+                        packages_with_mismatched_nix_repos.push(manifest_path);
+                    }
+                } else {
+                    packages_with_mismatched_nix_repos.push(manifest_path);
+                    global_nix_repo = Some(new_repo.clone());
+                }
+            }
+        }
+        if packages_with_mismatched_nix_repos.len() > 1 {
+            return Err(DistError::MismatchedNixRepos {
+                packages: packages_with_mismatched_nix_repos,
+            });
+        }
+
         // check publish jobs for global publish jobs
         // FIXME: when we add `dist publish` we can drop this,
         // as we can support granular publish settings
@@ -1140,10 +1393,25 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
             .as_ref()
             .map(|p| {
                 // until we have `dist publish` we need to enforce everyone agreeing on `prereleases`
-                let PublisherConfig { homebrew, npm } = p;
+                let PublisherConfig {
+                    homebrew,
+                    npm,
+                    scoop,
+                    winget,
+                    aur,
+                    nix,
+                    pypi,
+                } = p;
                 let h_pre = homebrew.as_ref().map(|p| p.prereleases);
                 let npm_pre = npm.as_ref().map(|p| p.prereleases);
-                let choices = [h_pre, npm_pre];
+                let scoop_pre = scoop.as_ref().map(|p| p.prereleases);
+                let winget_pre = winget.as_ref().map(|p| p.prereleases);
+                let aur_pre = aur.as_ref().map(|p| p.prereleases);
+                let nix_pre = nix.as_ref().map(|p| p.prereleases);
+                let pypi_pre = pypi.as_ref().map(|p| p.prereleases);
+                let choices = [
+                    h_pre, npm_pre, scoop_pre, winget_pre, aur_pre, nix_pre, pypi_pre,
+                ];
                 let mut global_choice = None;
                 #[allow(clippy::manual_flatten)]
                 for choice in choices {
@@ -1191,7 +1459,19 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
             &dist_dir,
             config.builds.ssldotcom_windows_sign.clone(),
             config.builds.macos_sign,
+            &config.builds.signing,
+            &config.artifacts,
         )?;
+        if config.artifacts.minisign_public_key.is_some()
+            && !matches!(
+                config.artifacts.checksum,
+                ChecksumStyle::Sha256 | ChecksumStyle::Sha512
+            )
+        {
+            return Err(DistError::MinisignUnsupportedChecksum {
+                checksum: config.artifacts.checksum,
+            });
+        }
         let github_attestations = config
             .hosts
             .github
@@ -1205,6 +1485,9 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                 is_init: config.dist_version.is_some(),
                 allow_dirty,
                 global_homebrew_tap,
+                global_scoop_bucket,
+                global_winget_fork,
+                global_nix_repo,
                 global_publishers,
                 precise_cargo_builds,
                 target_dir,
@@ -1581,13 +1864,95 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                 archive: None,
                 file_path: file_path.clone(),
                 required_binaries: Default::default(),
-                kind: ArtifactKind::SBOM(SBOMImpl {}),
+                kind: ArtifactKind::SBOM(SBOMImpl { generate: None }),
                 checksum: None,
                 is_global: true,
             },
         );
     }
 
+    fn add_sboms(&mut self, to_package: PackageIdx, to_release: ReleaseIdx) {
+        if !self.global_artifacts_enabled() || !self.inner.config.artifacts.sbom {
+            return;
+        }
+
+        let release = self.release(to_release);
+        let package = self.workspaces.package(to_package);
+        let workspace_idx = self.workspaces.workspace_for_package(to_package);
+        let workspace = self.workspaces.workspace(workspace_idx);
+        let source = match workspace.kind {
+            axoproject::WorkspaceKind::Rust => match &package.cargo_package_id {
+                Some(package_id) => {
+                    let cargo = &release.config.builds.cargo;
+                    SbomSource::Cargo {
+                        manifest_path: package.manifest_path.clone(),
+                        package_id: package_id.repr().to_owned(),
+                        features: CargoTargetFeatures {
+                            default_features: cargo.default_features,
+                            features: if cargo.all_features {
+                                CargoTargetFeatureList::All
+                            } else {
+                                CargoTargetFeatureList::List(cargo.features.clone())
+                            },
+                        },
+                    }
+                }
+                None => SbomSource::Generic,
+            },
+            axoproject::WorkspaceKind::Javascript => {
+                // npm workspaces share one lockfile at their root
+                let lockfile = [&package.package_root, &workspace.workspace_dir]
+                    .into_iter()
+                    .map(|dir| dir.join("package-lock.json"))
+                    .find(|path| path.exists());
+                if lockfile.is_none() {
+                    warn!(
+                        "{} has no package-lock.json, so its SBOMs won't list its npm dependencies",
+                        release.app_name
+                    );
+                }
+                SbomSource::Npm {
+                    lockfile,
+                    package_root: package.package_root.clone(),
+                }
+            }
+            axoproject::WorkspaceKind::Generic => SbomSource::Generic,
+        };
+        info!("adding SBOMs to release {}", release.id);
+
+        for format in [SbomFormat::CycloneDx, SbomFormat::Spdx] {
+            let release = self.release(to_release);
+            let artifact_name = ArtifactId::new(format!("{}.{}", release.id, format.ext()));
+            let file_path = self.inner.dist_dir.join(artifact_name.as_str());
+            let step = GenerateSbomStep {
+                format,
+                source: source.clone(),
+                app_name: release.app_name.clone(),
+                app_version: release.version.to_string(),
+                app_desc: release.app_desc.clone(),
+                app_license: release.app_license.clone(),
+                app_repository_url: release.app_repository_url.clone(),
+                components: release.config.artifacts.sbom_components.clone(),
+                dest_path: file_path.clone(),
+            };
+            self.add_global_artifact(
+                to_release,
+                Artifact {
+                    id: artifact_name,
+                    target_triples: vec![],
+                    archive: None,
+                    file_path,
+                    required_binaries: Default::default(),
+                    kind: ArtifactKind::SBOM(SBOMImpl {
+                        generate: Some(step),
+                    }),
+                    checksum: None,
+                    is_global: true,
+                },
+            );
+        }
+    }
+
     fn create_omnibor_artifact(&mut self, artifact_idx: ArtifactIdx, is_global: bool) -> Artifact {
         let artifact = self.artifact(artifact_idx);
         let id = artifact.id.clone();
@@ -1620,7 +1985,7 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
             return;
         }
 
-        let dist_dir = &self.inner.dist_dir;
+        let dist_dir = self.inner.dist_dir.clone();
         let checksum = self.inner.config.artifacts.checksum;
         let file_name = ArtifactId::new(format!("{}.sum", checksum.ext()));
         let file_path = dist_dir.join(file_name.as_str());
@@ -1635,12 +2000,223 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                 required_binaries: Default::default(),
                 kind: ArtifactKind::UnifiedChecksum(UnifiedChecksumStep {
                     checksum,
-                    dest_path: file_path,
+                    dest_path: file_path.clone(),
                 }),
                 checksum: None, // who checksums the checksummers...
                 is_global: true,
             },
         );
+
+        // ...and who signs them
+        if self.inner.config.artifacts.minisign_public_key.is_some() {
+            let sig_name = ArtifactId::new(format!("{}.sum.sig", checksum.ext()));
+            let sig_path = dist_dir.join(sig_name.as_str());
+            self.add_global_artifact(
+                to_release,
+                Artifact {
+                    id: sig_name,
+                    target_triples: Default::default(),
+                    archive: None,
+                    file_path: sig_path.clone(),
+                    required_binaries: Default::default(),
+                    kind: ArtifactKind::MinisignSignature(MinisignSignatureStep {
+                        src_path: file_path,
+                        dest_path: sig_path,
+                    }),
+                    checksum: None,
+                    is_global: true,
+                },
+            );
+        }
+    }
+
+    /// Get the info installers need to verify the signed unified checksum file, if any
+    fn checksum_signature_info(&self, app_name: &str) -> DistResult<Option<ChecksumSignatureInfo>> {
+        let Some(public_key) = &self.inner.config.artifacts.minisign_public_key else {
+            return Ok(None);
+        };
+        let checksum = self.inner.config.artifacts.checksum;
+        let env_app_name = app_name.to_ascii_uppercase().replace('-', "_");
+        Ok(Some(ChecksumSignatureInfo {
+            checksums: ArtifactId::new(format!("{}.sum", checksum.ext())),
+            signature: ArtifactId::new(format!("{}.sum.sig", checksum.ext())),
+            checksum_style: checksum.ext().to_string(),
+            public_key: crate::sign::minisign_public_key_line(public_key)?,
+            no_verify_env_var: format!("{env_app_name}_NO_VERIFY"),
+        }))
+    }
+
+    fn add_offline_bundle(&mut self, to_release: ReleaseIdx) {
+        if !self.global_artifacts_enabled() {
+            return;
+        }
+
+        if !self.inner.config.artifacts.offline_bundle {
+            return;
+        }
+
+        let release = self.release(to_release);
+        let mut static_assets = vec![];
+        let mut checksum_files = vec![];
+        for &idx in &release.global_artifacts {
+            let artifact = self.artifact(idx);
+            match &artifact.kind {
+                ArtifactKind::Installer(InstallerImpl::Shell(_) | InstallerImpl::Powershell(_)) => {
+                    static_assets.push((StaticAssetKind::Other, artifact.file_path.clone()));
+                }
+                ArtifactKind::UnifiedChecksum(_) | ArtifactKind::MinisignSignature(_) => {
+                    checksum_files.push((StaticAssetKind::Other, artifact.file_path.clone()));
+                }
+                _ => {}
+            }
+        }
+        if static_assets.is_empty() {
+            warn!(
+                "skipping offline bundle for {}: it needs the shell or powershell installer",
+                release.id
+            );
+            return;
+        }
+        info!("adding offline bundle to release {}", release.id);
+
+        // Everything the installers would otherwise download
+        let dist_dir = &self.inner.dist_dir;
+        let support = &release.platform_support;
+        let downloads = support
+            .archives
+            .iter()
+            .map(|archive| &archive.id)
+            .chain(support.updaters.iter().map(|updater| &updater.id));
+        for id in downloads {
+            static_assets.push((StaticAssetKind::Other, dist_dir.join(id.as_str())));
+        }
+        static_assets.extend(checksum_files);
+
+        let bundle_dir_name = format!("{}-offline", release.id);
+        let zip_style = ZipStyle::Tar(CompressionImpl::Gzip);
+        let artifact_name = ArtifactId::new(format!("{bundle_dir_name}{}", zip_style.ext()));
+        let artifact = Artifact {
+            id: artifact_name.clone(),
+            target_triples: vec![],
+            file_path: dist_dir.join(artifact_name.as_str()),
+            required_binaries: FastMap::new(),
+            archive: Some(Archive {
+                with_root: Some(Utf8PathBuf::from(bundle_dir_name.clone())),
+                dir_path: dist_dir.join(&bundle_dir_name),
+                zip_style,
+                static_assets,
+            }),
+            kind: ArtifactKind::OfflineBundle(OfflineBundle {}),
+            checksum: None,
+            is_global: true,
+        };
+        self.add_global_artifact(to_release, artifact);
+    }
+
+    fn add_detached_signatures(&mut self, to_release: ReleaseIdx) {
+        let signatures = self.inner.config.artifacts.signatures.clone();
+        if signatures.is_empty() {
+            return;
+        }
+
+        // Everything users download to install the app gets signed
+        let release = self.release(to_release);
+        let mut artifacts = vec![];
+        if self.global_artifacts_enabled() {
+            artifacts.extend(release.global_artifacts.iter().map(|&idx| (None, idx)));
+        }
+        if self.local_artifacts_enabled() {
+            for &variant_idx in &release.variants {
+                let variant = self.variant(variant_idx);
+                artifacts.extend(
+                    variant
+                        .local_artifacts
+                        .iter()
+                        .map(|&idx| (Some(variant_idx), idx)),
+                );
+            }
+        }
+
+        for (variant_idx, artifact_idx) in artifacts {
+            let artifact = self.artifact(artifact_idx);
+            if !matches!(
+                artifact.kind,
+                ArtifactKind::ExecutableZip(_)
+                    | ArtifactKind::Installer(_)
+                    | ArtifactKind::SourceTarball(_)
+                    | ArtifactKind::OfflineBundle(_)
+            ) {
+                continue;
+            }
+            let target_triples = artifact.target_triples.clone();
+            let src_path = artifact.file_path.clone();
+            let for_artifact = artifact.id.clone();
+            for &style in &signatures {
+                let sig_id = ArtifactId::new(format!("{for_artifact}.{}", style.ext()));
+                let sig_path = self.inner.dist_dir.join(sig_id.as_str());
+                let signature = Artifact {
+                    id: sig_id,
+                    target_triples: target_triples.clone(),
+                    archive: None,
+                    file_path: sig_path.clone(),
+                    required_binaries: Default::default(),
+                    kind: ArtifactKind::DetachedSignature(DetachedSignatureStep {
+                        style,
+                        src_path: src_path.clone(),
+                        dest_path: sig_path,
+                        for_artifact: for_artifact.clone(),
+                    }),
+                    checksum: None,
+                    is_global: variant_idx.is_none(),
+                };
+                if let Some(variant_idx) = variant_idx {
+                    self.add_local_artifact(variant_idx, signature);
+                } else {
+                    self.add_global_artifact(to_release, signature);
+                }
+            }
+        }
+    }
+
+    fn add_provenance(&mut self, to_release: ReleaseIdx, tag: &str) {
+        if !self.global_artifacts_enabled() {
+            return;
+        }
+
+        if !self.inner.config.artifacts.provenance {
+            return;
+        }
+
+        let release = self.release(to_release);
+        info!("adding provenance statement to release {}", release.id);
+
+        let artifact_name = ArtifactId::new(format!("{}.intoto.jsonl", release.id));
+        let file_path = self.inner.dist_dir.join(artifact_name.as_str());
+        let step = ProvenanceStep {
+            app_name: release.app_name.clone(),
+            app_version: release.version.to_string(),
+            tag: tag.to_owned(),
+            source_repo: release.app_repository_url.clone(),
+            source_commit: self
+                .workspaces
+                .repo
+                .as_ref()
+                .and_then(|repo| repo.head.clone()),
+            dest_path: file_path.clone(),
+        };
+        self.add_global_artifact(
+            to_release,
+            Artifact {
+                id: artifact_name,
+                target_triples: vec![],
+                archive: None,
+                file_path,
+                required_binaries: Default::default(),
+                kind: ArtifactKind::Provenance(step),
+                checksum: None,
+                is_global: true,
+            },
+        );
     }
 
     fn add_source_tarball(&mut self, _tag: &str, to_release: ReleaseIdx) {
@@ -1983,6 +2559,11 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
             .artifact_download_url()
             .expect("couldn't compute a URL to download artifacts from!?");
         let hosting = schema_release.hosting.clone();
+        let release_selection = ReleaseSelection::from_hosting(
+            &hosting,
+            &release.version.to_string(),
+            self.inner.config.artifacts.checksum,
+        );
         let artifact_name = ArtifactId::new(format!("{release_id}-installer.sh"));
         let artifact_path = self.inner.dist_dir.join(artifact_name.as_str());
         let installer_url = format!("{download_url}/{artifact_name}");
@@ -2024,8 +2605,9 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                 install_paths: config
                     .install_path
                     .iter()
-                    .map(|p| p.clone().into_jinja())
+                    .filter_map(|p| p.clone().into_jinja())
                     .collect(),
+                system_install_path: InstallPathStrategy::system_path(&config.install_path, false),
                 install_success_msg: config.install_success_msg.to_owned(),
                 base_url: download_url.to_owned(),
                 hosting,
@@ -2039,6 +2621,8 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                 runtime_conditions,
                 platform_support: None,
                 env_vars,
+                checksum_signature: self.checksum_signature_info(&release.app_name)?,
+                release_selection,
             })),
             is_global: true,
         };
@@ -2155,6 +2739,32 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
             .collect();
         let bin_aliases = BinaryAliases(config.bin_aliases.clone()).for_targets(&target_triples);
 
+        // These have to agree with the bottles add_homebrew_bottles makes
+        let bottles = if config.bottles {
+            artifacts
+                .iter()
+                .filter_map(|artifact| {
+                    let tag = homebrew_bottle_tag(&artifact.target_triple)?;
+                    Some(HomebrewBottle {
+                        id: ArtifactId::new(homebrew_bottle_name(
+                            formula,
+                            &release.version.to_string(),
+                            tag,
+                        )),
+                        tag: tag.to_owned(),
+                    })
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+        let service = config.service.clone().filter(|service| {
+            if service.run.is_empty() {
+                warn!("skipping the Homebrew service: it has no command to run");
+            }
+            !service.run.is_empty()
+        });
+
         let inner = InstallerInfo {
             release: to_release,
             dest_path: artifact_path.clone(),
@@ -2163,8 +2773,9 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
             install_paths: config
                 .install_path
                 .iter()
-                .map(|p| p.clone().into_jinja())
+                .filter_map(|p| p.clone().into_jinja())
                 .collect(),
+            system_install_path: None,
             install_success_msg: config.install_success_msg.to_owned(),
             base_url: download_url.to_owned(),
             hosting,
@@ -2179,6 +2790,8 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
             platform_support: None,
             // Not actually needed for this installer type
             env_vars: None,
+            checksum_signature: None,
+            release_selection: None,
         };
 
         let installer_artifact = Artifact {
@@ -2199,6 +2812,10 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                     dependencies,
                     inner,
                     install_libraries: config.install_libraries.clone(),
+                    test: config.test.clone(),
+                    service,
+                    caveats: config.caveats.clone(),
+                    bottles,
                 },
                 fragments,
             })),
@@ -2218,33 +2835,120 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
         Ok(())
     }
 
-    // Create new versions only for major and minor releases, changes to only patch and metadata
-    // will result in the formula being updated in-place.
-    fn homebrew_version_artifact(
-        &self,
-        formula: String,
-        root_path: &Utf8Path,
-        artifact: Artifact,
-    ) -> Artifact {
-        let ArtifactKind::Installer(InstallerImpl::Homebrew(HomebrewImpl { info, fragments })) =
-            artifact.kind.clone()
-        else {
-            unreachable!();
-        };
-        let inner = info.inner.clone();
+    fn add_homebrew_bottles(&mut self, to_release: ReleaseIdx) -> DistResult<()> {
+        if !self.local_artifacts_enabled() {
+            return Ok(());
+        }
 
-        let version = match Version::parse(&info.inner.app_version) {
-            Ok(v) => format!("{}.{}", v.major, v.minor),
-            Err(_) => info.inner.app_version.clone(),
+        // Clone info we need from the release to avoid borrowing across the loop
+        let release = self.release(to_release);
+        let Some(config) = release.config.installers.homebrew.clone() else {
+            return Ok(());
         };
+        if !config.bottles {
+            return Ok(());
+        }
+        let formula = config.formula.clone().unwrap_or_else(|| release.id.clone());
+        let version = release.version.to_string();
+        let fragments = release.platform_support.fragments();
+        let variants = release.variants.clone();
+        let checksum = self.inner.config.artifacts.checksum;
 
-        // Append version name to install hint, e.g. `brew install org/homebrew-tap/foo@0.2`
-        let versioned_hint = format!("{}@{}", inner.hint, version);
-
-        let version_file_name = format!("{formula}@{version}.rb");
-        let version_file_path = root_path.join(&version_file_name);
+        // Make a bottle for every platform Homebrew supports
+        for variant_idx in variants {
+            let variant = self.variant(variant_idx);
+            let binaries = variant.binaries.clone();
+            let static_assets = variant.static_assets.clone();
+            let bin_aliases = BinaryAliases(config.bin_aliases.clone()).for_target(&variant.target);
+            let Some(tag) = homebrew_bottle_tag(&variant.target) else {
+                continue;
+            };
 
-        let version_formula = format!("{formula}AT{version}");
+            let variant_id = &variant.id;
+            let artifact_name = ArtifactId::new(homebrew_bottle_name(&formula, &version, tag));
+            let artifact_path = self.inner.dist_dir.join(artifact_name.as_str());
+            let dir_name = format!("{variant_id}_bottle");
+            let dir_path = self.inner.dist_dir.join(&dir_name);
+
+            let Some(artifact) = fragments
+                .clone()
+                .into_iter()
+                .find(|a| a.target_triple == variant.target)
+            else {
+                return Err(DistError::NoPackage { artifact_name })?;
+            };
+
+            let installer_artifact = Artifact {
+                id: artifact_name,
+                target_triples: vec![variant.target.clone()],
+                file_path: artifact_path.clone(),
+                required_binaries: FastMap::new(),
+                archive: Some(Archive {
+                    with_root: None,
+                    dir_path: dir_path.clone(),
+                    zip_style: ZipStyle::TempDir,
+                    static_assets: vec![],
+                }),
+                checksum: None,
+                kind: ArtifactKind::Installer(InstallerImpl::HomebrewBottle(HomebrewBottleInfo {
+                    artifact,
+                    file_path: artifact_path.clone(),
+                    package_dir: dir_path.clone(),
+                    formula: formula.clone(),
+                    version: version.clone(),
+                    bin_aliases,
+                    static_assets,
+                    install_libraries: config.install_libraries.clone(),
+                })),
+                is_global: false,
+            };
+
+            // Register the artifact to various things
+            let installer_idx = self.add_local_artifact(variant_idx, installer_artifact);
+            for binary_idx in binaries {
+                let binary = self.binary(binary_idx);
+                self.require_binary(
+                    installer_idx,
+                    variant_idx,
+                    binary_idx,
+                    dir_path.join(&binary.file_name),
+                );
+            }
+            if checksum != ChecksumStyle::False {
+                self.add_artifact_checksum(variant_idx, installer_idx, checksum);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Create new versions only for major and minor releases, changes to only patch and metadata
+    // will result in the formula being updated in-place.
+    fn homebrew_version_artifact(
+        &self,
+        formula: String,
+        root_path: &Utf8Path,
+        artifact: Artifact,
+    ) -> Artifact {
+        let ArtifactKind::Installer(InstallerImpl::Homebrew(HomebrewImpl { info, fragments })) =
+            artifact.kind.clone()
+        else {
+            unreachable!();
+        };
+        let inner = info.inner.clone();
+
+        let version = match Version::parse(&info.inner.app_version) {
+            Ok(v) => format!("{}.{}", v.major, v.minor),
+            Err(_) => info.inner.app_version.clone(),
+        };
+
+        // Append version name to install hint, e.g. `brew install org/homebrew-tap/foo@0.2`
+        let versioned_hint = format!("{}@{}", inner.hint, version);
+
+        let version_file_name = format!("{formula}@{version}.rb");
+        let version_file_path = root_path.join(&version_file_name);
+
+        let version_formula = format!("{formula}AT{version}");
 
         Artifact {
             id: ArtifactId::new(version_file_name),
@@ -2257,6 +2961,8 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                         dest_path: version_file_path,
                         ..inner
                     },
+                    // Bottles are named after the formula, so these would need their own
+                    bottles: vec![],
                     ..info
                 },
                 fragments,
@@ -2288,6 +2994,11 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
             .artifact_download_url()
             .expect("couldn't compute a URL to download artifacts from!?");
         let hosting = schema_release.hosting.clone();
+        let release_selection = ReleaseSelection::from_hosting(
+            &hosting,
+            &release.version.to_string(),
+            self.inner.config.artifacts.checksum,
+        );
         let artifact_name = ArtifactId::new(format!("{release_id}-installer.ps1"));
         let artifact_path = self.inner.dist_dir.join(artifact_name.as_str());
         let installer_url = format!("{download_url}/{artifact_name}");
@@ -2325,8 +3036,9 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                 install_paths: config
                     .install_path
                     .iter()
-                    .map(|p| p.clone().into_jinja())
+                    .filter_map(|p| p.clone().into_jinja())
                     .collect(),
+                system_install_path: InstallPathStrategy::system_path(&config.install_path, true),
                 install_success_msg: config.install_success_msg.to_owned(),
                 base_url: download_url.to_owned(),
                 hosting,
@@ -2340,6 +3052,8 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                 runtime_conditions: RuntimeConditions::default(),
                 platform_support: None,
                 env_vars,
+                checksum_signature: self.checksum_signature_info(&release.app_name)?,
+                release_selection,
             })),
             is_global: true,
         };
@@ -2368,17 +3082,7 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
         let hosting = schema_release.hosting.clone();
 
         let app_name = config.package.clone();
-        let npm_package_name = if let Some(scope) = &config.scope {
-            if scope.to_ascii_lowercase() != *scope {
-                return Err(DistError::ScopeMustBeLowercase {
-                    scope: scope.to_owned(),
-                });
-            }
-
-            format!("{scope}/{app_name}")
-        } else {
-            app_name.clone()
-        };
+        let npm_package_name = npm_package_name(config)?;
         let npm_package_version = release.version.to_string();
         let npm_package_desc = release.app_desc.clone();
         let npm_package_authors = release.app_authors.clone();
@@ -2411,6 +3115,9 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
         let bin_aliases = BinaryAliases(config.bin_aliases.clone()).for_targets(&target_triples);
 
         let runtime_conditions = release.platform_support.safe_conflated_runtime_conditions();
+        let platform_packages = config
+            .platform_packages
+            .then(|| npm_platform_packages(&npm_package_name, &release.platform_support));
 
         let installer_artifact = Artifact {
             id: artifact_name,
@@ -2435,6 +3142,7 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                 npm_package_homepage_url,
                 npm_package_keywords,
                 package_dir: dir_path,
+                platform_packages,
                 inner: InstallerInfo {
                     release: to_release,
                     dest_path: artifact_path,
@@ -2443,8 +3151,9 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                     install_paths: config
                         .install_path
                         .iter()
-                        .map(|p| p.clone().into_jinja())
+                        .filter_map(|p| p.clone().into_jinja())
                         .collect(),
+                    system_install_path: None,
                     install_success_msg: config.install_success_msg.to_owned(),
                     base_url: download_url.to_owned(),
                     hosting,
@@ -2459,6 +3168,8 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                     platform_support: None,
                     // Not actually needed for this installer type
                     env_vars: None,
+                    checksum_signature: None,
+                    release_selection: None,
                 },
             })),
             is_global: true,
@@ -2468,6 +3179,103 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
         Ok(())
     }
 
+    fn add_npm_platform_packages(&mut self, to_release: ReleaseIdx) -> DistResult<()> {
+        if !self.local_artifacts_enabled() {
+            return Ok(());
+        }
+
+        // Clone info we need from the release to avoid borrowing across the loop
+        let release = self.release(to_release);
+        let Some(config) = release.config.installers.npm.clone() else {
+            return Ok(());
+        };
+        if !config.platform_packages {
+            return Ok(());
+        }
+        let npm_package_name = npm_package_name(&config)?;
+        let npm_package_version = release.version.to_string();
+        let npm_package_desc = release.app_desc.clone();
+        let npm_package_license = release.app_license.clone();
+        let npm_package_repository_url = release.app_repository_url.clone();
+        let npm_package_homepage_url = release.app_homepage_url.clone();
+        let platform_packages = npm_platform_packages(&npm_package_name, &release.platform_support);
+        let variants = release.variants.clone();
+
+        // Make a package for every platform npm has a name for
+        for variant_idx in variants {
+            let variant = self.variant(variant_idx);
+            let binaries = variant.binaries.clone();
+            let target = &variant.target;
+            let Some((os, cpu)) = npm_os_cpu(target) else {
+                warn!("skipping npm platform package for {target}, npm has no equivalent platform");
+                continue;
+            };
+            let package_name = npm_platform_package_name(&npm_package_name, target);
+            // The platforms this package ends up being used for decide its libc
+            let platforms = platform_packages
+                .iter()
+                .filter(|(_, name)| **name == package_name)
+                .map(|(platform, _)| platform.clone())
+                .collect::<Vec<_>>();
+            let libc = if os == "linux" {
+                npm_libc(&platforms).map(ToOwned::to_owned)
+            } else {
+                None
+            };
+
+            let variant_id = &variant.id;
+            let dir_name = format!("{variant_id}-npm-platform-package");
+            let dir_path = self.inner.dist_dir.join(&dir_name);
+            let zip_style = ZipStyle::Tar(CompressionImpl::Gzip);
+            let artifact_name = ArtifactId::new(format!("{dir_name}{}", zip_style.ext()));
+            let artifact_path = self.inner.dist_dir.join(artifact_name.as_str());
+
+            let installer_artifact = Artifact {
+                id: artifact_name,
+                target_triples: vec![target.clone()],
+                file_path: artifact_path,
+                required_binaries: FastMap::new(),
+                archive: Some(Archive {
+                    // npm specifically expects the dir inside the tarball to be called "package"
+                    with_root: Some("package".into()),
+                    dir_path: dir_path.clone(),
+                    zip_style,
+                    static_assets: vec![],
+                }),
+                checksum: None,
+                kind: ArtifactKind::Installer(InstallerImpl::NpmPlatform(
+                    NpmPlatformInstallerInfo {
+                        npm_package_name: package_name,
+                        npm_package_version: npm_package_version.clone(),
+                        npm_package_desc: npm_package_desc.clone(),
+                        npm_package_repository_url: npm_package_repository_url.clone(),
+                        npm_package_homepage_url: npm_package_homepage_url.clone(),
+                        npm_package_license: npm_package_license.clone(),
+                        os: os.to_owned(),
+                        cpu: cpu.to_owned(),
+                        libc,
+                        package_dir: dir_path.clone(),
+                    },
+                )),
+                is_global: false,
+            };
+
+            // Register the artifact to various things
+            let installer_idx = self.add_local_artifact(variant_idx, installer_artifact);
+            for binary_idx in binaries {
+                let binary = self.binary(binary_idx);
+                self.require_binary(
+                    installer_idx,
+                    variant_idx,
+                    binary_idx,
+                    dir_path.join(&binary.file_name),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     fn add_msi_installer(&mut self, to_release: ReleaseIdx) -> DistResult<()> {
         if !self.local_artifacts_enabled() {
             return Ok(());
@@ -2563,130 +3371,1259 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                     dir_path.join(&binary.file_name),
                 );
             }
-            if checksum != ChecksumStyle::False {
-                self.add_artifact_checksum(variant_idx, installer_idx, checksum);
+            if checksum != ChecksumStyle::False {
+                self.add_artifact_checksum(variant_idx, installer_idx, checksum);
+            }
+            if self.inner.config.builds.omnibor {
+                let omnibor = self.create_omnibor_artifact(installer_idx, false);
+                self.add_local_artifact(variant_idx, omnibor);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_pkg_installer(&mut self, to_release: ReleaseIdx) -> DistResult<()> {
+        if !self.local_artifacts_enabled() {
+            return Ok(());
+        }
+
+        // Clone info we need from the release to avoid borrowing across the loop
+        let release = self.release(to_release);
+        let Some(config) = release.config.installers.pkg.clone() else {
+            return Ok(());
+        };
+        require_nonempty_installer(release, &config)?;
+        let version = release.version.clone();
+        let fragments = release.platform_support.fragments();
+
+        let variants = release.variants.clone();
+        let checksum = self.inner.config.artifacts.checksum;
+
+        // Make a pkg for every darwin platform
+        for variant_idx in variants {
+            let variant = self.variant(variant_idx);
+            let binaries = variant.binaries.clone();
+            let bin_aliases = BinaryAliases(config.bin_aliases.clone());
+            let target = &variant.target;
+            if !target.is_darwin() {
+                continue;
+            }
+
+            let variant_id = &variant.id;
+            let artifact_name = ArtifactId::new(format!("{variant_id}.pkg"));
+            let artifact_path = self.inner.dist_dir.join(artifact_name.as_str());
+            let dir_name = format!("{variant_id}_pkg");
+            let dir_path = self.inner.dist_dir.join(&dir_name);
+
+            // Compute which package we're actually building, based on the binaries
+            let mut package_info: Option<(String, PackageIdx)> = None;
+            for &binary_idx in &binaries {
+                let binary = self.binary(binary_idx);
+                if let Some((existing_spec, _)) = &package_info {
+                    // we haven't set ourselves up to bundle multiple packages yet
+                    if existing_spec != &binary.pkg_spec {
+                        return Err(DistError::MultiPackage {
+                            artifact_name,
+                            spec1: existing_spec.clone(),
+                            spec2: binary.pkg_spec.clone(),
+                        })?;
+                    }
+                } else {
+                    package_info = Some((binary.pkg_spec.clone(), binary.pkg_idx));
+                }
+            }
+
+            let Some(artifact) = fragments
+                .clone()
+                .into_iter()
+                .find(|a| a.target_triple == variant.target)
+            else {
+                return Err(DistError::NoPackage { artifact_name })?;
+            };
+
+            let bin_aliases = bin_aliases.for_target(&variant.target);
+
+            let identifier = if let Some(id) = &config.identifier {
+                id.to_owned()
+            } else {
+                return Err(DistError::MacPkgBundleIdentifierMissing {});
+            };
+
+            // Gather up the bundles the installer supports
+            let installer_artifact = Artifact {
+                id: artifact_name,
+                target_triples: vec![target.clone()],
+                file_path: artifact_path.clone(),
+                required_binaries: FastMap::new(),
+                archive: Some(Archive {
+                    with_root: None,
+                    dir_path: dir_path.clone(),
+                    zip_style: ZipStyle::TempDir,
+                    static_assets: vec![],
+                }),
+                checksum: None,
+                kind: ArtifactKind::Installer(InstallerImpl::Pkg(PkgInstallerInfo {
+                    file_path: artifact_path.clone(),
+                    artifact,
+                    package_dir: dir_path.clone(),
+                    identifier,
+                    install_location: config.install_location.clone(),
+                    version: version.to_string(),
+                    bin_aliases,
+                })),
+                is_global: false,
+            };
+
+            // Register the artifact to various things
+            let installer_idx = self.add_local_artifact(variant_idx, installer_artifact);
+            for binary_idx in binaries {
+                let binary = self.binary(binary_idx);
+                self.require_binary(
+                    installer_idx,
+                    variant_idx,
+                    binary_idx,
+                    dir_path.join(&binary.file_name),
+                );
+            }
+            if checksum != ChecksumStyle::False {
+                self.add_artifact_checksum(variant_idx, installer_idx, checksum);
+            }
+            if self.inner.config.builds.omnibor {
+                let omnibor = self.create_omnibor_artifact(installer_idx, false);
+                self.add_local_artifact(variant_idx, omnibor);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_deb_installer(&mut self, to_release: ReleaseIdx) -> DistResult<()> {
+        if !self.local_artifacts_enabled() {
+            return Ok(());
+        }
+
+        // Clone info we need from the release to avoid borrowing across the loop
+        let release = self.release(to_release);
+        let Some(config) = release.config.installers.deb.clone() else {
+            return Ok(());
+        };
+        require_nonempty_installer(release, &config)?;
+        let package_name = deb_package_name(&release.app_name);
+        let version = deb_version(&release.version);
+        let Some(maintainer) = release.app_authors.first().cloned() else {
+            return Err(DistError::DebMaintainerMissing {
+                package: release.app_name.clone(),
+            });
+        };
+        let description = release.app_desc.clone();
+        let license = release.app_license.clone();
+        let homepage = release
+            .app_homepage_url
+            .clone()
+            .or_else(|| release.app_repository_url.clone());
+        let apt_dependencies = release.config.builds.system_dependencies.apt.clone();
+        let platform_support = release.platform_support.clone();
+
+        let variants = release.variants.clone();
+        let checksum = self.inner.config.artifacts.checksum;
+
+        // Make a deb for every linux platform
+        for variant_idx in variants {
+            let variant = self.variant(variant_idx);
+            let binaries = variant.binaries.clone();
+            let bin_aliases = BinaryAliases(config.bin_aliases.clone());
+            let target = &variant.target;
+            if !target.is_linux() {
+                continue;
+            }
+            let Some(architecture) = deb_architecture(target) else {
+                warn!("skipping deb for {target}, Debian has no equivalent architecture");
+                continue;
+            };
+
+            let variant_id = &variant.id;
+            let artifact_name = ArtifactId::new(format!("{variant_id}.deb"));
+            let artifact_path = self.inner.dist_dir.join(artifact_name.as_str());
+            let dir_name = format!("{variant_id}_deb");
+            let dir_path = self.inner.dist_dir.join(&dir_name);
+
+            // Compute which package we're actually building, based on the binaries
+            let mut package_info: Option<(String, PackageIdx)> = None;
+            for &binary_idx in &binaries {
+                let binary = self.binary(binary_idx);
+                if let Some((existing_spec, _)) = &package_info {
+                    // we haven't set ourselves up to bundle multiple packages yet
+                    if existing_spec != &binary.pkg_spec {
+                        return Err(DistError::MultiPackage {
+                            artifact_name,
+                            spec1: existing_spec.clone(),
+                            spec2: binary.pkg_spec.clone(),
+                        })?;
+                    }
+                } else {
+                    package_info = Some((binary.pkg_spec.clone(), binary.pkg_idx));
+                }
+            }
+
+            let Some(artifact) = platform_support.fragment_for_target(&variant.target) else {
+                return Err(DistError::NoPackage { artifact_name })?;
+            };
+
+            let bin_aliases = bin_aliases.for_target(&variant.target);
+
+            let depends = apt_dependencies
+                .iter()
+                .filter(|(_, package)| {
+                    package.0.stage_wanted(&DependencyKind::Run)
+                        && package.0.wanted_for_target(target)
+                })
+                .map(|(name, package)| match &package.0.version {
+                    Some(version) => format!("{name} (>= {version})"),
+                    None => name.to_string(),
+                })
+                .collect();
+
+            let installer_artifact = Artifact {
+                id: artifact_name,
+                target_triples: vec![target.clone()],
+                file_path: artifact_path.clone(),
+                required_binaries: FastMap::new(),
+                archive: Some(Archive {
+                    with_root: None,
+                    dir_path: dir_path.clone(),
+                    zip_style: ZipStyle::TempDir,
+                    static_assets: vec![],
+                }),
+                checksum: None,
+                kind: ArtifactKind::Installer(InstallerImpl::Deb(DebInstallerInfo {
+                    file_path: artifact_path.clone(),
+                    artifact,
+                    package_dir: dir_path.clone(),
+                    package_name: package_name.clone(),
+                    version: version.clone(),
+                    architecture: architecture.to_owned(),
+                    maintainer: maintainer.clone(),
+                    description: description.clone(),
+                    license: license.clone(),
+                    homepage: homepage.clone(),
+                    depends,
+                    bin_aliases,
+                })),
+                is_global: false,
+            };
+
+            // Register the artifact to various things
+            let installer_idx = self.add_local_artifact(variant_idx, installer_artifact);
+            for binary_idx in binaries {
+                let binary = self.binary(binary_idx);
+                self.require_binary(
+                    installer_idx,
+                    variant_idx,
+                    binary_idx,
+                    dir_path.join(&binary.file_name),
+                );
+            }
+            if checksum != ChecksumStyle::False {
+                self.add_artifact_checksum(variant_idx, installer_idx, checksum);
+            }
+            if self.inner.config.builds.omnibor {
+                let omnibor = self.create_omnibor_artifact(installer_idx, false);
+                self.add_local_artifact(variant_idx, omnibor);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_rpm_installer(&mut self, to_release: ReleaseIdx) -> DistResult<()> {
+        if !self.local_artifacts_enabled() {
+            return Ok(());
+        }
+
+        // Clone info we need from the release to avoid borrowing across the loop
+        let release = self.release(to_release);
+        let Some(config) = release.config.installers.rpm.clone() else {
+            return Ok(());
+        };
+        require_nonempty_installer(release, &config)?;
+        let package_name = release.app_name.clone();
+        let version = rpm_version(&release.version);
+        let packager = release.app_authors.first().cloned();
+        let description = release.app_desc.clone();
+        let license = release.app_license.clone();
+        let homepage = release
+            .app_homepage_url
+            .clone()
+            .or_else(|| release.app_repository_url.clone());
+        let platform_support = release.platform_support.clone();
+
+        let variants = release.variants.clone();
+        let checksum = self.inner.config.artifacts.checksum;
+
+        // Make an rpm for every linux platform
+        for variant_idx in variants {
+            let variant = self.variant(variant_idx);
+            let binaries = variant.binaries.clone();
+            let bin_aliases = BinaryAliases(config.bin_aliases.clone());
+            let target = &variant.target;
+            if !target.is_linux() {
+                continue;
+            }
+            let Some(architecture) = rpm_architecture(target) else {
+                warn!("skipping rpm for {target}, RPM has no equivalent architecture");
+                continue;
+            };
+
+            let variant_id = &variant.id;
+            let artifact_name = ArtifactId::new(format!("{variant_id}.rpm"));
+            let artifact_path = self.inner.dist_dir.join(artifact_name.as_str());
+            let dir_name = format!("{variant_id}_rpm");
+            let dir_path = self.inner.dist_dir.join(&dir_name);
+
+            // Compute which package we're actually building, based on the binaries
+            let mut package_info: Option<(String, PackageIdx)> = None;
+            for &binary_idx in &binaries {
+                let binary = self.binary(binary_idx);
+                if let Some((existing_spec, _)) = &package_info {
+                    // we haven't set ourselves up to bundle multiple packages yet
+                    if existing_spec != &binary.pkg_spec {
+                        return Err(DistError::MultiPackage {
+                            artifact_name,
+                            spec1: existing_spec.clone(),
+                            spec2: binary.pkg_spec.clone(),
+                        })?;
+                    }
+                } else {
+                    package_info = Some((binary.pkg_spec.clone(), binary.pkg_idx));
+                }
+            }
+
+            let Some(artifact) = platform_support.fragment_for_target(&variant.target) else {
+                return Err(DistError::NoPackage { artifact_name })?;
+            };
+
+            let bin_aliases = bin_aliases.for_target(&variant.target);
+
+            let installer_artifact = Artifact {
+                id: artifact_name,
+                target_triples: vec![target.clone()],
+                file_path: artifact_path.clone(),
+                required_binaries: FastMap::new(),
+                archive: Some(Archive {
+                    with_root: None,
+                    dir_path: dir_path.clone(),
+                    zip_style: ZipStyle::TempDir,
+                    static_assets: vec![],
+                }),
+                checksum: None,
+                kind: ArtifactKind::Installer(InstallerImpl::Rpm(RpmInstallerInfo {
+                    file_path: artifact_path.clone(),
+                    artifact,
+                    package_dir: dir_path.clone(),
+                    package_name: package_name.clone(),
+                    version: version.clone(),
+                    architecture: architecture.to_owned(),
+                    lib_dir: rpm_lib_dir(architecture).to_owned(),
+                    packager: packager.clone(),
+                    description: description.clone(),
+                    license: license.clone(),
+                    homepage: homepage.clone(),
+                    bin_aliases,
+                })),
+                is_global: false,
+            };
+
+            // Register the artifact to various things
+            let installer_idx = self.add_local_artifact(variant_idx, installer_artifact);
+            for binary_idx in binaries {
+                let binary = self.binary(binary_idx);
+                self.require_binary(
+                    installer_idx,
+                    variant_idx,
+                    binary_idx,
+                    dir_path.join(&binary.file_name),
+                );
+            }
+            if checksum != ChecksumStyle::False {
+                self.add_artifact_checksum(variant_idx, installer_idx, checksum);
+            }
+            if self.inner.config.builds.omnibor {
+                let omnibor = self.create_omnibor_artifact(installer_idx, false);
+                self.add_local_artifact(variant_idx, omnibor);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_appimage_installer(&mut self, to_release: ReleaseIdx) -> DistResult<()> {
+        if !self.local_artifacts_enabled() {
+            return Ok(());
+        }
+
+        // Clone info we need from the release to avoid borrowing across the loop
+        let release = self.release(to_release);
+        let Some(config) = release.config.installers.appimage.clone() else {
+            return Ok(());
+        };
+        require_nonempty_installer(release, &config)?;
+        let app_name = release.app_name.clone();
+        // The .desktop file's Comment is a single line
+        let description = release
+            .app_desc
+            .as_deref()
+            .and_then(|desc| desc.trim().lines().next())
+            .map(|desc| desc.to_owned());
+        let platform_support = release.platform_support.clone();
+
+        let variants = release.variants.clone();
+        let checksum = self.inner.config.artifacts.checksum;
+
+        // Make an AppImage for every linux platform
+        for variant_idx in variants {
+            let variant = self.variant(variant_idx);
+            let binaries = variant.binaries.clone();
+            let target = &variant.target;
+            if !target.is_linux() {
+                continue;
+            }
+            let Some(architecture) = appimage_architecture(target) else {
+                warn!("skipping AppImage for {target}, AppImage has no equivalent architecture");
+                continue;
+            };
+
+            let variant_id = &variant.id;
+            let artifact_name = ArtifactId::new(format!("{variant_id}.AppImage"));
+            let artifact_path = self.inner.dist_dir.join(artifact_name.as_str());
+            let dir_name = format!("{variant_id}_appimage");
+            let dir_path = self.inner.dist_dir.join(&dir_name);
+
+            // Compute which package we're actually building, based on the binaries
+            let mut package_info: Option<(String, PackageIdx)> = None;
+            for &binary_idx in &binaries {
+                let binary = self.binary(binary_idx);
+                if let Some((existing_spec, _)) = &package_info {
+                    // we haven't set ourselves up to bundle multiple packages yet
+                    if existing_spec != &binary.pkg_spec {
+                        return Err(DistError::MultiPackage {
+                            artifact_name,
+                            spec1: existing_spec.clone(),
+                            spec2: binary.pkg_spec.clone(),
+                        })?;
+                    }
+                } else {
+                    package_info = Some((binary.pkg_spec.clone(), binary.pkg_idx));
+                }
+            }
+
+            let Some(artifact) = platform_support.fragment_for_target(&variant.target) else {
+                return Err(DistError::NoPackage { artifact_name })?;
+            };
+
+            // Run the executable named after the app if there is one, otherwise the first
+            let main_exe = artifact
+                .executables
+                .iter()
+                .find(|exe| Utf8Path::new(exe).file_stem() == Some(app_name.as_str()))
+                .or_else(|| artifact.executables.first())
+                .cloned()
+                .unwrap_or_else(|| app_name.clone());
+
+            let installer_artifact = Artifact {
+                id: artifact_name,
+                target_triples: vec![target.clone()],
+                file_path: artifact_path.clone(),
+                required_binaries: FastMap::new(),
+                archive: Some(Archive {
+                    with_root: None,
+                    dir_path: dir_path.clone(),
+                    zip_style: ZipStyle::TempDir,
+                    static_assets: vec![],
+                }),
+                checksum: None,
+                kind: ArtifactKind::Installer(InstallerImpl::Appimage(AppimageInstallerInfo {
+                    file_path: artifact_path.clone(),
+                    artifact,
+                    package_dir: dir_path.clone(),
+                    app_name: app_name.clone(),
+                    description: description.clone(),
+                    main_exe,
+                    architecture: architecture.to_owned(),
+                    icon: config.icon.clone(),
+                    desktop_file: config.desktop_file.clone(),
+                    categories: config.categories.clone(),
+                    terminal: config.terminal,
+                })),
+                is_global: false,
+            };
+
+            // Register the artifact to various things
+            let installer_idx = self.add_local_artifact(variant_idx, installer_artifact);
+            for binary_idx in binaries {
+                let binary = self.binary(binary_idx);
+                self.require_binary(
+                    installer_idx,
+                    variant_idx,
+                    binary_idx,
+                    dir_path.join(&binary.file_name),
+                );
+            }
+            if checksum != ChecksumStyle::False {
+                self.add_artifact_checksum(variant_idx, installer_idx, checksum);
+            }
+            if self.inner.config.builds.omnibor {
+                let omnibor = self.create_omnibor_artifact(installer_idx, false);
+                self.add_local_artifact(variant_idx, omnibor);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_pypi_installer(&mut self, to_release: ReleaseIdx) -> DistResult<()> {
+        if !self.local_artifacts_enabled() {
+            return Ok(());
+        }
+
+        // Clone info we need from the release to avoid borrowing across the loop
+        let release = self.release(to_release);
+        let Some(config) = release.config.installers.pypi.clone() else {
+            return Ok(());
+        };
+        require_nonempty_installer(release, &config)?;
+        let package_name = PipPackageName::new(release.app_name.clone());
+        let version = pep440_version(&release.version);
+        let description = release.app_desc.clone();
+        let authors = release.app_authors.clone();
+        let license = release.app_license.clone();
+        let homepage = release.app_homepage_url.clone();
+        let repository = release.app_repository_url.clone();
+        let keywords = release.app_keywords.clone().unwrap_or_default();
+        let platform_support = release.platform_support.clone();
+
+        let variants = release.variants.clone();
+        let checksum = self.inner.config.artifacts.checksum;
+
+        // Make a wheel for every platform pip has a tag for
+        let mut platform_tags = BTreeMap::new();
+        for variant_idx in variants {
+            let variant = self.variant(variant_idx);
+            let binaries = variant.binaries.clone();
+            let target = &variant.target;
+
+            let Some(artifact) = platform_support.fragment_for_target(&variant.target) else {
+                continue;
+            };
+            let Some(platform_tag) = wheel_platform_tag(target, &artifact.runtime_conditions)
+            else {
+                warn!("skipping wheel for {target}, pip has no equivalent platform tag");
+                continue;
+            };
+            // Only one wheel can have a given name (e.g. windows-msvc and windows-gnu)
+            if let Some(existing) = platform_tags.get(&platform_tag) {
+                warn!("skipping wheel for {target}, {existing} already has its platform tag ({platform_tag})");
+                continue;
+            }
+            platform_tags.insert(platform_tag.clone(), target.clone());
+
+            let artifact_name =
+                ArtifactId::new(wheel_filename(&package_name, &version, &platform_tag));
+            let artifact_path = self.inner.dist_dir.join(artifact_name.as_str());
+            let dir_name = format!("{}_pypi", variant.id);
+            let dir_path = self.inner.dist_dir.join(&dir_name);
+
+            // Compute which package we're actually building, based on the binaries
+            let mut package_info: Option<(String, PackageIdx)> = None;
+            for &binary_idx in &binaries {
+                let binary = self.binary(binary_idx);
+                if let Some((existing_spec, _)) = &package_info {
+                    // we haven't set ourselves up to bundle multiple packages yet
+                    if existing_spec != &binary.pkg_spec {
+                        return Err(DistError::MultiPackage {
+                            artifact_name,
+                            spec1: existing_spec.clone(),
+                            spec2: binary.pkg_spec.clone(),
+                        })?;
+                    }
+                } else {
+                    package_info = Some((binary.pkg_spec.clone(), binary.pkg_idx));
+                }
+            }
+
+            let installer_artifact = Artifact {
+                id: artifact_name,
+                target_triples: vec![target.clone()],
+                file_path: artifact_path.clone(),
+                required_binaries: FastMap::new(),
+                archive: Some(Archive {
+                    with_root: None,
+                    dir_path: dir_path.clone(),
+                    zip_style: ZipStyle::TempDir,
+                    static_assets: vec![],
+                }),
+                checksum: None,
+                kind: ArtifactKind::Installer(InstallerImpl::Pypi(PypiInstallerInfo {
+                    file_path: artifact_path.clone(),
+                    artifact,
+                    package_dir: dir_path.clone(),
+                    package_name: package_name.clone(),
+                    version: version.clone(),
+                    platform_tag,
+                    description: description.clone(),
+                    authors: authors.clone(),
+                    license: license.clone(),
+                    homepage: homepage.clone(),
+                    repository: repository.clone(),
+                    keywords: keywords.clone(),
+                })),
+                is_global: false,
+            };
+
+            // Register the artifact to various things
+            let installer_idx = self.add_local_artifact(variant_idx, installer_artifact);
+            for binary_idx in binaries {
+                let binary = self.binary(binary_idx);
+                self.require_binary(
+                    installer_idx,
+                    variant_idx,
+                    binary_idx,
+                    dir_path.join(&binary.file_name),
+                );
+            }
+            if checksum != ChecksumStyle::False {
+                self.add_artifact_checksum(variant_idx, installer_idx, checksum);
+            }
+            if self.inner.config.builds.omnibor {
+                let omnibor = self.create_omnibor_artifact(installer_idx, false);
+                self.add_local_artifact(variant_idx, omnibor);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_scoop_installer(&mut self, to_release: ReleaseIdx, tag: &str) -> DistResult<()> {
+        if !self.global_artifacts_enabled() {
+            return Ok(());
+        }
+        let release = self.release(to_release);
+        let Some(config) = &release.config.installers.scoop else {
+            return Ok(());
+        };
+        require_nonempty_installer(release, config)?;
+        let schema_release = self
+            .manifest
+            .release_by_name(&release.id)
+            .expect("couldn't find the release!?");
+        let download_url = schema_release
+            .artifact_download_url()
+            .expect("couldn't compute a URL to download artifacts from!?");
+        let hosting = schema_release.hosting.clone();
+
+        let artifact_name = ArtifactId::new(format!("{}.json", release.app_name));
+        let artifact_path = self.inner.dist_dir.join(artifact_name.as_str());
+
+        // If a bucket is specified, tell the user to add it before installing from it,
+        // otherwise Scoop can install straight from the manifest's URL
+        let hint = if let Some(bucket) = &self.inner.global_scoop_bucket {
+            // So that, for example, axodotdev/scoop-bucket gets added as axodotdev
+            let name = bucket.split('/').next().unwrap_or(bucket);
+            format!(
+                "scoop bucket add {name} https://github.com/{bucket}; scoop install {name}/{}",
+                release.app_name
+            )
+        } else {
+            format!("scoop install {download_url}/{artifact_name}")
+        };
+
+        // Pick an archive for each architecture Scoop knows about, preferring msvc.
+        // Scoop falls back to other architectures itself, so only list archives
+        // that were natively built for the architecture.
+        let mut architectures = BTreeMap::<String, ExecutableZipFragment>::new();
+        for fragment in release.platform_support.native_fragments() {
+            if !fragment.target_triple.is_windows() {
+                continue;
+            }
+            let Some(arch) = scoop_architecture(fragment.target_triple.as_str()) else {
+                continue;
+            };
+            if let Some(existing) = architectures.get(arch) {
+                if existing.target_triple.is_windows_msvc() {
+                    continue;
+                }
+            }
+            architectures.insert(arch.to_owned(), fragment);
+        }
+        if architectures.is_empty() {
+            warn!("skipping Scoop installer: not building any supported platforms (use --artifacts=global)");
+            return Ok(());
+        };
+        let target_triples = architectures
+            .values()
+            .map(|a| a.target_triple.clone())
+            .collect::<Vec<_>>();
+
+        let description = release.app_desc.clone();
+        if description.is_none() {
+            warn!("The Scoop installer is enabled but no description was specified\n  consider adding `description = ` to package in Cargo.toml");
+        }
+        let homepage = release
+            .app_homepage_url
+            .clone()
+            .or_else(|| release.app_repository_url.clone());
+        let bucket = config.bucket.clone();
+        if bucket.is_some() && release.config.publishers.scoop.is_none() {
+            warn!("A Scoop bucket was specified but the Scoop publish job is disabled\n  consider adding \"scoop\" to publish-jobs in Cargo.toml");
+        }
+        if release.config.publishers.scoop.is_some() && bucket.is_none() {
+            warn!("The Scoop publish job is enabled but no bucket was specified\n  consider setting the scoop-bucket field in Cargo.toml");
+        }
+
+        let installer_artifact = Artifact {
+            id: artifact_name,
+            target_triples,
+            archive: None,
+            file_path: artifact_path.clone(),
+            required_binaries: Default::default(),
+            checksum: None,
+            kind: ArtifactKind::Installer(InstallerImpl::Scoop(ScoopInstallerInfo {
+                file_path: artifact_path,
+                version: release.version.to_string(),
+                description,
+                homepage,
+                license: release.app_license.clone(),
+                base_url: download_url,
+                hosting,
+                tag_prefix: tag
+                    .strip_suffix(&release.version.to_string())
+                    .map(|prefix| prefix.to_owned()),
+                checksum: self.inner.config.artifacts.checksum,
+                architectures,
+                bin_aliases: config.bin_aliases.clone().into_iter().collect(),
+                hint,
+            })),
+            is_global: true,
+        };
+
+        self.add_global_artifact(to_release, installer_artifact);
+        Ok(())
+    }
+
+    fn add_winget_installer(&mut self, to_release: ReleaseIdx) -> DistResult<()> {
+        if !self.global_artifacts_enabled() {
+            return Ok(());
+        }
+        let release = self.release(to_release);
+        let Some(config) = &release.config.installers.winget else {
+            return Ok(());
+        };
+        require_nonempty_installer(release, config)?;
+        let Some(author) = release.app_authors.first() else {
+            return Err(DistError::WingetPublisherMissing {
+                package: release.app_name.clone(),
+            });
+        };
+        let publisher = winget_publisher(author);
+        let Some(license) = release.app_license.clone() else {
+            return Err(DistError::WingetLicenseMissing {
+                package: release.app_name.clone(),
+            });
+        };
+        let schema_release = self
+            .manifest
+            .release_by_name(&release.id)
+            .expect("couldn't find the release!?");
+        let download_url = schema_release
+            .artifact_download_url()
+            .expect("couldn't compute a URL to download artifacts from!?");
+
+        // Pick a zip archive for each architecture winget knows about, preferring msvc.
+        // winget can't unpack tarballs, and does its own emulation fallbacks, so we
+        // only list archives that were natively built for the architecture.
+        let mut zips = BTreeMap::<String, &FetchableArchive>::new();
+        for archive in &release.platform_support.archives {
+            if !archive.target_triple.is_windows() || archive.zip_style != ZipStyle::Zip {
+                continue;
+            }
+            let Some(arch) = winget_architecture(archive.target_triple.as_str()) else {
+                continue;
+            };
+            if let Some(existing) = zips.get(arch) {
+                if existing.target_triple.is_windows_msvc() {
+                    continue;
+                }
+            }
+            zips.insert(arch.to_owned(), archive);
+        }
+        // Likewise pick an msi for each architecture, if we're building them
+        let mut msis = BTreeMap::<String, (ArtifactId, TripleName)>::new();
+        if release.config.installers.msi.is_some() {
+            for &variant_idx in &release.variants {
+                let variant = self.variant(variant_idx);
+                if !variant.target.is_windows() {
+                    continue;
+                }
+                let Some(arch) = winget_architecture(variant.target.as_str()) else {
+                    continue;
+                };
+                if let Some((_, existing)) = msis.get(arch) {
+                    if existing.is_windows_msvc() {
+                        continue;
+                    }
+                }
+                let id = ArtifactId::new(format!("{}.msi", variant.id));
+                msis.insert(arch.to_owned(), (id, variant.target.clone()));
+            }
+        }
+        let mut target_triples = vec![];
+        let mut installers = vec![];
+        for (arch, archive) in zips {
+            target_triples.push(archive.target_triple.clone());
+            installers.push(WingetInstallerSource {
+                id: archive.id.clone(),
+                architecture: arch,
+                executables: Some(archive.executables.clone()),
+            });
+        }
+        for (arch, (id, target)) in msis {
+            if !target_triples.contains(&target) {
+                target_triples.push(target);
+            }
+            installers.push(WingetInstallerSource {
+                id,
+                architecture: arch,
+                executables: None,
+            });
+        }
+        if installers.is_empty() {
+            warn!("skipping winget installer: not building any supported platforms (use --artifacts=global)");
+            return Ok(());
+        };
+
+        let short_description = release.app_desc.clone().unwrap_or_else(|| {
+            warn!("The winget installer is enabled but no description was specified\n  consider adding `description = ` to package in Cargo.toml");
+            format!("The {} application", release.app_name)
+        });
+        let homepage = release
+            .app_homepage_url
+            .clone()
+            .or_else(|| release.app_repository_url.clone());
+        let identifier = config
+            .identifier
+            .clone()
+            .unwrap_or_else(|| winget_identifier(&publisher, &release.app_name));
+        let fork = config.fork.clone();
+        if fork.is_some() && release.config.publishers.winget.is_none() {
+            warn!("A winget-pkgs fork was specified but the winget publish job is disabled\n  consider adding \"winget\" to publish-jobs in Cargo.toml");
+        }
+        if release.config.publishers.winget.is_some() && fork.is_none() {
+            warn!("The winget publish job is enabled but no fork was specified\n  consider setting the winget-fork field in Cargo.toml");
+        }
+
+        let manifest_types = [
+            WingetManifestType::Version,
+            WingetManifestType::Installer,
+            WingetManifestType::DefaultLocale,
+        ];
+        let mut artifacts = vec![];
+        for manifest_type in manifest_types {
+            let artifact_name = ArtifactId::new(manifest_type.file_name(&identifier));
+            let artifact_path = self.inner.dist_dir.join(artifact_name.as_str());
+            artifacts.push(Artifact {
+                id: artifact_name,
+                target_triples: target_triples.clone(),
+                archive: None,
+                file_path: artifact_path.clone(),
+                required_binaries: Default::default(),
+                checksum: None,
+                kind: ArtifactKind::Installer(InstallerImpl::Winget(WingetInstallerInfo {
+                    manifest_type,
+                    file_path: artifact_path,
+                    identifier: identifier.clone(),
+                    version: release.version.to_string(),
+                    publisher: publisher.clone(),
+                    name: release.app_name.clone(),
+                    license: license.clone(),
+                    short_description: short_description.clone(),
+                    homepage: homepage.clone(),
+                    base_url: download_url.clone(),
+                    installers: installers.clone(),
+                    bin_aliases: config.bin_aliases.clone().into_iter().collect(),
+                })),
+                is_global: true,
+            });
+        }
+
+        for artifact in artifacts {
+            self.add_global_artifact(to_release, artifact);
+        }
+        Ok(())
+    }
+
+    fn add_chocolatey_installer(&mut self, to_release: ReleaseIdx) -> DistResult<()> {
+        if !self.global_artifacts_enabled() {
+            return Ok(());
+        }
+        let release = self.release(to_release);
+        let Some(config) = &release.config.installers.chocolatey else {
+            return Ok(());
+        };
+        require_nonempty_installer(release, config)?;
+        if release.app_authors.is_empty() {
+            return Err(DistError::ChocolateyAuthorsMissing {
+                package: release.app_name.clone(),
+            });
+        }
+        let release_id = &release.id;
+        let schema_release = self
+            .manifest
+            .release_by_name(release_id)
+            .expect("couldn't find the release!?");
+        let download_url = schema_release
+            .artifact_download_url()
+            .expect("couldn't compute a URL to download artifacts from!?");
+
+        // Chocolatey only distinguishes 32-bit and 64-bit windows, and can only
+        // unpack zips, so pick a natively-built zip for each, preferring msvc
+        let mut x86 = None::<&FetchableArchive>;
+        let mut x64 = None::<&FetchableArchive>;
+        for archive in &release.platform_support.archives {
+            if !archive.target_triple.is_windows() || archive.zip_style != ZipStyle::Zip {
+                continue;
+            }
+            let target = archive.target_triple.as_str();
+            let slot = if target.starts_with("x86_64-") {
+                &mut x64
+            } else if target.starts_with("i686-") {
+                &mut x86
+            } else {
+                continue;
+            };
+            if let Some(existing) = slot {
+                if existing.target_triple.is_windows_msvc() {
+                    continue;
+                }
             }
-            if self.inner.config.builds.omnibor {
-                let omnibor = self.create_omnibor_artifact(installer_idx, false);
-                self.add_local_artifact(variant_idx, omnibor);
+            *slot = Some(archive);
+        }
+        if x86.is_none() && x64.is_none() {
+            warn!("skipping Chocolatey installer: not building any supported platforms (use --artifacts=global)");
+            return Ok(());
+        };
+        let target_triples = [x86, x64]
+            .into_iter()
+            .flatten()
+            .map(|a| a.target_triple.clone())
+            .collect::<Vec<_>>();
+
+        let mut aliases = vec![];
+        let executables = x64.or(x86).map(|a| &a.executables[..]).unwrap_or_default();
+        for exe in executables {
+            let name = exe.strip_suffix(".exe").unwrap_or(exe);
+            for alias in config.bin_aliases.get(name).into_iter().flatten() {
+                aliases.push((alias.clone(), exe.clone()));
             }
         }
+        let x86 = x86.map(|a| ChocolateyArchive {
+            id: a.id.clone(),
+            checksum: None,
+            checksum_type: None,
+        });
+        let x64 = x64.map(|a| ChocolateyArchive {
+            id: a.id.clone(),
+            checksum: None,
+            checksum_type: None,
+        });
+
+        let description = release.app_desc.clone().unwrap_or_else(|| {
+            warn!("The Chocolatey installer is enabled but no description was specified\n  consider adding `description = ` to package in Cargo.toml");
+            format!("The {} application", release.app_name)
+        });
+        let homepage = release
+            .app_homepage_url
+            .clone()
+            .or_else(|| release.app_repository_url.clone());
+
+        let package_id = chocolatey_package_id(&release.app_name);
+        let version = release.version.to_string();
+        let dir_name = format!("{release_id}-chocolatey-package");
+        let dir_path = self.inner.dist_dir.join(&dir_name);
+        let artifact_name = ArtifactId::new(format!("{package_id}.{version}.nupkg"));
+        let artifact_path = self.inner.dist_dir.join(artifact_name.as_str());
+
+        let installer_artifact = Artifact {
+            id: artifact_name,
+            target_triples,
+            archive: Some(Archive {
+                with_root: None,
+                dir_path: dir_path.clone(),
+                zip_style: ZipStyle::Zip,
+                static_assets: vec![],
+            }),
+            file_path: artifact_path,
+            required_binaries: FastMap::new(),
+            checksum: None,
+            kind: ArtifactKind::Installer(InstallerImpl::Chocolatey(ChocolateyInstallerInfo {
+                package_dir: dir_path,
+                package_id,
+                version,
+                title: release.app_name.clone(),
+                authors: release.app_authors.join(", "),
+                description,
+                homepage,
+                tags: release.app_keywords.clone().unwrap_or_default(),
+                base_url: download_url,
+                x86,
+                x64,
+                aliases,
+            })),
+            is_global: true,
+        };
 
+        self.add_global_artifact(to_release, installer_artifact);
         Ok(())
     }
 
-    fn add_pkg_installer(&mut self, to_release: ReleaseIdx) -> DistResult<()> {
-        if !self.local_artifacts_enabled() {
+    fn add_aur_installer(&mut self, to_release: ReleaseIdx) -> DistResult<()> {
+        if !self.global_artifacts_enabled() {
             return Ok(());
         }
-
-        // Clone info we need from the release to avoid borrowing across the loop
         let release = self.release(to_release);
-        let Some(config) = release.config.installers.pkg.clone() else {
+        let Some(config) = &release.config.installers.aur else {
             return Ok(());
         };
-        require_nonempty_installer(release, &config)?;
-        let version = release.version.clone();
-        let fragments = release.platform_support.fragments();
+        require_nonempty_installer(release, config)?;
+        let schema_release = self
+            .manifest
+            .release_by_name(&release.id)
+            .expect("couldn't find the release!?");
+        let download_url = schema_release
+            .artifact_download_url()
+            .expect("couldn't compute a URL to download artifacts from!?");
 
-        let variants = release.variants.clone();
-        let checksum = self.inner.config.artifacts.checksum;
+        let pkgname = aur_package_name(&release.app_name);
+        let pkgver = aur_package_version(&release.version.to_string());
 
-        // Make a pkg for every darwin platform
-        for variant_idx in variants {
-            let variant = self.variant(variant_idx);
-            let binaries = variant.binaries.clone();
-            let bin_aliases = BinaryAliases(config.bin_aliases.clone());
-            let target = &variant.target;
-            if !target.is_darwin() {
+        // Pick an archive for each architecture makepkg knows about, preferring glibc.
+        // We only list archives that were natively built for the architecture.
+        let mut archives = BTreeMap::<String, &FetchableArchive>::new();
+        for archive in &release.platform_support.archives {
+            if !archive.target_triple.is_linux() {
                 continue;
             }
-
-            let variant_id = &variant.id;
-            let artifact_name = ArtifactId::new(format!("{variant_id}.pkg"));
-            let artifact_path = self.inner.dist_dir.join(artifact_name.as_str());
-            let dir_name = format!("{variant_id}_pkg");
-            let dir_path = self.inner.dist_dir.join(&dir_name);
-
-            // Compute which package we're actually building, based on the binaries
-            let mut package_info: Option<(String, PackageIdx)> = None;
-            for &binary_idx in &binaries {
-                let binary = self.binary(binary_idx);
-                if let Some((existing_spec, _)) = &package_info {
-                    // we haven't set ourselves up to bundle multiple packages yet
-                    if existing_spec != &binary.pkg_spec {
-                        return Err(DistError::MultiPackage {
-                            artifact_name,
-                            spec1: existing_spec.clone(),
-                            spec2: binary.pkg_spec.clone(),
-                        })?;
-                    }
-                } else {
-                    package_info = Some((binary.pkg_spec.clone(), binary.pkg_idx));
+            let Some(arch) = aur_architecture(archive.target_triple.as_str()) else {
+                continue;
+            };
+            if let Some(existing) = archives.get(arch) {
+                if !existing.target_triple.is_linux_musl() {
+                    continue;
                 }
             }
+            archives.insert(arch.to_owned(), archive);
+        }
+        if archives.is_empty() {
+            warn!("skipping AUR installer: not building any supported platforms (use --artifacts=global)");
+            return Ok(());
+        };
+        let target_triples = archives
+            .values()
+            .map(|a| a.target_triple.clone())
+            .collect::<Vec<_>>();
+        let sources = archives
+            .into_iter()
+            .map(|(arch, archive)| {
+                let ext = archive.zip_style.ext();
+                // Tarballs have a root dir named after the archive, zips are flat
+                let root_dir = (archive.zip_style != ZipStyle::Zip)
+                    .then(|| archive.id.as_str().strip_suffix(ext).map(str::to_owned))
+                    .flatten();
+                AurSource {
+                    id: archive.id.clone(),
+                    file_name: format!("{pkgname}-{pkgver}-{arch}{ext}"),
+                    arch,
+                    root_dir,
+                    executables: archive.executables.clone(),
+                }
+            })
+            .collect::<Vec<_>>();
 
-            let Some(artifact) = fragments
-                .clone()
-                .into_iter()
-                .find(|a| a.target_triple == variant.target)
-            else {
-                return Err(DistError::NoPackage { artifact_name })?;
-            };
-
-            let bin_aliases = bin_aliases.for_target(&variant.target);
+        let mut aliases = vec![];
+        for (exe, exe_aliases) in &config.bin_aliases {
+            for alias in exe_aliases {
+                aliases.push((alias.clone(), exe.clone()));
+            }
+        }
 
-            let identifier = if let Some(id) = &config.identifier {
-                id.to_owned()
-            } else {
-                return Err(DistError::MacPkgBundleIdentifierMissing {});
-            };
+        let pkgdesc = match &release.app_desc {
+            Some(desc) => aur_package_description(desc),
+            None => {
+                warn!("The AUR installer is enabled but no description was specified\n  consider adding `description = ` to package in Cargo.toml");
+                format!("The {} application", release.app_name)
+            }
+        };
+        let url = release
+            .app_homepage_url
+            .clone()
+            .or_else(|| release.app_repository_url.clone());
+        let license = match &release.app_license {
+            // Parse SPDX license expression and convert to PKGBUILD entries.
+            // If expression is malformed, fall back to plain input license string.
+            Some(app_license) => {
+                to_aur_license_format(app_license).unwrap_or_else(|_| vec![app_license.clone()])
+            }
+            None => {
+                warn!("The AUR installer is enabled but no license was specified\n  consider adding `license = ` to package in Cargo.toml");
+                vec![]
+            }
+        };
 
-            // Gather up the bundles the installer supports
-            let installer_artifact = Artifact {
+        let mut artifacts = vec![];
+        for file_type in [AurFileType::Pkgbuild, AurFileType::Srcinfo] {
+            let artifact_name = ArtifactId::new(file_type.file_name(&pkgname));
+            let artifact_path = self.inner.dist_dir.join(artifact_name.as_str());
+            artifacts.push(Artifact {
                 id: artifact_name,
-                target_triples: vec![target.clone()],
+                target_triples: target_triples.clone(),
+                archive: None,
                 file_path: artifact_path.clone(),
-                required_binaries: FastMap::new(),
-                archive: Some(Archive {
-                    with_root: None,
-                    dir_path: dir_path.clone(),
-                    zip_style: ZipStyle::TempDir,
-                    static_assets: vec![],
-                }),
+                required_binaries: Default::default(),
                 checksum: None,
-                kind: ArtifactKind::Installer(InstallerImpl::Pkg(PkgInstallerInfo {
-                    file_path: artifact_path.clone(),
-                    artifact,
-                    package_dir: dir_path.clone(),
-                    identifier,
-                    install_location: config.install_location.clone(),
-                    version: version.to_string(),
-                    bin_aliases,
+                kind: ArtifactKind::Installer(InstallerImpl::Aur(AurInstallerInfo {
+                    file_type,
+                    file_path: artifact_path,
+                    pkgname: pkgname.clone(),
+                    pkgver: pkgver.clone(),
+                    maintainer: release.app_authors.first().cloned(),
+                    pkgdesc: pkgdesc.clone(),
+                    url: url.clone(),
+                    license: license.clone(),
+                    provides: release.app_name.clone(),
+                    base_url: download_url.clone(),
+                    sources: sources.clone(),
+                    aliases: aliases.clone(),
                 })),
-                is_global: false,
-            };
+                is_global: true,
+            });
+        }
 
-            // Register the artifact to various things
-            let installer_idx = self.add_local_artifact(variant_idx, installer_artifact);
-            for binary_idx in binaries {
-                let binary = self.binary(binary_idx);
-                self.require_binary(
-                    installer_idx,
-                    variant_idx,
-                    binary_idx,
-                    dir_path.join(&binary.file_name),
-                );
-            }
-            if checksum != ChecksumStyle::False {
-                self.add_artifact_checksum(variant_idx, installer_idx, checksum);
+        for artifact in artifacts {
+            self.add_global_artifact(to_release, artifact);
+        }
+        Ok(())
+    }
+
+    fn add_nix_installer(&mut self, to_release: ReleaseIdx) -> DistResult<()> {
+        if !self.global_artifacts_enabled() {
+            return Ok(());
+        }
+        let release = self.release(to_release);
+        let Some(config) = &release.config.installers.nix else {
+            return Ok(());
+        };
+        require_nonempty_installer(release, config)?;
+        let schema_release = self
+            .manifest
+            .release_by_name(&release.id)
+            .expect("couldn't find the release!?");
+        let download_url = schema_release
+            .artifact_download_url()
+            .expect("couldn't compute a URL to download artifacts from!?");
+
+        // Pick an archive for each system Nix knows about, preferring glibc.
+        // Nix does its own emulation fallbacks, so we only list archives that
+        // were natively built for the system.
+        let mut archives = BTreeMap::<String, &FetchableArchive>::new();
+        for archive in &release.platform_support.archives {
+            let Some(system) = nix_system(archive.target_triple.as_str()) else {
+                continue;
+            };
+            if let Some(existing) = archives.get(system) {
+                if !existing.target_triple.is_linux_musl() {
+                    continue;
+                }
             }
-            if self.inner.config.builds.omnibor {
-                let omnibor = self.create_omnibor_artifact(installer_idx, false);
-                self.add_local_artifact(variant_idx, omnibor);
+            archives.insert(system.to_owned(), archive);
+        }
+        let Some(first_archive) = archives.values().next() else {
+            warn!("skipping Nix installer: not building any supported platforms (use --artifacts=global)");
+            return Ok(());
+        };
+        let executables = first_archive.executables.clone();
+        let target_triples = archives
+            .values()
+            .map(|a| a.target_triple.clone())
+            .collect::<Vec<_>>();
+        let sources = archives
+            .into_iter()
+            .map(|(system, archive)| {
+                let is_zip = archive.zip_style == ZipStyle::Zip;
+                // Tarballs have a root dir named after the archive, zips are flat
+                let root_dir = archive
+                    .id
+                    .as_str()
+                    .strip_suffix(archive.zip_style.ext())
+                    .filter(|_| !is_zip)
+                    .unwrap_or(".")
+                    .to_owned();
+                NixSource {
+                    id: archive.id.clone(),
+                    system,
+                    root_dir,
+                    is_zip,
+                    hash: None,
+                    needs_patchelf: false,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut aliases = vec![];
+        for (exe, exe_aliases) in &config.bin_aliases {
+            for alias in exe_aliases {
+                aliases.push((alias.clone(), exe.clone()));
             }
         }
+        if release.app_desc.is_none() {
+            warn!("The Nix installer is enabled but no description was specified\n  consider adding `description = ` to package in Cargo.toml");
+        }
+        let homepage = release
+            .app_homepage_url
+            .clone()
+            .or_else(|| release.app_repository_url.clone());
+        let licenses = release
+            .app_license
+            .as_deref()
+            .map(nix_licenses)
+            .unwrap_or_default();
+        if config.repo.is_some() && release.config.publishers.nix.is_none() {
+            warn!("A Nix repo was specified but the Nix publish job is disabled\n  consider adding \"nix\" to publish-jobs in Cargo.toml");
+        }
+
+        let artifact_name = ArtifactId::new(format!("{}.nix", release.app_name));
+        let artifact_path = self.inner.dist_dir.join(artifact_name.as_str());
+        let installer_artifact = Artifact {
+            id: artifact_name,
+            target_triples,
+            archive: None,
+            file_path: artifact_path.clone(),
+            required_binaries: FastMap::new(),
+            checksum: None,
+            kind: ArtifactKind::Installer(InstallerImpl::Nix(NixInstallerInfo {
+                file_path: artifact_path,
+                pname: release.app_name.clone(),
+                version: release.version.to_string(),
+                description: release.app_desc.clone(),
+                homepage,
+                licenses,
+                base_url: download_url,
+                sources,
+                executables,
+                aliases,
+            })),
+            is_global: true,
+        };
 
+        self.add_global_artifact(to_release, installer_artifact);
         Ok(())
     }
 
@@ -2742,6 +4679,7 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
         global_build_steps.extend(self.compute_extra_builds());
 
         Self::add_build_steps_for_artifacts(
+            &self.inner.signer,
             &self
                 .inner
                 .artifacts
@@ -2751,6 +4689,7 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
             &mut local_build_steps,
         );
         Self::add_build_steps_for_artifacts(
+            &self.inner.signer,
             &self
                 .inner
                 .artifacts
@@ -2766,7 +4705,11 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
         Ok(())
     }
 
-    fn add_build_steps_for_artifacts(artifacts: &Vec<&Artifact>, build_steps: &mut Vec<BuildStep>) {
+    fn add_build_steps_for_artifacts(
+        signer: &Signing,
+        artifacts: &Vec<&Artifact>,
+        build_steps: &mut Vec<BuildStep>,
+    ) {
         for artifact in artifacts {
             match &artifact.kind {
                 ArtifactKind::ExecutableZip(_zip) => {
@@ -2788,12 +4731,41 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                 ArtifactKind::Installer(installer) => {
                     // Installer generation is complex enough that they just get monolithic impls
                     build_steps.push(BuildStep::GenerateInstaller(installer.clone()));
+                    let kind = match installer {
+                        InstallerImpl::Msi(_) => Some(SigningKind::Msi),
+                        InstallerImpl::Pkg(_) => Some(SigningKind::Pkg),
+                        // powershell scripts get signed like executables
+                        InstallerImpl::Powershell(_) => Some(SigningKind::Binaries),
+                        _ => None,
+                    };
+                    if let Some(kind) = kind {
+                        Self::add_sign_step(signer, build_steps, kind, artifact);
+                    }
+                    // winget needs the sha256 of any msi it installs
+                    if let InstallerImpl::Msi(_) = installer {
+                        build_steps.push(BuildStep::Checksum(ChecksumImpl {
+                            checksum: ChecksumStyle::Sha256,
+                            src_path: artifact.file_path.clone(),
+                            dest_path: None,
+                            for_artifact: Some(artifact.id.clone()),
+                        }))
+                    }
                 }
                 ArtifactKind::Checksum(checksum) => {
                     build_steps.push(BuildStep::Checksum(checksum.clone()));
+                    if checksum.dest_path.is_some() {
+                        Self::add_sign_step(signer, build_steps, SigningKind::Checksums, artifact);
+                    }
                 }
                 ArtifactKind::UnifiedChecksum(unified_checksum) => {
                     build_steps.push(BuildStep::UnifiedChecksum(unified_checksum.clone()));
+                    Self::add_sign_step(signer, build_steps, SigningKind::Checksums, artifact);
+                }
+                ArtifactKind::MinisignSignature(signature) => {
+                    build_steps.push(BuildStep::MinisignSignature(signature.clone()));
+                }
+                ArtifactKind::DetachedSignature(signature) => {
+                    build_steps.push(BuildStep::DetachedSignature(signature.clone()));
                 }
                 ArtifactKind::SourceTarball(tarball) => {
                     build_steps.push(BuildStep::GenerateSourceTarball(SourceTarballStep {
@@ -2802,6 +4774,10 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                         target: tarball.target.to_owned(),
                         working_dir: tarball.working_dir.to_owned(),
                     }));
+                    Self::add_sign_step(signer, build_steps, SigningKind::Archives, artifact);
+                }
+                ArtifactKind::OfflineBundle(_) => {
+                    // artifact.archive handles everything
                 }
                 ArtifactKind::ExtraArtifact(_) => {
                     // compute_extra_builds handles this
@@ -2814,8 +4790,11 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                         use_latest: *use_latest,
                     }))
                 }
-                ArtifactKind::SBOM(_) => {
-                    // The SBOM is already generated.
+                ArtifactKind::SBOM(SBOMImpl { generate }) => {
+                    // Otherwise the SBOM is already generated.
+                    if let Some(step) = generate {
+                        build_steps.push(BuildStep::GenerateSbom(step.clone()));
+                    }
                 }
                 ArtifactKind::OmniborArtifactId(src) => {
                     let src_path = src.src_path.clone();
@@ -2827,6 +4806,9 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                         dest_path,
                     }));
                 }
+                ArtifactKind::Provenance(provenance) => {
+                    build_steps.push(BuildStep::Provenance(provenance.clone()));
+                }
             }
 
             if let Some(archive) = &artifact.archive {
@@ -2851,6 +4833,7 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                     with_root: archive.with_root.clone(),
                     zip_style: archive.zip_style,
                 }));
+                Self::add_sign_step(signer, build_steps, SigningKind::Archives, artifact);
                 // and get its sha256 checksum into the metadata
                 build_steps.push(BuildStep::Checksum(ChecksumImpl {
                     checksum: ChecksumStyle::Sha256,
@@ -2862,6 +4845,28 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
         }
     }
 
+    /// Sign an artifact once it's built, if anything is set up to sign files of this kind
+    fn add_sign_step(
+        signer: &Signing,
+        build_steps: &mut Vec<BuildStep>,
+        kind: SigningKind,
+        artifact: &Artifact,
+    ) {
+        // Only artifacts for a single target can use per-target signing rules
+        let target = match artifact.target_triples.as_slice() {
+            [target] => Some(target.clone()),
+            _ => None,
+        };
+        if signer.signs(kind, target.as_deref()) {
+            build_steps.push(BuildStep::Sign(SignStep {
+                kind,
+                target,
+                src_path: artifact.file_path.clone(),
+                for_artifact: artifact.id.clone(),
+            }));
+        }
+    }
+
     fn validate_distable_packages(&self, announcing: &AnnouncementTag) -> DistResult<()> {
         for release in &announcing.rust_releases {
             let package = self.workspaces.package(release.package_idx);
@@ -2994,6 +4999,15 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                     InstallerStyle::Npm,
                     InstallerStyle::Msi,
                     InstallerStyle::Pkg,
+                    InstallerStyle::Deb,
+                    InstallerStyle::Rpm,
+                    InstallerStyle::Scoop,
+                    InstallerStyle::Winget,
+                    InstallerStyle::Chocolatey,
+                    InstallerStyle::Aur,
+                    InstallerStyle::Nix,
+                    InstallerStyle::Pypi,
+                    InstallerStyle::Appimage,
                 ]
             } else {
                 &cfg.installers[..]
@@ -3003,18 +5017,45 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
                 match installer {
                     InstallerStyle::Shell => self.add_shell_installer(release)?,
                     InstallerStyle::Powershell => self.add_powershell_installer(release)?,
-                    InstallerStyle::Homebrew => self.add_homebrew_installer(release)?,
-                    InstallerStyle::Npm => self.add_npm_installer(release)?,
+                    InstallerStyle::Homebrew => {
+                        self.add_homebrew_installer(release)?;
+                        self.add_homebrew_bottles(release)?;
+                    }
+                    InstallerStyle::Npm => {
+                        self.add_npm_installer(release)?;
+                        self.add_npm_platform_packages(release)?;
+                    }
                     InstallerStyle::Msi => self.add_msi_installer(release)?,
                     InstallerStyle::Pkg => self.add_pkg_installer(release)?,
+                    InstallerStyle::Deb => self.add_deb_installer(release)?,
+                    InstallerStyle::Rpm => self.add_rpm_installer(release)?,
+                    InstallerStyle::Scoop => self.add_scoop_installer(release, &announcing.tag)?,
+                    InstallerStyle::Winget => self.add_winget_installer(release)?,
+                    InstallerStyle::Chocolatey => self.add_chocolatey_installer(release)?,
+                    InstallerStyle::Aur => self.add_aur_installer(release)?,
+                    InstallerStyle::Nix => self.add_nix_installer(release)?,
+                    InstallerStyle::Pypi => self.add_pypi_installer(release)?,
+                    InstallerStyle::Appimage => self.add_appimage_installer(release)?,
                 }
             }
 
             // Add SBOM file, if it exists.
             self.add_cyclonedx_sbom_file(info.package_idx, release);
 
+            // Add the SBOMs we generate ourselves
+            self.add_sboms(info.package_idx, release);
+
             // Add the unified checksum file
             self.add_unified_checksum_file(release);
+
+            // Bundle up the installers with everything they'd download
+            self.add_offline_bundle(release);
+
+            // Sign everything we just made, now that it's all final
+            self.add_detached_signatures(release);
+
+            // Describe how all of the above was made
+            self.add_provenance(release, &announcing.tag);
         }
 
         // Translate the result to DistManifest
@@ -3024,17 +5065,21 @@ impl<'pkg_graph> DistGraphBuilder<'pkg_graph> {
     }
 
     fn compute_ci(&mut self) -> DistResult<()> {
-        let CiConfig { github } = &self.inner.config.ci;
+        let CiConfig { github, gitlab } = &self.inner.config.ci;
 
         let mut has_ci = false;
         if let Some(github_config) = github {
             has_ci = true;
             self.inner.ci.github = Some(GithubCiInfo::new(&self.inner, github_config)?);
         }
+        if let Some(gitlab_config) = gitlab {
+            // GitLab pipelines are rendered statically, so nothing needs to go in the manifest
+            self.inner.ci.gitlab = Some(GitlabCiInfo::new(&self.inner, gitlab_config)?);
+        }
 
         // apply to manifest
         if has_ci {
-            let CiInfo { github } = &self.inner.ci;
+            let CiInfo { github, gitlab: _ } = &self.inner.ci;
             let github = github.as_ref().map(|info| {
                 let external_repo_commit = info
                     .github_release
@@ -3292,6 +5337,7 @@ fn tool_info() -> DistResult<Tools> {
         cargo_cyclonedx: find_cargo_subcommand("cargo", "cyclonedx", "--version"),
         cargo_xwin: find_cargo_subcommand("cargo", "xwin", "--version"),
         cargo_zigbuild: find_tool("cargo-zigbuild", "--version"),
+        appimagetool: find_tool("appimagetool", "--version"),
     })
 }
 
@@ -3316,7 +5362,12 @@ fn find_tool(name: &str, test_flag: &str) -> Option<Tool> {
         .check(false)
         .output()
         .ok()?;
-    let string_output = String::from_utf8(output.stdout).ok()?;
+    // Some tools (like appimagetool) print their version to stderr
+    let string_output = if output.stdout.is_empty() {
+        String::from_utf8(output.stderr).ok()?
+    } else {
+        String::from_utf8(output.stdout).ok()?
+    };
     let version = string_output.lines().next()?;
     Some(Tool {
         cmd: name.to_owned(),
@@ -3391,6 +5442,16 @@ pub struct InstallReceipt {
     pub cdylibs: Vec<String>,
     /// A list of all C static libraries installed by this app
     pub cstaticlibs: Vec<String>,
+    /// A list of all shell completion scripts installed by this app
+    pub completions: Vec<String>,
+    /// Every file installed for this app, other than env scripts and this receipt
+    pub installed_files: Vec<String>,
+    /// Scripts written to put the install dir on PATH when sourced
+    pub env_scripts: Vec<String>,
+    /// Shell rcfiles that had a line sourcing one of the env scripts added to them
+    pub modified_rcfiles: Vec<String>,
+    /// Dirs added to the user's PATH in the Windows registry
+    pub registry_path_entries: Vec<String>,
     /// Information about where to request information on new releases
     pub source: ReleaseSource,
     /// The version that was installed
@@ -3416,17 +5477,29 @@ impl InstallReceipt {
         };
         let source_type = if hosting.hosts.contains(&HostingStyle::Github) {
             ReleaseSourceType::GitHub
+        } else if hosting
+            .hosts
+            .iter()
+            .any(|host| matches!(host, HostingStyle::Gitlab | HostingStyle::S3))
+        {
+            // axoupdater can only look up GitHub releases, so a receipt would be useless
+            return Ok(None);
         } else {
             return Err(DistError::NoGitHubHosting {});
         };
 
         Ok(Some(InstallReceipt {
-            // These first five are placeholder values which the installer will update
+            // These first ten are placeholder values which the installer will update
             install_prefix: "AXO_INSTALL_PREFIX".to_owned(),
             install_layout: InstallLayout::Unspecified,
             binaries: vec!["CARGO_DIST_BINS".to_owned()],
             cdylibs: vec!["CARGO_DIST_DYLIBS".to_owned()],
             cstaticlibs: vec!["CARGO_DIST_STATICLIBS".to_owned()],
+            completions: vec!["CARGO_DIST_COMPLETIONS".to_owned()],
+            installed_files: vec!["CARGO_DIST_INSTALLED_FILES".to_owned()],
+            env_scripts: vec!["CARGO_DIST_ENV_SCRIPTS".to_owned()],
+            modified_rcfiles: vec!["CARGO_DIST_MODIFIED_RCFILES".to_owned()],
+            registry_path_entries: vec!["CARGO_DIST_REGISTRY_PATH_ENTRIES".to_owned()],
             version: release.version.to_string(),
             source: ReleaseSource {
                 release_type: source_type,
@@ -3444,6 +5517,22 @@ impl InstallReceipt {
     }
 }
 
+/// Get the name of the npm package for an app, with its scope
+fn npm_package_name(config: &NpmInstallerConfig) -> DistResult<String> {
+    let app_name = &config.package;
+    if let Some(scope) = &config.scope {
+        if scope.to_ascii_lowercase() != *scope {
+            return Err(DistError::ScopeMustBeLowercase {
+                scope: scope.to_owned(),
+            });
+        }
+
+        Ok(format!("{scope}/{app_name}"))
+    } else {
+        Ok(app_name.clone())
+    }
+}
+
 fn require_nonempty_installer(release: &Release, config: &CommonInstallerConfig) -> DistResult<()> {
     if config.install_libraries.is_empty() && release.bins.is_empty() {
         Err(DistError::EmptyInstaller {})
@@ -1,8 +1,8 @@
 use crate::{
     backend::diff_source,
-    config::{self, v0::parse_metadata_table, DistMetadata},
+    config::{self, v0::parse_metadata_table, DistMetadata, InstallPathStrategy},
     init::v0::apply_dist_to_workspace_toml,
-    DistResult,
+    DistError, DistResult,
 };
 use axoasset::SourceFile;
 use axoproject::WorkspaceKind;
@@ -210,3 +210,83 @@ targets = ["aarch64-apple-darwin", "x86_64-apple-darwin"]
         .map_err(miette::Report::new)
         .unwrap();
 }
+
+fn install_paths(paths: &[&str]) -> Vec<InstallPathStrategy> {
+    paths.iter().map(|p| p.parse().unwrap()).collect()
+}
+
+#[test]
+fn install_path_absolute() {
+    let absolute = |path: &str| InstallPathStrategy::Absolute {
+        path: path.to_owned(),
+    };
+    assert_eq!(
+        "/usr/local/bin".parse::<InstallPathStrategy>().unwrap(),
+        absolute("/usr/local/bin")
+    );
+    // Trailing slashes are dropped, but not the whole path
+    assert_eq!(
+        "/opt/x/".parse::<InstallPathStrategy>().unwrap(),
+        absolute("/opt/x")
+    );
+    assert_eq!("/".parse::<InstallPathStrategy>().unwrap(), absolute("/"));
+    assert_eq!(
+        r"C:\Program Files\x\"
+            .parse::<InstallPathStrategy>()
+            .unwrap(),
+        absolute(r"C:\Program Files\x")
+    );
+    assert_eq!(
+        "C:/x".parse::<InstallPathStrategy>().unwrap(),
+        absolute("C:/x")
+    );
+    // A drive letter alone isn't an absolute path
+    assert!(matches!(
+        "C:x".parse::<InstallPathStrategy>(),
+        Err(DistError::InstallPathInvalid { .. })
+    ));
+    assert!(absolute("/usr/local/bin").is_system());
+    assert!(!InstallPathStrategy::CargoHome.is_system());
+}
+
+#[test]
+fn install_path_system_path() {
+    // One list serves every installer, so each picks out the path for its own OS
+    let paths = install_paths(&["CARGO_HOME", r"C:\Program Files\x", "/usr/local/bin"]);
+    assert_eq!(
+        InstallPathStrategy::system_path(&paths, false).as_deref(),
+        Some("/usr/local/bin")
+    );
+    assert_eq!(
+        InstallPathStrategy::system_path(&paths, true).as_deref(),
+        Some(r"C:\Program Files\x")
+    );
+    InstallPathStrategy::validate_system_paths(&paths).unwrap();
+
+    let paths = install_paths(&["~/.myapp", "C:/x"]);
+    assert_eq!(InstallPathStrategy::system_path(&paths, false), None);
+    assert_eq!(
+        InstallPathStrategy::system_path(&paths, true).as_deref(),
+        Some("C:/x")
+    );
+
+    let paths = install_paths(&["CARGO_HOME"]);
+    assert_eq!(InstallPathStrategy::system_path(&paths, false), None);
+    assert_eq!(InstallPathStrategy::system_path(&paths, true), None);
+}
+
+#[test]
+fn install_path_two_system_paths() {
+    let paths = install_paths(&["/usr/local/bin", r"C:\x", "/opt/x/"]);
+    assert!(matches!(
+        InstallPathStrategy::validate_system_paths(&paths),
+        Err(DistError::InstallPathMultipleSystem { first, second })
+            if first == "/usr/local/bin" && second == "/opt/x"
+    ));
+    let paths = install_paths(&["C:/x", "/usr/local/bin", r"D:\y"]);
+    assert!(matches!(
+        InstallPathStrategy::validate_system_paths(&paths),
+        Err(DistError::InstallPathMultipleSystem { first, second })
+            if first == "C:/x" && second == r"D:\y"
+    ));
+}
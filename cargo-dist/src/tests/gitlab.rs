@@ -0,0 +1,110 @@
+use super::standin::StandInServer;
+use crate::host::gitlab::{
+    GitlabClient, GitlabRelease, GitlabReleaseAssets, GitlabReleaseLink, GitlabToken,
+};
+use crate::DistError;
+use axoasset::reqwest;
+use camino::Utf8PathBuf;
+
+fn client(token: GitlabToken) -> GitlabClient {
+    GitlabClient::new(reqwest::Client::new(), token)
+}
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn gitlab_upload_file() {
+    let server = StandInServer::start(vec![(201, r#"{"message":"201 Created"}"#.to_owned())]);
+    let tmp = tempfile::tempdir().unwrap();
+    let path = Utf8PathBuf::from_path_buf(tmp.path().join("axolotlsay.tar.gz")).unwrap();
+    axoasset::LocalAsset::write_new_all("hello", &path).unwrap();
+
+    let url = format!(
+        "{}/api/v4/projects/axodotdev%2Faxolotlsay/packages/generic/axolotlsay/0.1.0/axolotlsay.tar.gz",
+        server.url
+    );
+    let gitlab = client(GitlabToken::Private("glpat-secret".to_owned()));
+    runtime().block_on(gitlab.upload_file(&url, &path)).unwrap();
+
+    let requests = server.finish();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(request.method, "PUT");
+    assert_eq!(
+        request.path,
+        "/api/v4/projects/axodotdev%2Faxolotlsay/packages/generic/axolotlsay/0.1.0/axolotlsay.tar.gz"
+    );
+    assert_eq!(request.header("PRIVATE-TOKEN"), Some("glpat-secret"));
+    assert_eq!(request.body_str(), "hello");
+}
+
+#[test]
+fn gitlab_create_release() {
+    let server = StandInServer::start(vec![(201, "{}".to_owned())]);
+    let api_url = format!("{}/api/v4", server.url);
+    let release = GitlabRelease {
+        tag_name: "v0.1.0".to_owned(),
+        name: "v0.1.0".to_owned(),
+        description: "it's a release".to_owned(),
+        git_ref: Some("abc123".to_owned()),
+        assets: GitlabReleaseAssets {
+            links: vec![GitlabReleaseLink {
+                name: "axolotlsay.tar.gz".to_owned(),
+                url: format!("{api_url}/projects/mygroup%2Fsub%2Faxolotlsay/packages/generic/axolotlsay/0.1.0/axolotlsay.tar.gz"),
+                link_type: "package".to_owned(),
+            }],
+        },
+    };
+    let gitlab = client(GitlabToken::Job("ci-job-token".to_owned()));
+    runtime()
+        .block_on(gitlab.create_release(&api_url, "mygroup/sub/axolotlsay", &release))
+        .unwrap();
+
+    let requests = server.finish();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(request.method, "POST");
+    assert_eq!(
+        request.path,
+        "/api/v4/projects/mygroup%2Fsub%2Faxolotlsay/releases"
+    );
+    assert_eq!(request.header("JOB-TOKEN"), Some("ci-job-token"));
+    let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body["tag_name"], "v0.1.0");
+    assert_eq!(body["ref"], "abc123");
+    assert_eq!(body["assets"]["links"][0]["name"], "axolotlsay.tar.gz");
+    assert_eq!(body["assets"]["links"][0]["link_type"], "package");
+}
+
+#[test]
+fn gitlab_api_error() {
+    let server = StandInServer::start(vec![(403, r#"{"message":"403 Forbidden"}"#.to_owned())]);
+    let api_url = format!("{}/api/v4", server.url);
+    let release = GitlabRelease {
+        tag_name: "v0.1.0".to_owned(),
+        name: "v0.1.0".to_owned(),
+        description: String::new(),
+        git_ref: None,
+        assets: GitlabReleaseAssets { links: vec![] },
+    };
+    let gitlab = client(GitlabToken::Private("glpat-secret".to_owned()));
+    let result =
+        runtime().block_on(gitlab.create_release(&api_url, "axodotdev/axolotlsay", &release));
+    server.finish();
+
+    if let Err(DistError::GitlabApiError { url, status, body }) = &result {
+        assert_eq!(
+            url,
+            &format!("{api_url}/projects/axodotdev%2Faxolotlsay/releases")
+        );
+        assert_eq!(*status, 403);
+        assert_eq!(body, r#"{"message":"403 Forbidden"}"#);
+    } else {
+        panic!("unexpected result: {result:?}");
+    }
+}
@@ -38,7 +38,7 @@ fn github_simple() {
     let ci = Some(vec![CiStyle::Github]);
 
     let (_graph, announcing) = mock_announce(&mut workspaces);
-    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref());
+    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref(), None);
 
     let hosting = hosting.unwrap().unwrap();
     assert_eq!(hosting.hosts, &[HostingStyle::Github]);
@@ -55,7 +55,7 @@ fn github_and_axo_simple() {
     let ci = Some(vec![CiStyle::Github]);
 
     let (_graph, announcing) = mock_announce(&mut workspaces);
-    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref());
+    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref(), None);
 
     let hosting = hosting.unwrap().unwrap();
     assert_eq!(
@@ -75,7 +75,7 @@ fn github_implicit() {
     let ci = Some(vec![CiStyle::Github]);
 
     let (_graph, announcing) = mock_announce(&mut workspaces);
-    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref());
+    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref(), None);
 
     let hosting = hosting.unwrap().unwrap();
     assert_eq!(hosting.hosts, &[HostingStyle::Github]);
@@ -100,7 +100,7 @@ fn github_diff_repository_on_non_distables() {
     let ci = Some(vec![CiStyle::Github]);
 
     let (_graph, announcing) = mock_announce(&mut workspaces);
-    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref());
+    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref(), None);
 
     let hosting = hosting.unwrap().unwrap();
     assert_eq!(hosting.hosts, &[HostingStyle::Github]);
@@ -123,7 +123,7 @@ fn github_no_repository() {
     let ci = Some(vec![CiStyle::Github]);
 
     let (_graph, announcing) = mock_announce(&mut workspaces);
-    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref());
+    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref(), None);
 
     if let Err(DistError::CantEnableGithubNoUrl { manifest_list }) = &hosting {
         assert!(manifest_list.contains(".toml"));
@@ -150,7 +150,7 @@ fn github_diff_repository() {
     let ci = Some(vec![CiStyle::Github]);
 
     let (_graph, announcing) = mock_announce(&mut workspaces);
-    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref());
+    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref(), None);
 
     if let Err(DistError::CantEnableGithubUrlInconsistent {
         inner:
@@ -185,7 +185,7 @@ fn github_not_github_repository() {
     let ci = Some(vec![CiStyle::Github]);
 
     let (_graph, announcing) = mock_announce(&mut workspaces);
-    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref());
+    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref(), None);
 
     if let Err(DistError::CantEnableGithubUrlNotGithub {
         inner: AxoprojectError::NotGitHubError { url },
@@ -211,7 +211,7 @@ fn no_ci_no_problem() {
     let ci = None;
 
     let (_graph, announcing) = mock_announce(&mut workspaces);
-    let hosting = select_hosting(&workspaces, &announcing, hosting, ci);
+    let hosting = select_hosting(&workspaces, &announcing, hosting, ci, None);
 
     assert!(matches!(hosting, Ok(None)))
 }
@@ -229,7 +229,7 @@ fn github_dot_git() {
     let ci = Some(vec![CiStyle::Github]);
 
     let (_graph, announcing) = mock_announce(&mut workspaces);
-    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref());
+    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref(), None);
 
     let hosting = hosting.unwrap().unwrap();
     assert_eq!(hosting.hosts, &[HostingStyle::Github]);
@@ -254,7 +254,7 @@ fn github_trail_slash() {
     let ci = Some(vec![CiStyle::Github]);
 
     let (_graph, announcing) = mock_announce(&mut workspaces);
-    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref());
+    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref(), None);
 
     let hosting = hosting.unwrap().unwrap();
     assert_eq!(hosting.hosts, &[HostingStyle::Github]);
@@ -262,3 +262,96 @@ fn github_trail_slash() {
     assert_eq!(hosting.project, REPO_PROJECT);
     assert_eq!(hosting.source_host, "github");
 }
+
+#[test]
+fn gitlab_implicit() {
+    // ci = "gitlab" and hosting = None, on a self-hosted instance with nested groups
+    const GITLAB_REPO_URL: &str = "https://gitlab.example.com/mycoolorg/tools/radproj";
+    let mut workspaces = workspace_unified();
+    let num_packages = workspaces.all_packages().count();
+    for pkg_idx in 0..num_packages {
+        let package = workspaces.package_mut(PackageIdx(pkg_idx));
+        package.repository_url = Some(GITLAB_REPO_URL.to_owned());
+    }
+    let hosting = None;
+    let ci = Some(vec![CiStyle::Gitlab]);
+
+    let (_graph, announcing) = mock_announce(&mut workspaces);
+    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref(), None);
+
+    let hosting = hosting.unwrap().unwrap();
+    assert_eq!(hosting.hosts, &[HostingStyle::Gitlab]);
+    assert_eq!(hosting.domain, "https://gitlab.example.com");
+    assert_eq!(hosting.owner, "mycoolorg/tools");
+    assert_eq!(hosting.project, "radproj");
+    assert_eq!(hosting.source_host, "gitlab");
+    assert_eq!(
+        hosting.gitlab_api_url.as_deref(),
+        Some("https://gitlab.example.com/api/v4")
+    );
+}
+
+#[test]
+fn gitlab_and_github_hosting() {
+    // hosting = ["gitlab", "github"] needs a github repository, and to be told where GitLab is
+    let mut workspaces = workspace_unified();
+    let hosting = Some(vec![HostingStyle::Gitlab, HostingStyle::Github]);
+    let ci = Some(vec![CiStyle::Gitlab]);
+
+    let (_graph, announcing) = mock_announce(&mut workspaces);
+    let hosting = select_hosting(
+        &workspaces,
+        &announcing,
+        hosting,
+        ci.as_deref(),
+        Some("https://gitlab.com/api/v4/"),
+    );
+
+    let hosting = hosting.unwrap().unwrap();
+    assert_eq!(hosting.owner, REPO_OWNER);
+    assert_eq!(hosting.project, REPO_PROJECT);
+    assert_eq!(hosting.source_host, "github");
+    assert_eq!(
+        hosting.gitlab_api_url.as_deref(),
+        Some("https://gitlab.com/api/v4")
+    );
+}
+
+#[test]
+fn gitlab_and_github_hosting_no_api_url() {
+    // hosting = ["gitlab", "github"] can't guess the GitLab instance from a github repository
+    let mut workspaces = workspace_unified();
+    let hosting = Some(vec![HostingStyle::Gitlab, HostingStyle::Github]);
+    let ci = Some(vec![CiStyle::Gitlab]);
+
+    let (_graph, announcing) = mock_announce(&mut workspaces);
+    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref(), None);
+
+    if let Err(DistError::CantEnableGitlabNoApiUrl { url }) = &hosting {
+        assert_eq!(url, REPO_URL);
+    } else {
+        panic!("unexpected result: {hosting:?}");
+    }
+}
+
+#[test]
+fn s3_only_gitlab_repo() {
+    // hosting = ["s3"] doesn't need a github repository
+    const GITLAB_REPO_URL: &str = "https://gitlab.example.com/mycoolorg/tools/radproj";
+    let mut workspaces = workspace_unified();
+    let num_packages = workspaces.all_packages().count();
+    for pkg_idx in 0..num_packages {
+        let package = workspaces.package_mut(PackageIdx(pkg_idx));
+        package.repository_url = Some(GITLAB_REPO_URL.to_owned());
+    }
+    let hosting = Some(vec![HostingStyle::S3]);
+    let ci = Some(vec![CiStyle::Github]);
+
+    let (_graph, announcing) = mock_announce(&mut workspaces);
+    let hosting = select_hosting(&workspaces, &announcing, hosting, ci.as_deref(), None);
+
+    let hosting = hosting.unwrap().unwrap();
+    assert_eq!(hosting.hosts, &[HostingStyle::S3]);
+    assert_eq!(hosting.owner, "mycoolorg/tools");
+    assert_eq!(hosting.source_host, "gitlab");
+}
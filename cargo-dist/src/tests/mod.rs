@@ -1,4 +1,8 @@
 mod config;
+mod gitlab;
 mod host;
 mod mock;
+mod s3;
+mod sigstore;
+mod standin;
 mod tag;
@@ -0,0 +1,124 @@
+use super::standin::StandInServer;
+use crate::host::s3::{S3Bucket, S3Client, S3Credentials};
+use axoasset::reqwest;
+use camino::Utf8PathBuf;
+use ring::hmac;
+use sha2::{Digest, Sha256};
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    crate::encode_hex(&Sha256::digest(data))
+}
+
+fn hmac_sha256(key: &[u8], message: &str) -> Vec<u8> {
+    hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), message.as_bytes())
+        .as_ref()
+        .to_vec()
+}
+
+#[test]
+fn s3_upload_file() {
+    let server = StandInServer::start(vec![(200, String::new())]);
+    let tmp = tempfile::tempdir().unwrap();
+    let path = Utf8PathBuf::from_path_buf(tmp.path().join("axolotlsay.tar.gz")).unwrap();
+    axoasset::LocalAsset::write_new_all("hello", &path).unwrap();
+
+    let s3 = S3Client::new(
+        reqwest::Client::new(),
+        S3Credentials {
+            access_key_id: "AKIDEXAMPLE".to_owned(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_owned(),
+            session_token: None,
+        },
+    );
+    let bucket = S3Bucket {
+        endpoint: &server.url,
+        region: "us-east-1",
+        name: "my-bucket",
+    };
+    let time = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1440938160);
+    runtime()
+        .block_on(s3.upload_file_at(bucket, "v0.2.2/axolotlsay+musl.tar.gz", &path, time))
+        .unwrap();
+
+    let host = server.url.trim_start_matches("http://").to_owned();
+    let requests = server.finish();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(request.method, "PUT");
+    // Path-style, with the key uri-encoded but its slashes kept
+    assert_eq!(request.path, "/my-bucket/v0.2.2/axolotlsay%2Bmusl.tar.gz");
+    assert_eq!(request.body_str(), "hello");
+    let payload_hash = sha256_hex(b"hello");
+    assert_eq!(
+        request.header("x-amz-content-sha256"),
+        Some(payload_hash.as_str())
+    );
+    assert_eq!(request.header("x-amz-date"), Some("20150830T123600Z"));
+
+    // Sign the request we actually got, step by step, and check we agree
+    let canonical_request = format!(
+        "PUT\n{}\n\nhost:{host}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:20150830T123600Z\n\nhost;x-amz-content-sha256;x-amz-date\n{payload_hash}",
+        request.path
+    );
+    let scope = "20150830/us-east-1/s3/aws4_request";
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n20150830T123600Z\n{scope}\n{}",
+        sha256_hex(canonical_request.as_bytes())
+    );
+    let mut key = b"AWS4wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_vec();
+    for part in ["20150830", "us-east-1", "s3", "aws4_request"] {
+        key = hmac_sha256(&key, part);
+    }
+    let signature = crate::encode_hex(&hmac_sha256(&key, &string_to_sign));
+    assert_eq!(
+        request.header("authorization"),
+        Some(
+            format!(
+                "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/{scope}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={signature}"
+            )
+            .as_str()
+        )
+    );
+}
+
+#[test]
+fn s3_upload_file_error() {
+    let server = StandInServer::start(vec![(
+        403,
+        "<Error><Code>AccessDenied</Code></Error>".to_owned(),
+    )]);
+    let tmp = tempfile::tempdir().unwrap();
+    let path = Utf8PathBuf::from_path_buf(tmp.path().join("axolotlsay.tar.gz")).unwrap();
+    axoasset::LocalAsset::write_new_all("hello", &path).unwrap();
+
+    let s3 = S3Client::new(
+        reqwest::Client::new(),
+        S3Credentials {
+            access_key_id: "AKIDEXAMPLE".to_owned(),
+            secret_access_key: "secret".to_owned(),
+            session_token: Some("session".to_owned()),
+        },
+    );
+    let bucket = S3Bucket {
+        endpoint: &server.url,
+        region: "auto",
+        name: "my-bucket",
+    };
+    let err = runtime()
+        .block_on(s3.upload_file(bucket, "axolotlsay.tar.gz", &path))
+        .unwrap_err();
+
+    let requests = server.finish();
+    assert_eq!(requests[0].header("x-amz-security-token"), Some("session"));
+    assert!(matches!(
+        err,
+        crate::DistError::S3ApiError { status: 403, ref body, .. } if body.contains("AccessDenied")
+    ));
+}
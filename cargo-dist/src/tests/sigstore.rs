@@ -0,0 +1,156 @@
+use super::standin::StandInServer;
+use crate::config::SigstoreConfig;
+use crate::sign::SigstoreClient;
+use axoasset::reqwest;
+use base64::Engine;
+use camino::Utf8PathBuf;
+use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_ASN1};
+use sha2::Digest;
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+}
+
+fn b64(bytes: &[u8]) -> String {
+    base64::prelude::BASE64_STANDARD.encode(bytes)
+}
+
+fn unb64(encoded: &str) -> Vec<u8> {
+    base64::prelude::BASE64_STANDARD.decode(encoded).unwrap()
+}
+
+const CERTIFICATE: &str =
+    "-----BEGIN CERTIFICATE-----\nMIIBfakecertificate\nAAAA\n-----END CERTIFICATE-----\n";
+
+#[test]
+fn sigstore_bundle() {
+    let fulcio_response = serde_json::json!({
+        "signedCertificateEmbeddedSct": {
+            "chain": { "certificates": [CERTIFICATE, "-----BEGIN CERTIFICATE-----\nMIIBroot\n-----END CERTIFICATE-----\n"] }
+        }
+    });
+    let rekor_response = serde_json::json!({
+        "24296fb24b8ad77a": {
+            "body": "eyJraW5kIjoiaGFzaGVkcmVrb3JkIn0=",
+            "integratedTime": 1700000000,
+            "logID": "c0d23d6ad406973f",
+            "logIndex": 42,
+            "verification": {
+                "inclusionProof": {
+                    "checkpoint": "rekor.sigstore.dev - 1193050959916656506\n100\nq80=\n",
+                    "hashes": ["00ff", "1234"],
+                    "logIndex": 41,
+                    "rootHash": "abcd",
+                    "treeSize": 100
+                },
+                "signedEntryTimestamp": "MEUCIQ=="
+            }
+        }
+    });
+    let server = StandInServer::start(vec![
+        (201, fulcio_response.to_string()),
+        (201, rekor_response.to_string()),
+    ]);
+
+    let tmp = tempfile::tempdir().unwrap();
+    let file = Utf8PathBuf::from_path_buf(tmp.path().join("axolotlsay.tar.gz")).unwrap();
+    let dest =
+        Utf8PathBuf::from_path_buf(tmp.path().join("axolotlsay.tar.gz.sigstore.json")).unwrap();
+    axoasset::LocalAsset::write_new_all("hello", &file).unwrap();
+    let claims = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(r#"{"sub":"me@example.com"}"#);
+    let token = format!("e30.{claims}.c2ln");
+
+    let client = SigstoreClient::new(
+        reqwest::Client::new(),
+        SigstoreConfig {
+            fulcio_url: Some(server.url.clone()),
+            rekor_url: Some(server.url.clone()),
+        },
+    );
+    runtime()
+        .block_on(client.sign_with_token(&file, &dest, &token))
+        .unwrap();
+
+    let requests = server.finish();
+    assert_eq!(requests.len(), 2);
+
+    // Fulcio gets the token and our key, with proof we hold the key
+    let fulcio = &requests[0];
+    assert_eq!(fulcio.method, "POST");
+    assert_eq!(fulcio.path, "/api/v2/signingCert");
+    let body: serde_json::Value = serde_json::from_slice(&fulcio.body).unwrap();
+    assert_eq!(body["credentials"]["oidcIdentityToken"], token.as_str());
+    let public_key = body["publicKeyRequest"]["publicKey"]["content"]
+        .as_str()
+        .unwrap();
+    let spki = unb64(
+        &public_key
+            .lines()
+            .filter(|line| !line.starts_with("-----"))
+            .collect::<String>(),
+    );
+    // Strip the SubjectPublicKeyInfo header off the uncompressed point
+    let public_key = UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, spki[26..].to_vec());
+    let proof = unb64(
+        body["publicKeyRequest"]["proofOfPossession"]
+            .as_str()
+            .unwrap(),
+    );
+    public_key.verify(b"me@example.com", &proof).unwrap();
+
+    // Rekor gets the signature, the certificate and the digest of the file
+    let digest = sha2::Sha256::digest(b"hello");
+    let rekor = &requests[1];
+    assert_eq!(rekor.method, "POST");
+    assert_eq!(rekor.path, "/api/v1/log/entries");
+    let body: serde_json::Value = serde_json::from_slice(&rekor.body).unwrap();
+    assert_eq!(body["kind"], "hashedrekord");
+    assert_eq!(body["apiVersion"], "0.0.1");
+    assert_eq!(
+        body["spec"]["data"]["hash"]["value"],
+        crate::encode_hex(&digest).as_str()
+    );
+    assert_eq!(
+        body["spec"]["signature"]["publicKey"]["content"],
+        b64(CERTIFICATE.as_bytes()).as_str()
+    );
+    let signature = body["spec"]["signature"]["content"].as_str().unwrap();
+    public_key.verify(b"hello", &unb64(signature)).unwrap();
+
+    // And the bundle has it all, in the shapes a v0.3 bundle wants
+    let bundle: serde_json::Value =
+        serde_json::from_str(&axoasset::LocalAsset::load_string(&dest).unwrap()).unwrap();
+    assert_eq!(
+        bundle,
+        serde_json::json!({
+            "mediaType": "application/vnd.dev.sigstore.bundle.v0.3+json",
+            "verificationMaterial": {
+                "certificate": { "rawBytes": "MIIBfakecertificateAAAA" },
+                "tlogEntries": [{
+                    "logIndex": "42",
+                    "logId": { "keyId": "wNI9atQGlz8=" },
+                    "kindVersion": { "kind": "hashedrekord", "version": "0.0.1" },
+                    "integratedTime": "1700000000",
+                    "inclusionPromise": { "signedEntryTimestamp": "MEUCIQ==" },
+                    "inclusionProof": {
+                        "logIndex": "41",
+                        "rootHash": "q80=",
+                        "treeSize": "100",
+                        "hashes": ["AP8=", "EjQ="],
+                        "checkpoint": {
+                            "envelope": "rekor.sigstore.dev - 1193050959916656506\n100\nq80=\n"
+                        }
+                    },
+                    "canonicalizedBody": "eyJraW5kIjoiaGFzaGVkcmVrb3JkIn0="
+                }]
+            },
+            "messageSignature": {
+                "messageDigest": { "algorithm": "SHA2_256", "digest": b64(&digest) },
+                "signature": signature
+            }
+        })
+    );
+}
@@ -0,0 +1,106 @@
+//! A stand-in HTTP server, for testing our API clients without the network
+//!
+//! It answers each connection with the next canned response and records
+//! the requests it got, so tests can check what a client actually sent.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread::JoinHandle;
+
+/// A request the stand-in server received
+#[derive(Debug, Clone)]
+pub struct Request {
+    /// e.g. "PUT"
+    pub method: String,
+    /// The path and query of the request
+    pub path: String,
+    /// Headers, with lowercased names
+    pub headers: Vec<(String, String)>,
+    /// The raw body
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// Get the value of a header
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == &name.to_ascii_lowercase())
+            .map(|(_, val)| val.as_str())
+    }
+
+    /// The body, as a string
+    pub fn body_str(&self) -> &str {
+        std::str::from_utf8(&self.body).unwrap()
+    }
+}
+
+/// A running stand-in server
+pub struct StandInServer {
+    /// Base URL of the server, e.g. `http://127.0.0.1:1234`
+    pub url: String,
+    handle: JoinHandle<Vec<Request>>,
+}
+
+impl StandInServer {
+    /// Start a server that gives out these (status, body) responses in order, then stops
+    pub fn start(responses: Vec<(u16, String)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut requests = vec![];
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                requests.push(read_request(&mut reader));
+                let response = format!(
+                    "HTTP/1.1 {status} Stand-In\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        Self { url, handle }
+    }
+
+    /// Wait for every response to be given out and get the requests that were made
+    pub fn finish(self) -> Vec<Request> {
+        self.handle.join().unwrap()
+    }
+}
+
+fn read_request(reader: &mut impl BufRead) -> Request {
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap().to_owned();
+    let path = parts.next().unwrap().to_owned();
+
+    let mut headers = vec![];
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (key, val) = line.split_once(':').unwrap();
+        headers.push((key.trim().to_ascii_lowercase(), val.trim().to_owned()));
+    }
+
+    let len = headers
+        .iter()
+        .find(|(key, _)| key == "content-length")
+        .map(|(_, val)| val.parse().unwrap())
+        .unwrap_or(0);
+    let mut body = vec![0; len];
+    reader.read_exact(&mut body).unwrap();
+
+    Request {
+        method,
+        path,
+        headers,
+        body,
+    }
+}
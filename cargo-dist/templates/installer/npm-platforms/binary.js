@@ -0,0 +1,126 @@
+const { spawnSync } = require("child_process");
+const os = require("os");
+const path = require("path");
+
+const error = (msg) => {
+  console.error(msg);
+  process.exit(1);
+};
+
+const { name, supportedPlatforms, glibcMinimum } = require("./package.json");
+
+const builderGlibcMajorVersion = glibcMinimum.major;
+const builderGlibcMinorVersion = glibcMinimum.series;
+
+// The version of glibc node is running against, if it's running against glibc
+const getGlibcVersion = () => {
+  if (!process.report || typeof process.report.getReport !== "function") {
+    return null;
+  }
+  let report = process.report.getReport();
+  if (typeof report === "string") {
+    report = JSON.parse(report);
+  }
+  return report.header.glibcVersionRuntime || null;
+};
+
+// The rust-style target triples that could work here, best first
+const getTargetTriples = () => {
+  const rawOsType = os.type();
+  const rawArchitecture = os.arch();
+
+  let arch = "";
+  switch (rawArchitecture) {
+    case "x64":
+      arch = "x86_64";
+      break;
+    case "arm64":
+      arch = "aarch64";
+      break;
+  }
+
+  switch (rawOsType) {
+    case "Windows_NT":
+      return [`${arch}-pc-windows-msvc`];
+    case "Darwin":
+      return [`${arch}-apple-darwin`];
+    case "Linux": {
+      const glibcVersion = getGlibcVersion();
+      if (!glibcVersion) {
+        return [
+          `${arch}-unknown-linux-musl-dynamic`,
+          `${arch}-unknown-linux-musl-static`,
+        ];
+      }
+      const [libcMajorVersion, libcMinorVersion] = glibcVersion.split(".");
+      if (
+        libcMajorVersion != builderGlibcMajorVersion ||
+        libcMinorVersion < builderGlibcMinorVersion
+      ) {
+        // We can't run the glibc binaries, but we can run the static musl ones
+        // if they exist
+        console.warn(
+          "Your glibc isn't compatible; trying static musl binary instead",
+        );
+        return [`${arch}-unknown-linux-musl-static`];
+      }
+      return [
+        `${arch}-unknown-linux-gnu`,
+        `${arch}-unknown-linux-musl-static`,
+      ];
+    }
+  }
+  return [];
+};
+
+// Find the binary in whichever of the per-platform packages npm installed for us
+const getBinaryPath = (binaryName) => {
+  const targetTriples = getTargetTriples();
+  const candidates = targetTriples.filter((t) => supportedPlatforms[t]);
+  if (candidates.length === 0) {
+    error(
+      `Platform with type "${os.type()}" and architecture "${os.arch()}" is not supported by ${name}.\nYour system must be one of the following:\n\n${Object.keys(
+        supportedPlatforms,
+      ).join(",")}`,
+    );
+  }
+
+  for (const targetTriple of candidates) {
+    const platform = supportedPlatforms[targetTriple];
+    const binRelPath = platform.bins[binaryName];
+    if (!binRelPath) {
+      error(`${binaryName} is not a known binary in ${name}`);
+    }
+    let packageJson;
+    try {
+      packageJson = require.resolve(`${platform.packageName}/package.json`);
+    } catch (e) {
+      continue;
+    }
+    return path.join(path.dirname(packageJson), binRelPath);
+  }
+
+  const packageNames = candidates.map((t) => supportedPlatforms[t].packageName);
+  error(
+    `${name} couldn't find the package with the binaries for your platform (${packageNames.join(
+      " or ",
+    )}).\nIt should have been installed as an optional dependency; make sure optional dependencies aren't being skipped (e.g. with --omit=optional or --no-optional).`,
+  );
+};
+
+const run = (binaryName) => {
+  const [, , ...args] = process.argv;
+  const options = { cwd: process.cwd(), stdio: "inherit" };
+  const result = spawnSync(getBinaryPath(binaryName), args, options);
+
+  if (result.error) {
+    error(result.error);
+  }
+
+  process.exit(result.status);
+};
+
+module.exports = {
+  run,
+  getBinaryPath,
+};
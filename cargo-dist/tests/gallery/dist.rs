@@ -105,6 +105,14 @@ pub struct AppResult {
     homebrew_skip_install: bool,
     powershell_installer_path: Option<Utf8PathBuf>,
     npm_installer_package_path: Option<Utf8PathBuf>,
+    npm_platform_package_paths: Vec<Utf8PathBuf>,
+    scoop_installer_path: Option<Utf8PathBuf>,
+    winget_manifest_paths: Vec<Utf8PathBuf>,
+    chocolatey_package_path: Option<Utf8PathBuf>,
+    aur_package_paths: Vec<Utf8PathBuf>,
+    nix_installer_path: Option<Utf8PathBuf>,
+    provenance_path: Option<Utf8PathBuf>,
+    sbom_paths: Vec<Utf8PathBuf>,
     unified_checksum_path: Option<Utf8PathBuf>,
 }
 
@@ -116,6 +124,7 @@ pub struct PlanResult {
 pub struct GenerateResult {
     test_name: String,
     github_ci_path: Option<Utf8PathBuf>,
+    gitlab_ci_path: Option<Utf8PathBuf>,
     wxs_path: Option<Utf8PathBuf>,
 }
 
@@ -220,11 +229,15 @@ impl<'a> TestContext<'a, Tools> {
     ) -> Result<GenerateResult> {
         let ci_file_name = format!("{prefix}release.yml");
         let github_ci_path = Utf8Path::new(".github/workflows/").join(ci_file_name);
+        let gitlab_ci_path = Utf8Path::new(".gitlab-ci.yml").to_owned();
         let wxs_path = Utf8Path::new("wix/main.wxs").to_owned();
         // Delete files if they already exist
         if github_ci_path.exists() {
             LocalAsset::remove_file(&github_ci_path)?;
         }
+        if gitlab_ci_path.exists() {
+            LocalAsset::remove_file(&gitlab_ci_path)?;
+        }
         if wxs_path.exists() {
             LocalAsset::remove_file(&wxs_path)?;
         }
@@ -238,6 +251,7 @@ impl<'a> TestContext<'a, Tools> {
         Ok(GenerateResult {
             test_name: test_name.to_owned(),
             github_ci_path: github_ci_path.exists().then_some(github_ci_path),
+            gitlab_ci_path: gitlab_ci_path.exists().then_some(gitlab_ci_path),
             wxs_path: wxs_path.exists().then_some(wxs_path),
         })
     }
@@ -257,6 +271,53 @@ impl<'a> TestContext<'a, Tools> {
 
             let npm_installer =
                 Utf8PathBuf::from(format!("{target_dir}/{app_name}-npm-package.tar.gz"));
+            let mut npm_platform_packages = std::fs::read_dir(&target_dir)
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| Utf8PathBuf::from_path_buf(entry.ok()?.path()).ok())
+                .filter(|path| {
+                    path.file_name().is_some_and(|name| {
+                        name.starts_with(&app_name)
+                            && name.ends_with("-npm-platform-package.tar.gz")
+                    })
+                })
+                .collect::<Vec<_>>();
+            npm_platform_packages.sort();
+            let scoop_installer = Utf8PathBuf::from(format!("{target_dir}/{app_name}.json"));
+            // winget manifests are named after the package identifier, which has a publisher prefix
+            let mut winget_manifests = std::fs::read_dir(&target_dir)
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| Utf8PathBuf::from_path_buf(entry.ok()?.path()).ok())
+                .filter(|path| {
+                    path.file_name().is_some_and(|name| {
+                        name.ends_with(".yaml") && name.contains(&format!(".{app_name}."))
+                    })
+                })
+                .collect::<Vec<_>>();
+            winget_manifests.sort();
+            let chocolatey_package =
+                Utf8PathBuf::from(format!("{target_dir}/{app_name}-chocolatey-package"));
+            // the AUR package name gets a -bin suffix, so find its files by extension
+            let mut aur_packages = std::fs::read_dir(&target_dir)
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| Utf8PathBuf::from_path_buf(entry.ok()?.path()).ok())
+                .filter(|path| {
+                    path.file_name().is_some_and(|name| {
+                        name.starts_with(&app_name)
+                            && (name.ends_with(".PKGBUILD") || name.ends_with(".SRCINFO"))
+                    })
+                })
+                .collect::<Vec<_>>();
+            aur_packages.sort();
+            let nix_installer = Utf8PathBuf::from(format!("{target_dir}/{app_name}.nix"));
+            let provenance = Utf8PathBuf::from(format!("{target_dir}/{app_name}.intoto.jsonl"));
+            let sboms = ["cdx.json", "spdx.json"]
+                .into_iter()
+                .map(|ext| Utf8PathBuf::from(format!("{target_dir}/{app_name}.{ext}")))
+                .filter(|path| path.exists())
+                .collect();
             let unified_checksum_path = Utf8PathBuf::from(format!("{target_dir}/sha256.sum"));
             app_results.push(AppResult {
                 test_name: test_name.to_owned(),
@@ -268,6 +329,14 @@ impl<'a> TestContext<'a, Tools> {
                 homebrew_installer_path: homebrew_installer.exists().then_some(homebrew_installer),
                 homebrew_skip_install,
                 npm_installer_package_path: npm_installer.exists().then_some(npm_installer),
+                npm_platform_package_paths: npm_platform_packages,
+                scoop_installer_path: scoop_installer.exists().then_some(scoop_installer),
+                winget_manifest_paths: winget_manifests,
+                chocolatey_package_path: chocolatey_package.exists().then_some(chocolatey_package),
+                aur_package_paths: aur_packages,
+                nix_installer_path: nix_installer.exists().then_some(nix_installer),
+                provenance_path: provenance.exists().then_some(provenance),
+                sbom_paths: sboms,
                 unified_checksum_path: unified_checksum_path
                     .exists()
                     .then_some(unified_checksum_path),
@@ -382,6 +451,9 @@ impl DistResult {
             // If we can, run the shell script in a temp HOME
             app.runtest_shell_installer(ctx, expected_bin_dir)?;
 
+            // Check the shell script can read back the receipts it writes
+            app.runtest_shell_uninstall(ctx)?;
+
             // If we can, run the powershell script in a temp HOME
             app.runtest_powershell_installer(ctx, expected_bin_dir)?;
 
@@ -123,6 +123,109 @@ impl AppResult {
         Ok(())
     }
 
+    // Writes an install receipt with the script's own helpers, and checks `--uninstall` reads it back
+    //
+    // This stays inside a temp dir, so it doesn't need RUIN_MY_COMPUTER_WITH_INSTALLERS
+    #[allow(unused_variables)]
+    pub fn runtest_shell_uninstall(&self, ctx: &TestContext<Tools>) -> Result<()> {
+        #[cfg(target_family = "unix")]
+        {
+            let app_name = &self.app_name;
+            let test_name = &self.test_name;
+
+            // only do this if the script exists, and can uninstall
+            let Some(shell_path) = &self.shell_installer_path else {
+                return Ok(());
+            };
+            let script_text = LocalAsset::load_string(shell_path)?;
+            if !script_text.contains("\nuninstall() {\n") {
+                return Ok(());
+            }
+            eprintln!("running installer.sh --uninstall...");
+            use miette::IntoDiagnostic;
+            use std::os::unix::prelude::PermissionsExt;
+            std::fs::set_permissions(shell_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            let script = CommandInfo::new_unchecked("installer.sh", Some(shell_path.as_str()));
+
+            // Create/clobber a temp dir in target
+            let repo_dir = &ctx.repo_dir;
+            let repo_id = &ctx.repo_id;
+            let parent = repo_dir.parent().unwrap();
+            let tempdir = parent.join(format!("{repo_id}__{test_name}__uninstall"));
+            if tempdir.exists() {
+                std::fs::remove_dir_all(&tempdir).unwrap();
+            }
+
+            // "Install" into a dir whose path needs escaping in JSON, and has a "," in it
+            let install_dir = tempdir.join(r#"it's a "bin", \dir"#);
+            std::fs::create_dir_all(&install_dir).unwrap();
+            let installed_files = self
+                .bins
+                .iter()
+                .map(|bin| install_dir.join(bin))
+                .collect::<Vec<_>>();
+            let env_script = install_dir.join("env");
+            for file in installed_files.iter().chain([&env_script]) {
+                LocalAsset::write_new("", file)?;
+            }
+
+            // Build the receipt's strings and arrays the way the installer does
+            let helpers = ["json_escape", "js_array_append"]
+                .map(|name| shell_function(&script_text, name))
+                .join("\n");
+            let js_array = |items: &[&Utf8PathBuf]| -> Result<String> {
+                let output = std::process::Command::new("sh")
+                    .arg("-c")
+                    .arg(format!(
+                        r#"{helpers}
+_array=""
+for _item in "$@"; do
+    _array="$(js_array_append "$_array" "$_item")"
+done
+printf '%s' "$_array""#
+                    ))
+                    .arg("sh")
+                    .args(items)
+                    .output()
+                    .into_diagnostic()?;
+                assert!(output.status.success(), "js_array_append failed");
+                Ok(String::from_utf8(output.stdout).unwrap())
+            };
+            let receipt = format!(
+                r#"{{"binaries":[],"install_prefix":{},"install_layout":"flat","installed_files":[{}],"env_scripts":[{}],"modified_rcfiles":[],"modify_path":true}}"#,
+                js_array(&[&install_dir])?,
+                js_array(&installed_files.iter().collect::<Vec<_>>())?,
+                js_array(&[&env_script])?,
+            );
+            // The paths must survive a trip through a real JSON parser
+            let parsed: serde_json::Value = serde_json::from_str(&receipt).into_diagnostic()?;
+            assert_eq!(parsed["install_prefix"], install_dir.as_str());
+            assert_eq!(
+                parsed["installed_files"],
+                serde_json::json!(installed_files)
+            );
+            let config_dir = tempdir.join("config");
+            let receipt_file = config_dir.join(format!("{app_name}/{app_name}-receipt.json"));
+            LocalAsset::write_new_all(&receipt, &receipt_file)?;
+
+            script.output_checked(|cmd| {
+                cmd.arg("--uninstall")
+                    .env("HOME", &tempdir)
+                    .env("XDG_CONFIG_HOME", &config_dir)
+                    .env_remove("CARGO_HOME")
+            })?;
+
+            // Reading the receipt back must have found the very same paths
+            for file in &installed_files {
+                assert!(!file.exists(), "{file} wasn't uninstalled");
+            }
+            assert!(!env_script.exists(), "env script wasn't uninstalled");
+            assert!(!install_dir.exists(), "install dir wasn't cleaned up");
+            assert!(!receipt_file.exists(), "receipt wasn't removed");
+        }
+        Ok(())
+    }
+
     /// Run shellcheck on the shell scripts
     pub fn shellcheck(&self, ctx: &TestContext<Tools>) -> Result<()> {
         // Only do this if the script is available
@@ -143,3 +246,17 @@ impl AppResult {
         Ok(())
     }
 }
+
+/// Gets the source of a function defined at the top level of a shell script
+#[cfg(target_family = "unix")]
+fn shell_function<'a>(script: &'a str, name: &str) -> &'a str {
+    let start = script
+        .find(&format!("\n{name}() {{\n"))
+        .unwrap_or_else(|| panic!("script doesn't define {name}"))
+        + 1;
+    let len = script[start..]
+        .find("\n}\n")
+        .expect("function never ended!?")
+        + 2;
+    &script[start..start + len]
+}
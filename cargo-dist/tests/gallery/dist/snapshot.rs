@@ -46,6 +46,68 @@ impl DistResult {
                     .unwrap_or_default(),
                 app.npm_installer_package_path.as_deref(),
             )?;
+            // Just the package.json, the rest is binaries
+            for path in &app.npm_platform_package_paths {
+                append_snapshot_tarball_file(
+                    &mut snapshots,
+                    path.file_name().unwrap_or_default(),
+                    path,
+                    "package/package.json",
+                )?;
+            }
+            append_snapshot_file(
+                &mut snapshots,
+                app.scoop_installer_path
+                    .as_deref()
+                    .and_then(|p| p.file_name())
+                    .unwrap_or_default(),
+                app.scoop_installer_path.as_deref(),
+            )?;
+            for path in &app.winget_manifest_paths {
+                append_snapshot_file(
+                    &mut snapshots,
+                    path.file_name().unwrap_or_default(),
+                    Some(path),
+                )?;
+            }
+            for path in &app.aur_package_paths {
+                append_snapshot_file(
+                    &mut snapshots,
+                    path.file_name().unwrap_or_default(),
+                    Some(path),
+                )?;
+            }
+            append_snapshot_file(
+                &mut snapshots,
+                app.nix_installer_path
+                    .as_deref()
+                    .and_then(|p| p.file_name())
+                    .unwrap_or_default(),
+                app.nix_installer_path.as_deref(),
+            )?;
+            append_snapshot_dir(
+                &mut snapshots,
+                app.chocolatey_package_path
+                    .as_deref()
+                    .and_then(|p| p.file_name())
+                    .unwrap_or_default(),
+                app.chocolatey_package_path.as_deref(),
+            )?;
+            append_snapshot_provenance(
+                &mut snapshots,
+                app.provenance_path
+                    .as_deref()
+                    .and_then(|p| p.file_name())
+                    .unwrap_or_default(),
+                app.provenance_path.as_deref(),
+            )?;
+            for path in &app.sbom_paths {
+                append_snapshot_file(
+                    &mut snapshots,
+                    path.file_name().unwrap_or_default(),
+                    Some(path),
+                )?;
+            }
             append_snapshot_file(
                 &mut snapshots,
                 app.unified_checksum_path
@@ -98,6 +160,12 @@ impl GenerateResult {
             self.github_ci_path.as_deref(),
         )?;
 
+        append_snapshot_file(
+            &mut snapshots,
+            ".gitlab-ci.yml",
+            self.gitlab_ci_path.as_deref(),
+        )?;
+
         append_snapshot_file(&mut snapshots, "main.wxs", self.wxs_path.as_deref())?;
 
         Ok(Snapshots {
@@ -155,7 +223,16 @@ pub fn snapshot_settings_with_gallery_filter() -> insta::Settings {
     settings.add_filter(r#"sha256 ".*""#, r#"sha256 "CENSORED""#);
     settings.add_filter(r#""sha256": .*"#, r#""sha256": "CENSORED""#);
     settings.add_filter(r#""sha512": .*"#, r#""sha512": "CENSORED""#);
+    settings.add_filter(r"sha256sums_(\w+)=\('.*'\)", "sha256sums_$1=('CENSORED')");
+    settings.add_filter(r"sha256sums_(\w+) = .*", "sha256sums_$1 = CENSORED");
+    settings.add_filter(r#"hash = "sha256-.*";"#, r#"hash = "sha256-CENSORED";"#);
     settings.add_filter(r#""version":"[a-zA-Z\.0-9\-]*""#, r#""version":"CENSORED""#);
+    // SBOMs record the version of dist that made them
+    settings.add_filter(
+        r#""name": "dist",(\s+)"type": "application",(\s+)"version": ".*""#,
+        r#""name": "dist",$1"type": "application",$2"version": "CENSORED""#,
+    );
+    settings.add_filter(r#""Tool: dist-.*""#, r#""Tool: dist-CENSORED""#);
     settings.add_filter(
         r#""build_environment": \{\n\s+"macos": \{\n\s+"os_version": ".+"\n\s+}\n\s+}"#,
         r#""build_environment": "indeterminate""#,
@@ -223,6 +300,58 @@ pub fn snapshot_settings_with_dist_manifest_filter() -> insta::Settings {
     settings
 }
 
+fn append_snapshot_dir(out: &mut String, name: &str, src_path: Option<&Utf8Path>) -> Result<()> {
+    // Skip snapshotting this dir if absent
+    let Some(src_path) = src_path else {
+        return Ok(());
+    };
+
+    // We shove everything in a BTreeMap to keep ordering stable
+    let mut results = BTreeMap::new();
+    let mut dirs = vec![src_path.to_owned()];
+    while let Some(dir) = dirs.pop() {
+        for entry in dir.read_dir_utf8().expect("couldn't read dir") {
+            let path = entry.expect("couldn't read dir entry").into_path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else {
+                let relpath = path.strip_prefix(src_path).unwrap().as_str().to_owned();
+                results.insert(relpath, LocalAsset::load_string(&path)?);
+            }
+        }
+    }
+
+    for (path, val) in &results {
+        append_snapshot_string(out, &format!("{name}/{path}"), val)?;
+    }
+    Ok(())
+}
+
+fn append_snapshot_tarball_file(
+    out: &mut String,
+    name: &str,
+    src_path: &Utf8Path,
+    file_path: &str,
+) -> Result<()> {
+    use std::io::Read;
+
+    let file = LocalAsset::load_bytes(src_path)?;
+    let gz_decoder = flate2::read::GzDecoder::new(&file[..]);
+    let mut tar_decoder = tar::Archive::new(gz_decoder);
+    let entries = tar_decoder.entries().expect("couldn't read tar");
+    for entry in entries {
+        let mut entry = entry.expect("couldn't read tar entry");
+        if entry.path().expect("couldn't get tarred file's path") == Utf8Path::new(file_path) {
+            let mut val = String::new();
+            entry
+                .read_to_string(&mut val)
+                .expect("couldn't read tarred file to string");
+            return append_snapshot_string(out, &format!("{name}/{file_path}"), &val);
+        }
+    }
+    panic!("{src_path} didn't contain {file_path}");
+}
+
 fn append_snapshot_tarball(
     out: &mut String,
     name: &str,
@@ -274,6 +403,32 @@ fn append_snapshot_file(out: &mut String, name: &str, src_path: Option<&Utf8Path
     append_snapshot_string(out, name, &src)
 }
 
+fn append_snapshot_provenance(
+    out: &mut String,
+    name: &str,
+    src_path: Option<&Utf8Path>,
+) -> Result<()> {
+    // Skip snapshotting this file if absent
+    let Some(src_path) = src_path else {
+        return Ok(());
+    };
+
+    // Statements are one per line, so pretty-print them to let the filters at them,
+    // and drop the details of the machine that made them
+    let src = axoasset::LocalAsset::load_string(src_path)?;
+    let mut statements = vec![];
+    for line in src.lines() {
+        let mut statement: serde_json::Value =
+            serde_json::from_str(line).expect("provenance statement wasn't json");
+        let run_details = statement
+            .pointer_mut("/predicate/runDetails")
+            .expect("provenance statement had no runDetails");
+        *run_details = serde_json::Value::String("CENSORED".to_owned());
+        statements.push(serde_json::to_string_pretty(&statement).unwrap());
+    }
+    append_snapshot_string(out, name, &statements.join("\n"))
+}
+
 fn append_snapshot_string(out: &mut String, name: &str, val: &str) -> Result<()> {
     use std::fmt::Write;
 